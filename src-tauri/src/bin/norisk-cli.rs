@@ -0,0 +1,460 @@
+//! Headless command line front-end for the launcher.
+//!
+//! Boots the regular `State` without a Tauri app/webview and exposes the most
+//! important profile operations so they can be scripted on servers or CI boxes:
+//!
+//! ```text
+//! norisk-cli profiles list
+//! norisk-cli profiles create "Test Server Bot" --game-version 1.21.4 --loader fabric
+//! norisk-cli install "Test Server Bot" sodium --kind mod
+//! norisk-cli launch "Test Server Bot" --server localhost:25565
+//! norisk-cli processes
+//! norisk-cli stop "Test Server Bot"
+//! ```
+
+use clap::Parser;
+use noriskclient_launcher_v3_lib::cli::{
+    self as cli, Cli, Command, ContentKind, ProfilesCommand, StoreCommand,
+};
+use noriskclient_launcher_v3_lib::commands::process_command;
+use noriskclient_launcher_v3_lib::commands::profile_command::{self, CreateProfileParams};
+use noriskclient_launcher_v3_lib::error::{AppError, CommandError};
+use noriskclient_launcher_v3_lib::integrations::modrinth;
+use noriskclient_launcher_v3_lib::state::event_state::{EventPayload, EventType};
+use noriskclient_launcher_v3_lib::state::process_state::ProcessState;
+use noriskclient_launcher_v3_lib::state::profile_state::{Profile, ProfileKind};
+use noriskclient_launcher_v3_lib::state::State;
+use noriskclient_launcher_v3_lib::utils::profile_utils::{self, ContentType};
+use std::process::ExitCode;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval, Duration};
+use uuid::Uuid;
+
+type CliResult<T> = std::result::Result<T, String>;
+
+fn app_err(e: AppError) -> String {
+    e.to_string()
+}
+
+fn cmd_err(e: CommandError) -> String {
+    e.message
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    let cli = Cli::parse();
+
+    if let Err(e) = State::init_headless().await {
+        eprintln!("Failed to initialize launcher state: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    match run(cli.command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command) -> CliResult<()> {
+    match command {
+        Command::Profiles { command } => match command {
+            ProfilesCommand::List => list_profiles().await,
+            ProfilesCommand::Create {
+                name,
+                game_version,
+                loader,
+                loader_version,
+                pack,
+            } => {
+                let id = profile_command::create_profile(CreateProfileParams {
+                    name,
+                    game_version,
+                    loader,
+                    loader_version,
+                    selected_norisk_pack_id: pack,
+                    kind: ProfileKind::Client,
+                    linked_client_profile_id: None,
+                })
+                .await
+                .map_err(cmd_err)?;
+                println!("{}", id);
+                Ok(())
+            }
+        },
+        Command::Launch {
+            profile,
+            server,
+            world,
+            detach,
+        } => launch(&profile, world, server, detach).await,
+        Command::Stop { target } => stop(&target).await,
+        Command::Processes => list_processes().await,
+        Command::Logs { process_id, follow } => logs(process_id, follow).await,
+        Command::Install {
+            profile,
+            project,
+            version,
+            kind,
+            ignore_incompatibilities,
+        } => install(&profile, &project, version, kind, ignore_incompatibilities).await,
+        Command::Import { file } => {
+            let id = profile_command::import_profile(file.to_string_lossy().to_string())
+                .await
+                .map_err(cmd_err)?;
+            println!("{}", id);
+            Ok(())
+        }
+        Command::Store { command } => {
+            let state = State::get().await.map_err(app_err)?;
+            match command {
+                StoreCommand::Stats => {
+                    let stats = state.content_store.stats().await.map_err(app_err)?;
+                    println!(
+                        "{} blobs, {} bytes, {} references",
+                        stats.blob_count, stats.total_bytes, stats.reference_count
+                    );
+                }
+                StoreCommand::Gc => {
+                    let report = state
+                        .content_store
+                        .garbage_collect()
+                        .await
+                        .map_err(app_err)?;
+                    println!(
+                        "Removed {} blobs ({} bytes), pruned {} references",
+                        report.removed_blobs, report.freed_bytes, report.pruned_references
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Finds a user profile by ID or (case-insensitive) name.
+async fn resolve_profile(state: &State, name_or_id: &str) -> CliResult<Profile> {
+    if let Ok(id) = Uuid::parse_str(name_or_id) {
+        return state.profile_manager.get_profile(id).await.map_err(app_err);
+    }
+
+    let profiles = state
+        .profile_manager
+        .list_profiles()
+        .await
+        .map_err(app_err)?;
+    cli::find_profile(profiles, name_or_id).map_err(app_err)
+}
+
+async fn list_profiles() -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+    let mut profiles = state
+        .profile_manager
+        .list_profiles()
+        .await
+        .map_err(app_err)?;
+    profiles.sort_by_key(|p| p.name.to_lowercase());
+
+    for profile in profiles {
+        println!(
+            "{}  {:<32} {:<10} {}{}",
+            profile.id,
+            profile.name,
+            profile.game_version,
+            profile.loader.as_str(),
+            profile
+                .loader_version
+                .as_deref()
+                .map(|v| format!(" {}", v))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+async fn list_processes() -> CliResult<()> {
+    let processes = process_command::get_processes().await.map_err(cmd_err)?;
+    for process in processes {
+        println!(
+            "{}  pid {:<8} {:?}  {}",
+            process.id,
+            process.pid,
+            process.state,
+            process
+                .profile_name
+                .as_deref()
+                .unwrap_or("<unknown profile>")
+        );
+    }
+    Ok(())
+}
+
+async fn stop(target: &str) -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+
+    if let Ok(process_id) = Uuid::parse_str(target) {
+        if state
+            .process_manager
+            .get_process_metadata(process_id)
+            .await
+            .is_some()
+        {
+            return process_command::stop_process(process_id)
+                .await
+                .map_err(cmd_err);
+        }
+    }
+
+    let profile = resolve_profile(&state, target).await?;
+    let processes = state
+        .process_manager
+        .get_process_metadata_by_profile(profile.id)
+        .await;
+    if processes.is_empty() {
+        return Err(format!(
+            "Profile '{}' has no running processes",
+            profile.name
+        ));
+    }
+    for process in processes {
+        state
+            .process_manager
+            .stop_process(process.id)
+            .await
+            .map_err(app_err)?;
+        println!("Stopped process {} (pid {})", process.id, process.pid);
+    }
+    Ok(())
+}
+
+async fn install(
+    profile_ref: &str,
+    project: &str,
+    version_id: Option<String>,
+    kind: ContentKind,
+    ignore_incompatibilities: bool,
+) -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+    let profile = resolve_profile(&state, profile_ref).await?;
+
+    let version = match version_id {
+        Some(id) => modrinth::get_version_details(id).await.map_err(app_err)?,
+        None => {
+            // Only mods are loader specific, packs just need to match the game version
+            let loaders = match kind {
+                ContentKind::Mod => Some(vec![profile.loader.as_str().to_string()]),
+                _ => None,
+            };
+            modrinth::get_mod_versions(
+                project.to_string(),
+                loaders,
+                Some(vec![profile.game_version.clone()]),
+            )
+            .await
+            .map_err(app_err)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                format!(
+                    "No version of '{}' found for {} {}",
+                    project,
+                    profile.loader.as_str(),
+                    profile.game_version
+                )
+            })?
+        }
+    };
+
+    match ContentType::from(kind) {
+        ContentType::Mod => {
+            let primary_file = version
+                .files
+                .iter()
+                .find(|f| f.primary)
+                .or_else(|| version.files.first())
+                .ok_or_else(|| format!("Version {} has no files", version.id))?;
+            profile_command::add_modrinth_mod_to_profile(
+                profile.id,
+                version.project_id.clone(),
+                version.id.clone(),
+                primary_file.filename.clone(),
+                primary_file.url.clone(),
+                primary_file.hashes.sha1.clone(),
+                Some(version.name.clone()),
+                Some(version.version_number.clone()),
+                Some(version.loaders.clone()),
+                Some(version.game_versions.clone()),
+                Some(ignore_incompatibilities),
+            )
+            .await
+            .map_err(cmd_err)?;
+        }
+        content_type => {
+            profile_utils::install_modrinth_content(profile.id, &version, content_type)
+                .await
+                .map_err(app_err)?;
+        }
+    }
+
+    println!(
+        "Installed {} {} into '{}'",
+        version.name, version.version_number, profile.name
+    );
+    Ok(())
+}
+
+async fn launch(
+    profile_ref: &str,
+    world: Option<String>,
+    server: Option<String>,
+    detach: bool,
+) -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+    let profile = resolve_profile(&state, profile_ref).await?;
+    let mut events = state.event_state.subscribe();
+
+    profile_command::launch_profile(profile.id, world, server)
+        .await
+        .map_err(cmd_err)?;
+
+    // Phase 1: report install progress until the launch finishes or fails
+    let mut liveness_check = interval(Duration::from_secs(2));
+    let mut launch_task_gone = false;
+    let process_id = loop {
+        tokio::select! {
+            received = events.recv() => {
+                let event = match received {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return Err("Event channel closed".to_string()),
+                };
+                if event.target_id != Some(profile.id) {
+                    continue;
+                }
+                match event.event_type {
+                    EventType::Error => {
+                        return Err(event.error.unwrap_or(event.message));
+                    }
+                    EventType::LaunchSuccessful => {
+                        break newest_process(&state, profile.id).await;
+                    }
+                    _ => print_progress(&event),
+                }
+            }
+            _ = liveness_check.tick() => {
+                if state.process_manager.has_launching_process(profile.id) {
+                    continue;
+                }
+                // The launch task ends right before it emits its result, give the event
+                // one more tick before deciding from the processes alone
+                if !launch_task_gone {
+                    launch_task_gone = true;
+                    continue;
+                }
+                match newest_process(&state, profile.id).await {
+                    Some(process_id) => break Some(process_id),
+                    None => {
+                        return Err(format!(
+                            "Launch of '{}' ended without starting a game process",
+                            profile.name
+                        ))
+                    }
+                }
+            }
+        }
+    };
+
+    let Some(process_id) = process_id else {
+        println!("Launched '{}'", profile.name);
+        return Ok(());
+    };
+    println!("Launched '{}' as process {}", profile.name, process_id);
+
+    if detach {
+        return Ok(());
+    }
+    follow_process(process_id, events).await
+}
+
+/// The newest process of a profile, which is the one a launch just started.
+async fn newest_process(state: &State, profile_id: Uuid) -> Option<Uuid> {
+    state
+        .process_manager
+        .get_process_metadata_by_profile(profile_id)
+        .await
+        .into_iter()
+        .max_by_key(|p| p.start_time)
+        .map(|p| p.id)
+}
+
+fn print_progress(event: &EventPayload) {
+    match event.progress {
+        Some(progress) => eprintln!("[{:>3.0}%] {}", progress * 100.0, event.message),
+        None => eprintln!("{}", event.message),
+    }
+}
+
+async fn logs(process_id: Uuid, follow: bool) -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+    // Subscribe before reading the file so no line falls between the two
+    let events = state.event_state.subscribe();
+    let content = process_command::get_full_log(process_id)
+        .await
+        .map_err(cmd_err)?;
+    print!("{}", content);
+
+    if follow {
+        follow_process(process_id, events).await?;
+    }
+    Ok(())
+}
+
+/// Prints game output for `process_id` until the process is gone.
+async fn follow_process(
+    process_id: Uuid,
+    mut events: tokio::sync::broadcast::Receiver<EventPayload>,
+) -> CliResult<()> {
+    let state = State::get().await.map_err(app_err)?;
+    let mut liveness_check = interval(Duration::from_secs(2));
+
+    loop {
+        tokio::select! {
+            received = events.recv() => match received {
+                Ok(event) if event.target_id == Some(process_id) => match event.event_type {
+                    EventType::MinecraftOutput => println!("{}", event.message),
+                    EventType::MinecraftProcessExited => {
+                        eprintln!("Process {} exited", process_id);
+                        return match event.error {
+                            Some(error) => Err(error),
+                            None => Ok(()),
+                        };
+                    }
+                    _ => {}
+                },
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            _ = liveness_check.tick() => {
+                // Covers processes started by another launcher instance, which
+                // never produce an exit event in this one.
+                match state.process_manager.get_process_metadata(process_id).await {
+                    None => {
+                        eprintln!("Process {} is no longer running", process_id);
+                        return Ok(());
+                    }
+                    Some(metadata) => match metadata.state {
+                        ProcessState::Stopped => {
+                            eprintln!("Process {} stopped", process_id);
+                            return Ok(());
+                        }
+                        ProcessState::Crashed(error) => return Err(error),
+                        _ => {}
+                    },
+                }
+            }
+        }
+    }
+}
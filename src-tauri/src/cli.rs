@@ -0,0 +1,146 @@
+//! Command line definitions of `norisk-cli` and the helpers it shares with tests.
+
+use crate::error::{AppError, Result};
+use crate::state::profile_state::Profile;
+use crate::utils::profile_utils::ContentType;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+#[derive(Parser)]
+#[command(name = "norisk-cli", version, about = "Headless NoRiskClient launcher")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage profiles
+    Profiles {
+        #[command(subcommand)]
+        command: ProfilesCommand,
+    },
+    /// Install and launch a profile
+    Launch {
+        /// Profile name or ID
+        profile: String,
+        /// Quick Play: join this server directly
+        #[arg(long, conflicts_with = "world")]
+        server: Option<String>,
+        /// Quick Play: open this singleplayer world directly
+        #[arg(long)]
+        world: Option<String>,
+        /// Return as soon as the game process has started instead of following its output
+        #[arg(long)]
+        detach: bool,
+    },
+    /// Stop running game processes (by process ID or all processes of a profile)
+    Stop {
+        /// Process ID, profile name or profile ID
+        target: String,
+    },
+    /// List running game processes
+    Processes,
+    /// Print (and optionally follow) the log of a running game process
+    Logs {
+        /// Process ID
+        process_id: Uuid,
+        /// Keep printing new lines until the process exits
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Install Modrinth content into a profile
+    Install {
+        /// Profile name or ID
+        profile: String,
+        /// Modrinth project ID or slug
+        project: String,
+        /// Specific Modrinth version ID (defaults to the newest compatible version)
+        #[arg(long)]
+        version: Option<String>,
+        #[arg(long, value_enum, default_value_t = ContentKind::Mod)]
+        kind: ContentKind,
+        /// Install even if Modrinth marks the mod as incompatible with installed mods
+        #[arg(long)]
+        ignore_incompatibilities: bool,
+    },
+    /// Import a .mrpack, .noriskpack or CurseForge .zip file as a new profile
+    Import { file: PathBuf },
+    /// Inspect or clean up the shared content store
+    Store {
+        #[command(subcommand)]
+        command: StoreCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StoreCommand {
+    /// Show blob count, size and references
+    Stats,
+    /// Delete blobs no profile references anymore
+    Gc,
+}
+
+#[derive(Subcommand)]
+pub enum ProfilesCommand {
+    /// List all user profiles
+    List,
+    /// Create a new profile
+    Create {
+        name: String,
+        #[arg(long)]
+        game_version: String,
+        #[arg(long, default_value = "vanilla")]
+        loader: String,
+        #[arg(long)]
+        loader_version: Option<String>,
+        /// NoRisk pack ID to select for the new profile
+        #[arg(long)]
+        pack: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ContentKind {
+    Mod,
+    Resourcepack,
+    Shaderpack,
+    Datapack,
+}
+
+impl From<ContentKind> for ContentType {
+    fn from(kind: ContentKind) -> Self {
+        match kind {
+            ContentKind::Mod => ContentType::Mod,
+            ContentKind::Resourcepack => ContentType::ResourcePack,
+            ContentKind::Shaderpack => ContentType::ShaderPack,
+            ContentKind::Datapack => ContentType::DataPack,
+        }
+    }
+}
+
+/// Finds a profile by ID or (case-insensitive) name.
+pub fn find_profile(profiles: Vec<Profile>, name_or_id: &str) -> Result<Profile> {
+    if let Ok(id) = Uuid::parse_str(name_or_id) {
+        return profiles
+            .into_iter()
+            .find(|p| p.id == id)
+            .ok_or(AppError::ProfileNotFound(id));
+    }
+
+    let mut matches = profiles
+        .into_iter()
+        .filter(|p| p.name.eq_ignore_ascii_case(name_or_id));
+    match (matches.next(), matches.next()) {
+        (Some(profile), None) => Ok(profile),
+        (Some(_), Some(_)) => Err(AppError::InvalidInput(format!(
+            "Profile name '{}' is ambiguous, use the profile ID instead",
+            name_or_id
+        ))),
+        (None, _) => Err(AppError::NotFound(format!(
+            "No profile named '{}' found",
+            name_or_id
+        ))),
+    }
+}
@@ -31,6 +31,8 @@ pub struct InstallContentPayload {
     content_type: profile_utils::ContentType, // Use ContentType from profile_utils
     loaders: Option<Vec<String>>,             // Added loaders
     game_versions: Option<Vec<String>>,       // Added game_versions
+    #[serde(default)]
+    ignore_incompatibilities: Option<bool>, // Install despite Modrinth incompatibilities
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -705,6 +707,7 @@ pub async fn install_content_to_profile(
                 payload.version_number,
                 payload.loaders,       // Pass loaders
                 payload.game_versions, // Pass game_versions
+                payload.ignore_incompatibilities,
             )
             .await
         }
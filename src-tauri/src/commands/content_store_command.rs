@@ -0,0 +1,20 @@
+use crate::error::CommandError;
+use crate::state::content_store_state::{ContentStoreGcReport, ContentStoreStats};
+use crate::state::state_manager::State;
+use log::info;
+
+/// Returns blob count, size and number of tracked references of the shared content store
+#[tauri::command]
+pub async fn get_content_store_stats() -> Result<ContentStoreStats, CommandError> {
+    info!("Command: Getting content store stats");
+    let state = State::get().await?;
+    Ok(state.content_store.stats().await?)
+}
+
+/// Deletes all blobs of the shared content store that no profile references anymore
+#[tauri::command]
+pub async fn collect_content_store_garbage() -> Result<ContentStoreGcReport, CommandError> {
+    info!("Command: Collecting content store garbage");
+    let state = State::get().await?;
+    Ok(state.content_store.garbage_collect().await?)
+}
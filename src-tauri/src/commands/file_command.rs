@@ -1,6 +1,7 @@
 use crate::error::{AppError, CommandError};
 use crate::integrations::norisk_packs::NoriskModEntryDefinition;
 use crate::utils::file_utils;
+use crate::utils::mod_metadata::{self, JarMetadata};
 use crate::utils::path_utils;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use image::ImageEncoder;
@@ -332,6 +333,52 @@ pub async fn get_icons_for_archives(
     Ok(results_map)
 }
 
+/// Reads the embedded mod metadata (fabric.mod.json, quilt.mod.json, mods.toml, mcmod.info)
+/// of a list of mod jars, including the declared icons and jar-in-jar mods.
+///
+/// # Arguments
+///
+/// * `archive_paths` - A vector of strings representing the paths to the jar files.
+///
+/// # Returns
+///
+/// A `HashMap` keyed by the original file paths. The value is `None` if the file
+/// couldn't be read.
+#[tauri::command]
+pub async fn get_mod_metadata_for_archives(
+    archive_paths: Vec<String>,
+) -> Result<HashMap<String, Option<JarMetadata>>, CommandError> {
+    info!(
+        "Reading mod metadata for {} archives...",
+        archive_paths.len()
+    );
+    let mut results_map: HashMap<String, Option<JarMetadata>> = HashMap::new();
+
+    for path_str in archive_paths {
+        match mod_metadata::read_jar_metadata(Path::new(&path_str), true).await {
+            Ok(metadata) => {
+                let names: Vec<&str> = metadata
+                    .all_mods()
+                    .into_iter()
+                    .map(|m| m.display_name())
+                    .collect();
+                debug!("Found mods {:?} in {}", names, path_str);
+                results_map.insert(path_str, Some(metadata));
+            }
+            Err(AppError::FileNotFound(_)) => {
+                warn!("Archive file not found: {}", path_str);
+                results_map.insert(path_str, None);
+            }
+            Err(e) => {
+                error!("Error reading mod metadata of {}: {}", path_str, e);
+                results_map.insert(path_str, None);
+            }
+        }
+    }
+
+    Ok(results_map)
+}
+
 /// Fetches the first PNG icon found within Norisk Pack mods as Base64 strings.
 ///
 /// # Arguments
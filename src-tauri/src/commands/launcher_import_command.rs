@@ -0,0 +1,49 @@
+use crate::error::CommandError;
+use crate::integrations::launcher_import::{
+    import_external_instance, scan_default_launchers, scan_launcher_directory, ExternalInstance,
+    ExternalLauncher,
+};
+use crate::state::state_manager::State;
+use log::{error, info};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Scans the default locations of Prism/MultiMC, the vanilla launcher and ATLauncher for instances
+#[tauri::command]
+pub async fn scan_external_launchers_command() -> Result<Vec<ExternalInstance>, CommandError> {
+    info!("Command: Scanning other launchers for instances");
+    Ok(scan_default_launchers().await)
+}
+
+/// Scans a user selected launcher directory (e.g. a portable MultiMC folder) for instances
+#[tauri::command]
+pub async fn scan_external_launcher_directory_command(
+    launcher: ExternalLauncher,
+    path: String,
+) -> Result<Vec<ExternalInstance>, CommandError> {
+    info!("Command: Scanning {:?} directory {}", launcher, path);
+    Ok(scan_launcher_directory(launcher, &PathBuf::from(path)).await?)
+}
+
+/// Imports the given instances as new profiles and returns the IDs of the created profiles
+#[tauri::command]
+pub async fn import_external_instances_command(
+    instances: Vec<ExternalInstance>,
+) -> Result<Vec<Uuid>, CommandError> {
+    info!("Command: Importing {} external instances", instances.len());
+    let state = State::get().await?;
+    let mut created = Vec::new();
+
+    for instance in &instances {
+        let profile_id = import_external_instance(instance).await?;
+        if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+            error!(
+                "Failed to emit TriggerProfileUpdate event for imported profile {}: {}",
+                profile_id, e
+            );
+        }
+        created.push(profile_id);
+    }
+
+    Ok(created)
+}
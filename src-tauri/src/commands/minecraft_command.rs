@@ -10,9 +10,14 @@ use crate::minecraft::dto::fabric_meta::FabricVersionInfo;
 use crate::minecraft::dto::minecraft_profile::MinecraftProfile;
 use crate::minecraft::dto::quilt_meta::QuiltVersionInfo;
 use crate::minecraft::dto::VersionManifest;
+use crate::state::favorite_servers_state::{FavoriteServer, FavoriteServerUpdate};
+use crate::state::server_monitor_state::{
+    MonitoredServer, MonitoredServerStatus, ServerMonitorConfig, ServerSample,
+};
 use crate::state::skin_state::MinecraftSkin;
 use crate::state::state_manager::State;
 use crate::utils::mc_utils;
+use crate::utils::servers_dat_utils;
 use log::{debug, error, info};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -585,6 +590,152 @@ pub async fn ping_minecraft_server(
     Ok(ping_result)
 }
 
+/// Lists the launcher-wide favorite servers.
+#[tauri::command]
+pub async fn get_favorite_servers() -> Result<Vec<FavoriteServer>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.favorite_servers_manager.list_favorites().await)
+}
+
+#[tauri::command]
+pub async fn add_favorite_server(
+    name: String,
+    address: String,
+    icon_base64: Option<String>,
+    auto_sync: bool,
+) -> Result<FavoriteServer, CommandError> {
+    info!(
+        "Command called: add_favorite_server '{}' ({})",
+        name, address
+    );
+    let state = State::get().await?;
+    Ok(state
+        .favorite_servers_manager
+        .add_favorite(&name, &address, icon_base64, auto_sync)
+        .await?)
+}
+
+/// Updates a favorite server. Changing the address remembers the old one, so the next sync
+/// fixes it in every profile.
+#[tauri::command]
+pub async fn update_favorite_server(
+    id: Uuid,
+    update: FavoriteServerUpdate,
+) -> Result<FavoriteServer, CommandError> {
+    info!("Command called: update_favorite_server {}", id);
+    let state = State::get().await?;
+    Ok(state
+        .favorite_servers_manager
+        .update_favorite(id, update)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn remove_favorite_server(id: Uuid) -> Result<bool, CommandError> {
+    info!("Command called: remove_favorite_server {}", id);
+    let state = State::get().await?;
+    Ok(state.favorite_servers_manager.remove_favorite(id).await?)
+}
+
+/// Pushes favorite servers (all if no `favorite_ids` are given) into the server lists of the
+/// given profiles (all profiles if no `profile_ids` are given).
+#[tauri::command]
+pub async fn sync_favorite_servers(
+    favorite_ids: Option<Vec<Uuid>>,
+    profile_ids: Option<Vec<Uuid>>,
+) -> Result<Vec<servers_dat_utils::ServerSyncResult>, CommandError> {
+    info!("Command called: sync_favorite_servers");
+    let state = State::get().await?;
+    Ok(state
+        .favorite_servers_manager
+        .sync_to_profiles(favorite_ids.as_deref(), profile_ids.as_deref())
+        .await?)
+}
+
+/// Lists the monitored servers with their last known state.
+#[tauri::command]
+pub async fn get_monitored_servers() -> Result<Vec<MonitoredServerStatus>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.server_monitor_manager.list_servers().await)
+}
+
+#[tauri::command]
+pub async fn get_server_monitor_config() -> Result<ServerMonitorConfig, CommandError> {
+    let state = State::get().await?;
+    Ok(state.server_monitor_manager.get_config().await)
+}
+
+#[tauri::command]
+pub async fn add_monitored_server(
+    name: String,
+    address: String,
+) -> Result<MonitoredServer, CommandError> {
+    info!(
+        "Command called: add_monitored_server '{}' ({})",
+        name, address
+    );
+    let state = State::get().await?;
+    Ok(state
+        .server_monitor_manager
+        .add_server(&name, &address)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn set_monitored_server_enabled(id: Uuid, enabled: bool) -> Result<(), CommandError> {
+    info!(
+        "Command called: set_monitored_server_enabled {} -> {}",
+        id, enabled
+    );
+    let state = State::get().await?;
+    Ok(state
+        .server_monitor_manager
+        .set_server_enabled(id, enabled)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn remove_monitored_server(id: Uuid) -> Result<bool, CommandError> {
+    info!("Command called: remove_monitored_server {}", id);
+    let state = State::get().await?;
+    Ok(state.server_monitor_manager.remove_server(id).await?)
+}
+
+/// Sets how often the monitored servers are polled and how long their history is kept.
+#[tauri::command]
+pub async fn set_server_monitor_schedule(
+    interval_secs: u64,
+    history_hours: Option<u32>,
+) -> Result<(), CommandError> {
+    info!(
+        "Command called: set_server_monitor_schedule every {}s",
+        interval_secs
+    );
+    let state = State::get().await?;
+    Ok(state
+        .server_monitor_manager
+        .set_schedule(interval_secs, history_hours)
+        .await?)
+}
+
+/// Latency and player count samples of a monitored server, optionally only newer ones.
+#[tauri::command]
+pub async fn get_server_history(
+    id: Uuid,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<ServerSample>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.server_monitor_manager.get_history(id, since).await?)
+}
+
+/// Polls all monitored servers right away instead of waiting for the next interval.
+#[tauri::command]
+pub async fn poll_monitored_servers() -> Result<Vec<MonitoredServerStatus>, CommandError> {
+    info!("Command called: poll_monitored_servers");
+    let state = State::get().await?;
+    Ok(state.server_monitor_manager.poll_all().await?)
+}
+
 #[tauri::command]
 pub async fn add_skin_locally(
     payload: AddLocalSkinCommandPayload,
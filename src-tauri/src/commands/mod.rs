@@ -1,8 +1,10 @@
 pub mod cape_command;
 pub mod config_commands;
 pub mod content_command;
+pub mod content_store_command;
 pub mod file_command;
 pub mod java_command;
+pub mod launcher_import_command;
 pub mod minecraft_auth_command;
 pub mod minecraft_command;
 pub mod modrinth_commands;
@@ -10,3 +12,4 @@ pub mod nrc_commands;
 pub mod path_commands;
 pub mod process_command;
 pub mod profile_command;
+pub mod server_command;
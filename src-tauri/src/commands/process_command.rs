@@ -1,6 +1,10 @@
 use crate::error::CommandError;
+use crate::minecraft::crash_analyzer::{self, CrashAnalysis};
 use crate::state::process_state::ProcessMetadata;
+use crate::state::session_state::{GameSession, SessionAggregates, SessionFilter};
 use crate::state::state_manager::State;
+use crate::utils::launch_log_utils::{self, LaunchLogInfo};
+use crate::utils::process_stats_utils::{ProcessStats, ProcessStatsRecord};
 use tauri::Manager;
 use uuid::Uuid;
 
@@ -30,6 +34,26 @@ pub async fn get_processes_by_profile(
     Ok(processes)
 }
 
+/// Recent memory/CPU/thread/disk samples and run totals of a running game process.
+/// `None` until the first sample was taken.
+#[tauri::command]
+pub async fn get_process_stats(process_id: Uuid) -> Result<Option<ProcessStats>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.process_manager.get_process_stats(process_id))
+}
+
+/// Resource summaries of finished game processes (of one profile if given), newest first.
+#[tauri::command]
+pub async fn get_process_stats_history(
+    profile_id: Option<Uuid>,
+) -> Result<Vec<ProcessStatsRecord>, CommandError> {
+    let state = State::get().await?;
+    Ok(state
+        .process_manager
+        .get_process_stats_history(profile_id)
+        .await?)
+}
+
 #[tauri::command]
 pub async fn stop_process(process_id: Uuid) -> Result<(), CommandError> {
     let state = State::get().await?;
@@ -47,6 +71,74 @@ pub async fn get_full_log(process_id: Uuid) -> Result<String, CommandError> {
     Ok(log_content)
 }
 
+/// Recorded game sessions matching the filter, newest first.
+#[tauri::command]
+pub async fn get_game_sessions(
+    filter: Option<SessionFilter>,
+) -> Result<Vec<GameSession>, CommandError> {
+    let state = State::get().await?;
+    Ok(state
+        .session_manager
+        .list_sessions(&filter.unwrap_or_default())
+        .await)
+}
+
+/// Playtime per profile, account and week and crash rates per pack version.
+#[tauri::command]
+pub async fn get_session_stats(
+    filter: Option<SessionFilter>,
+) -> Result<SessionAggregates, CommandError> {
+    let state = State::get().await?;
+    Ok(state
+        .session_manager
+        .aggregate(&filter.unwrap_or_default())
+        .await)
+}
+
+/// Lists the captured stdout/stderr logs of past launches of a profile, newest first.
+#[tauri::command]
+pub async fn get_launch_logs(profile_id: Uuid) -> Result<Vec<LaunchLogInfo>, CommandError> {
+    let state = State::get().await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    Ok(
+        launch_log_utils::list_launch_logs(&launch_log_utils::launch_logs_dir(&instance_path))
+            .await?,
+    )
+}
+
+#[tauri::command]
+pub async fn read_launch_log(profile_id: Uuid, launch_id: String) -> Result<String, CommandError> {
+    let state = State::get().await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    Ok(launch_log_utils::read_launch_log(
+        &launch_log_utils::launch_logs_dir(&instance_path),
+        &launch_id,
+    )
+    .await?)
+}
+
+/// Analyzes the last run of a profile for known crash causes.
+#[tauri::command]
+pub async fn analyze_profile_crash(profile_id: Uuid) -> Result<CrashAnalysis, CommandError> {
+    Ok(crash_analyzer::analyze_profile(profile_id, None, None).await?)
+}
+
+/// Analyzes a crash report and/or log the user provides, e.g. from a log file.
+#[tauri::command]
+pub async fn analyze_crash_log(
+    crash_report: Option<String>,
+    log: Option<String>,
+    profile_id: Option<Uuid>,
+) -> Result<CrashAnalysis, CommandError> {
+    Ok(crash_analyzer::analyze_text(crash_report.as_deref(), log.as_deref(), profile_id).await?)
+}
+
 #[tauri::command]
 pub async fn open_log_window<R: tauri::Runtime>(
     app: tauri::AppHandle<R>,
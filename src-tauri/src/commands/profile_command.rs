@@ -1,16 +1,24 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, CommandError};
+use crate::integrations::curseforge;
 use crate::integrations::modrinth::ModrinthVersion;
 use crate::integrations::mrpack;
 use crate::integrations::norisk_packs::NoriskModpacksConfig;
 use crate::integrations::norisk_versions::NoriskVersionsConfig;
 use crate::minecraft::installer;
+use crate::minecraft::mod_bisect::{self, BisectReport};
+use crate::minecraft::mod_checker::{self, ModCheckReport};
+use crate::minecraft::offline_check::{self, OfflineReadinessReport};
+use crate::minecraft::profile_verifier::{self, ProfileVerifyReport};
+use crate::minecraft::server_installer;
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::{
-    default_profile_path, CustomModInfo, ModLoader, Profile, ProfileSettings, ProfileState,
+    default_profile_path, CustomModInfo, Mod, ModLoader, Profile, ProfileKind, ProfileSettings,
+    ProfileState,
 };
 use crate::state::state_manager::State;
 use crate::utils::datapack_utils::DataPackInfo;
+use crate::utils::level_dat_utils::{self, LevelDatEdit, WorldDetails};
 use crate::utils::mc_utils::{self, WorldInfo};
 use crate::utils::path_utils::find_unique_profile_segment;
 use crate::utils::profile_utils::{
@@ -19,7 +27,12 @@ use crate::utils::profile_utils::{
     LocalContentLoader as ProfileUtilLocalContentLoader, ScreenshotInfo,
 };
 use crate::utils::resourcepack_utils::ResourcePackInfo;
+use crate::utils::server_properties_utils;
+use crate::utils::servers_dat_utils::{self, ServerEntryEdit};
 use crate::utils::shaderpack_utils::ShaderPackInfo;
+use crate::utils::world_backup_utils::{BackupTrigger, WorldBackup};
+use crate::utils::world_map_utils::{self, WorldMap};
+use crate::utils::world_transfer_utils::{self, WorldCompatibilityReport, WorldImportResult};
 use crate::utils::world_utils;
 use crate::utils::{
     datapack_utils, path_utils, profile_utils, resourcepack_utils, shaderpack_utils,
@@ -39,11 +52,15 @@ use uuid::Uuid;
 // DTOs für Command-Parameter
 #[derive(Deserialize)]
 pub struct CreateProfileParams {
-    name: String,
-    game_version: String,
-    loader: String,
-    loader_version: Option<String>,
-    selected_norisk_pack_id: Option<String>,
+    pub name: String,
+    pub game_version: String,
+    pub loader: String,
+    pub loader_version: Option<String>,
+    pub selected_norisk_pack_id: Option<String>,
+    #[serde(default)]
+    pub kind: ProfileKind,
+    #[serde(default)]
+    pub linked_client_profile_id: Option<Uuid>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -76,6 +93,26 @@ pub struct ExportProfileParams {
     file_name: String,           // Base name without extension
     include_files: Option<Vec<PathBuf>>,
     open_folder: bool, // Whether to open the exports folder after export
+    #[serde(default)]
+    format: ExportFormat, // Target archive format, defaults to .noriskpack
+}
+
+// Archive formats a profile can be exported to
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Noriskpack,
+    Mrpack,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Noriskpack => "noriskpack",
+            ExportFormat::Mrpack => "mrpack",
+        }
+    }
 }
 
 // DTO for the new command
@@ -143,6 +180,8 @@ pub async fn create_profile(params: CreateProfileParams) -> Result<Uuid, Command
         background: None,
         is_standard_version: false,
         norisk_information: None,
+        kind: params.kind,
+        linked_client_profile_id: params.linked_client_profile_id,
     };
 
     let id = state.profile_manager.create_profile(profile).await?;
@@ -228,18 +267,30 @@ pub async fn launch_profile(
 
     let version = profile.game_version.clone();
     let modloader = profile.loader.clone();
-    let credentials = match state
-        .minecraft_account_manager_v2
-        .get_active_account()
-        .await
-    {
-        Ok(Some(creds)) => Some(creds),
-        Ok(None) => {
-            return Err(CommandError::from(AppError::NoCredentialsError));
+    let is_server = profile.kind == ProfileKind::Server;
+    let credentials = if is_server {
+        // Der Server startet erst, wenn die EULA akzeptiert wurde, und braucht keinen Account
+        let server_dir = state
+            .profile_manager
+            .calculate_instance_path_for_profile(&profile)?;
+        if !server_properties_utils::is_eula_accepted(&server_dir).await? {
+            return Err(CommandError::from(AppError::EulaNotAccepted(id)));
         }
-        Err(e) => {
-            info!("Error getting active account: {}", e);
-            return Err(CommandError::from(AppError::NoCredentialsError));
+        None
+    } else {
+        match state
+            .minecraft_account_manager_v2
+            .get_active_account()
+            .await
+        {
+            Ok(Some(creds)) => Some(creds),
+            Ok(None) => {
+                return Err(CommandError::from(AppError::NoCredentialsError));
+            }
+            Err(e) => {
+                info!("Error getting active account: {}", e);
+                return Err(CommandError::from(AppError::NoCredentialsError));
+            }
         }
     };
 
@@ -267,15 +318,21 @@ pub async fn launch_profile(
 
     // Spawn the installation task and get the JoinHandle
     let handle = tokio::spawn(async move {
-        let install_result = installer::install_minecraft_version(
-            &version,
-            &modloader.as_str(),
-            &profile_clone,
-            credentials,
-            quick_play_sp_clone,
-            quick_play_mp_clone,
-        )
-        .await;
+        let install_result = if is_server {
+            server_installer::install_and_launch_server(&profile_clone)
+                .await
+                .map(|_| ())
+        } else {
+            installer::install_minecraft_version(
+                &version,
+                &modloader.as_str(),
+                &profile_clone,
+                credentials,
+                quick_play_sp_clone,
+                quick_play_mp_clone,
+            )
+            .await
+        };
 
         // Get state again within the spawn context
         if let Ok(state) = State::get().await {
@@ -331,8 +388,8 @@ pub async fn launch_profile(
             }
         } else {
             error!(
-                "Failed to get state within spawned task for profile_id: {}. Install error (if any): {:?}", 
-                profile_id, 
+                "Failed to get state within spawned task for profile_id: {}. Install error (if any): {:?}",
+                profile_id,
                 install_result.err().map(|e| e.to_string())
             );
         }
@@ -528,6 +585,7 @@ pub async fn add_modrinth_mod_to_profile(
     version_number: Option<String>,
     loaders: Option<Vec<String>>,
     game_versions: Option<Vec<String>>,
+    ignore_incompatibilities: Option<bool>,
 ) -> Result<(), CommandError> {
     info!(
         "Executing add_mod_to_profile command for profile {}",
@@ -549,6 +607,8 @@ pub async fn add_modrinth_mod_to_profile(
             loaders,
             game_versions,
             true,
+            ignore_incompatibilities.unwrap_or(false),
+            None,
         )
         .await?)
 }
@@ -595,14 +655,38 @@ pub async fn set_profile_mod_enabled(
 }
 
 #[tauri::command]
-pub async fn delete_mod_from_profile(profile_id: Uuid, mod_id: Uuid) -> Result<(), CommandError> {
+pub async fn delete_mod_from_profile(
+    profile_id: Uuid,
+    mod_id: Uuid,
+    remove_orphaned_dependencies: Option<bool>,
+) -> Result<Vec<Mod>, CommandError> {
     info!(
-        "Received command delete_mod_from_profile: profile={}, mod={}",
-        profile_id, mod_id
+        "Received command delete_mod_from_profile: profile={}, mod={}, remove_orphaned_dependencies={:?}",
+        profile_id, mod_id, remove_orphaned_dependencies
     );
     let state = State::get().await?;
+    if remove_orphaned_dependencies.unwrap_or(false) {
+        let removed = state
+            .profile_manager
+            .delete_mod_with_orphaned_dependencies(profile_id, mod_id)
+            .await?;
+        return Ok(removed);
+    }
     state.profile_manager.delete_mod(profile_id, mod_id).await?;
-    Ok(())
+    Ok(Vec::new())
+}
+
+/// Dependencies that would be left unused by deleting the mod, so the UI can offer removing them too.
+#[tauri::command]
+pub async fn get_orphaned_mod_dependencies(
+    profile_id: Uuid,
+    mod_id: Uuid,
+) -> Result<Vec<Mod>, CommandError> {
+    let state = State::get().await?;
+    Ok(state
+        .profile_manager
+        .get_orphaned_dependencies(profile_id, mod_id)
+        .await?)
 }
 
 // Command to retrieve the list of available Norisk Modpacks
@@ -903,8 +987,8 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
         app_handle
             .dialog()
             .file()
-            .add_filter("Modpack Files", &["mrpack", "noriskpack"])
-            .set_title("Select Modpack File (.mrpack or .noriskpack)")
+            .add_filter("Modpack Files", &["mrpack", "noriskpack", "zip"])
+            .set_title("Select Modpack File (.mrpack, .noriskpack or CurseForge .zip)")
             .blocking_pick_file() // Use the blocking version for single file selection
     })
     .await
@@ -943,13 +1027,17 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
                 crate::integrations::norisk_packs::import_noriskpack_as_profile(file_path_buf)
                     .await?
             }
+            Some("zip") => {
+                log::info!("File extension is .zip, proceeding with CurseForge pack processing.");
+                import_curseforge_zip(file_path_buf).await?
+            }
             _ => {
                 log::error!(
                     "Selected file has an invalid extension: {:?}",
                     file_path_buf
                 );
                 return Err(CommandError::from(AppError::Other(
-                    "Invalid file type selected. Please select a .mrpack or .noriskpack file."
+                    "Invalid file type selected. Please select a .mrpack, .noriskpack or CurseForge .zip file."
                         .to_string(),
                 )));
             }
@@ -977,6 +1065,46 @@ pub async fn import_profile_from_file(app_handle: tauri::AppHandle) -> Result<()
     }
 }
 
+/// Imports a CurseForge modpack zip and reports files that could not be resolved
+/// (e.g. mods whose authors disabled third-party downloads) as an error event.
+async fn import_curseforge_zip(file_path: PathBuf) -> Result<Uuid, AppError> {
+    let state = State::get().await?;
+    let api_key = state.config_manager.get_config().await.curseforge_api_key;
+    let api = curseforge::CurseForgeApi::from_env_or_config(api_key);
+    let result = curseforge::import_curseforge_pack_as_profile(file_path, &api).await?;
+
+    if !result.unresolved.is_empty() {
+        let names: Vec<String> = result
+            .unresolved
+            .iter()
+            .map(|f| match &f.file_name {
+                Some(name) => format!("{} ({})", name, f.reason),
+                None => format!("project {} file {} ({})", f.project_id, f.file_id, f.reason),
+            })
+            .collect();
+
+        let state = State::get().await?;
+        if let Err(e) = state
+            .emit_event(EventPayload {
+                event_id: Uuid::new_v4(),
+                event_type: EventType::Error,
+                target_id: Some(result.profile_id),
+                message: format!(
+                    "{} file(s) of the CurseForge pack could not be imported and must be added manually",
+                    result.unresolved.len()
+                ),
+                progress: None,
+                error: Some(names.join("\n")),
+            })
+            .await
+        {
+            log::error!("Failed to emit unresolved files event: {}", e);
+        }
+    }
+
+    Ok(result.profile_id)
+}
+
 /// Imports a profile from a specified file path.
 #[tauri::command]
 pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError> {
@@ -1015,13 +1143,17 @@ pub async fn import_profile(file_path_str: String) -> Result<Uuid, CommandError>
             log::info!("File extension is .noriskpack, proceeding with noriskpack processing.");
             crate::integrations::norisk_packs::import_noriskpack_as_profile(file_path_buf).await?
         }
+        Some("zip") => {
+            log::info!("File extension is .zip, proceeding with CurseForge pack processing.");
+            import_curseforge_zip(file_path_buf).await?
+        }
         _ => {
             log::error!(
                 "Selected file has an invalid extension: {:?}",
                 file_path_buf
             );
             return Err(CommandError::from(AppError::Other(
-                "Invalid file type selected. Please select a .mrpack or .noriskpack file."
+                "Invalid file type selected. Please select a .mrpack, .noriskpack or CurseForge .zip file."
                     .to_string(),
             )));
         }
@@ -1285,6 +1417,8 @@ pub async fn copy_profile(params: CopyProfileParams) -> Result<Uuid, CommandErro
         norisk_information: source_profile.norisk_information.clone(),
         banner: source_profile.banner.clone(),
         background: source_profile.background.clone(),
+        kind: source_profile.kind,
+        linked_client_profile_id: source_profile.linked_client_profile_id,
     };
 
     // 6. Erstelle das neue Profilverzeichnis
@@ -1373,7 +1507,7 @@ pub async fn export_profile(
         .await
         .map_err(|e| CommandError::from(AppError::Io(e)))?;
 
-    // Sanitize the filename and add .noriskpack extension
+    // Sanitize the filename and add the extension of the chosen format
     let sanitized_name = sanitize(&params.file_name);
     if sanitized_name.is_empty() {
         return Err(CommandError::from(AppError::Other(
@@ -1382,20 +1516,58 @@ pub async fn export_profile(
     }
 
     // Generate complete filename with extension
-    let noriskpack_filename = format!("{}.noriskpack", sanitized_name);
+    let export_filename = format!("{}.{}", sanitized_name, params.format.extension());
 
     // Create full export path
-    let export_path = exports_dir.join(&noriskpack_filename);
+    let export_path = exports_dir.join(&export_filename);
 
-    info!("Exporting profile to {}", export_path.display());
+    info!(
+        "Exporting profile to {} ({:?})",
+        export_path.display(),
+        params.format
+    );
 
     // Perform the export
-    let result_path = profile_utils::export_profile_to_noriskpack(
-        params.profile_id,
-        Some(export_path.clone()),
-        params.include_files,
-    )
-    .await?;
+    let result_path = match params.format {
+        ExportFormat::Noriskpack => {
+            profile_utils::export_profile_to_noriskpack(
+                params.profile_id,
+                Some(export_path.clone()),
+                params.include_files,
+            )
+            .await?
+        }
+        ExportFormat::Mrpack => {
+            let export = mrpack::export_profile_to_mrpack(
+                params.profile_id,
+                Some(export_path.clone()),
+                params.include_files,
+            )
+            .await?;
+            if !export.skipped_mods.is_empty() {
+                // Die Datei ist trotzdem brauchbar, der Nutzer soll aber wissen, was fehlt
+                let state = State::get().await?;
+                let message = format!(
+                    "Not included in the .mrpack: {}",
+                    export.skipped_mods.join(", ")
+                );
+                if let Err(e) = state
+                    .emit_event(EventPayload {
+                        event_id: Uuid::new_v4(),
+                        event_type: EventType::Error,
+                        target_id: Some(params.profile_id),
+                        message: message.clone(),
+                        progress: None,
+                        error: Some(message),
+                    })
+                    .await
+                {
+                    warn!("Failed to report skipped .mrpack mods: {}", e);
+                }
+            }
+            export.path
+        }
+    };
 
     // Open the export directory if requested
     if params.open_folder {
@@ -1679,6 +1851,79 @@ pub async fn get_servers_for_profile(
     Ok(mc_utils::get_profile_servers(profile_id).await?)
 }
 
+/// Adds a server to the multiplayer list of a profile (at the end unless `index` is given).
+/// Returns the updated list.
+#[tauri::command]
+pub async fn add_profile_server(
+    profile_id: Uuid,
+    name: String,
+    address: String,
+    index: Option<usize>,
+) -> Result<Vec<mc_utils::ServerInfo>, CommandError> {
+    info!(
+        "Executing add_profile_server command for profile {}: '{}' ({})",
+        profile_id, name, address
+    );
+    Ok(
+        servers_dat_utils::modify_profile_servers(profile_id, |root| {
+            servers_dat_utils::add_server(root, &name, &address, index).map(|_| ())
+        })
+        .await?,
+    )
+}
+
+/// Edits the server at `index` in the multiplayer list of a profile.
+#[tauri::command]
+pub async fn edit_profile_server(
+    profile_id: Uuid,
+    index: usize,
+    edit: ServerEntryEdit,
+) -> Result<Vec<mc_utils::ServerInfo>, CommandError> {
+    info!(
+        "Executing edit_profile_server command for profile {}, index {}",
+        profile_id, index
+    );
+    Ok(
+        servers_dat_utils::modify_profile_servers(profile_id, |root| {
+            servers_dat_utils::edit_server(root, index, &edit)
+        })
+        .await?,
+    )
+}
+
+/// Removes the server at `index` from the multiplayer list of a profile.
+#[tauri::command]
+pub async fn remove_profile_server(
+    profile_id: Uuid,
+    index: usize,
+) -> Result<Vec<mc_utils::ServerInfo>, CommandError> {
+    info!(
+        "Executing remove_profile_server command for profile {}, index {}",
+        profile_id, index
+    );
+    Ok(
+        servers_dat_utils::modify_profile_servers(profile_id, |root| {
+            servers_dat_utils::remove_server(root, index)
+        })
+        .await?,
+    )
+}
+
+/// Moves a server within the multiplayer list of a profile.
+#[tauri::command]
+pub async fn move_profile_server(
+    profile_id: Uuid,
+    from_index: usize,
+    to_index: usize,
+) -> Result<Vec<mc_utils::ServerInfo>, CommandError> {
+    Ok(
+        servers_dat_utils::modify_profile_servers(profile_id, |root| {
+            servers_dat_utils::move_server(root, from_index, to_index)
+        })
+        .await?,
+    )
+}
+
 /// Copies a singleplayer world to another profile (or the same one) with a new name.
 #[tauri::command]
 pub async fn copy_world(params: CopyWorldParams) -> Result<String, CommandError> {
@@ -1811,6 +2056,12 @@ pub async fn delete_world(profile_id: Uuid, world_folder: String) -> Result<(),
 
     // Call the utility function to perform the deletion
     world_utils::delete_world_directory(profile_id, &world_folder).await?;
+    if let Err(e) = world_map_utils::clear_world_map_cache(profile_id, &world_folder).await {
+        warn!(
+            "Failed to remove cached map of deleted world '{}': {}",
+            world_folder, e
+        );
+    }
 
     // Trigger UI update for the affected profile
     if let Ok(state) = State::get().await {
@@ -1828,6 +2079,227 @@ pub async fn delete_world(profile_id: Uuid, world_folder: String) -> Result<(),
     Ok(())
 }
 
+/// Reads all details of a world: level.dat (seed, spawn, game rules, datapacks, ...), size on
+/// disk, player count and dimensions.
+#[tauri::command]
+pub async fn get_world_details(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<WorldDetails, CommandError> {
+    Ok(level_dat_utils::get_world_details(profile_id, &world_folder).await?)
+}
+
+/// Changes the name, difficulty, game type, commands setting or game rules of a world that
+/// isn't open. The previous level.dat is kept next to it.
+#[tauri::command]
+pub async fn edit_world_settings(
+    profile_id: Uuid,
+    world_folder: String,
+    edit: LevelDatEdit,
+) -> Result<WorldDetails, CommandError> {
+    info!(
+        "Executing edit_world_settings command for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    let details = level_dat_utils::edit_world(profile_id, &world_folder, &edit).await?;
+
+    if let Ok(state) = State::get().await {
+        if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+            warn!(
+                "Failed to emit profile update event after editing world '{}' in profile {}: {}",
+                world_folder, profile_id, e
+            );
+        }
+    }
+    Ok(details)
+}
+
+/// Renders a top-down map of a world dimension (default: overworld) from its region files.
+/// Tiles are cached and only regions changed since the last render are drawn again.
+#[tauri::command]
+pub async fn render_world_map(
+    profile_id: Uuid,
+    world_folder: String,
+    dimension: Option<String>,
+) -> Result<WorldMap, CommandError> {
+    Ok(world_map_utils::render_world_map(profile_id, &world_folder, dimension.as_deref()).await?)
+}
+
+/// Removes the cached map tiles of a world.
+#[tauri::command]
+pub async fn clear_world_map_cache(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<(), CommandError> {
+    Ok(world_map_utils::clear_world_map_cache(profile_id, &world_folder).await?)
+}
+
+/// Checks a world against another profile before copying it there: downgrades and
+/// dimensions of mods the target profile doesn't have.
+#[tauri::command]
+pub async fn check_world_transfer(
+    source_profile_id: Uuid,
+    source_world_folder: String,
+    target_profile_id: Uuid,
+) -> Result<WorldCompatibilityReport, CommandError> {
+    Ok(world_transfer_utils::check_world_transfer(
+        source_profile_id,
+        &source_world_folder,
+        target_profile_id,
+    )
+    .await?)
+}
+
+/// Exports a world as a zip. Without `output_path` the zip is written to the exports folder.
+/// Returns the path of the zip.
+#[tauri::command]
+pub async fn export_world(
+    profile_id: Uuid,
+    world_folder: String,
+    output_path: Option<String>,
+) -> Result<String, CommandError> {
+    info!(
+        "Executing export_world command for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    let output_path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let file_name = sanitize(&world_folder);
+            if file_name.is_empty() {
+                return Err(CommandError::from(AppError::InvalidInput(format!(
+                    "Invalid world folder name '{}'",
+                    world_folder
+                ))));
+            }
+            LAUNCHER_DIRECTORY
+                .root_dir()
+                .join("exports")
+                .join(format!("{}.zip", file_name))
+        }
+    };
+    let path =
+        world_transfer_utils::export_world_to_zip(profile_id, &world_folder, &output_path).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Reads the world in a zip and checks it against the target profile without importing it.
+#[tauri::command]
+pub async fn inspect_world_zip(
+    file_path: String,
+    target_profile_id: Uuid,
+) -> Result<WorldCompatibilityReport, CommandError> {
+    Ok(
+        world_transfer_utils::inspect_world_zip(&PathBuf::from(file_path), target_profile_id)
+            .await?,
+    )
+}
+
+/// Imports a world from a zip into a profile under a unique folder name.
+#[tauri::command]
+pub async fn import_world_zip(
+    profile_id: Uuid,
+    file_path: String,
+    world_name: Option<String>,
+) -> Result<WorldImportResult, CommandError> {
+    info!(
+        "Executing import_world_zip command for profile {} from {}",
+        profile_id, file_path
+    );
+    let result = world_transfer_utils::import_world_from_zip(
+        profile_id,
+        &PathBuf::from(file_path),
+        world_name,
+    )
+    .await?;
+
+    if let Ok(state) = State::get().await {
+        if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+            warn!(
+                "Failed to emit profile update event after importing world '{}' into profile {}: {}",
+                result.folder_name, profile_id, e
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Creates a manual backup of a world.
+#[tauri::command]
+pub async fn create_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<WorldBackup, CommandError> {
+    info!(
+        "Executing create_world_backup command for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .create_backup(profile_id, &world_folder, BackupTrigger::Manual)
+        .await?)
+}
+
+/// Lists the backups of a world, newest first.
+#[tauri::command]
+pub async fn list_world_backups(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<Vec<WorldBackup>, CommandError> {
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .list_backups(profile_id, &world_folder)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn delete_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+    backup_id: String,
+) -> Result<(), CommandError> {
+    info!(
+        "Executing delete_world_backup command for profile {}, world '{}', backup {}",
+        profile_id, world_folder, backup_id
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .delete_backup(profile_id, &world_folder, &backup_id)
+        .await?)
+}
+
+/// Restores a world backup. Without `new_world_name` the world is replaced (after backing up
+/// its current state), otherwise the backup is restored as a new world.
+/// Returns the folder name of the restored world.
+#[tauri::command]
+pub async fn restore_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+    backup_id: String,
+    new_world_name: Option<String>,
+) -> Result<String, CommandError> {
+    info!(
+        "Executing restore_world_backup command for profile {}, world '{}', backup {} (new name: {:?})",
+        profile_id, world_folder, backup_id, new_world_name
+    );
+    let state = State::get().await?;
+    let restored_folder = state
+        .world_backup_manager
+        .restore_backup(profile_id, &world_folder, &backup_id, new_world_name)
+        .await?;
+
+    if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+        warn!(
+            "Failed to emit profile update event after restoring world '{}' in profile {}: {}",
+            restored_folder, profile_id, e
+        );
+    }
+    Ok(restored_folder)
+}
+
 // Added: Command to list screenshots for a profile
 #[tauri::command]
 pub async fn list_profile_screenshots(
@@ -1984,3 +2456,73 @@ pub async fn get_local_content(
         }
     }
 }
+
+/// Checks whether a profile can be launched in offline mode (metadata, libraries, assets,
+/// client jar and Java already on disk).
+#[tauri::command]
+pub async fn check_offline_readiness(
+    profile_id: Uuid,
+) -> Result<OfflineReadinessReport, CommandError> {
+    info!(
+        "Executing check_offline_readiness command for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let report = offline_check::check_profile_offline_readiness(&profile).await?;
+    Ok(report)
+}
+
+/// Checks libraries, assets, client jar, natives, loader libraries and mods of a profile
+/// and re-downloads broken files. Each repair is reported as a `VerifyingProfile` event.
+#[tauri::command]
+pub async fn verify_profile(profile_id: Uuid) -> Result<ProfileVerifyReport, CommandError> {
+    info!(
+        "Executing verify_profile command for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let report = profile_verifier::verify_profile(&profile).await?;
+    info!(
+        "Profile {} verified: {} issues, {} repaired",
+        profile_id,
+        report.issues.len(),
+        report.repaired_count()
+    );
+    Ok(report)
+}
+
+/// Checks the mods a profile would launch with for missing dependencies, duplicates and
+/// declared incompatibilities without launching it.
+#[tauri::command]
+pub async fn check_profile_mods(profile_id: Uuid) -> Result<ModCheckReport, CommandError> {
+    info!(
+        "Executing check_profile_mods command for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    Ok(mod_checker::check_profile_mods(&profile).await?)
+}
+
+/// Launches the profile repeatedly with parts of its mods disabled to find the mods that
+/// crash it. Progress is reported as `BisectingMods` events, the mods are restored afterwards.
+#[tauri::command]
+pub async fn bisect_profile_mods(profile_id: Uuid) -> Result<BisectReport, CommandError> {
+    info!(
+        "Executing bisect_profile_mods command for profile {}",
+        profile_id
+    );
+    Ok(mod_bisect::bisect_profile(profile_id).await?)
+}
+
+/// Stops a running mod bisection after the current launch.
+#[tauri::command]
+pub async fn cancel_mod_bisect(profile_id: Uuid) -> Result<(), CommandError> {
+    info!(
+        "Executing cancel_mod_bisect command for profile {}",
+        profile_id
+    );
+    Ok(mod_bisect::cancel_bisect(profile_id)?)
+}
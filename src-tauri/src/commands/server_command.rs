@@ -0,0 +1,174 @@
+use crate::commands::profile_command::{self, CreateProfileParams};
+use crate::error::{AppError, CommandError};
+use crate::state::profile_state::{Mod, ModSource, ProfileKind};
+use crate::state::state_manager::State;
+use crate::utils::server_properties_utils::{self, DEFAULT_SERVER_PORT};
+use log::info;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Instance directory of a server profile, errors for client profiles.
+async fn server_dir(profile_id: Uuid) -> Result<PathBuf, CommandError> {
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    if profile.kind != ProfileKind::Server {
+        return Err(CommandError::from(AppError::InvalidOperation(format!(
+            "Profile '{}' is not a server profile",
+            profile.name
+        ))));
+    }
+    Ok(state
+        .profile_manager
+        .calculate_instance_path_for_profile(&profile)?)
+}
+
+/// Creates a server profile with the version, loader and Modrinth mods of a client profile,
+/// linked to it for `launch_client_into_server`.
+#[tauri::command]
+pub async fn create_server_profile_for_client(
+    client_profile_id: Uuid,
+    name: Option<String>,
+) -> Result<Uuid, CommandError> {
+    let state = State::get().await?;
+    let client = state.profile_manager.get_profile(client_profile_id).await?;
+    if client.kind != ProfileKind::Client {
+        return Err(CommandError::from(AppError::InvalidOperation(format!(
+            "Profile '{}' is not a client profile",
+            client.name
+        ))));
+    }
+    let params = CreateProfileParams {
+        name: name.unwrap_or_else(|| format!("{} Server", client.name)),
+        game_version: client.game_version.clone(),
+        loader: client.loader.as_str().to_string(),
+        loader_version: client.loader_version.clone(),
+        selected_norisk_pack_id: None,
+        kind: ProfileKind::Server,
+        linked_client_profile_id: Some(client.id),
+    };
+    let id = profile_command::create_profile(params).await?;
+
+    // The server needs the same mods, client-only ones are skipped when it starts
+    let mut server = state.profile_manager.get_profile(id).await?;
+    let mods: Vec<Mod> = client
+        .mods
+        .iter()
+        .filter(|m| matches!(m.source, ModSource::Modrinth { .. }))
+        .cloned()
+        .collect();
+    if !mods.is_empty() {
+        let copied_ids: HashSet<Uuid> = mods.iter().map(|m| m.id).collect();
+        server.mods = mods;
+        for m in &mut server.mods {
+            m.dependencies.retain(|id| copied_ids.contains(id));
+        }
+        state.profile_manager.update_profile(id, server).await?;
+    }
+    info!(
+        "Created server profile {} for client profile '{}'",
+        id, client.name
+    );
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn is_server_eula_accepted(profile_id: Uuid) -> Result<bool, CommandError> {
+    let dir = server_dir(profile_id).await?;
+    Ok(server_properties_utils::is_eula_accepted(&dir).await?)
+}
+
+/// Accepts the Minecraft EULA for a server profile. The UI has to show the EULA first.
+#[tauri::command]
+pub async fn accept_server_eula(profile_id: Uuid) -> Result<(), CommandError> {
+    let dir = server_dir(profile_id).await?;
+    server_properties_utils::accept_eula(&dir).await?;
+    info!(
+        "Accepted the Minecraft EULA for server profile {}",
+        profile_id
+    );
+    Ok(())
+}
+
+/// All entries of the server's `server.properties`, empty before its first start.
+#[tauri::command]
+pub async fn get_server_properties(
+    profile_id: Uuid,
+) -> Result<BTreeMap<String, String>, CommandError> {
+    let dir = server_dir(profile_id).await?;
+    let properties = server_properties_utils::read_server_properties(&dir).await?;
+    Ok(properties.entries())
+}
+
+/// Changes the given keys of `server.properties`, everything else in the file stays as is.
+/// The server reads them on its next start.
+#[tauri::command]
+pub async fn set_server_properties(
+    profile_id: Uuid,
+    properties: HashMap<String, String>,
+) -> Result<(), CommandError> {
+    let dir = server_dir(profile_id).await?;
+    let mut server_properties = server_properties_utils::read_server_properties(&dir).await?;
+    for (key, value) in &properties {
+        let key = key.trim();
+        if key.is_empty() || key.contains(['=', ':', '\n', '\r']) {
+            return Err(CommandError::from(AppError::InvalidInput(format!(
+                "Invalid server property key: {:?}",
+                key
+            ))));
+        }
+        server_properties.set(key, value);
+    }
+    server_properties_utils::write_server_properties(&dir, &server_properties).await?;
+    Ok(())
+}
+
+/// Sends a command to the console of a running server, e.g. `say hi` or `op Name`.
+#[tauri::command]
+pub async fn send_server_command(process_id: Uuid, command: String) -> Result<(), CommandError> {
+    let state = State::get().await?;
+    state
+        .process_manager
+        .send_server_command(process_id, &command)
+        .await?;
+    Ok(())
+}
+
+/// Launches a client profile with Quick Play into a server profile, using the linked client
+/// profile if none is given.
+#[tauri::command]
+pub async fn launch_client_into_server(
+    server_profile_id: Uuid,
+    client_profile_id: Option<Uuid>,
+) -> Result<(), CommandError> {
+    let state = State::get().await?;
+    let server = state.profile_manager.get_profile(server_profile_id).await?;
+    let client_id = client_profile_id
+        .or(server.linked_client_profile_id)
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Server profile '{}' has no linked client profile",
+                server.name
+            ))
+        })?;
+    let dir = server_dir(server_profile_id).await?;
+    let properties = server_properties_utils::read_server_properties(&dir).await?;
+
+    // An empty server-ip means the server listens on all interfaces
+    let host = properties
+        .get("server-ip")
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty() && *ip != "0.0.0.0")
+        .unwrap_or("localhost");
+    let port = properties.server_port();
+    let address = if port == DEFAULT_SERVER_PORT {
+        host.to_string()
+    } else {
+        format!("{}:{}", host, port)
+    };
+    info!(
+        "Launching client profile {} into server '{}' at {}",
+        client_id, server.name, address
+    );
+    profile_command::launch_profile(client_id, None, Some(address)).await
+}
@@ -127,6 +127,25 @@ pub enum AppError {
     #[error("Mrpack error: {0}")]
     MrpackError(String),
 
+    #[error("CurseForge error: {0}")]
+    CurseForgeError(String),
+
+    #[error("Not available offline: {0}")]
+    OfflineUnavailable(String),
+
+    #[error("Mod check failed: {0}")]
+    ModCheckFailed(String),
+
+    #[error("Mod bisection failed: {0}")]
+    ModBisectFailed(String),
+
+    #[error("Profile '{profile_id}' cannot add '{mod_name}': it is marked as incompatible with {conflicts}")]
+    ModIncompatible {
+        profile_id: Uuid,
+        mod_name: String,
+        conflicts: String,
+    },
+
     #[error("Unsupported OS: {0}")]
     UnsupportedOS(String),
 
@@ -184,6 +203,9 @@ pub enum AppError {
 
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
+
+    #[error("The Minecraft EULA has not been accepted for server profile {0}")]
+    EulaNotAccepted(Uuid),
 }
 
 #[derive(Serialize, Debug)]
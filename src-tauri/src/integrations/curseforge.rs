@@ -0,0 +1,644 @@
+use crate::error::{AppError, Result};
+use crate::integrations::mrpack;
+use crate::state::profile_state::{
+    Mod, ModLoader, ModSource, Profile, ProfileKind, ProfileSettings, ProfileState,
+};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::VerifiedDownload;
+use crate::utils::mirror_utils;
+use async_zip::tokio::read::seek::ZipFileReader;
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use reqwest::Method;
+use sanitize_filename::sanitize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, BufReader};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use uuid::Uuid;
+
+/// Default base URL of the official CurseForge API.
+pub const CURSEFORGE_API_BASE_URL: &str = "https://api.curseforge.com";
+/// Overrides the API base URL (e.g. to point the importer at a local mock server).
+pub const CURSEFORGE_API_BASE_ENV: &str = "NORISK_CURSEFORGE_API_BASE";
+/// API key sent as `x-api-key`. The official API rejects requests without one.
+pub const CURSEFORGE_API_KEY_ENV: &str = "NORISK_CURSEFORGE_API_KEY";
+
+// CurseForge "classId" values of the Minecraft game we care about
+const CLASS_ID_MODS: u32 = 6;
+const CLASS_ID_RESOURCE_PACKS: u32 = 12;
+const CLASS_ID_SHADERS: u32 = 6552;
+const CLASS_ID_DATA_PACKS: u32 = 6945;
+
+/// Represents the `manifest.json` of a CurseForge modpack zip.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    pub manifest_type: String, // "minecraftModpack"
+    pub manifest_version: u32, // Usually 1
+    pub name: String,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    #[serde(default)]
+    pub files: Vec<CurseForgeManifestFile>,
+    #[serde(default = "default_overrides_dir")]
+    pub overrides: String, // Name of the overrides folder inside the zip
+}
+
+fn default_overrides_dir() -> String {
+    "overrides".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+/// A loader entry, `id` has the form "<loader>-<version>" (e.g. "forge-47.2.0").
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u32,
+    #[serde(rename = "fileID")]
+    pub file_id: u32,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// --- API response structures ---
+
+#[derive(Deserialize, Debug)]
+struct CurseForgeDataResponse<T> {
+    data: T,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u32,
+    pub mod_id: u32,
+    pub display_name: Option<String>,
+    pub file_name: String,
+    pub download_url: Option<String>, // null if the author disabled third party downloads
+    #[serde(default)]
+    pub file_length: u64,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeFileHash>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CurseForgeFileHash {
+    pub value: String,
+    pub algo: u32, // 1 = SHA1, 2 = MD5
+}
+
+const HASH_ALGO_SHA1: u32 = 1;
+
+impl CurseForgeFile {
+    /// The SHA1 hash CurseForge lists for this file, if any.
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|h| h.algo == HASH_ALGO_SHA1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeProject {
+    pub id: u32,
+    pub name: String,
+    pub slug: Option<String>,
+    pub class_id: Option<u32>,
+}
+
+/// Small client for the parts of the CurseForge API needed by the importer.
+/// The base URL is configurable so the importer can be tested against a mock server.
+#[derive(Debug, Clone)]
+pub struct CurseForgeApi {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl CurseForgeApi {
+    pub fn new(base_url: impl Into<String>, api_key: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key,
+        }
+    }
+
+    /// Creates a client using `NORISK_CURSEFORGE_API_BASE` / `NORISK_CURSEFORGE_API_KEY`
+    /// if set, otherwise the official API with the key from the launcher config (if any).
+    pub fn from_env_or_config(configured_key: Option<String>) -> Self {
+        let base_url = std::env::var(CURSEFORGE_API_BASE_ENV)
+            .unwrap_or_else(|_| CURSEFORGE_API_BASE_URL.to_string());
+        let api_key = std::env::var(CURSEFORGE_API_KEY_ENV)
+            .ok()
+            .or(configured_key)
+            .filter(|key| !key.trim().is_empty());
+        Self::new(base_url, api_key)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn post<B: Serialize, T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = mirror_utils::send_with_fallback(Method::POST, &url, |builder| {
+            let builder = builder.header("Accept", "application/json").json(body);
+            match &self.api_key {
+                Some(key) => builder.header("x-api-key", key),
+                None => builder,
+            }
+        })
+        .await
+        .map_err(|e| AppError::CurseForgeError(format!("Request to {} failed: {}", url, e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!(
+                "CurseForge API error for {}: Status {}, Body: {}",
+                url, status, error_text
+            );
+            return Err(AppError::CurseForgeError(format!(
+                "CurseForge API returned status {} for {}",
+                status, url
+            )));
+        }
+
+        let parsed = response
+            .json::<CurseForgeDataResponse<T>>()
+            .await
+            .map_err(|e| {
+                AppError::CurseForgeError(format!("Failed to parse response of {}: {}", url, e))
+            })?;
+        Ok(parsed.data)
+    }
+
+    /// Fetches file metadata for the given file IDs (`POST /v1/mods/files`).
+    pub async fn get_files(&self, file_ids: &[u32]) -> Result<Vec<CurseForgeFile>> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.post(
+            "/v1/mods/files",
+            &serde_json::json!({ "fileIds": file_ids }),
+        )
+        .await
+    }
+
+    /// Fetches project metadata for the given project IDs (`POST /v1/mods`).
+    pub async fn get_projects(&self, project_ids: &[u32]) -> Result<Vec<CurseForgeProject>> {
+        if project_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.post("/v1/mods", &serde_json::json!({ "modIds": project_ids }))
+            .await
+    }
+}
+
+/// A non-mod file from the manifest (resource pack, shader, data pack) that has to be
+/// downloaded directly into the instance directory.
+#[derive(Serialize, Debug, Clone)]
+pub struct CurseForgeInstanceFile {
+    pub project_id: u32,
+    pub file_id: u32,
+    pub relative_path: String, // e.g. "resourcepacks/Faithful.zip"
+    pub url: String,
+    pub sha1: Option<String>,
+}
+
+/// A manifest file entry that could not be turned into a mod or instance file.
+#[derive(Serialize, Debug, Clone)]
+pub struct UnresolvedCurseForgeFile {
+    pub project_id: u32,
+    pub file_id: u32,
+    pub file_name: Option<String>,
+    pub reason: String,
+}
+
+/// Result of resolving all manifest files against the CurseForge API.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ResolvedCurseForgeFiles {
+    pub mods: Vec<Mod>,
+    pub instance_files: Vec<CurseForgeInstanceFile>,
+    pub unresolved: Vec<UnresolvedCurseForgeFile>,
+}
+
+/// Result of a full CurseForge import.
+#[derive(Serialize, Debug, Clone)]
+pub struct CurseForgeImportResult {
+    pub profile_id: Uuid,
+    pub unresolved: Vec<UnresolvedCurseForgeFile>,
+}
+
+/// Maps the modLoaders list of the manifest to our ModLoader and its version.
+/// Uses the primary loader if one is flagged, otherwise the first entry.
+pub fn determine_loader_from_mod_loaders(
+    mod_loaders: &[CurseForgeModLoader],
+) -> Result<(ModLoader, Option<String>)> {
+    let Some(entry) = mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| mod_loaders.first())
+    else {
+        return Ok((ModLoader::Vanilla, None));
+    };
+
+    let (name, version) = entry.id.split_once('-').ok_or_else(|| {
+        AppError::CurseForgeError(format!("Invalid mod loader id '{}'", entry.id))
+    })?;
+
+    let loader = match name.to_lowercase().as_str() {
+        "forge" => ModLoader::Forge,
+        "neoforge" => ModLoader::NeoForge,
+        "fabric" => ModLoader::Fabric,
+        "quilt" => ModLoader::Quilt,
+        other => return Err(AppError::InvalidModLoader(other.to_string())),
+    };
+
+    // Old NeoForge packs use "neoforge-<mc>-<version>" (e.g. "neoforge-1.20.1-47.1.84")
+    let version = match loader {
+        ModLoader::NeoForge => version
+            .rsplit_once('-')
+            .filter(|(prefix, _)| prefix.starts_with("1."))
+            .map(|(_, v)| v)
+            .unwrap_or(version),
+        _ => version,
+    };
+
+    Ok((loader, Some(version.to_string())))
+}
+
+/// Reads and parses `manifest.json` from a CurseForge modpack zip.
+pub async fn read_curseforge_manifest(pack_path: &Path) -> Result<CurseForgeManifest> {
+    let file = File::open(pack_path).await.map_err(|e| {
+        error!("Failed to open CurseForge pack {:?}: {}", pack_path, e);
+        AppError::Io(e)
+    })?;
+    let mut buf_reader = BufReader::new(file);
+    let mut zip = ZipFileReader::with_tokio(&mut buf_reader)
+        .await
+        .map_err(|e| AppError::CurseForgeError(format!("Failed to read pack zip: {}", e)))?;
+
+    let manifest_index = zip
+        .file()
+        .entries()
+        .iter()
+        .position(|e| {
+            e.filename()
+                .as_str()
+                .is_ok_and(|name| name == "manifest.json")
+        })
+        .ok_or_else(|| {
+            AppError::CurseForgeError(format!(
+                "manifest.json not found in archive {}",
+                pack_path.display()
+            ))
+        })?;
+
+    let mut content = String::new();
+    zip.reader_with_entry(manifest_index)
+        .await
+        .map_err(|e| AppError::CurseForgeError(format!("Failed to read manifest.json: {}", e)))?
+        .compat()
+        .read_to_string(&mut content)
+        .await
+        .map_err(AppError::Io)?;
+
+    let manifest: CurseForgeManifest = serde_json::from_str(&content)
+        .map_err(|e| AppError::CurseForgeError(format!("Failed to parse manifest.json: {}", e)))?;
+
+    if manifest.manifest_type != "minecraftModpack" {
+        return Err(AppError::CurseForgeError(format!(
+            "Unsupported manifest type '{}'",
+            manifest.manifest_type
+        )));
+    }
+
+    Ok(manifest)
+}
+
+/// Reads the manifest and creates a *potential* Profile (not saved) from it.
+pub async fn process_curseforge_pack(pack_path: &Path) -> Result<(Profile, CurseForgeManifest)> {
+    info!("Processing CurseForge pack: {:?}", pack_path);
+    let manifest = read_curseforge_manifest(pack_path).await?;
+    let (loader, mut loader_version) =
+        determine_loader_from_mod_loaders(&manifest.minecraft.mod_loaders)?;
+
+    // Forge versions are stored in their maven form ("<mc>-<forge>") like the forge installer expects
+    if loader == ModLoader::Forge {
+        loader_version = loader_version.map(|v| {
+            if v.starts_with(&format!("{}-", manifest.minecraft.version)) {
+                v
+            } else {
+                format!("{}-{}", manifest.minecraft.version, v)
+            }
+        });
+    }
+
+    info!(
+        "Determined requirements: MC={}, Loader={:?}, LoaderVersion={:?}",
+        manifest.minecraft.version, loader, loader_version
+    );
+
+    let profile = Profile {
+        id: Uuid::new_v4(),
+        name: manifest.name.clone(),
+        path: sanitize(&manifest.name),
+        game_version: manifest.minecraft.version.clone(),
+        loader,
+        loader_version,
+        created: Utc::now(),
+        last_played: None,
+        settings: ProfileSettings::default(),
+        state: ProfileState::NotInstalled,
+        mods: Vec::new(),
+        selected_norisk_pack_id: None,
+        disabled_norisk_mods_detailed: HashSet::new(),
+        source_standard_profile_id: None,
+        group: Some("MODPACKS".to_string()),
+        is_standard_version: false,
+        description: manifest
+            .author
+            .as_ref()
+            .map(|author| format!("CurseForge modpack by {}", author)),
+        norisk_information: None,
+        kind: ProfileKind::Client,
+        linked_client_profile_id: None,
+        banner: None,
+        background: None,
+    };
+
+    Ok((profile, manifest))
+}
+
+/// Resolves the manifest files against the CurseForge API.
+/// Mods become `ModSource::Url` entries, resource packs / shaders / data packs become
+/// instance files, everything else is reported as unresolved.
+pub async fn resolve_curseforge_files(
+    api: &CurseForgeApi,
+    manifest: &CurseForgeManifest,
+) -> Result<ResolvedCurseForgeFiles> {
+    let (pack_loader, _) = determine_loader_from_mod_loaders(&manifest.minecraft.mod_loaders)?;
+    let mut result = ResolvedCurseForgeFiles::default();
+
+    let wanted: Vec<&CurseForgeManifestFile> =
+        manifest.files.iter().filter(|f| f.required).collect();
+    if wanted.is_empty() {
+        return Ok(result);
+    }
+
+    info!(
+        "Resolving {} files of '{}' against CurseForge API at {}",
+        wanted.len(),
+        manifest.name,
+        api.base_url()
+    );
+
+    let file_ids: Vec<u32> = wanted.iter().map(|f| f.file_id).collect();
+    let project_ids: Vec<u32> = wanted
+        .iter()
+        .map(|f| f.project_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let files_by_id: HashMap<u32, CurseForgeFile> = match api.get_files(&file_ids).await {
+        Ok(files) => files.into_iter().map(|f| (f.id, f)).collect(),
+        Err(e) => {
+            // Without a (valid) API key nothing can be resolved, the pack is still imported
+            warn!(
+                "Failed to fetch CurseForge files, none can be resolved: {}",
+                e
+            );
+            result.unresolved = wanted
+                .iter()
+                .map(|entry| UnresolvedCurseForgeFile {
+                    project_id: entry.project_id,
+                    file_id: entry.file_id,
+                    file_name: None,
+                    reason: format!("CurseForge API unavailable: {}", e),
+                })
+                .collect();
+            return Ok(result);
+        }
+    };
+    let projects_by_id: HashMap<u32, CurseForgeProject> = match api.get_projects(&project_ids).await
+    {
+        Ok(projects) => projects.into_iter().map(|p| (p.id, p)).collect(),
+        Err(e) => {
+            // Without project info everything is treated as a mod
+            warn!(
+                "Failed to fetch CurseForge projects, assuming all files are mods: {}",
+                e
+            );
+            HashMap::new()
+        }
+    };
+
+    for entry in wanted {
+        let Some(file) = files_by_id.get(&entry.file_id) else {
+            result.unresolved.push(UnresolvedCurseForgeFile {
+                project_id: entry.project_id,
+                file_id: entry.file_id,
+                file_name: None,
+                reason: "File not found on CurseForge".to_string(),
+            });
+            continue;
+        };
+
+        let Some(download_url) = file.download_url.clone().filter(|u| !u.is_empty()) else {
+            result.unresolved.push(UnresolvedCurseForgeFile {
+                project_id: entry.project_id,
+                file_id: entry.file_id,
+                file_name: Some(file.file_name.clone()),
+                reason: "Author disabled third-party downloads".to_string(),
+            });
+            continue;
+        };
+
+        let sha1 = file.sha1().map(str::to_string);
+        let project = projects_by_id.get(&entry.project_id);
+        let target_dir = match project.and_then(|p| p.class_id).unwrap_or(CLASS_ID_MODS) {
+            CLASS_ID_MODS => None,
+            CLASS_ID_RESOURCE_PACKS => Some("resourcepacks"),
+            CLASS_ID_SHADERS => Some("shaderpacks"),
+            CLASS_ID_DATA_PACKS => Some("datapacks"),
+            other => {
+                result.unresolved.push(UnresolvedCurseForgeFile {
+                    project_id: entry.project_id,
+                    file_id: entry.file_id,
+                    file_name: Some(file.file_name.clone()),
+                    reason: format!("Unsupported CurseForge content class {}", other),
+                });
+                continue;
+            }
+        };
+
+        if let Some(dir) = target_dir {
+            result.instance_files.push(CurseForgeInstanceFile {
+                project_id: entry.project_id,
+                file_id: entry.file_id,
+                relative_path: format!("{}/{}", dir, sanitize(&file.file_name)),
+                url: download_url,
+                sha1,
+            });
+            continue;
+        }
+
+        debug!(
+            "Resolved CurseForge mod file {} -> {}",
+            file.id, file.file_name
+        );
+        result.mods.push(Mod {
+            id: Uuid::new_v4(),
+            source: ModSource::Url {
+                url: download_url,
+                file_name: Some(file.file_name.clone()),
+                file_hash_sha1: sha1,
+            },
+            enabled: true,
+            display_name: Some(
+                project
+                    .map(|p| p.name.clone())
+                    .or_else(|| file.display_name.clone())
+                    .unwrap_or_else(|| file.file_name.clone()),
+            ),
+            version: file.display_name.clone(),
+            game_versions: Some(vec![manifest.minecraft.version.clone()]),
+            file_name_override: None,
+            associated_loader: Some(pack_loader),
+            installed_as_dependency: false,
+            dependencies: Vec::new(),
+        });
+    }
+
+    info!(
+        "Resolved {} mods and {} instance files, {} files unresolved",
+        result.mods.len(),
+        result.instance_files.len(),
+        result.unresolved.len()
+    );
+    Ok(result)
+}
+
+/// Downloads resource packs / shaders / data packs straight into the instance directory.
+async fn download_instance_files(
+    target_dir: &Path,
+    files: &[CurseForgeInstanceFile],
+) -> Vec<UnresolvedCurseForgeFile> {
+    let mut failed = Vec::new();
+
+    for file in files {
+        let dest = target_dir.join(&file.relative_path);
+        let download = VerifiedDownload::new(&file.url, &dest)
+            .sha1(file.sha1.as_deref())
+            .download()
+            .await;
+
+        if let Err(e) = download {
+            warn!("Failed to download {} to {:?}: {}", file.url, dest, e);
+            failed.push(UnresolvedCurseForgeFile {
+                project_id: file.project_id,
+                file_id: file.file_id,
+                file_name: Some(file.relative_path.clone()),
+                reason: format!("Download failed: {}", e),
+            });
+        }
+    }
+
+    failed
+}
+
+/// Imports a CurseForge modpack zip as a new profile: parses the manifest, resolves
+/// the files, extracts the overrides and saves the profile.
+pub async fn import_curseforge_pack_as_profile(
+    pack_path: PathBuf,
+    api: &CurseForgeApi,
+) -> Result<CurseForgeImportResult> {
+    info!("Starting CurseForge import for: {:?}", pack_path);
+
+    let (mut profile, manifest) = process_curseforge_pack(&pack_path).await?;
+    let resolved = resolve_curseforge_files(api, &manifest).await?;
+    profile.mods = resolved.mods;
+    let mut unresolved = resolved.unresolved;
+
+    // Determine unique profile path segment
+    let base_profiles_dir = crate::state::profile_state::default_profile_path();
+    let mut sanitized_base_name = sanitize(&profile.name);
+    if sanitized_base_name.is_empty() {
+        let default_name = format!("imported-pack-{}", Utc::now().timestamp_millis());
+        warn!(
+            "Profile name '{}' became empty after sanitization. Using default: {}",
+            profile.name, default_name
+        );
+        profile.name = default_name.clone();
+        sanitized_base_name = default_name;
+    }
+    profile.path = crate::utils::path_utils::find_unique_profile_segment(
+        &base_profiles_dir,
+        &sanitized_base_name,
+    )
+    .await?;
+
+    let target_dir = base_profiles_dir.join(&profile.path);
+    fs::create_dir_all(&target_dir).await.map_err(|e| {
+        error!(
+            "Failed to create target profile directory {:?}: {}",
+            target_dir, e
+        );
+        AppError::Io(e)
+    })?;
+
+    // Overrides (jars in overrides/mods end up in custom_mods as local mods)
+    let overrides_prefix = format!("{}/", manifest.overrides.trim_end_matches('/'));
+    mrpack::extract_pack_overrides(&pack_path, &profile, &[overrides_prefix.as_str()]).await?;
+
+    unresolved.extend(download_instance_files(&target_dir, &resolved.instance_files).await);
+
+    let state = State::get().await?;
+    let profile_id = state.profile_manager.create_profile(profile).await?;
+    info!(
+        "Successfully created profile {} from CurseForge pack",
+        profile_id
+    );
+
+    for file in &unresolved {
+        warn!(
+            "Unresolved CurseForge file (project {}, file {}, {:?}): {}",
+            file.project_id, file.file_id, file.file_name, file.reason
+        );
+    }
+
+    Ok(CurseForgeImportResult {
+        profile_id,
+        unresolved,
+    })
+}
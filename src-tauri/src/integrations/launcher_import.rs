@@ -0,0 +1,753 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::integrations::modrinth;
+use crate::minecraft::dto::neo_forge_maven_meta::NeoForgeMavenMetadata;
+use crate::state::profile_state::{
+    default_profile_path, MemorySettings, Mod, ModLoader, ModSource, Profile, ProfileKind,
+    ProfileSettings, ProfileState,
+};
+use crate::state::state_manager::State;
+use crate::utils::{hash_utils, mc_utils, path_utils};
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use sanitize_filename::sanitize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Files and folders of a foreign game directory that belong to the other launcher
+/// (or are regenerated by the game) and are never copied into an imported profile.
+const EXCLUDED_GAME_DIR_ENTRIES: &[&str] = &[
+    "mods",
+    "versions",
+    "libraries",
+    "assets",
+    "logs",
+    "crash-reports",
+    "natives",
+    "bin",
+    "runtime",
+    "webcache",
+    "webcache2",
+    ".fabric",
+    ".quilt",
+    ".mixin.out",
+    "launcher_profiles.json",
+    "launcher_accounts.json",
+    "launcher_accounts_microsoft_store.json",
+    "launcher_msa_credentials.bin",
+    "launcher_settings.json",
+    "launcher_ui_state.json",
+    "launcher_log.txt",
+    "launcher_cef_log.txt",
+    "usercache.json",
+    "usernamecache.json",
+];
+
+/// Launchers we can migrate instances from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalLauncher {
+    Prism,
+    MultiMC,
+    Vanilla,
+    ATLauncher,
+}
+
+/// An instance/profile found in another launcher, ready to be imported.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalInstance {
+    pub launcher: ExternalLauncher,
+    pub name: String,
+    pub instance_path: PathBuf, // Root folder of the instance (for display)
+    pub game_dir: PathBuf,      // Folder containing mods/, config/, saves/, ...
+    pub game_version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+    pub memory: Option<MemorySettings>,
+    pub jvm_args: Option<String>,
+    pub java_path: Option<String>,
+}
+
+/// Loader information extracted from a launcher specific version description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoaderInfo {
+    pub game_version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+}
+
+// --- Parsing helpers (pure, no IO) ---
+
+/// Parses a MultiMC/Prism `instance.cfg` (INI without real sections) into key/value pairs.
+pub fn parse_instance_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('[') && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[derive(Deserialize, Debug)]
+struct MmcPack {
+    #[serde(default)]
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+    cached_version: Option<String>,
+}
+
+/// Reads game version and loader from the component list of a `mmc-pack.json`.
+pub fn parse_mmc_pack(content: &str) -> Result<LoaderInfo> {
+    let pack: MmcPack = serde_json::from_str(content)?;
+
+    let version_of = |uid: &str| {
+        pack.components
+            .iter()
+            .find(|c| c.uid == uid)
+            .and_then(|c| c.version.clone().or_else(|| c.cached_version.clone()))
+    };
+
+    let game_version = version_of("net.minecraft").ok_or_else(|| {
+        AppError::ParseError("mmc-pack.json has no net.minecraft component".to_string())
+    })?;
+
+    let (loader, loader_version) = if let Some(v) = version_of("net.fabricmc.fabric-loader") {
+        (ModLoader::Fabric, Some(v))
+    } else if let Some(v) = version_of("org.quiltmc.quilt-loader") {
+        (ModLoader::Quilt, Some(v))
+    } else if let Some(v) = version_of("net.neoforged") {
+        (ModLoader::NeoForge, Some(v))
+    } else if let Some(v) = version_of("net.minecraftforge") {
+        (
+            ModLoader::Forge,
+            Some(forge_maven_version(&game_version, &v)),
+        )
+    } else {
+        (ModLoader::Vanilla, None)
+    };
+
+    Ok(LoaderInfo {
+        game_version,
+        loader,
+        loader_version,
+    })
+}
+
+/// Interprets a vanilla launcher `lastVersionId` (the name of a folder in `versions/`).
+/// Supports plain versions and the ids created by the Fabric, Quilt, Forge and NeoForge installers.
+pub fn parse_vanilla_version_id(version_id: &str) -> Option<LoaderInfo> {
+    if version_id.is_empty() || version_id.starts_with("latest-") {
+        return None;
+    }
+
+    // fabric-loader-<loader>-<mc> / quilt-loader-<loader>-<mc>
+    for (prefix, loader) in [
+        ("fabric-loader-", ModLoader::Fabric),
+        ("quilt-loader-", ModLoader::Quilt),
+    ] {
+        if let Some(rest) = version_id.strip_prefix(prefix) {
+            let (loader_version, game_version) = rest.split_once('-')?;
+            return Some(LoaderInfo {
+                game_version: game_version.to_string(),
+                loader,
+                loader_version: Some(loader_version.to_string()),
+            });
+        }
+    }
+
+    // neoforge-<version>, the Minecraft version is encoded in the NeoForge version
+    if let Some(neoforge_version) = version_id.strip_prefix("neoforge-") {
+        let game_version =
+            NeoForgeMavenMetadata::parse_neoforge_version_to_minecraft(neoforge_version)?;
+        return Some(LoaderInfo {
+            game_version,
+            loader: ModLoader::NeoForge,
+            loader_version: Some(neoforge_version.to_string()),
+        });
+    }
+
+    // <mc>-forge-<forge> and the old <mc>-forge<mc>-<forge>
+    if let Some((game_version, forge_part)) = version_id.split_once("-forge") {
+        let forge_version = forge_part
+            .trim_start_matches('-')
+            .trim_start_matches(&format!("{}-", game_version));
+        return Some(LoaderInfo {
+            game_version: game_version.to_string(),
+            loader: ModLoader::Forge,
+            loader_version: Some(forge_maven_version(game_version, forge_version)),
+        });
+    }
+
+    Some(LoaderInfo {
+        game_version: version_id.to_string(),
+        loader: ModLoader::Vanilla,
+        loader_version: None,
+    })
+}
+
+/// Forge versions are stored in their maven form ("<mc>-<forge>") like the forge installer expects.
+fn forge_maven_version(game_version: &str, forge_version: &str) -> String {
+    if forge_version.starts_with(&format!("{}-", game_version)) {
+        forge_version.to_string()
+    } else {
+        format!("{}-{}", game_version, forge_version)
+    }
+}
+
+/// Parses a JVM memory value like "4G", "4096M" or "2048m" into megabytes.
+pub fn parse_memory_value(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit())?);
+    let number: u32 = number.parse().ok()?;
+    match unit.to_ascii_lowercase().as_str() {
+        "g" => number.checked_mul(1024),
+        "m" => Some(number),
+        "k" => Some(number / 1024),
+        _ => None,
+    }
+}
+
+/// Splits `-Xms`/`-Xmx` out of a JVM argument string.
+/// Returns the memory settings (if `-Xmx` was present) and the remaining arguments.
+pub fn split_memory_from_jvm_args(args: &str) -> (Option<MemorySettings>, Option<String>) {
+    let mut min = None;
+    let mut max = None;
+    let mut rest = Vec::new();
+
+    for arg in args.split_whitespace() {
+        if let Some(v) = arg.strip_prefix("-Xmx") {
+            max = parse_memory_value(v);
+        } else if let Some(v) = arg.strip_prefix("-Xms") {
+            min = parse_memory_value(v);
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    let memory = max.map(|max| MemorySettings {
+        min: min.unwrap_or_else(|| MemorySettings::default().min.min(max)),
+        max,
+    });
+    let rest = if rest.is_empty() {
+        None
+    } else {
+        Some(rest.join(" "))
+    };
+    (memory, rest)
+}
+
+// --- Scanners ---
+
+/// Default data directories of the supported launchers on this system.
+pub fn default_launcher_roots() -> Vec<(ExternalLauncher, PathBuf)> {
+    let mut roots = Vec::new();
+
+    if let Some(data_dir) = dirs::data_dir() {
+        roots.push((ExternalLauncher::Prism, data_dir.join("PrismLauncher")));
+        roots.push((ExternalLauncher::ATLauncher, data_dir.join("ATLauncher")));
+    }
+    if let Some(home) = dirs::home_dir() {
+        // Flatpak install of Prism
+        roots.push((
+            ExternalLauncher::Prism,
+            home.join(".var/app/org.prismlauncher.PrismLauncher/data/PrismLauncher"),
+        ));
+        // MultiMC is portable, these are just the most common places
+        roots.push((ExternalLauncher::MultiMC, home.join("MultiMC")));
+        roots.push((ExternalLauncher::MultiMC, home.join(".local/share/multimc")));
+    }
+    roots.push((
+        ExternalLauncher::Vanilla,
+        mc_utils::get_default_minecraft_dir(),
+    ));
+
+    roots
+        .into_iter()
+        .filter(|(_, path)| path.is_dir())
+        .collect()
+}
+
+/// Scans all default launcher locations for importable instances.
+pub async fn scan_default_launchers() -> Vec<ExternalInstance> {
+    let mut instances = Vec::new();
+    for (launcher, root) in default_launcher_roots() {
+        match scan_launcher_directory(launcher, &root).await {
+            Ok(found) => instances.extend(found),
+            Err(e) => warn!("Failed to scan {:?} at {:?}: {}", launcher, root, e),
+        }
+    }
+    instances
+}
+
+/// Scans a single launcher data directory for importable instances.
+pub async fn scan_launcher_directory(
+    launcher: ExternalLauncher,
+    root: &Path,
+) -> Result<Vec<ExternalInstance>> {
+    info!("Scanning {:?} directory {:?} for instances", launcher, root);
+    match launcher {
+        ExternalLauncher::Prism | ExternalLauncher::MultiMC => {
+            scan_mmc_instances(launcher, root).await
+        }
+        ExternalLauncher::Vanilla => scan_vanilla_profiles(root).await,
+        ExternalLauncher::ATLauncher => scan_atlauncher_instances(root).await,
+    }
+}
+
+async fn scan_mmc_instances(
+    launcher: ExternalLauncher,
+    root: &Path,
+) -> Result<Vec<ExternalInstance>> {
+    // The instance folder can be changed in the launcher config
+    let mut instances_dir = root.join("instances");
+    for cfg_name in ["prismlauncher.cfg", "multimc.cfg"] {
+        if let Ok(content) = fs::read_to_string(root.join(cfg_name)).await {
+            if let Some(dir) = parse_instance_cfg(&content).get("InstanceDir") {
+                let dir = PathBuf::from(dir);
+                instances_dir = if dir.is_absolute() {
+                    dir
+                } else {
+                    root.join(dir)
+                };
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut entries = match fs::read_dir(&instances_dir).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(result),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        let instance_path = entry.path();
+        let cfg_path = instance_path.join("instance.cfg");
+        let pack_path = instance_path.join("mmc-pack.json");
+        if !cfg_path.is_file() || !pack_path.is_file() {
+            continue;
+        }
+
+        let cfg = parse_instance_cfg(&fs::read_to_string(&cfg_path).await?);
+        let loader_info = match parse_mmc_pack(&fs::read_to_string(&pack_path).await?) {
+            Ok(info) => info,
+            Err(e) => {
+                warn!("Skipping instance {:?}: {}", instance_path, e);
+                continue;
+            }
+        };
+
+        let game_dir = [".minecraft", "minecraft"]
+            .iter()
+            .map(|name| instance_path.join(name))
+            .find(|p| p.is_dir())
+            .unwrap_or_else(|| instance_path.join(".minecraft"));
+
+        let is_set = |key: &str| cfg.get(key).is_some_and(|v| v == "true");
+        let memory = if is_set("OverrideMemory") {
+            cfg.get("MaxMemAlloc")
+                .and_then(|v| v.parse().ok())
+                .map(|max| MemorySettings {
+                    min: cfg
+                        .get("MinMemAlloc")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(max),
+                    max,
+                })
+        } else {
+            None
+        };
+        let jvm_args = if is_set("OverrideJavaArgs") {
+            cfg.get("JvmArgs").filter(|v| !v.is_empty()).cloned()
+        } else {
+            None
+        };
+        let java_path = if is_set("OverrideJavaLocation") {
+            cfg.get("JavaPath").filter(|v| !v.is_empty()).cloned()
+        } else {
+            None
+        };
+
+        result.push(ExternalInstance {
+            launcher,
+            name: cfg
+                .get("name")
+                .cloned()
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string()),
+            instance_path,
+            game_dir,
+            game_version: loader_info.game_version,
+            loader: loader_info.loader,
+            loader_version: loader_info.loader_version,
+            memory,
+            jvm_args,
+            java_path,
+        });
+    }
+
+    Ok(result)
+}
+
+#[derive(Deserialize, Debug)]
+struct VanillaLauncherProfiles {
+    #[serde(default)]
+    profiles: HashMap<String, VanillaLauncherProfile>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct VanillaLauncherProfile {
+    name: Option<String>,
+    last_version_id: Option<String>,
+    game_dir: Option<String>,
+    java_args: Option<String>,
+    java_dir: Option<String>,
+}
+
+async fn scan_vanilla_profiles(root: &Path) -> Result<Vec<ExternalInstance>> {
+    let profiles_path = root.join("launcher_profiles.json");
+    if !profiles_path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let parsed: VanillaLauncherProfiles =
+        serde_json::from_str(&fs::read_to_string(&profiles_path).await?)?;
+
+    let mut result = Vec::new();
+    for (key, profile) in parsed.profiles {
+        let Some(loader_info) = profile
+            .last_version_id
+            .as_deref()
+            .and_then(parse_vanilla_version_id)
+        else {
+            debug!(
+                "Skipping vanilla launcher profile '{}' (no fixed version)",
+                key
+            );
+            continue;
+        };
+
+        let (memory, jvm_args) = profile
+            .java_args
+            .as_deref()
+            .map(split_memory_from_jvm_args)
+            .unwrap_or((None, None));
+
+        let name = profile
+            .name
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| loader_info.game_version.clone());
+
+        result.push(ExternalInstance {
+            launcher: ExternalLauncher::Vanilla,
+            name,
+            instance_path: root.to_path_buf(),
+            game_dir: profile
+                .game_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(|| root.to_path_buf()),
+            game_version: loader_info.game_version,
+            loader: loader_info.loader,
+            loader_version: loader_info.loader_version,
+            memory,
+            jvm_args,
+            java_path: profile.java_dir,
+        });
+    }
+
+    Ok(result)
+}
+
+async fn scan_atlauncher_instances(root: &Path) -> Result<Vec<ExternalInstance>> {
+    let mut result = Vec::new();
+    let mut entries = match fs::read_dir(root.join("instances")).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(result),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        let instance_path = entry.path();
+        let json_path = instance_path.join("instance.json");
+        if !json_path.is_file() {
+            continue;
+        }
+
+        let json: serde_json::Value =
+            match serde_json::from_str(&fs::read_to_string(&json_path).await?) {
+                Ok(json) => json,
+                Err(e) => {
+                    warn!("Skipping ATLauncher instance {:?}: {}", instance_path, e);
+                    continue;
+                }
+            };
+        let launcher = &json["launcher"];
+
+        let Some(game_version) = json["id"].as_str().map(str::to_string) else {
+            warn!(
+                "Skipping ATLauncher instance {:?}: no Minecraft version",
+                instance_path
+            );
+            continue;
+        };
+
+        let loader_version = launcher["loaderVersion"]["version"]
+            .as_str()
+            .map(str::to_string);
+        let loader = match launcher["loaderVersion"]["type"].as_str() {
+            Some("Fabric") => ModLoader::Fabric,
+            Some("Quilt") => ModLoader::Quilt,
+            Some("Forge") => ModLoader::Forge,
+            Some("NeoForge") => ModLoader::NeoForge,
+            _ => ModLoader::Vanilla,
+        };
+        let loader_version = match loader {
+            ModLoader::Vanilla => None,
+            ModLoader::Forge => loader_version.map(|v| forge_maven_version(&game_version, &v)),
+            _ => loader_version,
+        };
+
+        let memory = launcher["maximumMemory"]
+            .as_u64()
+            .map(|max| MemorySettings {
+                min: launcher["initialMemory"].as_u64().unwrap_or(max) as u32,
+                max: max as u32,
+            });
+
+        result.push(ExternalInstance {
+            launcher: ExternalLauncher::ATLauncher,
+            name: launcher["name"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string()),
+            game_dir: instance_path.clone(),
+            instance_path,
+            game_version,
+            loader,
+            loader_version,
+            memory,
+            jvm_args: launcher["javaArguments"]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(str::to_string),
+            java_path: launcher["javaPath"].as_str().map(str::to_string),
+        });
+    }
+
+    Ok(result)
+}
+
+// --- Import ---
+
+/// Identifies the jars of a foreign `mods` folder via Modrinth hashes.
+/// Known jars become `ModSource::Modrinth` entries (and are seeded into the mod cache so they
+/// don't have to be downloaded again), unknown jars are copied into `custom_mods`.
+async fn import_mods(
+    source_mods_dir: &Path,
+    target_instance_dir: &Path,
+    game_version: &str,
+    loader: ModLoader,
+) -> Result<Vec<Mod>> {
+    let mut jars = Vec::new();
+    let mut entries = match fs::read_dir(source_mods_dir).await {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if path.is_file() && (file_name.ends_with(".jar") || file_name.ends_with(".jar.disabled")) {
+            let sha1 = hash_utils::calculate_sha1(&path).await?;
+            jars.push((path, file_name, sha1));
+        }
+    }
+
+    if jars.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let hashes: Vec<String> = jars.iter().map(|(_, _, sha1)| sha1.clone()).collect();
+    let versions = modrinth::get_versions_by_hashes(hashes, "sha1")
+        .await
+        .unwrap_or_else(|e| {
+            warn!(
+                "Modrinth hash lookup failed, importing all mods as local files: {}",
+                e
+            );
+            HashMap::new()
+        });
+
+    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
+    let custom_mods_dir = target_instance_dir.join("custom_mods");
+    fs::create_dir_all(&mod_cache_dir).await?;
+    fs::create_dir_all(&custom_mods_dir).await?;
+
+    let mut mods = Vec::new();
+    let mut local_count = 0;
+    for (path, file_name, sha1) in jars {
+        let enabled = !file_name.ends_with(".disabled");
+        let version = versions.get(&sha1);
+        let file = version.and_then(|v| {
+            v.files
+                .iter()
+                .find(|f| f.hashes.sha1.as_deref() == Some(sha1.as_str()))
+        });
+
+        match (version, file) {
+            (Some(version), Some(file)) => {
+                let cache_path = mod_cache_dir.join(&file.filename);
+                if !cache_path.exists() {
+                    fs::copy(&path, &cache_path).await?;
+                }
+                mods.push(Mod {
+                    id: Uuid::new_v4(),
+                    source: ModSource::Modrinth {
+                        project_id: version.project_id.clone(),
+                        version_id: version.id.clone(),
+                        file_name: file.filename.clone(),
+                        download_url: file.url.clone(),
+                        file_hash_sha1: Some(sha1),
+                    },
+                    enabled,
+                    display_name: Some(version.name.clone()),
+                    version: Some(version.version_number.clone()),
+                    game_versions: Some(vec![game_version.to_string()]),
+                    file_name_override: None,
+                    associated_loader: Some(loader),
+                    installed_as_dependency: false,
+                    dependencies: Vec::new(),
+                });
+            }
+            _ => {
+                fs::copy(&path, custom_mods_dir.join(&file_name)).await?;
+                local_count += 1;
+            }
+        }
+    }
+
+    info!(
+        "Identified {} mods on Modrinth, copied {} unknown jars to custom_mods",
+        mods.len(),
+        local_count
+    );
+    Ok(mods)
+}
+
+/// Copies everything from a foreign game directory except launcher owned files and mods.
+async fn copy_game_dir(source: &Path, target: &Path) -> Result<()> {
+    let state = State::get().await?;
+    let mut entries = fs::read_dir(source).await?;
+
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if EXCLUDED_GAME_DIR_ENTRIES.contains(&name.as_str()) {
+            continue;
+        }
+
+        let src = entry.path();
+        let dst = target.join(&name);
+        if src.is_dir() {
+            path_utils::copy_dir_recursively(&src, &dst, state.io_semaphore.clone()).await?;
+        } else {
+            fs::copy(&src, &dst).await.map_err(|e| {
+                error!("Failed to copy {:?} to {:?}: {}", src, dst, e);
+                AppError::Io(e)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a full profile from an instance of another launcher:
+/// settings, game files (configs, saves, packs, options) and mods.
+pub async fn import_external_instance(instance: &ExternalInstance) -> Result<Uuid> {
+    info!(
+        "Importing {:?} instance '{}' from {:?}",
+        instance.launcher, instance.name, instance.game_dir
+    );
+
+    if !instance.game_dir.is_dir() {
+        return Err(AppError::FileNotFound(instance.game_dir.clone()));
+    }
+
+    let mut settings = ProfileSettings::default();
+    if let Some(memory) = &instance.memory {
+        settings.memory = memory.clone();
+    }
+    settings.custom_jvm_args = instance.jvm_args.clone();
+    if let Some(java_path) = &instance.java_path {
+        settings.java_path = Some(java_path.clone());
+        settings.use_custom_java_path = true;
+    }
+
+    let base_profiles_dir = default_profile_path();
+    let mut segment_name = sanitize(&instance.name);
+    if segment_name.is_empty() {
+        segment_name = format!("imported-{}", Utc::now().timestamp_millis());
+    }
+    let path = path_utils::find_unique_profile_segment(&base_profiles_dir, &segment_name).await?;
+    let target_dir = base_profiles_dir.join(&path);
+    fs::create_dir_all(&target_dir).await?;
+
+    copy_game_dir(&instance.game_dir, &target_dir).await?;
+
+    let mods = if instance.loader == ModLoader::Vanilla {
+        Vec::new()
+    } else {
+        import_mods(
+            &instance.game_dir.join("mods"),
+            &target_dir,
+            &instance.game_version,
+            instance.loader,
+        )
+        .await?
+    };
+
+    let profile = Profile {
+        id: Uuid::new_v4(),
+        name: instance.name.clone(),
+        path,
+        game_version: instance.game_version.clone(),
+        loader: instance.loader,
+        loader_version: instance.loader_version.clone(),
+        created: Utc::now(),
+        last_played: None,
+        settings,
+        state: ProfileState::NotInstalled,
+        mods,
+        selected_norisk_pack_id: None,
+        disabled_norisk_mods_detailed: HashSet::new(),
+        source_standard_profile_id: None,
+        group: None,
+        is_standard_version: false,
+        description: Some(format!("Imported from {:?}", instance.launcher)),
+        norisk_information: None,
+        kind: ProfileKind::Client,
+        linked_client_profile_id: None,
+        banner: None,
+        background: None,
+    };
+
+    let state = State::get().await?;
+    let profile_id = state.profile_manager.create_profile(profile).await?;
+    info!(
+        "Imported instance '{}' as profile {}",
+        instance.name, profile_id
+    );
+    Ok(profile_id)
+}
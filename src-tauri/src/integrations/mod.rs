@@ -1,3 +1,5 @@
+pub mod curseforge;
+pub mod launcher_import;
 pub mod modrinth;
 pub mod mrpack;
 pub mod norisk_packs;
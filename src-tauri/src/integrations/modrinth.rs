@@ -1,7 +1,8 @@
 use crate::error::{AppError, Result};
+use crate::utils::mirror_utils;
 use futures::future::join_all;
 use log::{self, error, info};
-use reqwest;
+use reqwest::{self, Method};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
@@ -74,6 +75,21 @@ pub struct ModrinthDependency {
     pub dependency_type: ModrinthDependencyType, // required, optional, incompatible, embedded
 }
 
+impl ModrinthDependency {
+    /// Whether this is an `incompatible` entry that applies to the given project version.
+    /// An entry with a version_id only excludes that exact version.
+    pub fn excludes(&self, project_id: &str, version_id: &str) -> bool {
+        if self.dependency_type != ModrinthDependencyType::Incompatible {
+            return false;
+        }
+        match (&self.version_id, &self.project_id) {
+            (Some(excluded_version), _) => excluded_version == version_id,
+            (None, Some(excluded_project)) => excluded_project == project_id,
+            (None, None) => false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ModrinthDependencyType {
@@ -295,7 +311,6 @@ pub async fn search_projects(
     client_side_filter: Option<String>,
     server_side_filter: Option<String>,
 ) -> Result<ModrinthSearchResponse> {
-    let client = reqwest::Client::new();
     let base_url = format!("{}/search", MODRINTH_API_BASE_URL);
 
     let mut query_params: Vec<(String, String)> = Vec::new();
@@ -399,20 +414,20 @@ pub async fn search_projects(
 
     log::info!("Searching Modrinth: {}", final_url);
 
-    let response = client
-        .get(final_url)
-        // It's good practice to set a User-Agent
-        // Use format! correctly and ensure CARGO_PKG_VERSION is available
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .send()
-        .await
-        .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
+    let response = mirror_utils::send_with_fallback(Method::GET, final_url.as_str(), |builder| {
+        builder
+            // It's good practice to set a User-Agent
+            // Use format! correctly and ensure CARGO_PKG_VERSION is available
+            .header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -463,7 +478,6 @@ pub async fn get_mod_versions(
     loaders: Option<Vec<String>>,
     game_versions: Option<Vec<String>>,
 ) -> Result<Vec<ModrinthVersion>> {
-    let client = reqwest::Client::new();
     let url = format!(
         "{}/project/{}/version",
         MODRINTH_API_BASE_URL, project_id_or_slug
@@ -505,18 +519,17 @@ pub async fn get_mod_versions(
 
     log::info!("Getting Modrinth versions: {}", final_url);
 
-    let response = client
-        .get(final_url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, final_url.as_str(), |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Modrinth API request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -545,28 +558,26 @@ pub async fn get_mod_versions(
 // Function to get details for a specific Modrinth version ID
 // Based on https://docs.modrinth.com/api-spec/#tag/versions/operation/getVersion
 pub async fn get_version_details(version_id: String) -> Result<ModrinthVersion> {
-    let client = reqwest::Client::new();
     let url = format!("{}/version/{}", MODRINTH_API_BASE_URL, version_id);
 
     log::info!("Getting Modrinth version details: {}", url);
 
-    let response = client
-        .get(url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, &url, |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request failed for version {}: {}",
-                version_id, e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request failed for version {}: {}",
+            version_id, e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -613,11 +624,8 @@ pub async fn get_all_versions_for_projects(
         contexts.len()
     );
 
-    let client = reqwest::Client::new();
-
     // Create a list of futures, one for each context
     let futures = contexts.into_iter().map(|context| {
-        let client = client.clone();
         let original_context = context.clone();
 
         async move {
@@ -678,7 +686,6 @@ pub async fn get_version_by_hash(file_hash: String) -> Result<ModrinthVersion> {
         }
     };
 
-    let client = reqwest::Client::new();
     let url = format!(
         "{}/version_file/{}?algorithm={}", // Correct endpoint path
         MODRINTH_API_BASE_URL, file_hash, algorithm
@@ -690,23 +697,22 @@ pub async fn get_version_by_hash(file_hash: String) -> Result<ModrinthVersion> {
         url
     );
 
-    let response = client
-        .get(&url) // Pass URL by reference
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, &url, |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (support@norisk.gg)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request failed for hash {}: {}",
-                file_hash, e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request failed for hash {}: {}",
+            file_hash, e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -759,7 +765,6 @@ pub async fn get_versions_by_hashes(
         )));
     }
 
-    let client = reqwest::Client::new();
     let url = format!("{}/version_files", MODRINTH_API_BASE_URL); // POST endpoint
 
     let request_body = HashesRequestBody {
@@ -774,25 +779,25 @@ pub async fn get_versions_by_hashes(
         url
     );
 
-    let response = client
-        .post(&url) // Use POST
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .header("Content-Type", "application/json") // Set content type
-        .json(&request_body) // Send the serialized request body
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API POST request failed for hashes: {}",
-                e
-            ))
-        })?;
+    let response = mirror_utils::send_with_fallback(Method::POST, &url, |builder| {
+        builder
+            .header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+            .header("Content-Type", "application/json") // Set content type
+            .json(&request_body) // Send the serialized request body
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API POST request failed for hashes: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -884,7 +889,6 @@ impl ModrinthBulkUpdateRequestBody {
 pub async fn check_bulk_updates(
     request: ModrinthBulkUpdateRequestBody,
 ) -> Result<HashMap<String, ModrinthVersion>> {
-    let client = reqwest::Client::new();
     let url = format!("{}/version_files/update", MODRINTH_API_BASE_URL); // Update check endpoint
 
     log::info!(
@@ -892,25 +896,25 @@ pub async fn check_bulk_updates(
         request.hashes.len()
     );
 
-    let response = client
-        .post(&url)
-        .header(
-            "User-Agent",
-            format!(
-                "NoRiskClient-Launcher/{} (support@norisk.gg)",
-                env!("CARGO_PKG_VERSION")
-            ),
-        )
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API bulk update check request failed: {}",
-                e
-            ))
-        })?;
+    let response = mirror_utils::send_with_fallback(Method::POST, &url, |builder| {
+        builder
+            .header(
+                "User-Agent",
+                format!(
+                    "NoRiskClient-Launcher/{} (support@norisk.gg)",
+                    env!("CARGO_PKG_VERSION")
+                ),
+            )
+            .header("Content-Type", "application/json")
+            .json(&request)
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API bulk update check request failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -962,7 +966,6 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
     // Modrinth expects the IDs as a JSON array string in the query parameter
     let ids_json = serde_json::to_string(&ids).map_err(|e| AppError::Json(e))?; // Use appropriate error type
 
-    let client = reqwest::Client::new();
     // Note: No trailing slash needed for the base URL when using parse_with_params
     let base_url = format!("{}/projects", MODRINTH_API_BASE_URL);
 
@@ -977,23 +980,22 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
         final_url
     );
 
-    let response = client
-        .get(final_url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, final_url.as_str(), |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (support@norisk.gg)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::RequestError(format!(
-                "Modrinth API request failed for bulk projects: {}",
-                e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::RequestError(format!(
+            "Modrinth API request failed for bulk projects: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1064,28 +1066,26 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
 /// Fetches a list of all categories from Modrinth.
 /// https://docs.modrinth.com/api/operations/categorylist/
 pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
-    let client = reqwest::Client::new();
     let url = format!("{}/tag/category", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth categories from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, &url, |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch categories failed: {}",
-                e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch categories failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1121,28 +1121,26 @@ pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
 /// Fetches a list of all loaders from Modrinth.
 /// https://docs.modrinth.com/api/operations/loaderlist/
 pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
-    let client = reqwest::Client::new();
     let url = format!("{}/tag/loader", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth loaders from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, &url, |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch loaders failed: {}",
-                e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch loaders failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -1172,28 +1170,26 @@ pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
 /// Fetches a list of all game versions from Modrinth.
 /// https://docs.modrinth.com/api/operations/versionlist/
 pub async fn get_modrinth_game_versions() -> Result<Vec<ModrinthGameVersion>> {
-    let client = reqwest::Client::new();
     let url = format!("{}/tag/game_version", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth game versions from: {}", url);
 
-    let response = client
-        .get(&url)
-        .header(
+    let response = mirror_utils::send_with_fallback(Method::GET, &url, |builder| {
+        builder.header(
             "User-Agent",
             format!(
                 "NoRiskClient-Launcher/{} (contact@noriskclient.de)",
                 env!("CARGO_PKG_VERSION")
             ),
         )
-        .send()
-        .await
-        .map_err(|e| {
-            AppError::Other(format!(
-                "Modrinth API request to fetch game versions failed: {}",
-                e
-            ))
-        })?;
+    })
+    .await
+    .map_err(|e| {
+        AppError::Other(format!(
+            "Modrinth API request to fetch game versions failed: {}",
+            e
+        ))
+    })?;
 
     if !response.status().is_success() {
         let status = response.status();
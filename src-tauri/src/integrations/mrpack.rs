@@ -1,7 +1,8 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::integrations::modrinth;
 use crate::state::profile_state::{
-    Mod, ModLoader, ModSource, Profile, ProfileSettings, ProfileState,
+    Mod, ModLoader, ModSource, Profile, ProfileKind, ProfileSettings, ProfileState,
 };
 use crate::state::state_manager::State;
 use async_zip::tokio::read::seek::ZipFileReader;
@@ -189,6 +190,8 @@ pub async fn process_mrpack(pack_path: PathBuf) -> Result<(Profile, ModrinthInde
         is_standard_version: false,
         description: None,
         norisk_information: None,
+        kind: ProfileKind::Client,
+        linked_client_profile_id: None,
         banner: None,
         background: None,
     };
@@ -312,6 +315,8 @@ pub async fn resolve_manifest_files(manifest: &ModrinthIndex) -> Result<Vec<Mod>
                     game_versions: Some(vec![game_version.clone()]),
                     file_name_override: None,
                     associated_loader: Some(pack_loader),
+                    installed_as_dependency: false,
+                    dependencies: Vec::new(),
                 };
 
                 info!(
@@ -342,6 +347,17 @@ pub async fn resolve_manifest_files(manifest: &ModrinthIndex) -> Result<Vec<Mod>
 /// Extracts files from the "overrides" or "client-overrides" directory within a .mrpack archive
 /// into the specified target profile directory, using concurrent streaming operations.
 pub async fn extract_mrpack_overrides(pack_path: &Path, profile: &Profile) -> Result<()> {
+    extract_pack_overrides(pack_path, profile, &["overrides/", "client-overrides/"]).await
+}
+
+/// Extracts every entry below one of `override_prefixes` (e.g. "overrides/") of a pack archive
+/// into the profile directory. Jars in `mods/` are redirected to `custom_mods/`.
+/// Shared by the .mrpack and CurseForge importers.
+pub(crate) async fn extract_pack_overrides(
+    pack_path: &Path,
+    profile: &Profile,
+    override_prefixes: &[&str],
+) -> Result<()> {
     info!(
         "Extracting overrides for profile '{}' from {:?} using concurrent streaming...",
         profile.name, pack_path
@@ -420,14 +436,13 @@ pub async fn extract_mrpack_overrides(pack_path: &Path, profile: &Profile) -> Re
             entry_uncompressed_size = entry.uncompressed_size();
         }
 
-        let (is_override_type, path_prefix_to_strip) =
-            if entry_filename_str.starts_with("overrides/") {
-                (true, "overrides/")
-            } else if entry_filename_str.starts_with("client-overrides/") {
-                (true, "client-overrides/")
-            } else {
-                (false, "")
-            };
+        let (is_override_type, path_prefix_to_strip) = match override_prefixes
+            .iter()
+            .find(|prefix| entry_filename_str.starts_with(*prefix))
+        {
+            Some(prefix) => (true, *prefix),
+            None => (false, ""),
+        };
 
         if is_override_type {
             let path_after_prefix = match entry_filename_str.strip_prefix(path_prefix_to_strip) {
@@ -893,3 +908,454 @@ pub async fn download_and_process_mrpack(download_url: &str, file_name: &str) ->
     // Return the profile ID
     Ok(profile_id)
 }
+
+/// Maps a Modrinth project side value to the value expected in the `env` block of
+/// `modrinth.index.json`. The index only knows "required", "optional" and "unsupported".
+fn side_to_env_value(side: &str) -> String {
+    match side {
+        "optional" => "optional".to_string(),
+        "unsupported" => "unsupported".to_string(),
+        _ => "required".to_string(), // "required" and "unknown"
+    }
+}
+
+/// Streams a single file from disk into the zip archive at `zip_path`.
+async fn write_file_to_mrpack(
+    writer: &mut async_zip::tokio::write::ZipFileWriter<&mut File>,
+    source_path: &Path,
+    zip_path: &str,
+) -> Result<()> {
+    let mut source_file = File::open(source_path).await.map_err(AppError::Io)?;
+
+    let builder = async_zip::ZipEntryBuilder::new(
+        zip_path.to_string().into(),
+        async_zip::Compression::Deflate,
+    );
+    let mut entry_writer = writer
+        .write_entry_stream(builder)
+        .await
+        .map_err(|e| AppError::MrpackError(format!("Failed to create zip entry stream: {}", e)))?;
+
+    let mut buffer = [0u8; 8192];
+    loop {
+        let n = source_file.read(&mut buffer).await.map_err(AppError::Io)?;
+        if n == 0 {
+            break;
+        }
+        futures::AsyncWriteExt::write_all(&mut entry_writer, &buffer[..n])
+            .await
+            .map_err(|e| AppError::MrpackError(format!("Failed to write chunk: {}", e)))?;
+    }
+
+    entry_writer
+        .close()
+        .await
+        .map_err(|e| AppError::MrpackError(format!("Failed to close zip entry: {}", e)))?;
+    Ok(())
+}
+
+/// Result of a `.mrpack` export.
+#[derive(Debug, Clone, Serialize)]
+pub struct MrpackExport {
+    pub path: PathBuf,
+    /// Mods of the profile that are neither in the index nor bundled, with the reason
+    pub skipped_mods: Vec<String>,
+}
+
+/// sha1 and sha512 (as the index stores them) and size of a file on disk.
+pub async fn hash_mrpack_file(path: &Path) -> Result<(HashMap<String, String>, u64)> {
+    use sha1::Digest;
+
+    let mut file = File::open(path).await.map_err(AppError::Io)?;
+    let mut sha1 = sha1::Sha1::new();
+    let mut sha512 = sha2::Sha512::new();
+    let mut size = 0u64;
+    let mut buffer = [0u8; 8192];
+    loop {
+        let n = file.read(&mut buffer).await.map_err(AppError::Io)?;
+        if n == 0 {
+            break;
+        }
+        sha1.update(&buffer[..n]);
+        sha512.update(&buffer[..n]);
+        size += n as u64;
+    }
+    let mut hashes = HashMap::new();
+    hashes.insert("sha1".to_string(), hex::encode(sha1.finalize()));
+    hashes.insert("sha512".to_string(), hex::encode(sha512.finalize()));
+    Ok((hashes, size))
+}
+
+/// Exports a profile to a Modrinth `.mrpack` file
+///
+/// The archive contains a spec-compliant `modrinth.index.json`:
+/// - `ModSource::Modrinth` mods are referenced by download URL with the sha1/sha512 hashes
+///   and size of the jar on disk and client/server `env` taken from the Modrinth API.
+///   The URL is only used if Modrinth has a file with exactly these hashes
+/// - Local and URL jars (and Modrinth mods Modrinth has no matching file for) are
+///   bundled under `overrides/mods/`
+/// - Files specified in `include_files` are bundled under `overrides/`
+///
+/// Mods coming from the selected NoRisk pack are not exported, they belong to the pack
+/// and not to the profile. Mods that can't be exported (Maven, embedded, not downloaded)
+/// are listed in `MrpackExport::skipped_mods`.
+///
+/// @param profile_id: UUID of the profile to export
+/// @param output_path: Optional path where the .mrpack file should be saved, defaults to the exports folder
+/// @param include_files: Optional list of files/directories to include in the overrides folder
+/// @return: Result containing the path to the created .mrpack file and the skipped mods
+pub async fn export_profile_to_mrpack(
+    profile_id: Uuid,
+    output_path: Option<PathBuf>,
+    include_files: Option<Vec<PathBuf>>,
+) -> Result<MrpackExport> {
+    info!("Exporting profile {} to .mrpack", profile_id);
+
+    let state = State::get().await?;
+    let _permit = state.io_semaphore.acquire().await;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
+
+    // --- 1. Dependencies ---
+    let mut dependencies = HashMap::new();
+    dependencies.insert(
+        MINECRAFT_DEPENDENCY.to_string(),
+        profile.game_version.clone(),
+    );
+    let loader_key = match profile.loader {
+        ModLoader::Vanilla => None,
+        ModLoader::Fabric => Some(FABRIC_LOADER_DEPENDENCY),
+        ModLoader::Quilt => Some(QUILT_LOADER_DEPENDENCY),
+        ModLoader::Forge => Some(FORGE_DEPENDENCY),
+        ModLoader::NeoForge => Some(NEOFORGE_DEPENDENCY),
+    };
+    if let Some(key) = loader_key {
+        let loader_version = profile.loader_version.clone().ok_or_else(|| {
+            AppError::MrpackError(format!(
+                "Profile '{}' uses {} but has no loader version set; .mrpack requires one",
+                profile.name,
+                profile.loader.as_str()
+            ))
+        })?;
+        // Forge versions are stored in maven form ("1.20.1-47.2.0"), the index wants "47.2.0"
+        let loader_version = match profile.loader {
+            ModLoader::Forge => loader_version
+                .strip_prefix(&format!("{}-", profile.game_version))
+                .map(str::to_string)
+                .unwrap_or(loader_version),
+            _ => loader_version,
+        };
+        dependencies.insert(key.to_string(), loader_version);
+    }
+
+    // --- 2. Resolve Modrinth metadata (hashes, sizes, env) ---
+    let modrinth_mods: Vec<&Mod> = profile
+        .mods
+        .iter()
+        .filter(|m| matches!(m.source, ModSource::Modrinth { .. }))
+        .collect();
+
+    let known_sha1s: Vec<String> = modrinth_mods
+        .iter()
+        .filter_map(|m| match &m.source {
+            ModSource::Modrinth { file_hash_sha1, .. } => file_hash_sha1.clone(),
+            _ => None,
+        })
+        .collect();
+    let versions_by_hash = if known_sha1s.is_empty() {
+        HashMap::new()
+    } else {
+        modrinth::get_versions_by_hashes(known_sha1s, "sha1")
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to resolve Modrinth versions by hash for export: {}",
+                    e
+                );
+                HashMap::new()
+            })
+    };
+
+    let project_ids: Vec<String> = modrinth_mods
+        .iter()
+        .filter_map(|m| match &m.source {
+            ModSource::Modrinth { project_id, .. } => Some(project_id.clone()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let project_sides: HashMap<String, (String, String)> =
+        match modrinth::get_multiple_projects(project_ids).await {
+            Ok(projects) => projects
+                .into_iter()
+                .map(|p| (p.id, (p.client_side, p.server_side)))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to fetch Modrinth projects for env fields: {}", e);
+                HashMap::new()
+            }
+        };
+
+    let mut index_files = Vec::new();
+    // (source path on disk, path inside overrides/)
+    let mut override_files: Vec<(PathBuf, String)> = Vec::new();
+    let mut indexed_mod_file_names: HashSet<String> = HashSet::new();
+    let mut skipped_mods: Vec<String> = Vec::new();
+
+    for mod_info in &profile.mods {
+        let target_name = |file_name: &str| {
+            if mod_info.enabled {
+                file_name.to_string()
+            } else {
+                format!("{}.disabled", file_name)
+            }
+        };
+
+        match &mod_info.source {
+            ModSource::Modrinth {
+                project_id,
+                version_id,
+                file_name,
+                download_url,
+                file_hash_sha1,
+            } => {
+                // The index describes the jar the profile actually uses, not what Modrinth lists
+                let cache_path = mod_cache_dir.join(file_name);
+                if !cache_path.exists() {
+                    warn!(
+                        "Skipping Modrinth mod '{}': file not found in mod cache",
+                        file_name
+                    );
+                    skipped_mods.push(format!("{} (not downloaded)", file_name));
+                    continue;
+                }
+                let (hashes, file_size) = hash_mrpack_file(&cache_path).await?;
+                indexed_mod_file_names.insert(file_name.clone());
+
+                let version = match file_hash_sha1
+                    .as_ref()
+                    .and_then(|h| versions_by_hash.get(h))
+                {
+                    Some(version) => Some(version.clone()),
+                    None => modrinth::get_version_details(version_id.clone()).await.ok(),
+                };
+                // Only a download with the same content may be referenced
+                let remote_file = version.as_ref().and_then(|v| {
+                    v.files
+                        .iter()
+                        .find(|f| f.hashes.sha1.as_ref() == hashes.get("sha1"))
+                        .cloned()
+                });
+
+                match remote_file {
+                    Some(remote_file) => {
+                        let env = project_sides.get(project_id).map(|(client, server)| {
+                            let mut env = HashMap::new();
+                            env.insert("client".to_string(), side_to_env_value(client));
+                            env.insert("server".to_string(), side_to_env_value(server));
+                            env
+                        });
+
+                        let url = if remote_file.url.is_empty() {
+                            download_url.clone()
+                        } else {
+                            remote_file.url.clone()
+                        };
+
+                        index_files.push(ModrinthIndexFile {
+                            path: format!("mods/{}", target_name(file_name)),
+                            hashes,
+                            env,
+                            downloads: vec![url],
+                            file_size,
+                        });
+                    }
+                    None => {
+                        warn!(
+                            "No Modrinth file matches '{}', bundling it as override",
+                            file_name
+                        );
+                        override_files
+                            .push((cache_path, format!("mods/{}", target_name(file_name))));
+                    }
+                }
+            }
+            ModSource::Url {
+                file_name: Some(file_name),
+                ..
+            } => {
+                let cache_path = mod_cache_dir.join(file_name);
+                if cache_path.exists() {
+                    override_files.push((cache_path, format!("mods/{}", target_name(file_name))));
+                    indexed_mod_file_names.insert(file_name.clone());
+                } else {
+                    warn!(
+                        "Skipping URL mod '{}': file not found in mod cache",
+                        file_name
+                    );
+                    skipped_mods.push(format!("{} (not downloaded)", file_name));
+                }
+            }
+            ModSource::Local { .. } => {
+                // Bundled below together with the other jars of the custom_mods directory
+            }
+            other => {
+                let name = mod_info
+                    .display_name
+                    .clone()
+                    .unwrap_or_else(|| format!("{:?}", other));
+                warn!("Skipping mod source {:?} for .mrpack export", other);
+                skipped_mods.push(format!("{} (not exportable)", name));
+            }
+        }
+    }
+
+    // Local jars from the custom_mods directory
+    for custom_mod in state.profile_manager.list_custom_mods(&profile).await? {
+        let file_name = custom_mod
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| custom_mod.filename.clone());
+        indexed_mod_file_names.insert(custom_mod.filename.clone());
+        override_files.push((custom_mod.path, format!("mods/{}", file_name)));
+    }
+
+    if profile.selected_norisk_pack_id.is_some() {
+        info!(
+            "Profile '{}' uses a NoRisk pack; pack mods are not part of the .mrpack export",
+            profile.name
+        );
+    }
+
+    // --- 3. User selected instance files ---
+    if let Some(include_paths) = include_files {
+        let include_prefixes: Vec<String> = include_paths
+            .iter()
+            .filter_map(|p| p.strip_prefix(&instance_path).ok())
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .collect();
+
+        let mut all_files = Vec::new();
+        crate::utils::profile_utils::collect_all_files_recursive(&instance_path, &mut all_files)
+            .await?;
+
+        for file_path in all_files {
+            let Ok(rel_path) = file_path.strip_prefix(&instance_path) else {
+                continue;
+            };
+            let rel_path_str = rel_path.to_string_lossy().replace('\\', "/");
+            if !include_prefixes
+                .iter()
+                .any(|prefix| rel_path_str.starts_with(prefix))
+            {
+                continue;
+            }
+            // The mods folder is synced from the profile mods at launch, don't bundle jars twice
+            if let Some(mod_file) = rel_path_str.strip_prefix("mods/") {
+                let base_name = mod_file.trim_end_matches(".disabled");
+                if indexed_mod_file_names.contains(base_name) {
+                    continue;
+                }
+            }
+            override_files.push((file_path.clone(), rel_path_str));
+        }
+    }
+
+    // --- 4. Write the archive ---
+    let manifest = ModrinthIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: "1.0.0".to_string(),
+        name: profile.name.clone(),
+        summary: profile.description.clone(),
+        files: index_files,
+        dependencies,
+    };
+
+    let output_file = match output_path {
+        Some(path) => path,
+        None => {
+            let safe_name = sanitize(profile.name.replace(' ', "_").to_lowercase());
+            LAUNCHER_DIRECTORY
+                .root_dir()
+                .join("exports")
+                .join(format!("{}_v{}.mrpack", safe_name, profile.game_version))
+        }
+    };
+    if let Some(parent) = output_file.parent() {
+        fs::create_dir_all(parent).await.map_err(AppError::Io)?;
+    }
+
+    write_mrpack(&output_file, &manifest, override_files).await?;
+
+    if !skipped_mods.is_empty() {
+        warn!(
+            "{} mods were not exported to the .mrpack: {}",
+            skipped_mods.len(),
+            skipped_mods.join(", ")
+        );
+    }
+    info!(
+        "Successfully exported profile to: {}",
+        output_file.display()
+    );
+    Ok(MrpackExport {
+        path: output_file,
+        skipped_mods,
+    })
+}
+
+/// Writes `modrinth.index.json` and the override files (source path, path inside
+/// `overrides/`) into a new `.mrpack` archive.
+pub async fn write_mrpack(
+    output_file: &Path,
+    manifest: &ModrinthIndex,
+    override_files: Vec<(PathBuf, String)>,
+) -> Result<()> {
+    info!(
+        "Creating .mrpack archive at {} ({} indexed files, {} overrides)",
+        output_file.display(),
+        manifest.files.len(),
+        override_files.len()
+    );
+
+    let mut file = File::create(output_file).await.map_err(AppError::Io)?;
+    let mut writer = async_zip::tokio::write::ZipFileWriter::with_tokio(&mut file);
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    writer
+        .write_entry_whole(
+            async_zip::ZipEntryBuilder::new(
+                "modrinth.index.json".into(),
+                async_zip::Compression::Deflate,
+            ),
+            &manifest_json,
+        )
+        .await
+        .map_err(|e| {
+            AppError::MrpackError(format!("Failed to write modrinth.index.json: {}", e))
+        })?;
+
+    let mut written_paths = HashSet::new();
+    for (source_path, rel_path) in override_files {
+        let zip_path = format!("overrides/{}", rel_path);
+        if !written_paths.insert(zip_path.clone()) {
+            continue;
+        }
+        debug!("Adding override {} -> {}", source_path.display(), zip_path);
+        write_file_to_mrpack(&mut writer, &source_path, &zip_path).await?;
+    }
+
+    writer
+        .close()
+        .await
+        .map_err(|e| AppError::MrpackError(format!("Failed to finalize zip file: {}", e)))?;
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::state::profile_state::{Profile, ProfileState};
 use crate::state::state_manager::State;
+use crate::utils::hash_utils::calculate_sha1_from_bytes;
 use async_zip::tokio::read::seek::ZipFileReader;
 use chrono::Utc;
 use futures::future::try_join_all;
@@ -773,6 +774,32 @@ impl NoriskModpacksConfig {
         })
     }
 
+    /// Identifies the content of a pack for a Minecraft version and loader: a short hash of the
+    /// resolved mods and their versions. Packs have no version number, this changes whenever
+    /// one of their mods does.
+    pub fn pack_fingerprint(
+        &self,
+        pack_id: &str,
+        minecraft_version: &str,
+        loader: &str,
+    ) -> Result<String> {
+        let mut visited = HashSet::new();
+        let mut entries: Vec<String> = self
+            .resolve_pack_mods(pack_id, &mut visited)?
+            .iter()
+            .filter_map(|mod_entry| {
+                let target = mod_entry
+                    .compatibility
+                    .get(minecraft_version)?
+                    .get(loader)?;
+                Some(format!("{}={}", mod_entry.id, target.identifier))
+            })
+            .collect();
+        entries.sort();
+        let hash = calculate_sha1_from_bytes(entries.join("\n").as_bytes());
+        Ok(hash[..10].to_string())
+    }
+
     /// Prints the resolved mod list for each pack defined in the configuration.
     /// Useful for debugging the inheritance and exclusion logic.
     pub fn print_resolved_packs(&self) -> Result<()> {
@@ -462,7 +462,63 @@ async fn main() {
             commands::nrc_commands::discord_auth_link,
             commands::nrc_commands::discord_auth_status,
             commands::nrc_commands::discord_auth_unlink,
-            commands::nrc_commands::submit_crash_log_command
+            commands::nrc_commands::submit_crash_log_command,
+            commands::launcher_import_command::scan_external_launchers_command,
+            commands::launcher_import_command::scan_external_launcher_directory_command,
+            commands::launcher_import_command::import_external_instances_command,
+            commands::content_store_command::get_content_store_stats,
+            commands::content_store_command::collect_content_store_garbage,
+            commands::profile_command::check_offline_readiness,
+            commands::profile_command::verify_profile,
+            commands::file_command::get_mod_metadata_for_archives,
+            commands::profile_command::check_profile_mods,
+            commands::profile_command::get_orphaned_mod_dependencies,
+            commands::profile_command::bisect_profile_mods,
+            commands::profile_command::cancel_mod_bisect,
+            commands::process_command::analyze_profile_crash,
+            commands::process_command::analyze_crash_log,
+            commands::process_command::get_process_stats,
+            commands::process_command::get_process_stats_history,
+            commands::process_command::get_launch_logs,
+            commands::process_command::read_launch_log,
+            commands::process_command::get_game_sessions,
+            commands::process_command::get_session_stats,
+            commands::server_command::create_server_profile_for_client,
+            commands::server_command::is_server_eula_accepted,
+            commands::server_command::accept_server_eula,
+            commands::server_command::get_server_properties,
+            commands::server_command::set_server_properties,
+            commands::server_command::send_server_command,
+            commands::server_command::launch_client_into_server,
+            commands::profile_command::create_world_backup,
+            commands::profile_command::list_world_backups,
+            commands::profile_command::delete_world_backup,
+            commands::profile_command::restore_world_backup,
+            commands::profile_command::check_world_transfer,
+            commands::profile_command::export_world,
+            commands::profile_command::inspect_world_zip,
+            commands::profile_command::import_world_zip,
+            commands::profile_command::get_world_details,
+            commands::profile_command::edit_world_settings,
+            commands::profile_command::render_world_map,
+            commands::profile_command::clear_world_map_cache,
+            commands::profile_command::add_profile_server,
+            commands::profile_command::edit_profile_server,
+            commands::profile_command::remove_profile_server,
+            commands::profile_command::move_profile_server,
+            commands::minecraft_command::get_favorite_servers,
+            commands::minecraft_command::add_favorite_server,
+            commands::minecraft_command::update_favorite_server,
+            commands::minecraft_command::remove_favorite_server,
+            commands::minecraft_command::sync_favorite_servers,
+            commands::minecraft_command::get_monitored_servers,
+            commands::minecraft_command::get_server_monitor_config,
+            commands::minecraft_command::add_monitored_server,
+            commands::minecraft_command::set_monitored_server_enabled,
+            commands::minecraft_command::remove_monitored_server,
+            commands::minecraft_command::set_server_monitor_schedule,
+            commands::minecraft_command::get_server_history,
+            commands::minecraft_command::poll_monitored_servers
         ])
         .build(tauri::generate_context!()) 
         .expect("error while building tauri application") 
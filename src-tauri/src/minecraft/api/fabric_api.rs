@@ -1,6 +1,6 @@
 use crate::error::Result;
-use crate::minecraft::dto::fabric_meta::FabricVersionInfo;
-use reqwest;
+use crate::minecraft::dto::fabric_meta::{FabricInstallerVersion, FabricVersionInfo};
+use crate::utils::offline_utils;
 pub struct FabricApi {
     base_url: String,
 }
@@ -17,24 +17,15 @@ impl FabricApi {
         minecraft_version: &str,
     ) -> Result<Vec<FabricVersionInfo>> {
         let url = format!("{}/versions/loader/{}", self.base_url, minecraft_version);
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("fabric")
+            .join(format!("loader_versions_{}.json", minecraft_version));
 
-        let response = reqwest::get(&url).await.map_err(|e| {
-            crate::error::AppError::FabricError(format!("Failed to fetch Fabric versions: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(crate::error::AppError::FabricError(format!(
-                "Failed to fetch Fabric versions: Status {}",
-                response.status()
-            )));
-        }
-
-        let versions = response
-            .json::<Vec<FabricVersionInfo>>()
+        let versions: Vec<FabricVersionInfo> = offline_utils::fetch_json_cached(&url, &cache_path)
             .await
             .map_err(|e| {
                 crate::error::AppError::FabricError(format!(
-                    "Failed to parse Fabric versions: {}",
+                    "Failed to fetch Fabric versions: {}",
                     e
                 ))
             })?;
@@ -56,4 +47,33 @@ impl FabricApi {
                 crate::error::AppError::FabricError("No stable Fabric version found".to_string())
             })
     }
+
+    pub async fn get_installer_versions(&self) -> Result<Vec<FabricInstallerVersion>> {
+        let url = format!("{}/versions/installer", self.base_url);
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("fabric")
+            .join("installer_versions.json");
+
+        offline_utils::fetch_json_cached(&url, &cache_path)
+            .await
+            .map_err(|e| {
+                crate::error::AppError::FabricError(format!(
+                    "Failed to fetch Fabric installer versions: {}",
+                    e
+                ))
+            })
+    }
+
+    /// Executable server jar that starts Fabric with the vanilla `server.jar` next to it.
+    pub fn get_server_launcher_url(
+        &self,
+        minecraft_version: &str,
+        loader_version: &str,
+        installer_version: &str,
+    ) -> String {
+        format!(
+            "{}/versions/loader/{}/{}/{}/server/jar",
+            self.base_url, minecraft_version, loader_version, installer_version
+        )
+    }
 }
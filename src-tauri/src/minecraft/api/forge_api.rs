@@ -1,8 +1,8 @@
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::forge_maven_meta::ForgeMavenMetadata;
+use crate::utils::offline_utils;
 use log::info;
 use quick_xml::de::from_str;
-use reqwest;
 
 const FORGE_MAVEN_METADATA_URL: &str =
     "https://maven.minecraftforge.net/net/minecraftforge/forge/maven-metadata.xml";
@@ -21,22 +21,13 @@ impl ForgeApi {
     pub async fn get_all_versions(&self) -> Result<ForgeMavenMetadata> {
         info!("Fetching Forge versions from Maven repository...");
 
-        let response = reqwest::get(&self.base_url)
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("forge")
+            .join("maven-metadata.xml");
+        let xml_content = offline_utils::fetch_text_cached(&self.base_url, &cache_path)
             .await
             .map_err(|e| AppError::ForgeError(format!("Failed to fetch Forge versions: {}", e)))?;
 
-        if !response.status().is_success() {
-            return Err(AppError::ForgeError(format!(
-                "Failed to fetch Forge versions: Status {}",
-                response.status()
-            )));
-        }
-
-        let xml_content = response
-            .text()
-            .await
-            .map_err(|e| AppError::ForgeError(format!("Failed to read response: {}", e)))?;
-
         let metadata: ForgeMavenMetadata = from_str(&xml_content)
             .map_err(|e| AppError::ForgeError(format!("Failed to parse Forge metadata: {}", e)))?;
 
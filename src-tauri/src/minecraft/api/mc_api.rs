@@ -2,6 +2,7 @@ use crate::error::{AppError, Result};
 use crate::minecraft::dto::minecraft_profile::MinecraftProfile;
 use crate::minecraft::dto::piston_meta::PistonMeta;
 use crate::minecraft::dto::version_manifest::VersionManifest;
+use crate::utils::offline_utils;
 use log::debug;
 use reqwest;
 use serde_json::Value;
@@ -21,27 +22,17 @@ impl MinecraftApiService {
     }
 
     pub async fn get_version_manifest(&self) -> Result<VersionManifest> {
-        let response = reqwest::get(VERSION_MANIFEST_URL)
-            .await
-            .map_err(AppError::MinecraftApi)?;
-
-        let manifest = response
-            .json::<VersionManifest>()
-            .await
-            .map_err(AppError::MinecraftApi)?;
-
-        Ok(manifest)
+        let cache_path = offline_utils::metadata_cache_dir().join("version_manifest.json");
+        offline_utils::fetch_json_cached::<VersionManifest>(VERSION_MANIFEST_URL, &cache_path).await
     }
 
     pub async fn get_piston_meta(&self, url: &str) -> Result<PistonMeta> {
-        let response = reqwest::get(url).await.map_err(AppError::MinecraftApi)?;
-
-        let meta = response
-            .json::<PistonMeta>()
-            .await
-            .map_err(AppError::MinecraftApi)?;
-
-        Ok(meta)
+        // Piston meta URLs end with "<version id>.json"
+        let file_name = url.rsplit('/').next().unwrap_or(url);
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("piston_meta")
+            .join(file_name);
+        offline_utils::fetch_json_cached::<PistonMeta>(url, &cache_path).await
     }
 
     // Get user profile including skin information
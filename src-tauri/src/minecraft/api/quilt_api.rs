@@ -1,6 +1,6 @@
 use crate::error::Result;
-use crate::minecraft::dto::quilt_meta::QuiltVersionInfo;
-use reqwest;
+use crate::minecraft::dto::quilt_meta::{QuiltInstallerVersion, QuiltVersionInfo};
+use crate::utils::offline_utils;
 
 pub struct QuiltApi {
     base_url: String,
@@ -18,23 +18,14 @@ impl QuiltApi {
         minecraft_version: &str,
     ) -> Result<Vec<QuiltVersionInfo>> {
         let url = format!("{}/versions/loader/{}", self.base_url, minecraft_version);
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("quilt")
+            .join(format!("loader_versions_{}.json", minecraft_version));
 
-        let response = reqwest::get(&url).await.map_err(|e| {
-            crate::error::AppError::QuiltError(format!("Failed to fetch Quilt versions: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(crate::error::AppError::QuiltError(format!(
-                "Failed to fetch Quilt versions: Status {}",
-                response.status()
-            )));
-        }
-
-        let versions = response
-            .json::<Vec<QuiltVersionInfo>>()
+        let versions: Vec<QuiltVersionInfo> = offline_utils::fetch_json_cached(&url, &cache_path)
             .await
             .map_err(|e| {
-                crate::error::AppError::QuiltError(format!("Failed to parse Quilt versions: {}", e))
+                crate::error::AppError::QuiltError(format!("Failed to fetch Quilt versions: {}", e))
             })?;
 
         Ok(versions)
@@ -54,4 +45,20 @@ impl QuiltApi {
                 crate::error::AppError::QuiltError("No stable Quilt version found".to_string())
             })
     }
+
+    pub async fn get_installer_versions(&self) -> Result<Vec<QuiltInstallerVersion>> {
+        let url = format!("{}/versions/installer", self.base_url);
+        let cache_path = offline_utils::metadata_cache_dir()
+            .join("quilt")
+            .join("installer_versions.json");
+
+        offline_utils::fetch_json_cached(&url, &cache_path)
+            .await
+            .map_err(|e| {
+                crate::error::AppError::QuiltError(format!(
+                    "Failed to fetch Quilt installer versions: {}",
+                    e
+                ))
+            })
+    }
 }
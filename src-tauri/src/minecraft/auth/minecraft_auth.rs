@@ -29,6 +29,7 @@ use uuid::Uuid;
 
 use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::minecraft::api::NoRiskApi;
+use crate::utils::offline_utils;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NoRiskTokenClaims {
@@ -756,6 +757,14 @@ impl MinecraftAuthStore {
         };
 
         if let Some(account) = active_account {
+            if offline_utils::is_offline_mode() {
+                // Offline launch: use the stored tokens as they are, no Microsoft/NoRisk refresh
+                info!(
+                    "[Account Manager] Offline mode, skipping token refresh for account: {}",
+                    account.username
+                );
+                return Ok(Some(account));
+            }
             info!(
                 "[Account Manager] Refreshing credentials for active account: {}",
                 account.username
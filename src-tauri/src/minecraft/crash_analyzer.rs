@@ -0,0 +1,685 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::java_detector;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Built-in signatures, more can be added as JSON files in `meta/crash_signatures`.
+const BUILTIN_SIGNATURES: &str = include_str!("crash_signatures.json");
+const CUSTOM_SIGNATURES_DIR: &str = "crash_signatures";
+
+const CRASH_REPORT_HEADER: &str = "---- Minecraft Crash Report ----";
+/// Stack frames kept per exception
+const MAX_FRAMES: usize = 8;
+/// Findings a single signature can produce, e.g. one per missing dependency
+const MAX_FINDINGS_PER_SIGNATURE: usize = 10;
+/// Ids the loaders report for themselves, mod fixes for them make no sense
+const PLATFORM_MOD_IDS: &[&str] = &[
+    "minecraft",
+    "java",
+    "fabricloader",
+    "quilt_loader",
+    "forge",
+    "neoforge",
+    "fml",
+];
+
+static EXCEPTION_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:Caused by: )?([a-zA-Z_$][\w$]*(?:\.[\w$]+)+(?:Exception|Error|Throwable))(?::\s*(.*))?$")
+        .unwrap()
+});
+static MIXIN_FAILURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Mixin apply (?:for mod ([\w\-]+) )?failed ([^\s:]+):(\S+?)(?: from mod \S+)? -> ([\w.$/]+)")
+        .unwrap()
+});
+static SUSPECTED_MOD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([^\t(]+?) \(([a-z0-9_\-.]+)\), Version: (\S+)").unwrap());
+static RUNNING_JAVA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*Java Version: (\d[\w.]*)|java version (\d[\w.]*)").unwrap());
+static REQUIRED_JAVA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"class file version (\d+)\.\d+|requires version (\d+) or later of [^\n]*\(java\)")
+        .unwrap()
+});
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{(\w+)\}").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashCategory {
+    OutOfMemory,
+    JavaVersion,
+    MissingDependency,
+    IncompatibleMods,
+    MixinFailure,
+    GraphicsDriver,
+    CorruptedFiles,
+    ModError,
+    Other,
+}
+
+/// A known crash, matched with regexes against the crash report and the log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashSignature {
+    pub id: String,
+    pub title: String,
+    pub category: CrashCategory,
+    /// Shown to the user, may contain `{placeholders}` (see `fixes`)
+    pub message: String,
+    /// The signature matches if any of these does
+    pub patterns: Vec<String>,
+    /// Fix templates. Strings like `{mod_id}` are replaced with named groups of the match or
+    /// with `running_java`, `required_java`, `recommended_java`, `max_memory_mb` and
+    /// `recommended_memory_mb`. Fixes with unknown values are left out
+    #[serde(default)]
+    pub fixes: Vec<Value>,
+    /// Findings of signatures with a higher priority come first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SuggestedFix {
+    RaiseMemory {
+        recommended_mb: u32,
+    },
+    SwitchJava {
+        major_version: u32,
+    },
+    DisableMod {
+        mod_id: String,
+    },
+    InstallMod {
+        mod_id: String,
+    },
+    UpdateMod {
+        mod_id: String,
+    },
+    UpdateGraphicsDriver,
+    /// Redownload damaged files, see `verify_profile`
+    VerifyProfile,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashFinding {
+    pub signature_id: String,
+    pub title: String,
+    pub category: CrashCategory,
+    pub message: String,
+    /// The text the signature matched
+    pub evidence: String,
+    pub fixes: Vec<SuggestedFix>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExceptionInfo {
+    pub class_name: String,
+    pub message: Option<String>,
+    pub frames: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SuspectedMod {
+    pub name: String,
+    pub mod_id: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MixinFailure {
+    /// Only Fabric names the mod
+    pub mod_id: Option<String>,
+    pub config: String,
+    pub mixin: String,
+    pub target: String,
+}
+
+/// What the analysis is run against, everything is optional.
+#[derive(Debug, Clone, Default)]
+pub struct CrashAnalysisContext {
+    pub max_memory_mb: Option<u32>,
+    pub system_memory_mb: Option<u64>,
+    /// The Java version Minecraft itself asks for
+    pub recommended_java: Option<u32>,
+    /// Used if the text doesn't mention the Java version
+    pub running_java: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CrashAnalysis {
+    pub description: Option<String>,
+    /// Outermost exception first, the root cause is the last one
+    pub exceptions: Vec<ExceptionInfo>,
+    pub suspected_mods: Vec<SuspectedMod>,
+    pub mixin_failures: Vec<MixinFailure>,
+    pub running_java: Option<u32>,
+    pub required_java: Option<u32>,
+    /// Most relevant first
+    pub findings: Vec<CrashFinding>,
+}
+
+struct CompiledSignature {
+    signature: CrashSignature,
+    patterns: Vec<Regex>,
+}
+
+pub struct SignatureDatabase {
+    signatures: Vec<CompiledSignature>,
+}
+
+impl SignatureDatabase {
+    /// Fails on the first invalid pattern.
+    pub fn new(signatures: Vec<CrashSignature>) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            let patterns = signature
+                .patterns
+                .iter()
+                .map(|p| {
+                    Regex::new(p).map_err(|e| {
+                        AppError::ParseError(format!(
+                            "Invalid pattern in crash signature '{}': {}",
+                            signature.id, e
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            compiled.push(CompiledSignature {
+                signature,
+                patterns,
+            });
+        }
+        Ok(Self {
+            signatures: compiled,
+        })
+    }
+
+    pub fn builtin() -> Result<Self> {
+        Self::new(builtin_signatures()?)
+    }
+
+    /// Built-in signatures plus the ones in `meta/crash_signatures/*.json`. Custom signatures
+    /// replace built-in ones with the same id, broken files are skipped.
+    pub async fn load() -> Result<Self> {
+        let dir = custom_signatures_dir();
+        if !fs::try_exists(&dir).await? {
+            return Self::builtin();
+        }
+
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut signatures = builtin_signatures()?;
+        for path in files {
+            let custom = match read_custom_signatures(&path).await {
+                Ok(custom) => custom,
+                Err(e) => {
+                    warn!("Skipping crash signatures in {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            info!("Loaded {} crash signatures from {:?}", custom.len(), path);
+            for signature in custom {
+                signatures.retain(|s| s.id != signature.id);
+                signatures.push(signature);
+            }
+        }
+        Self::new(signatures)
+    }
+
+    /// Parses the crash report and the log and matches them against the signatures.
+    pub fn analyze(
+        &self,
+        crash_report: Option<&str>,
+        log: Option<&str>,
+        context: &CrashAnalysisContext,
+    ) -> CrashAnalysis {
+        let crash_report = crash_report.filter(|r| !r.trim().is_empty());
+        let log = log.filter(|l| !l.trim().is_empty());
+        // The game also prints the crash report to the log
+        let report = crash_report
+            .or_else(|| log.and_then(|l| l.rfind(CRASH_REPORT_HEADER).map(|start| &l[start..])));
+        let text = match (crash_report, log) {
+            (Some(report), Some(log)) => format!("{}\n{}", report, log),
+            (Some(text), None) | (None, Some(text)) => text.to_string(),
+            (None, None) => String::new(),
+        };
+
+        let mut analysis = CrashAnalysis {
+            description: report.and_then(parse_description),
+            exceptions: match report {
+                Some(report) => parse_exceptions(report),
+                None => log.map(parse_log_exceptions).unwrap_or_default(),
+            },
+            suspected_mods: report.map(parse_suspected_mods).unwrap_or_default(),
+            mixin_failures: parse_mixin_failures(&text),
+            running_java: parse_running_java(&text).or(context.running_java),
+            required_java: parse_required_java(&text),
+            findings: Vec::new(),
+        };
+
+        let mut variables: HashMap<&str, String> = HashMap::new();
+        let mut set = |name, value: Option<String>| {
+            if let Some(value) = value {
+                variables.insert(name, value);
+            }
+        };
+        set("running_java", analysis.running_java.map(|v| v.to_string()));
+        set(
+            "required_java",
+            analysis.required_java.map(|v| v.to_string()),
+        );
+        set(
+            "recommended_java",
+            context.recommended_java.map(|v| v.to_string()),
+        );
+        set(
+            "max_memory_mb",
+            context.max_memory_mb.map(|v| v.to_string()),
+        );
+        set(
+            "recommended_memory_mb",
+            recommended_memory_mb(context).map(|v| v.to_string()),
+        );
+
+        let mut signatures: Vec<&CompiledSignature> = self.signatures.iter().collect();
+        signatures.sort_by_key(|s| -s.signature.priority);
+        let mut seen = HashSet::new();
+        for compiled in signatures {
+            let mut count = 0;
+            for pattern in &compiled.patterns {
+                for captures in pattern.captures_iter(&text) {
+                    if count >= MAX_FINDINGS_PER_SIGNATURE {
+                        break;
+                    }
+                    let finding =
+                        build_finding(&compiled.signature, pattern, &captures, &variables);
+                    // The crash report is usually in the log too
+                    if seen.insert((finding.signature_id.clone(), finding.message.clone())) {
+                        analysis.findings.push(finding);
+                        count += 1;
+                    }
+                }
+            }
+        }
+        analysis
+    }
+}
+
+fn builtin_signatures() -> Result<Vec<CrashSignature>> {
+    Ok(serde_json::from_str(BUILTIN_SIGNATURES)?)
+}
+
+async fn read_custom_signatures(path: &Path) -> Result<Vec<CrashSignature>> {
+    let content = fs::read_to_string(path).await?;
+    let signatures: Vec<CrashSignature> = serde_json::from_str(&content)?;
+    // Validate before merging so one bad file doesn't break the others
+    SignatureDatabase::new(signatures.clone())?;
+    Ok(signatures)
+}
+
+fn custom_signatures_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(CUSTOM_SIGNATURES_DIR)
+}
+
+fn build_finding(
+    signature: &CrashSignature,
+    pattern: &Regex,
+    captures: &Captures,
+    variables: &HashMap<&str, String>,
+) -> CrashFinding {
+    let lookup = |name: &str| -> Option<String> {
+        pattern
+            .capture_names()
+            .flatten()
+            .any(|n| n == name)
+            .then(|| captures.name(name).map(|m| m.as_str().trim().to_string()))
+            .flatten()
+            .or_else(|| variables.get(name).cloned())
+    };
+
+    let fixes = signature
+        .fixes
+        .iter()
+        .filter_map(|template| {
+            let fix = fill_value(template, &lookup)?;
+            match serde_json::from_value::<SuggestedFix>(fix) {
+                Ok(fix) => Some(fix),
+                Err(e) => {
+                    warn!("Invalid fix in crash signature '{}': {}", signature.id, e);
+                    None
+                }
+            }
+        })
+        .filter(|fix| match fix {
+            SuggestedFix::DisableMod { mod_id }
+            | SuggestedFix::InstallMod { mod_id }
+            | SuggestedFix::UpdateMod { mod_id } => !PLATFORM_MOD_IDS.contains(&mod_id.as_str()),
+            _ => true,
+        })
+        .collect();
+
+    CrashFinding {
+        signature_id: signature.id.clone(),
+        title: signature.title.clone(),
+        category: signature.category,
+        message: fill_str(&signature.message, &lookup).unwrap_or_else(|| signature.title.clone()),
+        evidence: captures[0].trim().to_string(),
+        fixes,
+    }
+}
+
+/// Replaces all placeholders, `None` if one of them is unknown.
+fn fill_str(template: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let mut missing = false;
+    let filled = PLACEHOLDER.replace_all(template, |c: &Captures| {
+        lookup(&c[1]).unwrap_or_else(|| {
+            missing = true;
+            String::new()
+        })
+    });
+    (!missing).then(|| filled.into_owned())
+}
+
+/// Fills the strings of a fix template. A string that is only a placeholder becomes a number
+/// if the value is one, so `"{required_java}"` can fill a `u32`.
+fn fill_value(template: &Value, lookup: &dyn Fn(&str) -> Option<String>) -> Option<Value> {
+    match template {
+        Value::String(s) => {
+            let filled = fill_str(s, lookup)?;
+            let whole_placeholder = PLACEHOLDER
+                .find(s)
+                .is_some_and(|m| m.start() == 0 && m.end() == s.len());
+            Some(match filled.parse::<u64>() {
+                Ok(number) if whole_placeholder => Value::from(number),
+                _ => Value::String(filled),
+            })
+        }
+        Value::Array(values) => values
+            .iter()
+            .map(|v| fill_value(v, lookup))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| fill_value(v, lookup).map(|v| (k.clone(), v)))
+            .collect::<Option<serde_json::Map<_, _>>>()
+            .map(Value::Object),
+        other => Some(other.clone()),
+    }
+}
+
+/// 1.5x the current maximum rounded up to full GB, at least 4 GB and at most 3/4 of the RAM.
+fn recommended_memory_mb(context: &CrashAnalysisContext) -> Option<u32> {
+    let current = context.max_memory_mb?;
+    let mut recommended = (current as u64 * 3 / 2).div_ceil(1024) * 1024;
+    recommended = recommended.max(4096);
+    if let Some(system) = context.system_memory_mb {
+        recommended = recommended.min(system * 3 / 4 / 512 * 512);
+    }
+    (recommended > current as u64).then_some(recommended as u32)
+}
+
+fn parse_description(report: &str) -> Option<String> {
+    report
+        .lines()
+        .find_map(|l| l.strip_prefix("Description: "))
+        .map(|d| d.trim().to_string())
+}
+
+/// Reads the exception chain that follows the description of a crash report.
+fn parse_exceptions(report: &str) -> Vec<ExceptionInfo> {
+    let body = report
+        .find("\nDescription: ")
+        .map(|start| &report[start + 1..])
+        .unwrap_or(report);
+    let mut lines = body
+        .lines()
+        .skip_while(|l| !EXCEPTION_LINE.is_match(l.trim_end()));
+    read_exception_chain(&mut lines)
+}
+
+/// Without a crash report, takes the last exception logged as an error.
+fn parse_log_exceptions(log: &str) -> Vec<ExceptionInfo> {
+    let lines: Vec<&str> = log.lines().collect();
+    let start = lines.windows(2).rposition(|pair| {
+        (pair[0].contains("/ERROR]") || pair[0].contains("/FATAL]"))
+            && EXCEPTION_LINE.is_match(pair[1].trim_end())
+    });
+    match start {
+        Some(start) => read_exception_chain(&mut lines[start + 1..].iter().copied()),
+        None => Vec::new(),
+    }
+}
+
+fn read_exception_chain(lines: &mut dyn Iterator<Item = &str>) -> Vec<ExceptionInfo> {
+    let mut chain: Vec<ExceptionInfo> = Vec::new();
+    for line in lines {
+        let line = line.trim_end();
+        let trimmed = line.trim_start();
+        if let Some(frame) = trimmed.strip_prefix("at ") {
+            if let Some(current) = chain.last_mut() {
+                if current.frames.len() < MAX_FRAMES {
+                    current.frames.push(frame.to_string());
+                }
+            }
+            continue;
+        }
+        if trimmed.starts_with("... ") && trimmed.ends_with(" more") {
+            continue;
+        }
+        // The first exception has no prefix, all further ones are causes
+        if chain.is_empty() || line.starts_with("Caused by: ") {
+            if let Some(c) = EXCEPTION_LINE.captures(line) {
+                chain.push(ExceptionInfo {
+                    class_name: c[1].to_string(),
+                    message: c.get(2).map(|m| m.as_str().to_string()),
+                    frames: Vec::new(),
+                });
+                continue;
+            }
+        }
+        break;
+    }
+    chain
+}
+
+fn parse_suspected_mods(report: &str) -> Vec<SuspectedMod> {
+    let mut suspected = Vec::new();
+    let mut in_section = false;
+    for line in report.lines() {
+        let trimmed = line.trim_start();
+        let rest = if let Some(rest) = trimmed
+            .strip_prefix("Suspected Mods:")
+            .or_else(|| trimmed.strip_prefix("Suspected Mod:"))
+        {
+            in_section = true;
+            rest.trim()
+        } else if in_section && line.starts_with(char::is_whitespace) {
+            line.trim()
+        } else {
+            in_section = false;
+            continue;
+        };
+        if let Some(c) = SUSPECTED_MOD.captures(rest) {
+            let mod_id = c[2].to_string();
+            if !suspected.iter().any(|s: &SuspectedMod| s.mod_id == mod_id) {
+                suspected.push(SuspectedMod {
+                    name: c[1].to_string(),
+                    mod_id,
+                    version: c[3].to_string(),
+                });
+            }
+        }
+    }
+    suspected
+}
+
+fn parse_mixin_failures(text: &str) -> Vec<MixinFailure> {
+    let mut failures: Vec<MixinFailure> = Vec::new();
+    for c in MIXIN_FAILURE.captures_iter(text) {
+        let failure = MixinFailure {
+            mod_id: c.get(1).map(|m| m.as_str().to_string()),
+            config: c[2].to_string(),
+            mixin: c[3].to_string(),
+            target: c[4].to_string(),
+        };
+        if !failures.contains(&failure) {
+            failures.push(failure);
+        }
+    }
+    failures
+}
+
+fn parse_running_java(text: &str) -> Option<u32> {
+    let c = RUNNING_JAVA.captures(text)?;
+    let version = c.get(1).or_else(|| c.get(2))?.as_str();
+    java_detector::parse_java_major_version(version)
+}
+
+/// The highest Java version something in the text asks for.
+fn parse_required_java(text: &str) -> Option<u32> {
+    REQUIRED_JAVA
+        .captures_iter(text)
+        .filter_map(|c| {
+            if let Some(class_version) = c.get(1) {
+                // Class file version 52 is Java 8
+                class_version
+                    .as_str()
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(|v| v.checked_sub(44))
+            } else {
+                c.get(2)?.as_str().parse().ok()
+            }
+        })
+        .max()
+}
+
+/// Fills the context from the profile and the system, best effort.
+async fn context_for_profile(profile: &Profile) -> CrashAnalysisContext {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    let api_service = MinecraftApiService::new();
+    let recommended_java = match api_service.get_version_manifest().await {
+        Ok(manifest) => match manifest
+            .versions
+            .iter()
+            .find(|v| v.id == profile.game_version)
+        {
+            Some(version) => api_service
+                .get_piston_meta(&version.url)
+                .await
+                .ok()
+                .map(|meta| meta.java_version.major_version as u32),
+            None => None,
+        },
+        Err(e) => {
+            warn!(
+                "Could not load the version manifest for the crash analysis: {}",
+                e
+            );
+            None
+        }
+    };
+    CrashAnalysisContext {
+        max_memory_mb: Some(profile.settings.memory.max),
+        system_memory_mb: Some(sys.total_memory() / (1024 * 1024)),
+        recommended_java,
+        running_java: None,
+    }
+}
+
+/// Analyzes pasted text or a log file opened by the user.
+pub async fn analyze_text(
+    crash_report: Option<&str>,
+    log: Option<&str>,
+    profile_id: Option<Uuid>,
+) -> Result<CrashAnalysis> {
+    let context = match profile_id {
+        Some(profile_id) => {
+            let state = State::get().await?;
+            let profile = state.profile_manager.get_profile(profile_id).await?;
+            context_for_profile(&profile).await
+        }
+        None => CrashAnalysisContext::default(),
+    };
+    let database = SignatureDatabase::load().await?;
+    Ok(database.analyze(crash_report, log, &context))
+}
+
+/// Analyzes the last run of a profile: `latest.log`, the crash report it points to and, if the
+/// process id is known, the JVM error log.
+pub async fn analyze_profile(
+    profile_id: Uuid,
+    crash_report: Option<String>,
+    pid: Option<u32>,
+) -> Result<CrashAnalysis> {
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+
+    let mut log = read_lossy(&instance_path.join("logs").join("latest.log")).await;
+    let crash_report = match crash_report {
+        Some(report) => Some(report),
+        None => match log.as_deref().and_then(saved_crash_report_path) {
+            Some(path) => read_lossy(&path).await,
+            None => None,
+        },
+    };
+    // Native crashes (e.g. in the graphics driver) only end up in the JVM error log
+    if let Some(pid) = pid {
+        let jvm_error_log = instance_path.join(format!("hs_err_pid{}.log", pid));
+        if let Some(jvm_log) = read_lossy(&jvm_error_log).await {
+            log = Some(match log {
+                Some(log) => format!("{}\n{}", log, jvm_log),
+                None => jvm_log,
+            });
+        }
+    }
+
+    let context = context_for_profile(&profile).await;
+    let database = SignatureDatabase::load().await?;
+    let analysis = database.analyze(crash_report.as_deref(), log.as_deref(), &context);
+    info!(
+        "Crash analysis for profile {}: {} findings",
+        profile_id,
+        analysis.findings.len()
+    );
+    Ok(analysis)
+}
+
+async fn read_lossy(path: &Path) -> Option<String> {
+    fs::read(path)
+        .await
+        .ok()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The game logs where it saved the crash report.
+fn saved_crash_report_path(log: &str) -> Option<PathBuf> {
+    log.lines().rev().find_map(|line| {
+        let (_, rest) = line.split_once("Crash report saved to:")?;
+        let path = rest.trim().trim_start_matches("#@!@#").trim();
+        (!path.is_empty()).then(|| PathBuf::from(path))
+    })
+}
@@ -2,9 +2,9 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::forge_install_profile::ForgeInstallProfile;
 use crate::minecraft::dto::forge_meta::ForgeVersion;
+use crate::utils::download_utils::VerifiedDownload;
 use async_zip::tokio::read::seek::ZipFileReader;
 use log::info;
-use reqwest;
 use std::path::PathBuf;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
@@ -48,26 +48,14 @@ impl ForgeInstallerDownloadService {
         // Konstruiere die Download-URL
         let url = format!("https://maven.minecraftforge.net/{}", maven_path);
 
-        // Lade die JAR herunter
+        // Lade die JAR herunter (landet erst als .part und wird bei Abbruch fortgesetzt)
         info!("Downloading from: {}", url);
-        let response = reqwest::get(&url).await.map_err(|e| {
-            AppError::Download(format!("Failed to download Forge installer: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Download(format!(
-                "Failed to download Forge installer: Status {}",
-                response.status()
-            )));
-        }
-
-        let bytes = response.bytes().await.map_err(|e| {
-            AppError::Download(format!("Failed to download Forge installer: {}", e))
-        })?;
-
-        // Speichere die JAR-Datei
-        let mut file = fs::File::create(&jar_path).await?;
-        file.write_all(&bytes).await?;
+        VerifiedDownload::new(&url, &jar_path)
+            .download()
+            .await
+            .map_err(|e| {
+                AppError::Download(format!("Failed to download Forge installer: {}", e))
+            })?;
 
         info!(
             "Successfully downloaded Forge installer to: {}",
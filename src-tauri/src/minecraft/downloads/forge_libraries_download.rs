@@ -2,13 +2,11 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::forge_install_profile::ForgeInstallProfile;
 use crate::minecraft::dto::forge_meta::ForgeVersion;
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use futures::stream::{iter, StreamExt};
 use log::info;
-use reqwest;
-use sha1::{Digest, Sha1};
 use std::path::PathBuf;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 
 const LIBRARIES_DIR: &str = "libraries";
 const DEFAULT_CONCURRENT_DOWNLOADS: usize = 10;
@@ -86,77 +84,18 @@ impl ForgeLibrariesDownload {
 
         let target_path = self.get_library_path(download_info);
 
-        // Check if file exists and verify hash if available
-        if fs::try_exists(&target_path).await? {
-            if let Some(expected_sha1) = &download_info.sha1 {
-                let file_content = fs::read(&target_path).await?;
-                let mut hasher = Sha1::new();
-                hasher.update(&file_content);
-                let actual_sha1 = format!("{:x}", hasher.finalize());
-
-                if actual_sha1 == *expected_sha1 {
-                    info!(
-                        "📦 Library already exists and hash matches: {}",
-                        download_info.path
-                    );
-                    return Ok(());
-                } else {
-                    info!(
-                        "⚠️ Library exists but hash mismatch, redownloading: {}",
-                        download_info.path
-                    );
-                }
-            } else {
-                info!(
-                    "📦 Library already exists (no hash to verify): {}",
-                    download_info.path
-                );
-                return Ok(());
-            }
-        }
-
-        // Download the file
-        info!("⬇️ Downloading: {}", download_info.path);
-
-        let response = reqwest::get(&download_info.url)
-            .await
-            .map_err(|e| AppError::Download(format!("Failed to download library: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Download(format!(
-                "Failed to download library: Status {}",
-                response.status()
-            )));
-        }
-
-        let bytes = response
-            .bytes()
+        // Existing files are verified against sha1/size, broken ones are redownloaded
+        let outcome = VerifiedDownload::new(&download_info.url, &target_path)
+            .sha1(download_info.sha1.as_deref())
+            .size(u64::try_from(download_info.size).ok())
+            .download()
             .await
             .map_err(|e| AppError::Download(format!("Failed to download library: {}", e)))?;
-
-        // Verify hash if available
-        if let Some(expected_sha1) = &download_info.sha1 {
-            let mut hasher = Sha1::new();
-            hasher.update(&bytes);
-            let actual_sha1 = format!("{:x}", hasher.finalize());
-
-            if actual_sha1 != *expected_sha1 {
-                return Err(AppError::Download(format!(
-                    "Hash mismatch for {}: expected {}, got {}",
-                    download_info.path, expected_sha1, actual_sha1
-                )));
-            }
-        }
-
-        // Create parent directories if they don't exist
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!("📦 Library already exists: {}", download_info.path);
+            return Ok(());
         }
 
-        // Save the file
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
-
         info!("💾 Saved: {}", download_info.path);
         Ok(())
     }
@@ -324,27 +263,12 @@ impl ForgeLibrariesDownload {
                 info!("\n⬇️ Downloading: {}", maven_path);
                 info!("  📎 URL: {}", url);
 
-                let response = reqwest::get(&url).await.map_err(|e| {
-                    AppError::Download(format!("Failed to download library: {}", e))
-                })?;
-
-                if !response.status().is_success() {
-                    info!(
-                        "❌ Failed to download library '{}': Status {}",
-                        library.name,
-                        response.status()
-                    );
+                // Legacy libraries are best effort, not every one of them is hosted anywhere
+                if let Err(e) = VerifiedDownload::new(&url, &target_path).download().await {
+                    info!("❌ Failed to download library '{}': {}", library.name, e);
                     return Ok(());
                 }
 
-                let bytes = response.bytes().await.map_err(|e| {
-                    AppError::Download(format!("Failed to download library: {}", e))
-                })?;
-
-                // Speichere die Datei
-                let mut file = fs::File::create(&target_path).await?;
-                file.write_all(&bytes).await?;
-
                 info!("✅ Successfully downloaded: {}", maven_path);
                 Ok(())
             });
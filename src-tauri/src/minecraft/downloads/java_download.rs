@@ -2,6 +2,7 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::{JavaDistribution, ZuluApiResponse};
 use crate::state::State;
+use crate::utils::download_utils::{DownloadProgress, VerifiedDownload};
 use crate::utils::system_info::{Architecture, OperatingSystem, ARCHITECTURE, OS};
 use async_zip::tokio::read::seek::ZipFileReader;
 use flate2::read::GzDecoder;
@@ -15,7 +16,7 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use tar::Archive;
 use tokio::fs;
-use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::io::BufReader;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 
 const JAVA_DIR: &str = "java";
@@ -27,6 +28,7 @@ const LEGACY_JAVA_COMPONENT: &str = "jre-legacy";
 pub struct JavaDownloadService {
     base_path: PathBuf,
     concurrent_extractions: usize,
+    progress: Option<DownloadProgress>,
 }
 
 impl JavaDownloadService {
@@ -35,9 +37,16 @@ impl JavaDownloadService {
         Self {
             base_path,
             concurrent_extractions: DEFAULT_CONCURRENT_EXTRACTIONS,
+            progress: None,
         }
     }
 
+    /// Reports the byte progress of the JDK archive download through the given event.
+    pub fn with_progress(mut self, progress: DownloadProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     // Check if we need to use x86_64 Java based on the Java component
     pub fn needs_x86_64_java(&self, java_component: Option<&str>) -> bool {
         // Only needed on Apple Silicon Macs
@@ -104,7 +113,8 @@ impl JavaDownloadService {
         info!("Java Download URL: {}", initial_url);
 
         // For Zulu, we need to make an extra API call to get the actual download URL
-        let download_url = if distribution.requires_api_response() {
+        let (download_url, expected_sha256, expected_size) = if distribution.requires_api_response()
+        {
             info!("Fetching actual download URL from Zulu API...");
             let client = reqwest::Client::new();
             let response = client
@@ -127,9 +137,13 @@ impl JavaDownloadService {
             })?;
 
             info!("Actual download URL: {}", zulu_response.url);
-            zulu_response.url
+            (
+                zulu_response.url,
+                zulu_response.sha256_hash,
+                zulu_response.size,
+            )
         } else {
-            initial_url
+            (initial_url, None, None)
         };
 
         // Create version-specific directory with architecture suffix for legacy support
@@ -142,28 +156,16 @@ impl JavaDownloadService {
         let version_dir = self.base_path.join(dir_name);
         fs::create_dir_all(&version_dir).await?;
 
-        // Download the Java distribution
-        let response = reqwest::get(&download_url)
-            .await
-            .map_err(|e| AppError::JavaDownload(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::JavaDownload(format!(
-                "Failed to download Java: Status {}",
-                response.status()
-            )));
-        }
-
-        let bytes = response
-            .bytes()
+        // Stream the archive to disk (JDKs are ~200 MB, never buffer them in memory)
+        let archive_path = version_dir.join(format!("java.{}", OS.get_archive_type()?));
+        VerifiedDownload::new(&download_url, &archive_path)
+            .sha256(expected_sha256.as_deref())
+            .size(expected_size)
+            .progress(self.progress.clone())
+            .download()
             .await
             .map_err(|e| AppError::JavaDownload(e.to_string()))?;
 
-        // Save the downloaded file
-        let archive_path = version_dir.join(format!("java.{}", OS.get_archive_type()?));
-        let mut file = fs::File::create(&archive_path).await?;
-        file.write_all(&bytes).await?;
-
         // Extract the archive
         self.extract_java_archive(&archive_path, &version_dir)
             .await?;
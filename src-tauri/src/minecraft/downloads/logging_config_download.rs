@@ -1,11 +1,9 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::piston_meta::LoggingClient;
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use log::{error, info};
-use reqwest;
 use std::path::PathBuf;
-use tokio::fs;
-use tokio::io::AsyncWriteExt;
 
 const LOGGING_DIR: &str = "assets/log_configs";
 
@@ -29,55 +27,23 @@ impl MinecraftLoggingDownloadService {
         let file_name = logging.file.id.clone();
         let target_path = self.logging_configs_path.join(&file_name);
 
-        // Check if file exists with correct size
-        if fs::try_exists(&target_path).await? {
-            if let Ok(metadata) = fs::metadata(&target_path).await {
-                if metadata.len() as i64 == logging.file.size {
-                    info!("[Logging Config Download] Config file {} already exists with correct size.", file_name);
-                    return Ok(target_path);
-                }
-                info!("[Logging Config Download] Config file {} exists but size mismatch, redownloading.", file_name);
-            }
-        }
-
-        info!(
-            "[Logging Config Download] Downloading logging config: {}",
-            file_name
-        );
-        let response = reqwest::get(&logging.file.url).await.map_err(|e| {
-            error!("[Logging Config Download] Request error: {}", e);
-            AppError::Download(format!("Failed to download logging configuration: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "No error details available".to_string());
-
-            error!(
-                "[Logging Config Download] Failed with status {}: {}",
-                status, error_text
+        let outcome = VerifiedDownload::new(&logging.file.url, &target_path)
+            .sha1(Some(&logging.file.sha1))
+            .size(u64::try_from(logging.file.size).ok())
+            .download()
+            .await
+            .map_err(|e| {
+                error!("[Logging Config Download] Download failed: {}", e);
+                AppError::Download(format!("Failed to download logging configuration: {}", e))
+            })?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!(
+                "[Logging Config Download] Config file {} already exists and is valid.",
+                file_name
             );
-            return Err(AppError::Download(format!(
-                "Failed to download logging config - Status {}: {}",
-                status, error_text
-            )));
+            return Ok(target_path);
         }
 
-        let bytes = response.bytes().await.map_err(|e| {
-            error!("[Logging Config Download] Failed to read bytes: {}", e);
-            AppError::Download(format!("Failed to read logging config data: {}", e))
-        })?;
-
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
-
         info!(
             "[Logging Config Download] Successfully downloaded logging config to: {}",
             target_path.display()
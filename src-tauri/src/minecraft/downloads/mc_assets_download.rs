@@ -3,15 +3,15 @@ use crate::error::{AppError, Result};
 use crate::minecraft::dto::piston_meta::{AssetIndex, AssetIndexContent, AssetObject};
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::State;
+use crate::utils::download_utils::VerifiedDownload;
 use crate::utils::mc_utils;
 use futures::stream::{iter, StreamExt};
 use log::{debug, error, info, trace, warn};
-use reqwest;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
 const ASSETS_DIR: &str = "assets";
@@ -113,7 +113,15 @@ impl MinecraftAssetsDownloadService {
         )
         .await?;
 
-        let assets: Vec<(String, AssetObject)> = asset_index_content.objects.into_iter().collect();
+        // Several names can point to the same object, download each hash only once so
+        // no two tasks write the same `.part` file
+        let mut assets_by_hash: HashMap<String, (String, AssetObject)> = HashMap::new();
+        for (name, asset) in asset_index_content.objects {
+            assets_by_hash
+                .entry(asset.hash.clone())
+                .or_insert((name, asset));
+        }
+        let assets: Vec<(String, AssetObject)> = assets_by_hash.into_values().collect();
         let mut downloads = Vec::new();
         let assets_path = self.assets_path.clone();
         let task_counter = Arc::new(AtomicUsize::new(1)); // Start counter at 1
@@ -163,83 +171,23 @@ impl MinecraftAssetsDownloadService {
                     hash
                 );
 
-                let response_result = reqwest::get(&url).await;
-
-                let response = match response_result {
-                    Ok(resp) => resp,
-                    Err(e) => {
-                        error!(
-                            "[Assets Download Task {}] Request error for {}: {}",
-                            task_id, name_clone, e
-                        );
-                        return Err(AppError::Download(format!(
-                            "Failed to initiate download for asset {}: {}",
-                            name_clone, e
-                        )));
-                    }
-                };
-
-                if !response.status().is_success() {
-                    let status = response.status();
-                    let error_text = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "No error details available".to_string());
+                // Asset objects are named after their sha1
+                if let Err(e) = VerifiedDownload::new(&url, &target_path)
+                    .sha1(Some(&hash))
+                    .size(u64::try_from(size).ok())
+                    .download()
+                    .await
+                {
                     error!(
-                        "[Assets Download Task {}] Failed download for {}: Status {}, Error: {}",
-                        task_id, name_clone, status, error_text
+                        "[Assets Download Task {}] Failed download for {}: {}",
+                        task_id, name_clone, e
                     );
                     return Err(AppError::Download(format!(
-                        "Failed to download asset {} - Status {}: {}",
-                        name_clone, status, error_text
+                        "Failed to download asset {}: {}",
+                        name_clone, e
                     )));
                 }
 
-                let bytes_result = response.bytes().await;
-                let bytes = match bytes_result {
-                    Ok(b) => b,
-                    Err(e) => {
-                        error!(
-                            "[Assets Download Task {}] Error reading bytes for {}: {}",
-                            task_id, name_clone, e
-                        );
-                        return Err(AppError::Download(format!(
-                            "Failed to read bytes for asset {}: {}",
-                            name_clone, e
-                        )));
-                    }
-                };
-
-                if let Some(parent) = target_path.parent() {
-                    if let Err(e) = fs::create_dir_all(parent).await {
-                        error!(
-                            "[Assets Download Task {}] Error creating directory for {}: {}",
-                            task_id, name_clone, e
-                        );
-                        return Err(AppError::Io(e));
-                    }
-                }
-
-                let file_result = fs::File::create(&target_path).await;
-                let mut file = match file_result {
-                    Ok(f) => f,
-                    Err(e) => {
-                        error!(
-                            "[Assets Download Task {}] Error creating file for {}: {}",
-                            task_id, name_clone, e
-                        );
-                        return Err(AppError::Io(e));
-                    }
-                };
-
-                if let Err(e) = file.write_all(&bytes).await {
-                    error!(
-                        "[Assets Download Task {}] Error writing file for {}: {}",
-                        task_id, name_clone, e
-                    );
-                    return Err(AppError::Io(e));
-                }
-
                 // Increment completed counter
                 let completed = completed_counter_clone.fetch_add(1, Ordering::SeqCst) + 1;
                 let total = total_to_download_clone.load(Ordering::SeqCst);
@@ -405,82 +353,20 @@ impl MinecraftAssetsDownloadService {
             "[Assets Download] Downloading asset index: {}",
             asset_index.id
         );
-        let response_result = reqwest::get(&asset_index.url).await;
-        let response = match response_result {
-            Ok(resp) => resp,
-            Err(e) => {
-                error!(
-                    "[Assets Download] Failed request for asset index {}: {}",
-                    asset_index.id, e
-                );
-                return Err(AppError::Download(format!(
-                    "Failed to download asset index {}: {}",
-                    asset_index.id, e
-                )));
-            }
-        };
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "No error details available".to_string());
+        if let Err(e) = VerifiedDownload::new(&asset_index.url, &index_path)
+            .sha1(Some(&asset_index.sha1))
+            .size(u64::try_from(asset_index.size).ok())
+            .download()
+            .await
+        {
             error!(
-                "[Assets Download] Failed response for asset index {}: Status {}, Error: {}",
-                asset_index.id, status, error_text
+                "[Assets Download] Failed download of asset index {}: {}",
+                asset_index.id, e
             );
             return Err(AppError::Download(format!(
-                "Failed to download asset index {} - Status {}: {}",
-                asset_index.id, status, error_text
-            )));
-        }
-
-        let bytes_result = response.bytes().await;
-        let bytes = match bytes_result {
-            Ok(b) => b,
-            Err(e) => {
-                error!(
-                    "[Assets Download] Failed reading bytes for asset index {}: {}",
-                    asset_index.id, e
-                );
-                return Err(AppError::Download(format!(
-                    "Failed to read bytes for asset index {}: {}",
-                    asset_index.id, e
-                )));
-            }
-        };
-
-        // Ensure parent directory exists
-        if let Some(parent) = index_path.parent() {
-            if let Err(e) = fs::create_dir_all(parent).await {
-                error!(
-                    "[Assets Download] Failed creating directory for asset index {}: {}",
-                    asset_index.id, e
-                );
-                return Err(AppError::Io(e));
-            }
-        }
-
-        // Write the index file
-        let file_result = fs::File::create(&index_path).await;
-        let mut file = match file_result {
-            Ok(f) => f,
-            Err(e) => {
-                error!(
-                    "[Assets Download] Failed creating file for asset index {}: {}",
-                    asset_index.id, e
-                );
-                return Err(AppError::Io(e));
-            }
-        };
-
-        if let Err(e) = file.write_all(&bytes).await {
-            error!(
-                "[Assets Download] Failed writing file for asset index {}: {}",
+                "Failed to download asset index {}: {}",
                 asset_index.id, e
-            );
-            return Err(AppError::Io(e));
+            )));
         }
 
         info!(
@@ -488,7 +374,8 @@ impl MinecraftAssetsDownloadService {
             asset_index.id
         );
 
-        Ok(serde_json::from_slice(&bytes)?)
+        let content = fs::read(&index_path).await?;
+        Ok(serde_json::from_slice(&content)?)
     }
 
     /// Helper method for emitting progress events
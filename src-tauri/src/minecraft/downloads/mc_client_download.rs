@@ -1,11 +1,10 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
-use crate::error::{AppError, Result};
+use crate::error::Result;
 use crate::minecraft::dto::piston_meta::DownloadInfo;
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use log::info;
-use reqwest;
 use std::path::PathBuf;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 
 const VERSIONS_DIR: &str = "versions";
 
@@ -29,34 +28,16 @@ impl MinecraftClientDownloadService {
 
         fs::create_dir_all(&version_dir).await?;
 
-        if target_path.exists() {
-            let metadata = fs::metadata(&target_path).await?;
-            if metadata.len() as i64 == client_info.size {
-                info!("Client jar already exists with correct size");
-                return Ok(());
-            }
+        let outcome = VerifiedDownload::new(&client_info.url, &target_path)
+            .sha1(Some(&client_info.sha1))
+            .size(u64::try_from(client_info.size).ok())
+            .download()
+            .await?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!("Client jar already exists and is valid");
+            return Ok(());
         }
 
-        let url = &client_info.url;
-        let response = reqwest::get(url).await.map_err(AppError::MinecraftApi)?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "No error details available".to_string());
-            return Err(AppError::Download(format!(
-                "Failed to download client jar - Status {}: {}",
-                status, error_text
-            )));
-        }
-
-        let bytes = response.bytes().await.map_err(AppError::MinecraftApi)?;
-
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
-
         info!("Downloaded client jar to: {}", target_path.display());
         Ok(())
     }
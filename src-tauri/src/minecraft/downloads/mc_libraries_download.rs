@@ -1,12 +1,10 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
-use crate::error::{AppError, Result};
+use crate::error::Result;
 use crate::minecraft::dto::piston_meta::{DownloadInfo, Library};
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use futures::stream::{iter, StreamExt};
 use log::info;
-use reqwest;
 use std::path::PathBuf;
-use tokio::fs;
-use tokio::io::AsyncWriteExt;
 
 const LIBRARIES_DIR: &str = "libraries";
 const DEFAULT_CONCURRENT_DOWNLOADS: usize = 12;
@@ -75,41 +73,18 @@ impl MinecraftLibrariesDownloadService {
     async fn download_file(&self, download_info: &DownloadInfo) -> Result<()> {
         let target_path = self.get_library_path(download_info);
 
-        if fs::try_exists(&target_path).await? {
-            let metadata = fs::metadata(&target_path).await?;
-            if metadata.len() as i64 == download_info.size {
-                info!(
-                    "File already exists with correct size: {}",
-                    target_path.display()
-                );
-                return Ok(());
-            }
-        }
-
-        let url = &download_info.url;
-        let response = reqwest::get(url).await.map_err(AppError::MinecraftApi)?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "No error details available".to_string());
-            return Err(AppError::Download(format!(
-                "Failed to download file from {} - Status {}: {}",
-                url, status, error_text
-            )));
+        let outcome = VerifiedDownload::new(&download_info.url, &target_path)
+            .sha1(Some(&download_info.sha1))
+            .size(u64::try_from(download_info.size).ok())
+            .download()
+            .await?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!(
+                "File already exists and is valid: {}",
+                target_path.display()
+            );
         }
 
-        let bytes = response.bytes().await.map_err(AppError::MinecraftApi)?;
-
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
-
         Ok(())
     }
 
@@ -2,15 +2,13 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::downloads::mod_resolver::TargetMod;
 use crate::state::profile_state::{self, ModSource, Profile};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use futures::stream::{iter, StreamExt};
-use hex;
 use log::{debug, error, info, warn};
-use reqwest;
-use sha1::{Digest, Sha1};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use tokio::fs::{self, read_dir};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 const DEFAULT_CONCURRENT_MOD_DOWNLOADS: usize = 4;
 const MOD_CACHE_DIR_NAME: &str = "mod_cache";
@@ -97,14 +95,25 @@ impl ModDownloadService {
                             e
                         })
                     }
-                    ModSource::Url { url, file_name, .. } => {
-                        let fname = file_name.as_deref().unwrap_or("unknown");
-                        debug!(
-                            "Skipping URL mod source (cache): {} from {}",
-                            display_name_opt.as_deref().unwrap_or(fname),
-                            url
+                    ModSource::Url {
+                        url,
+                        file_hash_sha1,
+                        ..
+                    } => {
+                        info!(
+                            "Preparing URL mod for cache: {} ({})",
+                            display_name, filename
                         );
-                        Ok(())
+                        Self::download_and_verify_file(
+                            &url,
+                            &target_path,
+                            file_hash_sha1.as_deref(),
+                        )
+                        .await
+                        .map_err(|e| {
+                            error!("Failed cache mod {}: {}", display_name, e);
+                            e
+                        })
                     }
                     ModSource::Local { file_name } => {
                         debug!("Skipping local mod (cache check): {}", file_name);
@@ -213,6 +222,9 @@ impl ModDownloadService {
             .cloned()
             .collect();
 
+        let state = State::get().await?;
+
+        let mut removed_paths = Vec::new();
         for filename in &mods_to_remove {
             let target_path = profile_mods_dir.join(filename);
             info!("Removing mod from '{}': {}", profile_name, filename);
@@ -220,19 +232,17 @@ impl ModDownloadService {
                 error!("Failed to remove {:?}: {}", target_path, e);
                 AppError::Io(e)
             })?;
+            removed_paths.push(target_path);
         }
+        state.content_store.release_paths(&removed_paths).await?;
 
+        // Mods are hardlinked from the content store instead of copied into every profile
+        let mut files_to_link = Vec::new();
         for filename in &mods_to_add {
             if let Some(cache_path) = required_mods.get(filename) {
                 let target_path = profile_mods_dir.join(filename);
-                info!("Copying mod to '{}': {}", profile_name, filename);
-                fs::copy(cache_path, &target_path).await.map_err(|e| {
-                    error!(
-                        "Failed to copy {:?} to {:?}: {}",
-                        cache_path, target_path, e
-                    );
-                    AppError::Io(e)
-                })?;
+                info!("Linking mod to '{}': {}", profile_name, filename);
+                files_to_link.push((cache_path.clone(), target_path));
             } else {
                 error!(
                     "Cache path not found for required mod '{}'! This indicates an internal error.",
@@ -244,6 +254,14 @@ impl ModDownloadService {
                 )));
             }
         }
+        state
+            .content_store
+            .link_files(&files_to_link)
+            .await
+            .map_err(|e| {
+                error!("Failed to link mods into {:?}: {}", profile_mods_dir, e);
+                e
+            })?;
 
         info!(
             "Mod sync completed for '{}' -> {:?}",
@@ -259,103 +277,13 @@ impl ModDownloadService {
         expected_sha1: Option<&str>,
     ) -> Result<()> {
         debug!("Checking file: {:?}", target_path);
-
-        if target_path.exists() {
-            if let Some(expected_hash) = expected_sha1 {
-                debug!("File exists, verifying SHA1 hash...");
-                let current_hash = Self::calculate_sha1(target_path).await?;
-                if current_hash.eq_ignore_ascii_case(expected_hash) {
-                    info!("File already exists and hash matches: {:?}", target_path);
-                    return Ok(());
-                } else {
-                    warn!(
-                        "Hash mismatch (Expected: {}, Found: {}). Redownloading: {:?}",
-                        expected_hash, current_hash, target_path
-                    );
-                    fs::remove_file(target_path).await.map_err(|e| {
-                        AppError::Download(format!("Failed to remove {:?}: {}", target_path, e))
-                    })?;
-                }
-            } else {
-                info!("File exists, skipping (no hash check): {:?}", target_path);
-                return Ok(());
-            }
+        let outcome = VerifiedDownload::new(url, target_path)
+            .sha1(expected_sha1)
+            .download()
+            .await?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!("File already exists and is valid: {:?}", target_path);
         }
-
-        info!("Downloading from {} to {:?}", url, target_path);
-        let response = reqwest::get(url)
-            .await
-            .map_err(|e| AppError::Download(format!("Request failed for {}: {}", url, e)))?;
-
-        if !response.status().is_success() {
-            return Err(AppError::Download(format!(
-                "Download failed: Status {}",
-                response.status()
-            )));
-        }
-
-        if let Some(parent) = target_path.parent() {
-            if !parent.exists() {
-                fs::create_dir_all(parent).await?;
-            }
-        }
-        let mut file = fs::File::create(target_path).await.map_err(|e| {
-            AppError::Download(format!("Failed to create file {:?}: {}", target_path, e))
-        })?;
-        let mut stream = response.bytes_stream();
-
-        while let Some(chunk_result) = stream.next().await {
-            let chunk =
-                chunk_result.map_err(|e| AppError::Download(format!("Stream error: {}", e)))?;
-            file.write_all(&chunk)
-                .await
-                .map_err(|e| AppError::Download(format!("Write error: {}", e)))?;
-        }
-
-        debug!("Finished writing file: {:?}", target_path);
-
-        if let Some(expected_hash) = expected_sha1 {
-            debug!("Verifying SHA1 after download...");
-            let downloaded_hash = Self::calculate_sha1(target_path).await?;
-            if !downloaded_hash.eq_ignore_ascii_case(expected_hash) {
-                error!(
-                    "Hash mismatch after download! Expected: {}, Found: {}. Deleting: {:?}",
-                    expected_hash, downloaded_hash, target_path
-                );
-                fs::remove_file(target_path).await.map_err(|e| {
-                    AppError::Download(format!(
-                        "Failed to remove invalid file {:?}: {}",
-                        target_path, e
-                    ))
-                })?;
-                return Err(AppError::Download(
-                    "Hash mismatch after download".to_string(),
-                ));
-            } else {
-                info!("Hash verified: {:?}", target_path);
-            }
-        }
-
         Ok(())
     }
-
-    /// Calculates the SHA1 hash of a file asynchronously.
-    async fn calculate_sha1(file_path: &PathBuf) -> Result<String> {
-        let mut file = fs::File::open(file_path)
-            .await
-            .map_err(|e| AppError::Io(e))?;
-        let mut hasher = Sha1::new();
-        let mut buffer = [0; 1024];
-
-        loop {
-            let n = file.read(&mut buffer).await.map_err(|e| AppError::Io(e))?;
-            if n == 0 {
-                break;
-            }
-            hasher.update(&buffer[..n]);
-        }
-
-        let hash_bytes = hasher.finalize();
-        Ok(hex::encode(hash_bytes))
-    }
 }
@@ -829,6 +829,7 @@ impl NoriskClientAssetsDownloadService {
             batch_count += 1;
             let mut batch_copied = 0;
             let mut batch_skipped = 0;
+            let mut batch_links = Vec::new();
 
             for (name, _asset) in chunk {
                 let source_path = source_dir.join(&name);
@@ -905,7 +906,13 @@ impl NoriskClientAssetsDownloadService {
                             fs::create_dir_all(parent).await?;
                         }
                     }
-                    fs::copy(&source_path, &target_path).await?;
+                    if state.is_some() && !is_override {
+                        // Linked from the content store at the end of the batch
+                        batch_links.push((source_path.clone(), target_path.clone()));
+                    } else {
+                        // Overrides are meant to be edited by the user, they stay real copies
+                        fs::copy(&source_path, &target_path).await?;
+                    }
                     copied_count += 1;
                     batch_copied += 1;
                 } else {
@@ -914,6 +921,10 @@ impl NoriskClientAssetsDownloadService {
                 }
             }
 
+            if let Some(state_ref) = &state {
+                state_ref.content_store.link_files(&batch_links).await?;
+            }
+
             if let (Some(state_ref), Some(profile_id_val)) = (&state, profile_id) {
                 let progress_within_copy = (batch_count as f64 / total_batches as f64) * 0.9 + 0.1;
                 self.emit_copy_event(
@@ -1,13 +1,11 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::Result;
 use crate::minecraft::dto::quilt_meta::{QuiltLibrary, QuiltVersionInfo};
+use crate::utils::download_utils::{DownloadOutcome, VerifiedDownload};
 use futures::stream::StreamExt;
 use log::info;
-use reqwest;
-use sha1::{Digest, Sha1};
 use std::path::PathBuf;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 
 pub struct QuiltLibrariesDownloadService {
     base_path: PathBuf,
@@ -116,29 +114,15 @@ impl QuiltLibrariesDownloadService {
 
         // Download the artifact
         info!("⬇️ Downloading Maven artifact: {}", maven);
-        let response = reqwest::get(&url).await.map_err(|e| {
-            crate::error::AppError::QuiltError(format!("Failed to download Maven artifact: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(crate::error::AppError::QuiltError(format!(
-                "Failed to download Maven artifact: Status {}",
-                response.status()
-            )));
-        }
-
-        let bytes = response.bytes().await.map_err(|e| {
-            crate::error::AppError::QuiltError(format!("Failed to download Maven artifact: {}", e))
-        })?;
-
-        // Create parent directories if they don't exist
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        // Save the file
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
+        VerifiedDownload::new(&url, &target_path)
+            .download()
+            .await
+            .map_err(|e| {
+                crate::error::AppError::QuiltError(format!(
+                    "Failed to download Maven artifact: {}",
+                    e
+                ))
+            })?;
 
         info!("💾 Saved Maven artifact: {}", maven);
         Ok(())
@@ -270,72 +254,21 @@ impl QuiltLibrariesDownloadService {
             .join(version)
             .join(format!("{}-{}.jar", artifact, version));
 
-        // Check if file exists and verify hash if available
-        if fs::try_exists(&target_path).await? {
-            if let Some(expected_sha1) = &library.sha1 {
-                let file_content = fs::read(&target_path).await?;
-                let mut hasher = Sha1::new();
-                hasher.update(&file_content);
-                let actual_sha1 = format!("{:x}", hasher.finalize());
-
-                if actual_sha1 == *expected_sha1 {
-                    info!(
-                        "📦 Library already exists and hash matches: {}",
-                        library.name
-                    );
-                    return Ok(());
-                } else {
-                    info!(
-                        "⚠️ Library exists but hash mismatch, redownloading: {}",
-                        library.name
-                    );
-                }
-            } else {
-                info!("📦 Library already exists: {}", library.name);
-                return Ok(());
-            }
-        }
-
-        // Download the library
-        info!("⬇️ Downloading: {} from {}", library.name, url);
-        let response = reqwest::get(url).await.map_err(|e| {
-            crate::error::AppError::QuiltError(format!("Failed to download library: {}", e))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(crate::error::AppError::QuiltError(format!(
-                "Failed to download library: Status {}",
-                response.status()
-            )));
-        }
-
-        let bytes = response.bytes().await.map_err(|e| {
-            crate::error::AppError::QuiltError(format!("Failed to download library: {}", e))
-        })?;
-
-        // Verify hash if available
-        if let Some(expected_sha1) = &library.sha1 {
-            let mut hasher = Sha1::new();
-            hasher.update(&bytes);
-            let actual_sha1 = format!("{:x}", hasher.finalize());
-
-            if actual_sha1 != *expected_sha1 {
-                return Err(crate::error::AppError::QuiltError(format!(
-                    "Hash mismatch for {}: expected {}, got {}",
-                    library.name, expected_sha1, actual_sha1
-                )));
-            }
-        }
-
-        // Create parent directories if they don't exist
-        if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent).await?;
+        // Existing files are verified against sha1/size, broken ones are redownloaded
+        let outcome = VerifiedDownload::new(&url, &target_path)
+            .sha1(library.sha1.as_deref())
+            .sha256(library.sha256.as_deref())
+            .size(library.size)
+            .download()
+            .await
+            .map_err(|e| {
+                crate::error::AppError::QuiltError(format!("Failed to download library: {}", e))
+            })?;
+        if outcome == DownloadOutcome::AlreadyPresent {
+            info!("📦 Library already exists: {}", library.name);
+            return Ok(());
         }
 
-        // Save the file
-        let mut file = fs::File::create(&target_path).await?;
-        file.write_all(&bytes).await?;
-
         info!("💾 Saved: {}", library.name);
         Ok(())
     }
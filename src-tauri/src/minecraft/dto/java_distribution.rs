@@ -19,7 +19,7 @@ impl Default for DistributionSelection {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum JavaDistribution {
     #[serde(rename = "temurin")]
     Temurin,
@@ -40,6 +40,10 @@ impl Default for JavaDistribution {
 #[derive(Deserialize)]
 pub struct ZuluApiResponse {
     pub url: String,
+    #[serde(default)]
+    pub sha256_hash: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 impl JavaDistribution {
@@ -9,7 +9,6 @@ use crate::minecraft::downloads::mc_libraries_download::MinecraftLibrariesDownlo
 use crate::minecraft::downloads::mc_natives_download::MinecraftNativesDownloadService;
 use crate::minecraft::downloads::NoriskPackDownloadService;
 use crate::minecraft::downloads::{ModDownloadService, NoriskClientAssetsDownloadService};
-use crate::minecraft::dto::JavaDistribution;
 use crate::minecraft::{MinecraftLaunchParameters, MinecraftLauncher};
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::{ModLoader, Profile};
@@ -19,11 +18,16 @@ use rand::Rng;
 use uuid::Uuid;
 
 use super::minecraft_auth::Credentials;
+use super::mod_checker::{self, ModCheckContext};
 use super::modloader::ModloaderFactory;
+use super::offline_check;
 use crate::minecraft::downloads::MinecraftLoggingDownloadService;
+use crate::utils::download_utils::DownloadProgress;
+use crate::utils::hook_utils::{self, HookContext, ResolvedHooks};
 use crate::utils::mc_utils;
+use crate::utils::offline_utils;
 
-async fn emit_progress_event(
+pub(crate) async fn emit_progress_event(
     state: &State,
     event_type: EventType,
     profile_id: Uuid,
@@ -126,6 +130,20 @@ pub async fn install_minecraft_version(
     let piston_meta = api_service.get_piston_meta(&version.url).await?;
     piston_meta.display_info();
 
+    // Offline: everything must already be on disk, fail early with a list of what's missing
+    let offline_mode = offline_utils::is_offline_mode();
+    if offline_mode {
+        info!("[Launch] Offline mode enabled, checking local installation...");
+        offline_check::check_offline_readiness(
+            &piston_meta,
+            profile,
+            &launcher_config.java_distribution,
+        )
+        .await?
+        .into_result()?;
+        info!("[Launch] All required files are present, launching without network access");
+    }
+
     // Get Java version from Minecraft version manifest
     let java_version = piston_meta.java_version.major_version as u32;
     info!("\nChecking Java {} for Minecraft...", java_version);
@@ -221,11 +239,15 @@ pub async fn install_minecraft_version(
     } else {
         // Download Java since custom path is not valid or not set
         info!("Downloading Java {}...", java_version);
-        let java_service = JavaDownloadService::new();
+        let java_service = JavaDownloadService::new().with_progress(DownloadProgress::new(
+            EventType::InstallingJava,
+            Some(profile.id),
+            format!("Downloading Java {}", java_version),
+        ));
         let downloaded_path = java_service
             .get_or_download_java(
                 java_version,
-                &JavaDistribution::Zulu,
+                &launcher_config.java_distribution,
                 Some(&piston_meta.java_version.component),
             )
             .await?;
@@ -336,12 +358,16 @@ pub async fn install_minecraft_version(
     let norisk_assets_service = NoriskClientAssetsDownloadService::new()
         .with_concurrent_downloads(launcher_config.concurrent_downloads);
 
-    // Download assets for this profile - progress events are now handled internally
-    norisk_assets_service
-        .download_nrc_assets_for_profile(&profile, credentials.as_ref(), is_experimental_mode)
-        .await?;
+    if offline_mode {
+        info!("Offline mode, using already downloaded NoRiskClient assets");
+    } else {
+        // Download assets for this profile - progress events are now handled internally
+        norisk_assets_service
+            .download_nrc_assets_for_profile(&profile, credentials.as_ref(), is_experimental_mode)
+            .await?;
 
-    info!("NoRiskClient Asset download completed!");
+        info!("NoRiskClient Asset download completed!");
+    }
 
     // Emit client download event
     let client_event_id = emit_progress_event(
@@ -464,7 +490,12 @@ pub async fn install_minecraft_version(
             "Fetching Norisk config because pack '{}' is selected. Attempting to refresh first.",
             pack_id
         );
-        if let Some(creds) = credentials.as_ref() {
+        if offline_mode {
+            info!(
+                "Offline mode, using cached Norisk pack configuration for pack '{}'",
+                pack_id
+            );
+        } else if let Some(creds) = credentials.as_ref() {
             match creds
                 .norisk_credentials
                 .get_token_for_mode(is_experimental_mode)
@@ -531,6 +562,17 @@ pub async fn install_minecraft_version(
         "Profile mod cache check/download completed successfully for profile '{}'",
         profile.name
     );
+    // Mods not from Modrinth only get a proper name and version from their jars
+    if let Err(e) = state
+        .profile_manager
+        .fill_mod_metadata_from_cache(profile.id, &LAUNCHER_DIRECTORY.meta_dir().join("mod_cache"))
+        .await
+    {
+        warn!(
+            "Failed to fill mod names from jar metadata for profile '{}': {}",
+            profile.name, e
+        );
+    }
 
     emit_progress_event(
         &state,
@@ -669,6 +711,23 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
+    // --- Step: Check mod dependencies and conflicts ---
+    if modloader_enum != ModLoader::Vanilla && !target_mods.is_empty() {
+        // The Java version of a custom installation isn't known here
+        let check_context = ModCheckContext::for_profile(profile)
+            .with_java_major_version((!custom_java_valid).then_some(java_version));
+        let check_report = mod_checker::check_target_mods(&target_mods, &check_context).await;
+        if check_report.has_errors() {
+            if launcher_config.block_launch_on_mod_issues {
+                return check_report.into_result();
+            }
+            warn!(
+                "Mod check found {} errors, launching anyway because blocking is disabled",
+                check_report.error_count()
+            );
+        }
+    }
+
     // --- Step: Sync mods from cache to profile directory ---
     let sync_event_id = emit_progress_event(
         &state,
@@ -724,10 +783,24 @@ pub async fn install_minecraft_version(
         launch_params = launch_params.with_additional_jvm_args(jvm_args);
     }
 
+    // Favorite servers marked for auto-sync are pushed into servers.dat before every launch
+    match state
+        .favorite_servers_manager
+        .sync_before_launch(&game_directory)
+        .await
+    {
+        Ok(changes) if changes.added + changes.updated > 0 => info!(
+            "Synced favorite servers into '{}': {} added, {} updated",
+            profile.name, changes.added, changes.updated
+        ),
+        Ok(_) => {}
+        Err(e) => warn!("Failed to sync favorite servers before launch: {}", e),
+    }
+
     // --- Execute pre-launch hooks ---
     let launcher_config = state.config_manager.get_config().await;
-    if let Some(hook) = &launcher_config.hooks.pre_launch {
-        info!("Executing pre-launch hook: {}", hook);
+    let hooks = ResolvedHooks::resolve(&launcher_config.hooks, &profile.settings.hooks);
+    if let Some(hook) = &hooks.pre_launch {
         let hook_event_id = emit_progress_event(
             &state,
             EventType::LaunchingMinecraft,
@@ -738,26 +811,22 @@ pub async fn install_minecraft_version(
         )
         .await?;
 
-        let mut cmd = hook.split(' ');
-        if let Some(command) = cmd.next() {
-            let result = std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
-                .current_dir(&game_directory)
-                .spawn()
-                .map_err(|e| AppError::Io(e))?
-                .wait()
-                .map_err(|e| AppError::Io(e))?;
-
-            if !result.success() {
-                let error_msg = format!(
-                    "Pre-launch hook failed with exit code: {}",
-                    result.code().unwrap_or(-1)
-                );
-                error!("{}", error_msg);
-                return Err(AppError::Other(error_msg));
+        let hook_context = HookContext::new(game_directory.clone(), profile.id, &profile.name)
+            .with_java_path(java_path.clone());
+        if let Err(e) = hook_utils::run_pre_launch_hook(
+            hook,
+            &hook_context,
+            &profile.settings.env_vars,
+            hooks.pre_launch_timeout,
+        )
+        .await
+        {
+            if hooks.abort_on_pre_launch_failure {
+                error!("{}", e);
+                return Err(e);
             }
+            warn!("{} (continuing launch)", e);
         }
-        info!("Pre-launch hook executed successfully");
     }
 
     // --- Launch Minecraft ---
@@ -5,8 +5,9 @@ use crate::minecraft::minecraft_auth::Credentials;
 use crate::minecraft::ClasspathBuilder;
 use crate::minecraft::GameArguments;
 use crate::minecraft::JvmArguments;
-use crate::state::profile_state::{Profile, WindowSize};
+use crate::state::profile_state::{Profile, ProfileHooks, ProfileKind, WindowSize};
 use crate::state::state_manager::State;
+use crate::utils::hook_utils::{self, HookContext, ResolvedHooks};
 use log::{debug, error, info, warn};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -219,19 +220,48 @@ impl MinecraftLauncher {
 
         // 2. Java-Befehl initialisieren (mit wrapper support)
         let launcher_config = state.config_manager.get_config().await;
-        let mut command = match launcher_config.hooks.wrapper {
-            Some(wrapper) => {
-                info!("Using wrapper command: {}", wrapper);
-                // Exactly like Modrinth: use the whole wrapper string as command and add java path as arg
-                {
-                    let mut it = Command::new(wrapper);
-                    it.arg(&self.java_path);
-                    it
-                }
+        let (hooks, env_vars) = match &profile {
+            Some(p) => (
+                ResolvedHooks::resolve(&launcher_config.hooks, &p.settings.hooks),
+                p.settings.env_vars.clone(),
+            ),
+            None => (
+                ResolvedHooks::resolve(&launcher_config.hooks, &ProfileHooks::default()),
+                HashMap::new(),
+            ),
+        };
+        let hook_context = HookContext::new(
+            self.game_directory.clone(),
+            params.profile_id,
+            profile.as_ref().map(|p| p.name.clone()).unwrap_or_default(),
+        )
+        .with_java_path(self.java_path.clone());
+
+        let wrapper_parts = hooks
+            .wrapper
+            .as_deref()
+            .map(hook_utils::split_command_line)
+            .unwrap_or_default();
+        let mut command = match wrapper_parts.split_first() {
+            Some((program, args)) => {
+                info!("Using wrapper command: {:?}", wrapper_parts);
+                // The wrapper (e.g. "gamemoderun" or "mangohud --dlsym") runs java as its last argument
+                let mut it = Command::new(hook_context.substitute(program));
+                it.args(args.iter().map(|arg| hook_context.substitute(arg)));
+                it.arg(&self.java_path);
+                it
             }
             None => Command::new(&self.java_path),
         };
         command.current_dir(&self.game_directory);
+        if !env_vars.is_empty() {
+            info!(
+                "Setting {} custom environment variables: {:?}",
+                env_vars.len(),
+                env_vars.keys().collect::<Vec<_>>()
+            );
+            command.envs(&env_vars);
+        }
 
         // Define paths
         let natives_path = LAUNCHER_DIRECTORY
@@ -452,9 +482,8 @@ impl MinecraftLauncher {
                 None => (None, None, None, None),
             };
 
-        // Get post-exit hook from config at launch time (not at exit time)
-        let launcher_config = state.config_manager.get_config().await;
-        let post_exit_hook = launcher_config.hooks.post_exit.clone();
+        // Resolve the post-exit hook at launch time (not at exit time)
+        let post_exit_hook = hooks.post_exit.clone();
 
         // Start the process using ProcessManager with additional metadata
         process_manager
@@ -469,6 +498,7 @@ impl MinecraftLauncher {
                 profile_norisk_pack,
                 profile_name,
                 post_exit_hook,
+                ProfileKind::Client,
             )
             .await?;
 
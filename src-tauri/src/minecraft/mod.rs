@@ -1,10 +1,16 @@
 pub mod api;
 pub mod auth;
+pub mod crash_analyzer;
 pub mod downloads;
 pub mod dto;
 pub mod installer;
 pub mod launch;
+pub mod mod_bisect;
+pub mod mod_checker;
 pub mod modloader;
+pub mod offline_check;
+pub mod profile_verifier;
+pub mod server_installer;
 
 pub use api::*;
 pub use auth::*;
@@ -0,0 +1,857 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::installer;
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::process_state::ProcessExit;
+use crate::state::profile_state::{self, ModSource, Profile, ProfileManager};
+use crate::state::state_manager::State;
+use crate::utils::mod_metadata::{self, DependencyKind, JarMetadata, ModEnvironment};
+use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::fs;
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use uuid::Uuid;
+
+/// Log lines that only show up once the game reached the title screen.
+const STARTED_MARKERS: &[&str] = &[
+    "Sound engine started",
+    "minecraft:textures/atlas/blocks.png-atlas",
+];
+
+/// Log lines of crashes and of loaders refusing to start.
+const CRASH_MARKERS: &[&str] = &[
+    "---- Minecraft Crash Report ----",
+    "#@!@# Game crashed!",
+    "Incompatible mods found!",
+    "Incompatible mod set!",
+    "Mod loading has failed",
+    "Loading errors encountered",
+    "LoadingFailedException",
+    "Mixin apply failed",
+    "MixinApplyError",
+];
+
+/// A run that doesn't reach the title screen in this time is inconclusive.
+const RUN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// How long the game keeps running after the title screen to catch crashes right after it.
+const SETTLE_TIME: Duration = Duration::from_secs(15);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+/// Inconclusive runs (e.g. the game was closed by hand) are repeated this often.
+const MAX_RETRIES: usize = 1;
+
+/// Profiles that are being bisected, with their cancel flag.
+static ACTIVE_BISECTS: Lazy<DashMap<Uuid, Arc<AtomicBool>>> = Lazy::new(DashMap::new);
+
+/// A mod that can be switched off during bisection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BisectModRef {
+    /// Entry of `Profile::mods`
+    Profile { mod_id: Uuid },
+    /// Jar in the custom_mods folder
+    Custom { filename: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BisectCandidate {
+    pub mod_ref: BisectModRef,
+    pub name: String,
+    /// Candidates this one can't run without, they are always enabled together with it
+    pub requires: Vec<BisectModRef>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Crashed,
+    /// Reached the title screen without crashing
+    Stable,
+    /// Neither, e.g. the game was closed before the title screen
+    Inconclusive,
+}
+
+/// What was seen of a single launch so far.
+#[derive(Debug, Clone, Default)]
+pub struct RunObservation {
+    /// `Some` once the process exited, with its exit code if it has one
+    pub exit: Option<Option<i32>>,
+    /// A crash report was written during the run
+    pub crash_report: bool,
+    /// The log of the run
+    pub log: String,
+}
+
+/// Classifies a run from its exit code, crash reports and log markers.
+pub fn classify_run(observation: &RunObservation) -> RunOutcome {
+    if observation.crash_report || CRASH_MARKERS.iter().any(|m| observation.log.contains(m)) {
+        return RunOutcome::Crashed;
+    }
+    // Killed by a signal (no exit code) counts as a crash too, we only stop the game ourselves
+    // after classifying the run
+    if matches!(observation.exit, Some(code) if code != Some(0)) {
+        return RunOutcome::Crashed;
+    }
+    if STARTED_MARKERS.iter().any(|m| observation.log.contains(m)) {
+        return RunOutcome::Stable;
+    }
+    RunOutcome::Inconclusive
+}
+
+/// Launches the game with a set of mods and reports how it went.
+#[async_trait]
+pub trait BisectRunner: Send {
+    /// Runs the game with exactly the given candidates enabled.
+    async fn run(&mut self, enabled: &[BisectModRef]) -> Result<RunOutcome>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BisectStatus {
+    /// `culprits` is the smallest set of mods that still crashes
+    Found,
+    /// The game didn't crash with all mods enabled
+    NotReproducible,
+    /// The game also crashes without any of the mods
+    CrashesWithoutMods,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BisectRun {
+    pub enabled_mods: usize,
+    pub outcome: RunOutcome,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BisectReport {
+    pub status: BisectStatus,
+    pub culprits: Vec<BisectCandidate>,
+    /// Dependencies the culprits were tested with
+    pub dependencies: Vec<BisectCandidate>,
+    pub runs: Vec<BisectRun>,
+}
+
+/// Finds the smallest set of candidates that still crashes by repeatedly testing halves.
+/// Crashes that need two mods from different halves are found as well.
+pub async fn bisect<R: BisectRunner>(
+    candidates: &[BisectCandidate],
+    runner: &mut R,
+) -> Result<BisectReport> {
+    let mut bisector = Bisector::new(candidates, runner);
+    let all: Vec<usize> = (0..candidates.len()).collect();
+
+    let status = if !bisector.crashes(&all).await? {
+        BisectStatus::NotReproducible
+    } else if bisector.crashes(&[]).await? {
+        BisectStatus::CrashesWithoutMods
+    } else {
+        BisectStatus::Found
+    };
+    let culprits = if status == BisectStatus::Found {
+        bisector.minimize(Vec::new(), all).await?
+    } else {
+        Vec::new()
+    };
+
+    let dependencies: Vec<usize> = bisector
+        .closure(&culprits)
+        .into_iter()
+        .filter(|i| !culprits.contains(i))
+        .collect();
+    let to_candidates =
+        |indices: &[usize]| indices.iter().map(|i| candidates[*i].clone()).collect();
+    Ok(BisectReport {
+        status,
+        culprits: to_candidates(&culprits),
+        dependencies: to_candidates(&dependencies),
+        runs: bisector.runs,
+    })
+}
+
+struct Bisector<'a, R: BisectRunner> {
+    candidates: &'a [BisectCandidate],
+    requires: Vec<Vec<usize>>,
+    runner: &'a mut R,
+    runs: Vec<BisectRun>,
+}
+
+impl<'a, R: BisectRunner> Bisector<'a, R> {
+    fn new(candidates: &'a [BisectCandidate], runner: &'a mut R) -> Self {
+        let index: HashMap<&BisectModRef, usize> = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (&c.mod_ref, i))
+            .collect();
+        // Requirements outside of the candidates can't be switched off anyway
+        let requires = candidates
+            .iter()
+            .map(|c| {
+                c.requires
+                    .iter()
+                    .filter_map(|r| index.get(r).copied())
+                    .collect()
+            })
+            .collect();
+        Self {
+            candidates,
+            requires,
+            runner,
+            runs: Vec::new(),
+        }
+    }
+
+    /// The given candidates plus everything they need, sorted.
+    fn closure(&self, set: &[usize]) -> Vec<usize> {
+        let mut seen = BTreeSet::new();
+        let mut queue = set.to_vec();
+        while let Some(i) = queue.pop() {
+            if seen.insert(i) {
+                queue.extend(&self.requires[i]);
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    async fn crashes(&mut self, set: &[usize]) -> Result<bool> {
+        let enabled = self.closure(set);
+        let refs: Vec<BisectModRef> = enabled
+            .iter()
+            .map(|i| self.candidates[*i].mod_ref.clone())
+            .collect();
+
+        for attempt in 0..=MAX_RETRIES {
+            let outcome = self.runner.run(&refs).await?;
+            info!(
+                "[Bisect] Run {} with {} mods: {:?}",
+                self.runs.len() + 1,
+                refs.len(),
+                outcome
+            );
+            self.runs.push(BisectRun {
+                enabled_mods: refs.len(),
+                outcome,
+            });
+            match outcome {
+                RunOutcome::Crashed => return Ok(true),
+                RunOutcome::Stable => return Ok(false),
+                RunOutcome::Inconclusive if attempt < MAX_RETRIES => {
+                    warn!("[Bisect] Run was inconclusive, trying again");
+                }
+                RunOutcome::Inconclusive => {}
+            }
+        }
+        Err(AppError::ModBisectFailed(format!(
+            "The game neither crashed nor reached the title screen with {} mods",
+            refs.len()
+        )))
+    }
+
+    /// Smallest subset of `suspects` that crashes together with `required`.
+    /// Expects `required` + `suspects` to crash and `required` alone not to.
+    fn minimize(
+        &mut self,
+        required: Vec<usize>,
+        suspects: Vec<usize>,
+    ) -> BoxFuture<'_, Result<Vec<usize>>> {
+        Box::pin(async move {
+            if suspects.len() == 1 {
+                let suspect = suspects[0];
+                // The crash may come from a dependency that is enabled with the suspect.
+                // Dependencies that need the suspect themselves can't be tested without it.
+                let required_closure = self.closure(&required);
+                let dependencies: Vec<usize> = self
+                    .closure(&suspects)
+                    .into_iter()
+                    .filter(|d| *d != suspect && !required_closure.contains(d))
+                    .filter(|d| !self.closure(&[*d]).contains(&suspect))
+                    .collect();
+                if !dependencies.is_empty()
+                    && self
+                        .crashes(&[required.clone(), dependencies.clone()].concat())
+                        .await?
+                {
+                    return self.minimize(required, dependencies).await;
+                }
+                return Ok(suspects);
+            }
+
+            let (first, second) = suspects.split_at(suspects.len() / 2);
+            let (first, second) = (first.to_vec(), second.to_vec());
+            if self
+                .crashes(&[required.clone(), first.clone()].concat())
+                .await?
+            {
+                return self.minimize(required, first).await;
+            }
+            if self
+                .crashes(&[required.clone(), second.clone()].concat())
+                .await?
+            {
+                return self.minimize(required, second).await;
+            }
+
+            // Only crashes with mods from both halves
+            let from_first = self
+                .minimize([required.clone(), second.clone()].concat(), first)
+                .await?;
+            let from_second = self
+                .minimize([required, from_first.clone()].concat(), second)
+                .await?;
+            Ok([from_first, from_second].concat())
+        })
+    }
+}
+
+/// Directory of the session files that remember which mods a running bisection switched off.
+fn sessions_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join("mod_bisect")
+}
+
+fn session_file(profile_id: Uuid) -> PathBuf {
+    sessions_dir().join(format!("{}.json", profile_id))
+}
+
+/// The enabled profile and custom mods of a profile with their dependencies between each other.
+/// Pack mods are not bisected.
+pub async fn collect_candidates(profile: &Profile) -> Result<Vec<BisectCandidate>> {
+    let state = State::get().await?;
+    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
+    let mut candidates: Vec<BisectCandidate> = Vec::new();
+    let mut jars: Vec<Option<PathBuf>> = Vec::new();
+
+    for mod_entry in &profile.mods {
+        // Same filter as the mod resolver, other mods aren't loaded anyway
+        if !mod_entry.enabled
+            || mod_entry.associated_loader != Some(profile.loader)
+            || matches!(
+                mod_entry.source,
+                ModSource::Local { .. } | ModSource::Embedded { .. }
+            )
+        {
+            continue;
+        }
+        let file_name = profile_state::get_profile_mod_filename(&mod_entry.source).ok();
+        candidates.push(BisectCandidate {
+            mod_ref: BisectModRef::Profile {
+                mod_id: mod_entry.id,
+            },
+            name: mod_entry
+                .display_name
+                .clone()
+                .or_else(|| file_name.clone())
+                .unwrap_or_else(|| mod_entry.id.to_string()),
+            requires: mod_entry
+                .dependencies
+                .iter()
+                .map(|id| BisectModRef::Profile { mod_id: *id })
+                .collect(),
+        });
+        jars.push(file_name.map(|f| mod_cache_dir.join(f)));
+    }
+
+    for custom_mod in state.profile_manager.list_custom_mods(profile).await? {
+        if !custom_mod.is_enabled {
+            continue;
+        }
+        candidates.push(BisectCandidate {
+            mod_ref: BisectModRef::Custom {
+                filename: custom_mod.filename.clone(),
+            },
+            name: custom_mod
+                .metadata
+                .as_ref()
+                .map(|m| m.display_name().to_string())
+                .unwrap_or_else(|| custom_mod.filename.clone()),
+            requires: Vec::new(),
+        });
+        jars.push(Some(custom_mod.path));
+    }
+
+    let mut metadata: Vec<Option<JarMetadata>> = Vec::with_capacity(jars.len());
+    for jar in &jars {
+        metadata.push(match jar {
+            Some(path) if path.exists() => mod_metadata::read_jar_metadata(path, false).await.ok(),
+            _ => None,
+        });
+    }
+    add_metadata_requirements(&mut candidates, &metadata, profile);
+    Ok(candidates)
+}
+
+/// Adds the required dependencies declared in the jars, resolved to the candidates providing them.
+fn add_metadata_requirements(
+    candidates: &mut [BisectCandidate],
+    metadata: &[Option<JarMetadata>],
+    profile: &Profile,
+) {
+    let mut providers: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, jar) in metadata.iter().enumerate() {
+        for mod_metadata in jar.iter().flat_map(|j| j.all_mods()) {
+            for id in std::iter::once(&mod_metadata.mod_id).chain(&mod_metadata.provides) {
+                providers.entry(id.to_lowercase()).or_default().push(i);
+            }
+        }
+    }
+
+    for (i, jar) in metadata.iter().enumerate() {
+        let Some(jar) = jar else { continue };
+        let mut requires: Vec<BisectModRef> = Vec::new();
+        for mod_metadata in jar.loadable_mods(&profile.loader) {
+            for dependency in &mod_metadata.dependencies {
+                if dependency.kind != DependencyKind::Required
+                    || dependency.environment == ModEnvironment::Server
+                {
+                    continue;
+                }
+                let Some(providing) = providers.get(&dependency.mod_id.to_lowercase()) else {
+                    continue;
+                };
+                // Bundled in the jar itself
+                if providing.contains(&i) {
+                    continue;
+                }
+                for provider in providing {
+                    let mod_ref = &candidates[*provider].mod_ref;
+                    if !requires.contains(mod_ref) {
+                        requires.push(mod_ref.clone());
+                    }
+                }
+            }
+        }
+        for mod_ref in requires {
+            if !candidates[i].requires.contains(&mod_ref) {
+                candidates[i].requires.push(mod_ref);
+            }
+        }
+    }
+}
+
+/// Enables exactly the given candidates of the profile.
+async fn apply_enabled(
+    profile_manager: &ProfileManager,
+    profile_id: Uuid,
+    candidates: &[BisectModRef],
+    enabled: &[BisectModRef],
+) -> Result<()> {
+    let mut profile_mods = HashMap::new();
+    for candidate in candidates {
+        let is_enabled = enabled.contains(candidate);
+        match candidate {
+            BisectModRef::Profile { mod_id } => {
+                profile_mods.insert(*mod_id, is_enabled);
+            }
+            BisectModRef::Custom { filename } => {
+                profile_manager
+                    .set_custom_mod_enabled(profile_id, filename.clone(), is_enabled)
+                    .await?;
+            }
+        }
+    }
+    profile_manager
+        .set_mods_enabled(profile_id, &profile_mods)
+        .await
+}
+
+/// Re-enables the mods of bisections that were interrupted by closing the launcher.
+pub async fn restore_interrupted_sessions(profile_manager: &ProfileManager) -> Result<()> {
+    let dir = sessions_dir();
+    if !dir.exists() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let Some(profile_id) = path
+            .file_stem()
+            .and_then(|s| Uuid::parse_str(&s.to_string_lossy()).ok())
+        else {
+            continue;
+        };
+        let candidates: Vec<BisectModRef> =
+            serde_json::from_str(&fs::read_to_string(&path).await?)?;
+        info!(
+            "[Bisect] Restoring {} mods of an interrupted bisection of profile {}",
+            candidates.len(),
+            profile_id
+        );
+        match apply_enabled(profile_manager, profile_id, &candidates, &candidates).await {
+            Ok(()) | Err(AppError::ProfileNotFound(_)) => fs::remove_file(&path).await?,
+            Err(e) => error!(
+                "[Bisect] Failed to restore mods of profile {}: {}",
+                profile_id, e
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Bisects the mods of a profile by launching it repeatedly. The enabled mods are restored
+/// afterwards, also when the bisection fails or is cancelled.
+pub async fn bisect_profile(profile_id: Uuid) -> Result<BisectReport> {
+    let state = State::get().await?;
+    if !state
+        .process_manager
+        .get_process_metadata_by_profile(profile_id)
+        .await
+        .is_empty()
+    {
+        return Err(AppError::ModBisectFailed(
+            "Close the running game before bisecting its mods".to_string(),
+        ));
+    }
+    let cancelled = Arc::new(AtomicBool::new(false));
+    // Never replace the cancel flag of the running bisection
+    match ACTIVE_BISECTS.entry(profile_id) {
+        Entry::Occupied(_) => {
+            return Err(AppError::ModBisectFailed(
+                "A bisection is already running for this profile".to_string(),
+            ));
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(cancelled.clone());
+        }
+    }
+
+    let result = bisect_profile_inner(&state, profile_id, cancelled).await;
+    ACTIVE_BISECTS.remove(&profile_id);
+    result
+}
+
+async fn bisect_profile_inner(
+    state: &State,
+    profile_id: Uuid,
+    cancelled: Arc<AtomicBool>,
+) -> Result<BisectReport> {
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let candidates = collect_candidates(&profile).await?;
+    if candidates.is_empty() {
+        return Err(AppError::ModBisectFailed(
+            "The profile has no enabled mods".to_string(),
+        ));
+    }
+    info!(
+        "[Bisect] Starting bisection of {} mods for profile '{}'",
+        candidates.len(),
+        profile.name
+    );
+
+    // Remember the switched off mods in case the launcher is closed in between
+    let refs: Vec<BisectModRef> = candidates.iter().map(|c| c.mod_ref.clone()).collect();
+    fs::create_dir_all(sessions_dir()).await?;
+    fs::write(session_file(profile_id), serde_json::to_string(&refs)?).await?;
+
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    let mut runner = ProfileBisectRunner {
+        profile_id,
+        candidates: refs.clone(),
+        instance_path,
+        cancelled,
+        event_id: Uuid::new_v4(),
+        runs: 0,
+        // Two runs to reproduce, two per halving step
+        expected_runs: 2 + 2 * (usize::BITS - candidates.len().leading_zeros()) as usize,
+    };
+    let result = bisect(&candidates, &mut runner).await;
+
+    let restored = apply_enabled(&state.profile_manager, profile_id, &refs, &refs).await;
+    match &restored {
+        Ok(()) => fs::remove_file(session_file(profile_id)).await?,
+        Err(e) => error!(
+            "[Bisect] Failed to restore mods of profile {}: {}",
+            profile_id, e
+        ),
+    }
+
+    let message = match &result {
+        Ok(report) => match report.status {
+            BisectStatus::Found => format!(
+                "Found the crashing mods: {}",
+                report
+                    .culprits
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            BisectStatus::NotReproducible => {
+                "The game didn't crash with all mods enabled".to_string()
+            }
+            BisectStatus::CrashesWithoutMods => {
+                "The game also crashes without any of the mods".to_string()
+            }
+        },
+        Err(e) => e.to_string(),
+    };
+    runner
+        .emit(&message, 1.0, result.as_ref().err().map(|e| e.to_string()))
+        .await;
+    let report = result?;
+    restored?;
+    Ok(report)
+}
+
+/// Stops a running bisection after the current run.
+pub fn cancel_bisect(profile_id: Uuid) -> Result<()> {
+    match ACTIVE_BISECTS.get(&profile_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+        None => Err(AppError::ModBisectFailed(
+            "No bisection is running for this profile".to_string(),
+        )),
+    }
+}
+
+/// Launches the real profile and watches the process, log and crash reports.
+struct ProfileBisectRunner {
+    profile_id: Uuid,
+    candidates: Vec<BisectModRef>,
+    instance_path: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    event_id: Uuid,
+    runs: usize,
+    expected_runs: usize,
+}
+
+#[async_trait]
+impl BisectRunner for ProfileBisectRunner {
+    async fn run(&mut self, enabled: &[BisectModRef]) -> Result<RunOutcome> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(AppError::ModBisectFailed("Cancelled".to_string()));
+        }
+        self.runs += 1;
+        let progress = (self.runs as f64 / self.expected_runs as f64).min(0.95);
+        self.emit(
+            &format!(
+                "Run {}: testing {} of {} mods",
+                self.runs,
+                enabled.len(),
+                self.candidates.len()
+            ),
+            progress,
+            None,
+        )
+        .await;
+
+        let state = State::get().await?;
+        apply_enabled(
+            &state.profile_manager,
+            self.profile_id,
+            &self.candidates,
+            enabled,
+        )
+        .await?;
+
+        let profile = state.profile_manager.get_profile(self.profile_id).await?;
+        let credentials = state
+            .minecraft_account_manager_v2
+            .get_active_account()
+            .await
+            .ok()
+            .flatten()
+            .ok_or(AppError::NoCredentialsError)?;
+
+        let launch_time = SystemTime::now();
+        let mut exits = state.process_manager.subscribe_process_exits();
+        let known_processes: HashSet<Uuid> = state
+            .process_manager
+            .get_process_metadata_by_profile(self.profile_id)
+            .await
+            .iter()
+            .map(|p| p.id)
+            .collect();
+
+        match installer::install_minecraft_version(
+            &profile.game_version,
+            profile.loader.as_str(),
+            &profile,
+            Some(credentials),
+            None,
+            None,
+        )
+        .await
+        {
+            Ok(()) => {}
+            // The launch check already found broken dependencies or conflicts in this set
+            Err(AppError::ModCheckFailed(message)) => {
+                info!("[Bisect] Launch blocked by the mod check: {}", message);
+                return Ok(RunOutcome::Crashed);
+            }
+            Err(e) => return Err(e),
+        }
+
+        let process_id = state
+            .process_manager
+            .get_process_metadata_by_profile(self.profile_id)
+            .await
+            .into_iter()
+            .map(|p| p.id)
+            .find(|id| !known_processes.contains(id))
+            .ok_or_else(|| {
+                AppError::ModBisectFailed("The game process could not be found".to_string())
+            })?;
+
+        self.watch(&state, process_id, launch_time, &mut exits)
+            .await
+    }
+}
+
+impl ProfileBisectRunner {
+    async fn watch(
+        &self,
+        state: &State,
+        process_id: Uuid,
+        launch_time: SystemTime,
+        exits: &mut broadcast::Receiver<ProcessExit>,
+    ) -> Result<RunOutcome> {
+        let started = Instant::now();
+        let mut title_screen_at: Option<Instant> = None;
+        let mut observation = RunObservation::default();
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if observation.exit.is_none() {
+                observation.exit = received_exit(exits, process_id);
+            }
+            observation.crash_report =
+                has_new_crash_report(&self.instance_path.join("crash-reports"), launch_time).await;
+            if let Some(log) = read_log_since(
+                &self.instance_path.join("logs").join("latest.log"),
+                launch_time,
+            )
+            .await
+            {
+                observation.log = log;
+            }
+
+            let exited = observation.exit.is_some();
+            let outcome = match classify_run(&observation) {
+                RunOutcome::Stable => {
+                    let since = *title_screen_at.get_or_insert_with(Instant::now);
+                    (exited || since.elapsed() >= SETTLE_TIME).then_some(RunOutcome::Stable)
+                }
+                RunOutcome::Crashed => Some(RunOutcome::Crashed),
+                RunOutcome::Inconclusive if exited => Some(RunOutcome::Inconclusive),
+                RunOutcome::Inconclusive if started.elapsed() >= RUN_TIMEOUT => {
+                    warn!("[Bisect] Run timed out before the title screen");
+                    Some(RunOutcome::Inconclusive)
+                }
+                RunOutcome::Inconclusive => None,
+            };
+
+            if self.cancelled.load(Ordering::SeqCst) {
+                self.stop(state, process_id, exited, exits).await;
+                return Err(AppError::ModBisectFailed("Cancelled".to_string()));
+            }
+            if let Some(outcome) = outcome {
+                self.stop(state, process_id, exited, exits).await;
+                return Ok(outcome);
+            }
+        }
+    }
+
+    /// Stops the game if it still runs and waits until it is gone before the next launch.
+    async fn stop(
+        &self,
+        state: &State,
+        process_id: Uuid,
+        exited: bool,
+        exits: &mut broadcast::Receiver<ProcessExit>,
+    ) {
+        if exited {
+            return;
+        }
+        if let Err(e) = state.process_manager.stop_process(process_id).await {
+            warn!("[Bisect] Failed to stop process {}: {}", process_id, e);
+        }
+        let wait_for_exit = async {
+            loop {
+                match exits.recv().await {
+                    Ok(exit) if exit.process_id == process_id => break,
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        };
+        if tokio::time::timeout(STOP_TIMEOUT, wait_for_exit)
+            .await
+            .is_err()
+        {
+            warn!("[Bisect] Process {} didn't exit in time", process_id);
+        }
+    }
+
+    async fn emit(&self, message: &str, progress: f64, error: Option<String>) {
+        let Ok(state) = State::get().await else {
+            return;
+        };
+        if let Err(e) = state
+            .emit_event(EventPayload {
+                event_id: self.event_id,
+                event_type: EventType::BisectingMods,
+                target_id: Some(self.profile_id),
+                message: message.to_string(),
+                progress: Some(progress),
+                error,
+            })
+            .await
+        {
+            warn!("[Bisect] Failed to emit event: {}", e);
+        }
+    }
+}
+
+fn received_exit(
+    exits: &mut broadcast::Receiver<ProcessExit>,
+    process_id: Uuid,
+) -> Option<Option<i32>> {
+    loop {
+        match exits.try_recv() {
+            Ok(exit) if exit.process_id == process_id => return Some(exit.exit_code),
+            Ok(_) | Err(TryRecvError::Lagged(_)) => {}
+            Err(_) => return None,
+        }
+    }
+}
+
+async fn has_new_crash_report(crash_reports_dir: &Path, since: SystemTime) -> bool {
+    let Ok(mut entries) = fs::read_dir(crash_reports_dir).await else {
+        return false;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let is_report = entry.file_name().to_string_lossy().starts_with("crash-");
+        let is_new = entry
+            .metadata()
+            .await
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified >= since);
+        if is_report && is_new {
+            return true;
+        }
+    }
+    false
+}
+
+/// The log of the current run, `None` while `latest.log` is still the one of an earlier run.
+async fn read_log_since(log_path: &Path, since: SystemTime) -> Option<String> {
+    let modified = fs::metadata(log_path).await.ok()?.modified().ok()?;
+    if modified < since {
+        return None;
+    }
+    let bytes = fs::read(log_path).await.ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
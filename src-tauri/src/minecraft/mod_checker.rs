@@ -0,0 +1,506 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::downloads::mod_resolver::{self, TargetMod};
+use crate::state::profile_state::{ModLoader, Profile};
+use crate::state::state_manager::State;
+use crate::utils::mod_metadata::{
+    self, DependencyKind, JarMetadata, ModEnvironment, ModMetadata, ModMetadataFormat,
+};
+use crate::utils::version_range::{self, RangeSyntax};
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How many errors are listed in the error message before it gets truncated.
+const MAX_LISTED_ISSUES: usize = 5;
+
+/// What the mods are checked against.
+#[derive(Debug, Clone)]
+pub struct ModCheckContext {
+    pub minecraft_version: String,
+    pub loader: ModLoader,
+    /// `None` if the profile uses the latest loader version
+    pub loader_version: Option<String>,
+    /// `None` if unknown (custom Java path)
+    pub java_major_version: Option<u32>,
+}
+
+impl ModCheckContext {
+    pub fn for_profile(profile: &Profile) -> Self {
+        Self {
+            minecraft_version: profile.game_version.clone(),
+            loader: profile.loader,
+            loader_version: profile
+                .loader_version
+                .as_ref()
+                .map(|v| v.trim_end_matches(" (stable)").trim().to_string())
+                .filter(|v| !v.is_empty()),
+            java_major_version: None,
+        }
+    }
+
+    pub fn with_java_major_version(mut self, java_major_version: Option<u32>) -> Self {
+        self.java_major_version = java_major_version;
+        self
+    }
+
+    /// Ids the loader itself provides, checked like mods.
+    fn platform_providers(&self) -> Vec<(&'static str, Option<String>)> {
+        let loader_version = self.loader_version.clone();
+        let mut providers = vec![
+            ("minecraft", Some(self.minecraft_version.clone())),
+            ("java", self.java_major_version.map(|v| v.to_string())),
+        ];
+        match self.loader {
+            ModLoader::Fabric => providers.push(("fabricloader", loader_version)),
+            ModLoader::Quilt => {
+                providers.push(("quilt_loader", loader_version));
+                // Quilt loads Fabric mods, but its Fabric version isn't known here
+                providers.push(("fabricloader", None));
+            }
+            ModLoader::Forge => {
+                // Forge versions may be stored as `1.20.1-47.2.0`
+                let forge_version = loader_version.map(|v| {
+                    v.strip_prefix(&format!("{}-", self.minecraft_version))
+                        .map(str::to_string)
+                        .unwrap_or(v)
+                });
+                providers.push(("forge", forge_version));
+                providers.push(("fml", None));
+            }
+            ModLoader::NeoForge => {
+                providers.push(("neoforge", loader_version));
+                providers.push(("forge", None));
+                providers.push(("fml", None));
+            }
+            ModLoader::Vanilla => {}
+        }
+        providers
+    }
+}
+
+/// A jar that would end up in the mods folder. `metadata` is `None` if it couldn't be read.
+#[derive(Debug, Clone)]
+pub struct ModFile {
+    pub file_name: String,
+    pub metadata: Option<JarMetadata>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModIssueSeverity {
+    /// The game would crash or refuse to start
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModIssueKind {
+    MissingDependency,
+    DependencyVersionMismatch,
+    DuplicateMod,
+    Incompatible,
+    Discouraged,
+    WrongLoader,
+    ServerOnly,
+    UnreadableJar,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModIssue {
+    pub severity: ModIssueSeverity,
+    pub kind: ModIssueKind,
+    pub file_name: String,
+    pub mod_id: Option<String>,
+    /// The dependency or the mod it conflicts with
+    pub related_mod_id: Option<String>,
+    pub version_range: Option<String>,
+    pub found_version: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModCheckReport {
+    pub checked_files: usize,
+    pub issues: Vec<ModIssue>,
+}
+
+impl ModCheckReport {
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == ModIssueSeverity::Error)
+            .count()
+    }
+
+    /// Turns a report with errors into an `AppError::ModCheckFailed` listing them.
+    pub fn into_result(self) -> Result<()> {
+        let errors: Vec<&ModIssue> = self
+            .issues
+            .iter()
+            .filter(|i| i.severity == ModIssueSeverity::Error)
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let mut message = errors
+            .iter()
+            .take(MAX_LISTED_ISSUES)
+            .map(|i| i.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        if errors.len() > MAX_LISTED_ISSUES {
+            message.push_str(&format!(" (and {} more)", errors.len() - MAX_LISTED_ISSUES));
+        }
+        Err(AppError::ModCheckFailed(message))
+    }
+}
+
+struct Provider<'a> {
+    version: Option<&'a str>,
+    file_name: &'a str,
+}
+
+struct LoadedMod<'a> {
+    file_name: &'a str,
+    metadata: &'a ModMetadata,
+    nested: bool,
+}
+
+/// Checks the declared dependencies, breaks/conflicts and duplicate ids of the given jars
+/// against each other and against the Minecraft, loader and Java version.
+pub fn check_mods(mods: &[ModFile], context: &ModCheckContext) -> ModCheckReport {
+    let mut report = ModCheckReport {
+        checked_files: mods.len(),
+        issues: Vec::new(),
+    };
+    if context.loader == ModLoader::Vanilla {
+        return report;
+    }
+
+    let mut loaded: Vec<LoadedMod> = Vec::new();
+    for mod_file in mods {
+        let Some(metadata) = &mod_file.metadata else {
+            report.issues.push(ModIssue {
+                severity: ModIssueSeverity::Warning,
+                kind: ModIssueKind::UnreadableJar,
+                file_name: mod_file.file_name.clone(),
+                mod_id: None,
+                related_mod_id: None,
+                version_range: None,
+                found_version: None,
+                message: format!("{} could not be read", mod_file.file_name),
+            });
+            continue;
+        };
+
+        let loadable = metadata.loadable_mods(&context.loader);
+        if loadable.is_empty() && !metadata.mods.is_empty() {
+            // Loaders skip jars of other loaders, so this only means the mod does nothing
+            report.issues.push(ModIssue {
+                severity: ModIssueSeverity::Warning,
+                kind: ModIssueKind::WrongLoader,
+                file_name: mod_file.file_name.clone(),
+                mod_id: Some(metadata.mods[0].mod_id.clone()),
+                related_mod_id: None,
+                version_range: None,
+                found_version: None,
+                message: format!(
+                    "{} is a {} mod and won't be loaded by {}",
+                    mod_file.file_name,
+                    format_name(metadata.mods[0].format),
+                    context.loader.as_str()
+                ),
+            });
+            continue;
+        }
+        for mod_metadata in loadable {
+            loaded.push(LoadedMod {
+                file_name: &mod_file.file_name,
+                metadata: mod_metadata,
+                nested: false,
+            });
+        }
+        collect_nested(metadata, &mod_file.file_name, context, &mut loaded);
+    }
+
+    // Everything that can satisfy a dependency, keyed by lowercase id
+    let platform = context.platform_providers();
+    let mut providers: HashMap<String, Vec<Provider>> = HashMap::new();
+    for (id, version) in &platform {
+        providers.entry(id.to_string()).or_default().push(Provider {
+            version: version.as_deref(),
+            file_name: context.loader.as_str(),
+        });
+    }
+    for loaded_mod in &loaded {
+        let ids = std::iter::once(&loaded_mod.metadata.mod_id).chain(&loaded_mod.metadata.provides);
+        for id in ids {
+            providers
+                .entry(id.to_lowercase())
+                .or_default()
+                .push(Provider {
+                    version: loaded_mod.metadata.version.as_deref(),
+                    file_name: loaded_mod.file_name,
+                });
+        }
+    }
+
+    // The same mod in two jars of the mods folder. Nested copies are deduplicated by the loader.
+    let mut files_by_id: HashMap<String, Vec<&str>> = HashMap::new();
+    for loaded_mod in loaded.iter().filter(|m| !m.nested) {
+        let files = files_by_id
+            .entry(loaded_mod.metadata.mod_id.to_lowercase())
+            .or_default();
+        if !files.contains(&loaded_mod.file_name) {
+            files.push(loaded_mod.file_name);
+        }
+    }
+    let mut duplicates: Vec<(String, Vec<&str>)> = files_by_id
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .collect();
+    duplicates.sort();
+    for (mod_id, files) in duplicates {
+        report.issues.push(ModIssue {
+            severity: ModIssueSeverity::Error,
+            kind: ModIssueKind::DuplicateMod,
+            file_name: files[0].to_string(),
+            mod_id: Some(mod_id.clone()),
+            related_mod_id: None,
+            version_range: None,
+            found_version: None,
+            message: format!(
+                "Mod '{}' is installed more than once: {}",
+                mod_id,
+                files.join(", ")
+            ),
+        });
+    }
+
+    for loaded_mod in &loaded {
+        let metadata = loaded_mod.metadata;
+        if metadata.environment == ModEnvironment::Server && !loaded_mod.nested {
+            report.issues.push(ModIssue {
+                severity: ModIssueSeverity::Warning,
+                kind: ModIssueKind::ServerOnly,
+                file_name: loaded_mod.file_name.to_string(),
+                mod_id: Some(metadata.mod_id.clone()),
+                related_mod_id: None,
+                version_range: None,
+                found_version: None,
+                message: format!(
+                    "{} is a server-only mod and has no effect on the client",
+                    metadata.display_name()
+                ),
+            });
+        }
+
+        let syntax = match metadata.format {
+            ModMetadataFormat::Fabric | ModMetadataFormat::Quilt => RangeSyntax::Fabric,
+            _ => RangeSyntax::Maven,
+        };
+        for dependency in &metadata.dependencies {
+            if dependency.environment == ModEnvironment::Server {
+                continue;
+            }
+            // Bundled (jar-in-jar) mods satisfy dependencies, but a jar can't conflict with itself
+            let is_conflict = matches!(
+                dependency.kind,
+                DependencyKind::Incompatible | DependencyKind::Discouraged
+            );
+            let candidates: Vec<&Provider> = providers
+                .get(&dependency.mod_id.to_lowercase())
+                .map(|list| {
+                    list.iter()
+                        .filter(|p| !is_conflict || p.file_name != loaded_mod.file_name)
+                        .collect()
+                })
+                .unwrap_or_default();
+            // Unknown versions and unparseable ranges count as matching
+            let version_matches =
+                |provider: &Provider| match (&dependency.version_range, provider.version) {
+                    (Some(range), Some(version)) => {
+                        version_range::matches(range, version, syntax).unwrap_or(true)
+                    }
+                    _ => true,
+                };
+            let issue = |severity, kind, found_version: Option<&str>, message: String| ModIssue {
+                severity,
+                kind,
+                file_name: loaded_mod.file_name.to_string(),
+                mod_id: Some(metadata.mod_id.clone()),
+                related_mod_id: Some(dependency.mod_id.clone()),
+                version_range: dependency.version_range.clone(),
+                found_version: found_version.map(str::to_string),
+                message,
+            };
+            let wanted = dependency
+                .version_range
+                .as_deref()
+                .map(|range| format!(" {}", range))
+                .unwrap_or_default();
+
+            match dependency.kind {
+                DependencyKind::Required | DependencyKind::Optional => {
+                    let required = dependency.kind == DependencyKind::Required;
+                    if candidates.is_empty() {
+                        if required {
+                            report.issues.push(issue(
+                                ModIssueSeverity::Error,
+                                ModIssueKind::MissingDependency,
+                                None,
+                                format!(
+                                    "{} requires {}{}, which is not installed",
+                                    metadata.display_name(),
+                                    dependency.mod_id,
+                                    wanted
+                                ),
+                            ));
+                        }
+                    } else if !candidates.iter().any(|p| version_matches(p)) {
+                        let found = candidates[0].version;
+                        report.issues.push(issue(
+                            if required {
+                                ModIssueSeverity::Error
+                            } else {
+                                ModIssueSeverity::Warning
+                            },
+                            ModIssueKind::DependencyVersionMismatch,
+                            found,
+                            format!(
+                                "{} requires {}{}, but {} is installed",
+                                metadata.display_name(),
+                                dependency.mod_id,
+                                wanted,
+                                found.unwrap_or("another version")
+                            ),
+                        ));
+                    }
+                }
+                DependencyKind::Incompatible | DependencyKind::Discouraged => {
+                    if let Some(conflicting) = candidates.iter().find(|p| version_matches(p)) {
+                        let incompatible = dependency.kind == DependencyKind::Incompatible;
+                        report.issues.push(issue(
+                            if incompatible {
+                                ModIssueSeverity::Error
+                            } else {
+                                ModIssueSeverity::Warning
+                            },
+                            if incompatible {
+                                ModIssueKind::Incompatible
+                            } else {
+                                ModIssueKind::Discouraged
+                            },
+                            conflicting.version,
+                            format!(
+                                "{} {} {}{} ({})",
+                                metadata.display_name(),
+                                if incompatible {
+                                    "is incompatible with"
+                                } else {
+                                    "conflicts with"
+                                },
+                                dependency.mod_id,
+                                wanted,
+                                conflicting.file_name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Adds the loadable mods of nested jars, attributed to the outer file.
+fn collect_nested<'a>(
+    metadata: &'a JarMetadata,
+    file_name: &'a str,
+    context: &ModCheckContext,
+    loaded: &mut Vec<LoadedMod<'a>>,
+) {
+    for nested in &metadata.nested {
+        for mod_metadata in nested.metadata.loadable_mods(&context.loader) {
+            loaded.push(LoadedMod {
+                file_name,
+                metadata: mod_metadata,
+                nested: true,
+            });
+        }
+        collect_nested(&nested.metadata, file_name, context, loaded);
+    }
+}
+
+fn format_name(format: ModMetadataFormat) -> &'static str {
+    match format {
+        ModMetadataFormat::Fabric => "Fabric",
+        ModMetadataFormat::Quilt => "Quilt",
+        ModMetadataFormat::Forge | ModMetadataFormat::LegacyForge => "Forge",
+        ModMetadataFormat::NeoForge => "NeoForge",
+    }
+}
+
+/// Reads the metadata of the resolved mods and checks them.
+pub async fn check_target_mods(
+    target_mods: &[TargetMod],
+    context: &ModCheckContext,
+) -> ModCheckReport {
+    let mut mod_files = Vec::with_capacity(target_mods.len());
+    for target in target_mods {
+        let metadata = match mod_metadata::read_jar_metadata(&target.cache_path, false).await {
+            Ok(metadata) => Some(metadata),
+            Err(e) => {
+                warn!("[ModCheck] Failed to read {}: {}", target.filename, e);
+                None
+            }
+        };
+        mod_files.push(ModFile {
+            file_name: target.filename.clone(),
+            metadata,
+        });
+    }
+
+    let report = check_mods(&mod_files, context);
+    info!(
+        "[ModCheck] Checked {} mods: {} errors, {} warnings",
+        report.checked_files,
+        report.error_count(),
+        report.issues.len() - report.error_count()
+    );
+    for issue in &report.issues {
+        warn!("[ModCheck] {:?}: {}", issue.severity, issue.message);
+    }
+    report
+}
+
+/// Resolves the mods the profile would launch with (pack, profile and custom mods) and checks them.
+/// Mods that aren't downloaded yet are reported as unreadable.
+pub async fn check_profile_mods(profile: &Profile) -> Result<ModCheckReport> {
+    let state = State::get().await?;
+    let norisk_config = if profile.selected_norisk_pack_id.is_some() {
+        Some(state.norisk_pack_manager.get_config().await)
+    } else {
+        None
+    };
+    let custom_mods = state.profile_manager.list_custom_mods(profile).await?;
+    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
+    let target_mods = mod_resolver::resolve_target_mods(
+        profile,
+        norisk_config.as_ref(),
+        Some(&custom_mods),
+        &profile.game_version,
+        profile.loader.as_str(),
+        &mod_cache_dir,
+    )
+    .await?;
+
+    Ok(check_target_mods(&target_mods, &ModCheckContext::for_profile(profile)).await)
+}
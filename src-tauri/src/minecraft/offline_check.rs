@@ -0,0 +1,258 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::downloads::java_download::JavaDownloadService;
+use crate::minecraft::dto::piston_meta::{AssetIndexContent, DownloadInfo, PistonMeta};
+use crate::minecraft::dto::JavaDistribution;
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::offline_utils;
+use log::{info, warn};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// How many missing paths are listed in the error message before it gets truncated.
+const MAX_LISTED_MISSING: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingFileKind {
+    Metadata,
+    Library,
+    AssetIndex,
+    Asset,
+    ClientJar,
+    LoggingConfig,
+    Java,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingFile {
+    pub kind: MissingFileKind,
+    pub path: PathBuf,
+}
+
+/// Result of checking whether a version can be launched without network access.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct OfflineReadinessReport {
+    pub version_id: String,
+    pub missing: Vec<MissingFile>,
+}
+
+impl OfflineReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Turns a non-empty report into an `AppError::OfflineUnavailable` listing what's missing.
+    pub fn into_result(self) -> Result<()> {
+        if self.is_ready() {
+            return Ok(());
+        }
+        let listed: Vec<String> = self
+            .missing
+            .iter()
+            .take(MAX_LISTED_MISSING)
+            .map(|m| format!("{:?}: {}", m.kind, m.path.display()))
+            .collect();
+        let remaining = self.missing.len().saturating_sub(MAX_LISTED_MISSING);
+        let mut message = format!(
+            "Minecraft {} is not fully installed, {} files missing: {}",
+            self.version_id,
+            self.missing.len(),
+            listed.join(", ")
+        );
+        if remaining > 0 {
+            message.push_str(&format!(" (and {} more)", remaining));
+        }
+        Err(AppError::OfflineUnavailable(message))
+    }
+
+    fn add(&mut self, kind: MissingFileKind, path: PathBuf) {
+        self.missing.push(MissingFile { kind, path });
+    }
+}
+
+/// Checks the files `install_minecraft_version` would otherwise download: libraries,
+/// asset index and objects, client jar, logging config and the Java runtime.
+/// Only checks presence and size, hashing every asset on each launch would be too slow.
+pub async fn check_offline_readiness(
+    piston_meta: &PistonMeta,
+    profile: &Profile,
+    java_distribution: &JavaDistribution,
+) -> Result<OfflineReadinessReport> {
+    let meta_dir = LAUNCHER_DIRECTORY.meta_dir();
+    let mut report = OfflineReadinessReport {
+        version_id: piston_meta.id.clone(),
+        ..Default::default()
+    };
+
+    // Libraries (same layout as MinecraftLibrariesDownloadService)
+    let libraries_dir = meta_dir.join("libraries");
+    for library in &piston_meta.libraries {
+        let artifacts = library.downloads.artifact.iter().chain(
+            library
+                .downloads
+                .classifiers
+                .iter()
+                .flat_map(|classifiers| classifiers.values()),
+        );
+        for artifact in artifacts {
+            if let Some(path) = library_path(&libraries_dir, artifact) {
+                if !file_matches(&path, artifact.size).await {
+                    report.add(MissingFileKind::Library, path);
+                }
+            }
+        }
+    }
+
+    // Assets
+    let assets_dir = meta_dir.join("assets");
+    let index_path = assets_dir
+        .join("indexes")
+        .join(format!("{}.json", piston_meta.asset_index.id));
+    match fs::read(&index_path).await {
+        Ok(content) => match serde_json::from_slice::<AssetIndexContent>(&content) {
+            Ok(index) => {
+                for object in index.objects.values() {
+                    let Some(shard) = object.hash.get(..2) else {
+                        continue;
+                    };
+                    let path = assets_dir.join("objects").join(shard).join(&object.hash);
+                    if !file_matches(&path, object.size).await {
+                        report.add(MissingFileKind::Asset, path);
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Asset index {:?} is not valid JSON: {}", index_path, e);
+                report.add(MissingFileKind::AssetIndex, index_path);
+            }
+        },
+        Err(_) => report.add(MissingFileKind::AssetIndex, index_path),
+    }
+
+    // Client jar
+    let client_path = meta_dir
+        .join("versions")
+        .join(&piston_meta.id)
+        .join(format!("{}.jar", piston_meta.id));
+    if !file_matches(&client_path, piston_meta.downloads.client.size).await {
+        report.add(MissingFileKind::ClientJar, client_path);
+    }
+
+    // Logging config
+    if let Some(logging) = &piston_meta.logging {
+        let logging_path = meta_dir
+            .join("assets")
+            .join("log_configs")
+            .join(&logging.client.file.id);
+        if !file_matches(&logging_path, logging.client.file.size).await {
+            report.add(MissingFileKind::LoggingConfig, logging_path);
+        }
+    }
+
+    // Java: a valid custom path or the runtime the installer would download
+    let java_version = piston_meta.java_version.major_version as u32;
+    let custom_java = profile
+        .settings
+        .java_path
+        .as_ref()
+        .filter(|_| profile.settings.use_custom_java_path)
+        .map(PathBuf::from)
+        .filter(|path| path.exists());
+    if custom_java.is_none() {
+        let java_service = JavaDownloadService::new();
+        let force_x86_64 =
+            java_service.needs_x86_64_java(Some(&piston_meta.java_version.component));
+        if java_service
+            .find_java_binary(java_distribution, &java_version, force_x86_64)
+            .await
+            .is_err()
+        {
+            report.add(
+                MissingFileKind::Java,
+                meta_dir.join("java").join(format!(
+                    "{}_{}",
+                    java_distribution.get_name(),
+                    java_version
+                )),
+            );
+        }
+    }
+
+    info!(
+        "Offline readiness of {} for profile '{}': {} missing files",
+        piston_meta.id,
+        profile.name,
+        report.missing.len()
+    );
+    Ok(report)
+}
+
+/// Resolves the cached metadata for the profile's version and checks it.
+/// Missing metadata is reported instead of returned as an error.
+pub async fn check_profile_offline_readiness(profile: &Profile) -> Result<OfflineReadinessReport> {
+    let api_service = MinecraftApiService::new();
+    let metadata_missing = |path: PathBuf| OfflineReadinessReport {
+        version_id: profile.game_version.clone(),
+        missing: vec![MissingFile {
+            kind: MissingFileKind::Metadata,
+            path,
+        }],
+    };
+
+    let manifest = match api_service.get_version_manifest().await {
+        Ok(manifest) => manifest,
+        Err(AppError::OfflineUnavailable(e)) => {
+            warn!("{}", e);
+            return Ok(metadata_missing(
+                offline_utils::metadata_cache_dir().join("version_manifest.json"),
+            ));
+        }
+        Err(e) => return Err(e),
+    };
+    let version = manifest
+        .versions
+        .iter()
+        .find(|v| v.id == profile.game_version)
+        .ok_or_else(|| {
+            AppError::VersionNotFound(format!("Version {} not found", profile.game_version))
+        })?;
+
+    let piston_meta = match api_service.get_piston_meta(&version.url).await {
+        Ok(piston_meta) => piston_meta,
+        Err(AppError::OfflineUnavailable(e)) => {
+            warn!("{}", e);
+            return Ok(metadata_missing(
+                offline_utils::metadata_cache_dir()
+                    .join("piston_meta")
+                    .join(format!("{}.json", profile.game_version)),
+            ));
+        }
+        Err(e) => return Err(e),
+    };
+
+    let config = State::get().await?.config_manager.get_config().await;
+    check_offline_readiness(&piston_meta, profile, &config.java_distribution).await
+}
+
+fn library_path(libraries_dir: &Path, artifact: &DownloadInfo) -> Option<PathBuf> {
+    if let Some(path) = &artifact.path {
+        return Some(libraries_dir.join(path));
+    }
+    artifact
+        .url
+        .split("libraries.minecraft.net/")
+        .nth(1)
+        .map(|path| libraries_dir.join(path))
+}
+
+/// File exists and, if the expected size is known, has that size.
+async fn file_matches(path: &Path, expected_size: i64) -> bool {
+    match fs::metadata(path).await {
+        Ok(metadata) => expected_size <= 0 || metadata.len() as i64 == expected_size,
+        Err(_) => false,
+    }
+}
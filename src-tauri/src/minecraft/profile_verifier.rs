@@ -0,0 +1,694 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::integrations::norisk_packs::NoriskModpacksConfig;
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::downloads::java_download::JavaDownloadService;
+use crate::minecraft::downloads::mc_natives_download::MinecraftNativesDownloadService;
+use crate::minecraft::downloads::mod_resolver::{self, TargetMod};
+use crate::minecraft::downloads::{ModDownloadService, NoriskPackDownloadService};
+use crate::minecraft::dto::piston_meta::{
+    AssetIndexContent, AssetObject, DownloadInfo, PistonMeta,
+};
+use crate::minecraft::dto::JavaDistribution;
+use crate::minecraft::modloader::ModloaderFactory;
+use crate::state::content_store_state::ContentStoreManager;
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::profile_state::{self, ModLoader, ModSource, Profile};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::VerifiedDownload;
+use crate::utils::hash_utils;
+use futures::stream::{iter, StreamExt};
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+const ASSET_BASE_URL: &str = "https://resources.download.minecraft.net";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyItemKind {
+    Library,
+    AssetIndex,
+    Asset,
+    ClientJar,
+    LoggingConfig,
+    Natives,
+    LoaderLibrary,
+    Mod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyProblem {
+    Missing,
+    /// Hash/size mismatch or a jar that can't be opened
+    Corrupt,
+}
+
+/// A broken file found during verification and what happened when repairing it.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyIssue {
+    pub kind: VerifyItemKind,
+    pub path: PathBuf,
+    pub problem: VerifyProblem,
+    pub repaired: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ProfileVerifyReport {
+    pub profile_id: Uuid,
+    pub checked_files: usize,
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl ProfileVerifyReport {
+    pub fn repaired_count(&self) -> usize {
+        self.issues.iter().filter(|i| i.repaired).count()
+    }
+
+    pub fn unrepaired_count(&self) -> usize {
+        self.issues.iter().filter(|i| !i.repaired).count()
+    }
+}
+
+/// A file with a known download source. Files without hashes are only checked for
+/// existence (and for jars, whether the zip can be opened).
+struct VerifyItem {
+    kind: VerifyItemKind,
+    path: PathBuf,
+    download: VerifiedDownload,
+}
+
+impl VerifyItem {
+    fn new(kind: VerifyItemKind, path: PathBuf, download: VerifiedDownload) -> Self {
+        Self {
+            kind,
+            path,
+            download,
+        }
+    }
+
+    fn from_download_info(kind: VerifyItemKind, path: PathBuf, info: &DownloadInfo) -> Self {
+        let download = VerifiedDownload::new(&info.url, &path)
+            .sha1(Some(&info.sha1))
+            .size(u64::try_from(info.size).ok());
+        Self::new(kind, path, download)
+    }
+}
+
+/// Walks everything a launch of `profile` needs (vanilla libraries, asset index and objects,
+/// client jar, logging config, natives, loader libraries and the resolved mod list), checks
+/// it against the known hashes and re-downloads only the broken files.
+pub async fn verify_profile(profile: &Profile) -> Result<ProfileVerifyReport> {
+    let state = State::get().await?;
+    let config = state.config_manager.get_config().await;
+    let mut verifier = ProfileVerifier {
+        state: &state,
+        profile,
+        event_id: Uuid::new_v4(),
+        concurrency: config.concurrent_io_limit.max(1),
+        concurrent_downloads: config.concurrent_downloads.max(1),
+        java_distribution: config.java_distribution,
+        report: ProfileVerifyReport {
+            profile_id: profile.id,
+            ..Default::default()
+        },
+    };
+    verifier.run().await?;
+    Ok(verifier.report)
+}
+
+struct ProfileVerifier<'a> {
+    state: &'a State,
+    profile: &'a Profile,
+    event_id: Uuid,
+    concurrency: usize,
+    concurrent_downloads: usize,
+    java_distribution: JavaDistribution,
+    report: ProfileVerifyReport,
+}
+
+impl ProfileVerifier<'_> {
+    async fn run(&mut self) -> Result<()> {
+        info!(
+            "[Verify] Verifying profile '{}' ({} {:?})",
+            self.profile.name, self.profile.game_version, self.profile.loader
+        );
+        self.emit("Resolving version metadata...", 0.0, None).await;
+
+        let api_service = MinecraftApiService::new();
+        let manifest = api_service.get_version_manifest().await?;
+        let version = manifest
+            .versions
+            .iter()
+            .find(|v| v.id == self.profile.game_version)
+            .ok_or_else(|| {
+                AppError::VersionNotFound(format!(
+                    "Version {} not found",
+                    self.profile.game_version
+                ))
+            })?;
+        let piston_meta = api_service.get_piston_meta(&version.url).await?;
+        let meta_dir = LAUNCHER_DIRECTORY.meta_dir();
+
+        // 1. Vanilla libraries and client jar
+        self.emit("Verifying libraries...", 0.1, None).await;
+        let libraries_dir = meta_dir.join("libraries");
+        let mut items = Vec::new();
+        for library in &piston_meta.libraries {
+            let artifacts = library.downloads.artifact.iter().chain(
+                library
+                    .downloads
+                    .classifiers
+                    .iter()
+                    .flat_map(|classifiers| classifiers.values()),
+            );
+            for artifact in artifacts {
+                if let Some(path) = library_path(&libraries_dir, artifact) {
+                    items.push(VerifyItem::from_download_info(
+                        VerifyItemKind::Library,
+                        path,
+                        artifact,
+                    ));
+                }
+            }
+        }
+        let client_path = meta_dir
+            .join("versions")
+            .join(&piston_meta.id)
+            .join(format!("{}.jar", piston_meta.id));
+        items.push(VerifyItem::from_download_info(
+            VerifyItemKind::ClientJar,
+            client_path,
+            &piston_meta.downloads.client,
+        ));
+        if let Some(logging) = &piston_meta.logging {
+            let file = &logging.client.file;
+            let path = meta_dir.join("assets").join("log_configs").join(&file.id);
+            let download = VerifiedDownload::new(&file.url, &path)
+                .sha1(Some(&file.sha1))
+                .size(u64::try_from(file.size).ok());
+            items.push(VerifyItem::new(
+                VerifyItemKind::LoggingConfig,
+                path,
+                download,
+            ));
+        }
+        let libraries_repaired = self.verify_items(items).await > 0;
+
+        // 2. Assets (the index first, the objects are read from it)
+        self.emit("Verifying assets...", 0.3, None).await;
+        self.verify_assets(&piston_meta).await?;
+
+        // 3. Natives are extracted from the library jars
+        self.emit("Verifying natives...", 0.5, None).await;
+        self.verify_natives(&piston_meta, libraries_repaired).await;
+
+        // 4. Loader libraries
+        if self.profile.loader != ModLoader::Vanilla {
+            self.emit(
+                &format!("Verifying {} libraries...", self.profile.loader.as_str()),
+                0.6,
+                None,
+            )
+            .await;
+            self.verify_loader_libraries(&piston_meta).await?;
+        }
+
+        // 5. Mods in the cache and in the profile
+        self.emit("Verifying mods...", 0.8, None).await;
+        self.verify_mods().await?;
+
+        let message = format!(
+            "Verification finished: {} files checked, {} repaired, {} broken",
+            self.report.checked_files,
+            self.report.repaired_count(),
+            self.report.unrepaired_count()
+        );
+        info!("[Verify] {}", message);
+        let error = (self.report.unrepaired_count() > 0).then(|| {
+            format!(
+                "{} files could not be repaired",
+                self.report.unrepaired_count()
+            )
+        });
+        self.emit(&message, 1.0, error).await;
+        Ok(())
+    }
+
+    async fn verify_assets(&mut self, piston_meta: &PistonMeta) -> Result<()> {
+        let asset_index = &piston_meta.asset_index;
+        let assets_dir = LAUNCHER_DIRECTORY.meta_dir().join("assets");
+        let index_path = assets_dir
+            .join("indexes")
+            .join(format!("{}.json", asset_index.id));
+        let index_download = VerifiedDownload::new(&asset_index.url, &index_path)
+            .sha1(Some(&asset_index.sha1))
+            .size(u64::try_from(asset_index.size).ok());
+        self.verify_items(vec![VerifyItem::new(
+            VerifyItemKind::AssetIndex,
+            index_path.clone(),
+            index_download,
+        )])
+        .await;
+
+        let content = match fs::read(&index_path).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("[Verify] Asset index unavailable, skipping objects: {}", e);
+                return Ok(());
+            }
+        };
+        let index: AssetIndexContent = serde_json::from_slice(&content)?;
+        // Several names can share an object, checking it twice would race on its `.part` file
+        let objects: HashMap<&str, &AssetObject> = index
+            .objects
+            .values()
+            .filter(|object| object.hash.len() > 2)
+            .map(|object| (object.hash.as_str(), object))
+            .collect();
+        let items = objects
+            .into_values()
+            .map(|object| {
+                let shard = &object.hash[..2];
+                let path = assets_dir.join("objects").join(shard).join(&object.hash);
+                let url = format!("{}/{}/{}", ASSET_BASE_URL, shard, object.hash);
+                let download = VerifiedDownload::new(url, &path)
+                    .sha1(Some(&object.hash))
+                    .size(u64::try_from(object.size).ok());
+                VerifyItem::new(VerifyItemKind::Asset, path, download)
+            })
+            .collect();
+        self.verify_items(items).await;
+        Ok(())
+    }
+
+    async fn verify_natives(&mut self, piston_meta: &PistonMeta, force_extract: bool) {
+        let has_natives = piston_meta
+            .libraries
+            .iter()
+            .any(|lib| lib.natives.is_some() || lib.name.contains(":natives-"));
+        if !has_natives {
+            return;
+        }
+
+        let natives_path = LAUNCHER_DIRECTORY
+            .meta_dir()
+            .join("natives")
+            .join(&piston_meta.id);
+        self.report.checked_files += 1;
+        let is_empty = match std::fs::read_dir(&natives_path) {
+            Ok(mut entries) => entries.next().is_none(),
+            Err(_) => true,
+        };
+        // Re-extract after library repairs too, the old natives came from the broken jars
+        if !is_empty && !force_extract {
+            return;
+        }
+
+        let result = MinecraftNativesDownloadService::new()
+            .extract_natives(&piston_meta.libraries, &piston_meta.id)
+            .await;
+        if is_empty {
+            self.record(
+                VerifyItemKind::Natives,
+                natives_path,
+                VerifyProblem::Missing,
+                result.err().map(|e| e.to_string()),
+            )
+            .await;
+        } else if let Err(e) = result {
+            warn!("[Verify] Failed to re-extract natives: {}", e);
+        }
+    }
+
+    async fn verify_loader_libraries(&mut self, piston_meta: &PistonMeta) -> Result<()> {
+        let java_path = self.resolve_java(piston_meta).await?;
+        let installer = ModloaderFactory::create_installer_with_config(
+            &self.profile.loader,
+            java_path,
+            self.concurrent_downloads,
+        );
+
+        // The loader installers already re-download files whose hashes are known,
+        // this pass catches the hashless ones (e.g. the loader jar itself)
+        let install_result = installer
+            .install(&self.profile.game_version, self.profile)
+            .await?;
+        let libraries = install_result.libraries;
+        self.report.checked_files += libraries.len();
+
+        let mut broken = Vec::new();
+        for path in libraries {
+            if let Some(problem) = check_jar(&path, None).await {
+                broken.push((path, problem));
+            }
+        }
+        if broken.is_empty() {
+            return Ok(());
+        }
+
+        for (path, problem) in &broken {
+            warn!("[Verify] Broken loader library {:?} ({:?})", path, problem);
+            let _ = fs::remove_file(path).await;
+        }
+        let reinstall = installer
+            .install(&self.profile.game_version, self.profile)
+            .await;
+        for (path, problem) in broken {
+            let error = match &reinstall {
+                Err(e) => Some(e.to_string()),
+                Ok(_) => check_jar(&path, None)
+                    .await
+                    .map(|_| "File is still broken after reinstalling".to_string()),
+            };
+            self.record(VerifyItemKind::LoaderLibrary, path, problem, error)
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn verify_mods(&mut self) -> Result<()> {
+        let norisk_config = if self.profile.selected_norisk_pack_id.is_some() {
+            Some(self.state.norisk_pack_manager.get_config().await)
+        } else {
+            None
+        };
+        let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
+        // Custom mods live in the profile and can't be re-downloaded, so they're not checked
+        let target_mods = mod_resolver::resolve_target_mods(
+            self.profile,
+            norisk_config.as_ref(),
+            None,
+            &self.profile.game_version,
+            self.profile.loader.as_str(),
+            &mod_cache_dir,
+        )
+        .await?;
+        self.report.checked_files += target_mods.len();
+
+        // Modrinth mods of the profile carry their sha1
+        let known_hashes: HashMap<String, String> = self
+            .profile
+            .mods
+            .iter()
+            .filter_map(|m| match &m.source {
+                ModSource::Modrinth {
+                    file_hash_sha1: Some(sha1),
+                    ..
+                } => profile_state::get_profile_mod_filename(&m.source)
+                    .ok()
+                    .map(|name| (name, sha1.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let broken = find_broken_cached_mods(&target_mods, &known_hashes).await;
+        if !broken.is_empty() {
+            self.repair_cached_mods(broken, norisk_config.as_ref())
+                .await;
+        }
+
+        // The copies in the profile's mods folder, a repaired cache doesn't fix those
+        let mods_dir = self
+            .state
+            .profile_manager
+            .get_profile_mods_path(self.profile)?;
+        let (checked, repairs) = repair_profile_mods(
+            &self.state.content_store,
+            &target_mods,
+            &mods_dir,
+            &known_hashes,
+        )
+        .await;
+        self.report.checked_files += checked;
+        for (path, problem, error) in repairs {
+            self.record(VerifyItemKind::Mod, path, problem, error).await;
+        }
+        Ok(())
+    }
+
+    async fn repair_cached_mods(
+        &mut self,
+        broken: Vec<(PathBuf, Option<String>, VerifyProblem)>,
+        norisk_config: Option<&NoriskModpacksConfig>,
+    ) {
+        for (path, _, problem) in &broken {
+            warn!("[Verify] Broken mod {:?} ({:?})", path, problem);
+            let _ = fs::remove_file(path).await;
+        }
+
+        // The downloaders only fetch what's missing from the cache
+        let mut download_error = ModDownloadService::with_concurrency(self.concurrent_downloads)
+            .download_mods_to_cache(self.profile)
+            .await
+            .err();
+        if let (Some(pack_id), Some(config)) =
+            (&self.profile.selected_norisk_pack_id, norisk_config)
+        {
+            if let Err(e) = NoriskPackDownloadService::with_concurrency(self.concurrent_downloads)
+                .download_pack_mods_to_cache(
+                    config,
+                    pack_id,
+                    &self.profile.game_version,
+                    self.profile.loader.as_str(),
+                )
+                .await
+            {
+                download_error.get_or_insert(e);
+            }
+        }
+
+        for (path, sha1, problem) in broken {
+            let error = check_jar(&path, sha1.as_deref()).await.map(|_| {
+                download_error
+                    .as_ref()
+                    .map(|e| e.to_string())
+                    .unwrap_or_else(|| "File is still broken after re-downloading".to_string())
+            });
+            self.record(VerifyItemKind::Mod, path, problem, error).await;
+        }
+    }
+
+    /// Checks all items and re-downloads the broken ones. Returns the number of broken items.
+    async fn verify_items(&mut self, items: Vec<VerifyItem>) -> usize {
+        self.report.checked_files += items.len();
+
+        let broken: Vec<(VerifyItem, VerifyProblem)> = iter(items)
+            .map(|item| async move {
+                let problem = check_download(&item.download, &item.path).await;
+                (item, problem)
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|(item, problem)| async move { problem.map(|p| (item, p)) })
+            .collect()
+            .await;
+        if broken.is_empty() {
+            return 0;
+        }
+
+        info!("[Verify] Repairing {} broken files", broken.len());
+        let broken_count = broken.len();
+        // `download()` deletes an invalid existing file before downloading it again
+        let results: Vec<(VerifyItem, VerifyProblem, Option<String>)> = iter(broken)
+            .map(|(item, problem)| async move {
+                let error = item.download.download().await.err().map(|e| e.to_string());
+                (item, problem, error)
+            })
+            .buffer_unordered(self.concurrent_downloads)
+            .collect()
+            .await;
+        for (item, problem, error) in results {
+            self.record(item.kind, item.path, problem, error).await;
+        }
+        broken_count
+    }
+
+    async fn resolve_java(&self, piston_meta: &PistonMeta) -> Result<PathBuf> {
+        let java_version = piston_meta.java_version.major_version as u32;
+        if self.profile.settings.use_custom_java_path {
+            if let Some(custom_path) = &self.profile.settings.java_path {
+                let path = PathBuf::from(custom_path);
+                if path.exists() {
+                    return Ok(path);
+                }
+            }
+        }
+        JavaDownloadService::new()
+            .get_or_download_java(
+                java_version,
+                &self.java_distribution,
+                Some(&piston_meta.java_version.component),
+            )
+            .await
+    }
+
+    async fn record(
+        &mut self,
+        kind: VerifyItemKind,
+        path: PathBuf,
+        problem: VerifyProblem,
+        error: Option<String>,
+    ) {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let message = match &error {
+            None => format!("Repaired {:?} {} ({:?})", kind, file_name, problem),
+            Some(_) => format!("Failed to repair {:?} {} ({:?})", kind, file_name, problem),
+        };
+        info!("[Verify] {}", message);
+        self.emit(&message, 0.0, error.clone()).await;
+
+        self.report.issues.push(VerifyIssue {
+            kind,
+            path,
+            problem,
+            repaired: error.is_none(),
+            error,
+        });
+    }
+
+    async fn emit(&self, message: &str, progress: f64, error: Option<String>) {
+        // Progress 0.0 on single fixes keeps the overall bar where the last step left it
+        let progress = (progress > 0.0).then_some(progress);
+        if let Err(e) = self
+            .state
+            .emit_event(EventPayload {
+                event_id: self.event_id,
+                event_type: EventType::VerifyingProfile,
+                target_id: Some(self.profile.id),
+                message: message.to_string(),
+                progress,
+                error,
+            })
+            .await
+        {
+            warn!("[Verify] Failed to emit event: {}", e);
+        }
+    }
+}
+
+fn library_path(libraries_dir: &Path, artifact: &DownloadInfo) -> Option<PathBuf> {
+    if let Some(path) = &artifact.path {
+        return Some(libraries_dir.join(path));
+    }
+    artifact
+        .url
+        .split("libraries.minecraft.net/")
+        .nth(1)
+        .map(|path| libraries_dir.join(path))
+}
+
+/// Cached mod jars that are missing, don't match their known sha1 (by file name) or,
+/// without one, can't be opened. Returns the cache path, known sha1 and problem.
+pub async fn find_broken_cached_mods(
+    target_mods: &[TargetMod],
+    known_hashes: &HashMap<String, String>,
+) -> Vec<(PathBuf, Option<String>, VerifyProblem)> {
+    let mut broken = Vec::new();
+    for target in target_mods {
+        let sha1 = known_hashes.get(&target.filename).map(String::as_str);
+        if let Some(problem) = check_jar(&target.cache_path, sha1).await {
+            broken.push((target.cache_path.clone(), sha1.map(str::to_string), problem));
+        }
+    }
+    broken
+}
+
+/// Checks the mods in a profile's mods folder against their known sha1 (or the cached jar)
+/// and re-links broken ones from the content store. Mods that are not in the folder yet
+/// are skipped, the next launch links them. Returns the number of checked files and the
+/// path, problem and repair error of every broken one.
+pub async fn repair_profile_mods(
+    store: &ContentStoreManager,
+    target_mods: &[TargetMod],
+    mods_dir: &Path,
+    known_hashes: &HashMap<String, String>,
+) -> (usize, Vec<(PathBuf, VerifyProblem, Option<String>)>) {
+    let mut checked = 0;
+    let mut results = Vec::new();
+    for target in target_mods {
+        let path = mods_dir.join(&target.filename);
+        if !fs::try_exists(&path).await.unwrap_or(false) {
+            continue;
+        }
+        let expected = match known_hashes.get(&target.filename) {
+            Some(sha1) => sha1.clone(),
+            // A broken cache file was already reported, there is nothing to compare with
+            None => match hash_utils::calculate_sha1(&target.cache_path).await {
+                Ok(sha1) => sha1,
+                Err(_) => continue,
+            },
+        };
+        checked += 1;
+        let Some(problem) = check_jar(&path, Some(&expected)).await else {
+            continue;
+        };
+
+        warn!("[Verify] Broken mod {:?} in profile ({:?})", path, problem);
+        let relink = async {
+            // Written in place through a hardlink, the blob is broken as well
+            store.remove_corrupt_blob(&expected).await?;
+            fs::remove_file(&path).await?;
+            store.release_paths(std::slice::from_ref(&path)).await?;
+            store
+                .link_files(&[(target.cache_path.clone(), path.clone())])
+                .await
+        };
+        let error = match relink.await {
+            Err(e) => Some(e.to_string()),
+            Ok(_) => check_jar(&path, Some(&expected))
+                .await
+                .map(|_| "File is still broken after re-linking".to_string()),
+        };
+        results.push((path, problem, error));
+    }
+    (checked, results)
+}
+
+async fn check_download(download: &VerifiedDownload, path: &Path) -> Option<VerifyProblem> {
+    if !fs::try_exists(path).await.unwrap_or(false) {
+        return Some(VerifyProblem::Missing);
+    }
+    match download.verify(path).await {
+        Ok(()) => None,
+        Err(e) => {
+            warn!("[Verify] {}", e);
+            Some(VerifyProblem::Corrupt)
+        }
+    }
+}
+
+/// Jar check for files without a download source of their own: sha1 if known,
+/// otherwise whether the zip directory can be read.
+async fn check_jar(path: &Path, sha1: Option<&str>) -> Option<VerifyProblem> {
+    if !fs::try_exists(path).await.unwrap_or(false) {
+        return Some(VerifyProblem::Missing);
+    }
+    if sha1.is_some() {
+        // The URL is irrelevant, only `verify` is used
+        return check_download(&VerifiedDownload::new("", path).sha1(sha1), path).await;
+    }
+    let jar_path = path.to_path_buf();
+    let readable = tokio::task::spawn_blocking(move || {
+        std::fs::File::open(&jar_path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| zip::ZipArchive::new(file).map_err(|e| e.to_string()))
+            .map(|_| ())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    match readable {
+        Ok(()) => None,
+        Err(e) => {
+            warn!("[Verify] {:?} is not a readable jar: {}", path, e);
+            Some(VerifyProblem::Corrupt)
+        }
+    }
+}
@@ -0,0 +1,581 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::api::fabric_api::FabricApi;
+use crate::minecraft::api::forge_api::ForgeApi;
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::api::quilt_api::QuiltApi;
+use crate::minecraft::api::NeoForgeApi;
+use crate::minecraft::downloads::forge_installer_download::ForgeInstallerDownloadService;
+use crate::minecraft::downloads::java_download::JavaDownloadService;
+use crate::minecraft::downloads::mod_resolver;
+use crate::minecraft::downloads::neo_forge_installer_download::NeoForgeInstallerDownloadService;
+use crate::minecraft::downloads::ModDownloadService;
+use crate::minecraft::dto::piston_meta::PistonMeta;
+use crate::minecraft::installer::emit_progress_event;
+use crate::state::event_state::EventType;
+use crate::state::profile_state::{ModLoader, Profile, ProfileKind};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::{DownloadProgress, VerifiedDownload};
+use crate::utils::java_detector;
+use crate::utils::mod_metadata::{self, ModEnvironment};
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// The vanilla server, Fabric and Quilt expect it under this name next to their launcher
+const SERVER_JAR: &str = "server.jar";
+const QUILT_LAUNCHER_JAR: &str = "quilt-server-launch.jar";
+/// Remembers which loader version the installer set up, so it only runs again on changes
+const INSTALLED_LOADER_FILE: &str = ".installed_loader";
+/// Lines of installer output kept for the error message
+const INSTALLER_OUTPUT_TAIL: usize = 20;
+
+/// How the server is started, relative to the server directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerLaunchTarget {
+    /// `java -jar <jar>`
+    Jar(PathBuf),
+    /// `java @<args file>`, written by the Forge/NeoForge installers since 1.17
+    ArgsFile(PathBuf),
+}
+
+impl ServerLaunchTarget {
+    pub fn java_args(&self) -> Vec<String> {
+        match self {
+            Self::Jar(jar) => vec!["-jar".to_string(), jar.to_string_lossy().to_string()],
+            Self::ArgsFile(args_file) => vec![format!("@{}", args_file.to_string_lossy())],
+        }
+    }
+}
+
+/// Finds what the Forge/NeoForge installer set up in a server directory: the args file of
+/// newer versions or the server jar of older ones.
+pub async fn find_forge_launch_target(
+    server_dir: &Path,
+    loader: ModLoader,
+    loader_version: &str,
+) -> Result<Option<ServerLaunchTarget>> {
+    let (maven_dir, jar_prefix) = match loader {
+        ModLoader::Forge => ("net/minecraftforge/forge", "forge"),
+        ModLoader::NeoForge => ("net/neoforged/neoforge", "neoforge"),
+        _ => return Ok(None),
+    };
+    let args_file_name = if cfg!(windows) {
+        "win_args.txt"
+    } else {
+        "unix_args.txt"
+    };
+    let args_file = Path::new("libraries")
+        .join(maven_dir)
+        .join(loader_version)
+        .join(args_file_name);
+    if fs::try_exists(server_dir.join(&args_file)).await? {
+        return Ok(Some(ServerLaunchTarget::ArgsFile(args_file)));
+    }
+
+    // Before 1.17: forge-<version>.jar, up to 1.12 forge-<version>-universal.jar
+    let prefix = format!("{}-{}", jar_prefix, loader_version);
+    let mut jars = Vec::new();
+    if fs::try_exists(server_dir).await? {
+        let mut entries = fs::read_dir(server_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(&prefix)
+                && file_name.ends_with(".jar")
+                && !file_name.contains("installer")
+            {
+                jars.push(file_name);
+            }
+        }
+    }
+    // The shortest name is the plain server jar, not a -shim or -sources jar
+    jars.sort_by_key(|name| name.len());
+    Ok(jars
+        .into_iter()
+        .next()
+        .map(|jar| ServerLaunchTarget::Jar(PathBuf::from(jar))))
+}
+
+async fn installed_loader_matches(server_dir: &Path, marker: &str) -> bool {
+    fs::read_to_string(server_dir.join(INSTALLED_LOADER_FILE))
+        .await
+        .is_ok_and(|installed| installed.trim() == marker)
+}
+
+/// Runs a loader installer jar in the server directory.
+async fn run_installer(java_path: &Path, server_dir: &Path, args: &[String]) -> Result<()> {
+    info!("Running server installer: {:?}", args);
+    let output = tokio::process::Command::new(java_path)
+        .args(args)
+        .current_dir(server_dir)
+        .output()
+        .await?;
+    if output.status.success() {
+        return Ok(());
+    }
+    // The Forge installer logs to stdout, so both streams go into the error
+    let combined = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = combined.lines().filter(|l| !l.trim().is_empty()).collect();
+    let tail = lines[lines.len().saturating_sub(INSTALLER_OUTPUT_TAIL)..].join("\n");
+    Err(AppError::Other(format!(
+        "Server installer failed ({}):\n{}",
+        output.status, tail
+    )))
+}
+
+/// `requested` if the loader has it for this Minecraft version, otherwise the newest one.
+fn pick_loader_version(
+    loader: ModLoader,
+    requested: Option<&str>,
+    available: &[String],
+) -> Result<String> {
+    let requested = requested
+        .map(|v| v.trim_end_matches(" (stable)").trim())
+        .filter(|v| !v.is_empty());
+    if let Some(requested) = requested {
+        if available.iter().any(|v| v == requested) {
+            return Ok(requested.to_string());
+        }
+        warn!(
+            "{:?} version '{}' not available, falling back to the latest",
+            loader, requested
+        );
+    }
+    available
+        .first()
+        .cloned()
+        .ok_or_else(|| AppError::VersionNotFound(format!("No {:?} server version found", loader)))
+}
+
+async fn download_vanilla_server(
+    piston_meta: &PistonMeta,
+    server_dir: &Path,
+    profile_id: Uuid,
+) -> Result<()> {
+    let server = piston_meta.downloads.server.as_ref().ok_or_else(|| {
+        AppError::VersionNotFound(format!(
+            "Minecraft {} has no dedicated server download",
+            piston_meta.id
+        ))
+    })?;
+    VerifiedDownload::new(&server.url, server_dir.join(SERVER_JAR))
+        .sha1(Some(&server.sha1))
+        .size(u64::try_from(server.size).ok())
+        .progress(Some(DownloadProgress::new(
+            EventType::DownloadingClient,
+            Some(profile_id),
+            format!("Downloading Minecraft server {}", piston_meta.id),
+        )))
+        .download()
+        .await?;
+    Ok(())
+}
+
+async fn resolve_java(profile: &Profile, piston_meta: &PistonMeta) -> Result<PathBuf> {
+    let required = piston_meta.java_version.major_version as u32;
+    if profile.settings.use_custom_java_path {
+        if let Some(custom_path) = &profile.settings.java_path {
+            let path = PathBuf::from(custom_path);
+            match java_detector::get_java_info(&path).await {
+                Ok(java) if java.major_version >= required => return Ok(path),
+                Ok(java) => warn!(
+                    "Custom Java {} is older than the required Java {}, downloading Java",
+                    java.major_version, required
+                ),
+                Err(e) => warn!("Custom Java path is not valid: {}, downloading Java", e),
+            }
+        }
+    }
+    let config = State::get().await?.config_manager.get_config().await;
+    JavaDownloadService::new()
+        .with_progress(DownloadProgress::new(
+            EventType::InstallingJava,
+            Some(profile.id),
+            format!("Downloading Java {}", required),
+        ))
+        .get_or_download_java(
+            required,
+            &config.java_distribution,
+            Some(&piston_meta.java_version.component),
+        )
+        .await
+}
+
+/// Installs the loader's server into the server directory. Returns how to start it and the
+/// loader version used.
+async fn install_loader(
+    profile: &Profile,
+    piston_meta: &PistonMeta,
+    java_path: &Path,
+    server_dir: &Path,
+) -> Result<(ServerLaunchTarget, Option<String>)> {
+    let minecraft_version = piston_meta.id.as_str();
+    let requested = profile.loader_version.as_deref();
+    match profile.loader {
+        ModLoader::Vanilla => {
+            download_vanilla_server(piston_meta, server_dir, profile.id).await?;
+            Ok((ServerLaunchTarget::Jar(PathBuf::from(SERVER_JAR)), None))
+        }
+        ModLoader::Fabric => {
+            download_vanilla_server(piston_meta, server_dir, profile.id).await?;
+            let fabric_api = FabricApi::new();
+            let loader_versions: Vec<String> = fabric_api
+                .get_loader_versions(minecraft_version)
+                .await?
+                .into_iter()
+                .map(|v| v.loader.version)
+                .collect();
+            let loader_version = match requested {
+                Some(_) => pick_loader_version(ModLoader::Fabric, requested, &loader_versions)?,
+                None => {
+                    fabric_api
+                        .get_latest_stable_version(minecraft_version)
+                        .await?
+                        .loader
+                        .version
+                }
+            };
+            let installer_version = fabric_api
+                .get_installer_versions()
+                .await?
+                .into_iter()
+                .find(|v| v.stable)
+                .ok_or_else(|| {
+                    AppError::FabricError("No stable Fabric installer found".to_string())
+                })?
+                .version;
+            // Same name the Fabric website uses for this jar
+            let launcher_jar = format!(
+                "fabric-server-mc.{}-loader.{}-launcher.{}.jar",
+                minecraft_version, loader_version, installer_version
+            );
+            let launcher_path = server_dir.join(&launcher_jar);
+            VerifiedDownload::new(
+                fabric_api.get_server_launcher_url(
+                    minecraft_version,
+                    &loader_version,
+                    &installer_version,
+                ),
+                &launcher_path,
+            )
+            .download()
+            .await?;
+            ensure_launcher_jar(&launcher_path).await?;
+            Ok((
+                ServerLaunchTarget::Jar(PathBuf::from(launcher_jar)),
+                Some(loader_version),
+            ))
+        }
+        ModLoader::Quilt => {
+            download_vanilla_server(piston_meta, server_dir, profile.id).await?;
+            let quilt_api = QuiltApi::new();
+            let loader_version = match requested {
+                Some(_) => {
+                    let loader_versions: Vec<String> = quilt_api
+                        .get_loader_versions(minecraft_version)
+                        .await?
+                        .into_iter()
+                        .map(|v| v.loader.version)
+                        .collect();
+                    pick_loader_version(ModLoader::Quilt, requested, &loader_versions)?
+                }
+                None => {
+                    quilt_api
+                        .get_latest_stable_version(minecraft_version)
+                        .await?
+                        .loader
+                        .version
+                }
+            };
+            let marker = format!("quilt-{}-{}", minecraft_version, loader_version);
+            let launcher_jar = PathBuf::from(QUILT_LAUNCHER_JAR);
+            if !installed_loader_matches(server_dir, &marker).await
+                || !fs::try_exists(server_dir.join(&launcher_jar)).await?
+            {
+                // Newest installer first
+                let installer = quilt_api
+                    .get_installer_versions()
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| AppError::QuiltError("No Quilt installer found".to_string()))?;
+                let installer_path = LAUNCHER_DIRECTORY
+                    .meta_dir()
+                    .join("quilt_installer")
+                    .join(format!("quilt-installer-{}.jar", installer.version));
+                VerifiedDownload::new(&installer.url, &installer_path)
+                    .download()
+                    .await?;
+                run_installer(
+                    java_path,
+                    server_dir,
+                    &[
+                        "-jar".to_string(),
+                        installer_path.to_string_lossy().to_string(),
+                        "install".to_string(),
+                        "server".to_string(),
+                        minecraft_version.to_string(),
+                        loader_version.clone(),
+                        format!("--install-dir={}", server_dir.to_string_lossy()),
+                    ],
+                )
+                .await?;
+                fs::write(server_dir.join(INSTALLED_LOADER_FILE), &marker).await?;
+            }
+            Ok((ServerLaunchTarget::Jar(launcher_jar), Some(loader_version)))
+        }
+        ModLoader::Forge | ModLoader::NeoForge => {
+            let loader = profile.loader;
+            let available = if loader == ModLoader::Forge {
+                ForgeApi::new()
+                    .get_all_versions()
+                    .await?
+                    .get_versions_for_minecraft(minecraft_version)
+            } else {
+                NeoForgeApi::new()
+                    .get_all_versions()
+                    .await?
+                    .get_versions_for_minecraft(minecraft_version)
+            };
+            let loader_version = pick_loader_version(loader, requested, &available)?;
+            let marker = format!("{}-{}", loader.as_str(), loader_version);
+            if installed_loader_matches(server_dir, &marker).await {
+                if let Some(target) =
+                    find_forge_launch_target(server_dir, loader, &loader_version).await?
+                {
+                    return Ok((target, Some(loader_version)));
+                }
+            }
+
+            // The installer also downloads the vanilla server and all libraries
+            let installer_path = if loader == ModLoader::Forge {
+                ForgeInstallerDownloadService::new()
+                    .download_installer(&loader_version)
+                    .await?
+            } else {
+                NeoForgeInstallerDownloadService::new()
+                    .download_installer(&loader_version)
+                    .await?
+            };
+            let event_type = if loader == ModLoader::Forge {
+                EventType::InstallingForge
+            } else {
+                EventType::InstallingNeoForge
+            };
+            let state = State::get().await?;
+            emit_progress_event(
+                &state,
+                event_type.clone(),
+                profile.id,
+                &format!("Installing {:?} server {}...", loader, loader_version),
+                0.0,
+                None,
+            )
+            .await?;
+            run_installer(
+                java_path,
+                server_dir,
+                &[
+                    "-jar".to_string(),
+                    installer_path.to_string_lossy().to_string(),
+                    "--installServer".to_string(),
+                    server_dir.to_string_lossy().to_string(),
+                ],
+            )
+            .await?;
+            emit_progress_event(
+                &state,
+                event_type,
+                profile.id,
+                &format!("{:?} server installed!", loader),
+                1.0,
+                None,
+            )
+            .await?;
+            fs::write(server_dir.join(INSTALLED_LOADER_FILE), &marker).await?;
+
+            let target = find_forge_launch_target(server_dir, loader, &loader_version)
+                .await?
+                .ok_or_else(|| {
+                    AppError::Other(format!(
+                        "The {:?} installer did not create a server to start",
+                        loader
+                    ))
+                })?;
+            Ok((target, Some(loader_version)))
+        }
+    }
+}
+
+/// The Fabric meta API publishes no checksum for the server launcher, so at least make sure a
+/// runnable jar arrived. A broken file is removed, otherwise it would be kept as already present.
+async fn ensure_launcher_jar(path: &Path) -> Result<()> {
+    let jar_path = path.to_path_buf();
+    let check = tokio::task::spawn_blocking(move || -> std::result::Result<(), String> {
+        let file = std::fs::File::open(&jar_path).map_err(|e| e.to_string())?;
+        let mut archive =
+            zip::ZipArchive::new(std::io::BufReader::new(file)).map_err(|e| e.to_string())?;
+        archive
+            .by_name("META-INF/MANIFEST.MF")
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Jar check task failed: {}", e)))?;
+
+    if let Err(e) = check {
+        warn!("Removing invalid Fabric server launcher {:?}: {}", path, e);
+        fs::remove_file(path).await?;
+        return Err(AppError::FabricError(format!(
+            "Downloaded Fabric server launcher is not a valid jar: {}",
+            e
+        )));
+    }
+    Ok(())
+}
+
+/// Puts the profile's mods (not the NoRisk pack, those are client mods) into the server.
+async fn sync_server_mods(state: &State, profile: &Profile) -> Result<PathBuf> {
+    let launcher_config = state.config_manager.get_config().await;
+    let mod_downloader = ModDownloadService::with_concurrency(launcher_config.concurrent_downloads);
+    mod_downloader.download_mods_to_cache(profile).await?;
+    let custom_mods = state.profile_manager.list_custom_mods(profile).await?;
+    let target_mods = mod_resolver::resolve_target_mods(
+        profile,
+        None,
+        Some(&custom_mods),
+        &profile.game_version,
+        profile.loader.as_str(),
+        &LAUNCHER_DIRECTORY.meta_dir().join("mod_cache"),
+    )
+    .await?;
+
+    // Client-only mods (e.g. Sodium) crash the dedicated server, they stay out of its folder
+    let mut server_mods = Vec::with_capacity(target_mods.len());
+    for target in target_mods {
+        let client_only = match mod_metadata::read_jar_metadata(&target.cache_path, false).await {
+            Ok(metadata) => {
+                let mods = metadata.loadable_mods(&profile.loader);
+                !mods.is_empty() && mods.iter().all(|m| m.environment == ModEnvironment::Client)
+            }
+            Err(e) => {
+                warn!("Can't read mod metadata of {}: {}", target.filename, e);
+                false
+            }
+        };
+        if client_only {
+            info!("Skipping client-only mod {} on the server", target.filename);
+        } else {
+            server_mods.push(target);
+        }
+    }
+
+    let mods_path = state.profile_manager.get_profile_mods_path(profile)?;
+    mod_downloader
+        .sync_mods_to_profile(&server_mods, &mods_path)
+        .await?;
+    info!(
+        "Synced {} mods into server profile '{}'",
+        server_mods.len(),
+        profile.name
+    );
+    Ok(mods_path)
+}
+
+/// Installs the dedicated server of a server profile and starts it under the
+/// `ProcessManager`. Returns the process id, its console is fed via `send_server_command`.
+pub async fn install_and_launch_server(profile: &Profile) -> Result<Uuid> {
+    if profile.kind != ProfileKind::Server {
+        return Err(AppError::InvalidOperation(format!(
+            "Profile '{}' is not a server profile",
+            profile.name
+        )));
+    }
+    let state = State::get().await?;
+    let server_dir = state
+        .profile_manager
+        .calculate_instance_path_for_profile(profile)?;
+    fs::create_dir_all(&server_dir).await?;
+    info!(
+        "Installing {:?} server {} for profile '{}' in {:?}",
+        profile.loader, profile.game_version, profile.name, server_dir
+    );
+
+    let api_service = MinecraftApiService::new();
+    let manifest = api_service.get_version_manifest().await?;
+    let version = manifest
+        .versions
+        .iter()
+        .find(|v| v.id == profile.game_version)
+        .ok_or_else(|| {
+            AppError::VersionNotFound(format!("Version {} not found", profile.game_version))
+        })?;
+    let piston_meta = api_service.get_piston_meta(&version.url).await?;
+
+    let java_path = resolve_java(profile, &piston_meta).await?;
+    let (target, loader_version) =
+        install_loader(profile, &piston_meta, &java_path, &server_dir).await?;
+
+    let mut command = std::process::Command::new(&java_path);
+    command.current_dir(&server_dir);
+    command.envs(&profile.settings.env_vars);
+    command.arg(format!("-Xms{}M", profile.settings.memory.min));
+    command.arg(format!("-Xmx{}M", profile.settings.memory.max));
+    if let Some(jvm_args) = &profile.settings.custom_jvm_args {
+        command.args(jvm_args.split_whitespace());
+    }
+    if profile.loader != ModLoader::Vanilla {
+        let mods_path = sync_server_mods(&state, profile).await?;
+        if profile.loader == ModLoader::Fabric {
+            // The mods are copied into a per-version folder below the server's own mods/
+            command.arg(format!(
+                "-Dfabric.modsFolder={}",
+                mods_path.to_string_lossy()
+            ));
+        }
+    }
+    command.args(target.java_args());
+    command.arg("nogui");
+    command.args(&profile.settings.extra_game_args);
+
+    emit_progress_event(
+        &state,
+        EventType::LaunchingMinecraft,
+        profile.id,
+        "Starting server...",
+        0.0,
+        None,
+    )
+    .await?;
+    let process_id = state
+        .process_manager
+        .start_process(
+            profile.id,
+            command,
+            None,
+            None,
+            Some(piston_meta.id.clone()),
+            Some(profile.loader.as_str().to_string()),
+            loader_version,
+            None,
+            Some(profile.name.clone()),
+            None,
+            ProfileKind::Server,
+        )
+        .await?;
+    emit_progress_event(
+        &state,
+        EventType::LaunchingMinecraft,
+        profile.id,
+        "Server started!",
+        1.0,
+        None,
+    )
+    .await?;
+    Ok(process_id)
+}
@@ -353,7 +353,7 @@ impl CapeManager {
 
 #[async_trait]
 impl PostInitializationHandler for CapeManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("CapeManager: on_state_ready called. Loading capes...");
         self.load_capes_internal().await?;
         info!("CapeManager: Successfully loaded capes in on_state_ready.");
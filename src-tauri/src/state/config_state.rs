@@ -1,6 +1,8 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::Result;
+use crate::minecraft::dto::JavaDistribution;
 use crate::state::post_init::PostInitializationHandler;
+use crate::utils::{mirror_utils, offline_utils};
 use async_trait::async_trait;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
@@ -13,12 +15,87 @@ use uuid::Uuid;
 const CONFIG_FILENAME: &str = "launcher_config.json";
 const CONFIG_CURRENT_VERSION: u32 = 1;
 
-/// Game initialization hooks
-#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+/// Game initialization hooks. Profiles can override them, see `ProfileHooks`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Hooks {
     pub pre_launch: Option<String>,
     pub wrapper: Option<String>,
     pub post_exit: Option<String>,
+    /// Kill the pre-launch hook after this many seconds (no limit if unset)
+    #[serde(default)]
+    pub pre_launch_timeout_secs: Option<u64>,
+    /// Abort the launch if the pre-launch hook fails or times out
+    #[serde(default = "default_abort_on_pre_launch_failure")]
+    pub abort_on_pre_launch_failure: bool,
+}
+
+impl Default for Hooks {
+    fn default() -> Self {
+        Self {
+            pre_launch: None,
+            wrapper: None,
+            post_exit: None,
+            pre_launch_timeout_secs: None,
+            abort_on_pre_launch_failure: default_abort_on_pre_launch_failure(),
+        }
+    }
+}
+
+/// Mirrors for one upstream. `upstream` is a URL prefix (e.g. `https://libraries.minecraft.net/`),
+/// every mirror is a prefix that replaces it. `file://` mirrors point to a local artifact cache.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MirrorRule {
+    pub upstream: String,
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+/// URL rewriting for Mojang, Maven and Modrinth endpoints (see `utils::mirror_utils`)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MirrorConfig {
+    #[serde(default)]
+    pub rules: Vec<MirrorRule>,
+    /// Try the original URL after all mirrors failed. Disable for air-gapped setups.
+    #[serde(default = "default_fallback_to_upstream")]
+    pub fallback_to_upstream: bool,
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            fallback_to_upstream: default_fallback_to_upstream(),
+        }
+    }
+}
+
+/// Per-launch files with the game's stdout/stderr (`<instance>/logs/launches`)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LaunchLogConfig {
+    /// A launch log continues in a new file after this size
+    #[serde(default = "default_launch_log_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+    /// Files per launch. The first one (with the startup output) is always kept,
+    /// the oldest of the others are deleted.
+    #[serde(default = "default_launch_log_max_files_per_launch")]
+    pub max_files_per_launch: usize,
+    /// Launch logs older than this are deleted
+    #[serde(default = "default_launch_log_retention_days")]
+    pub retention_days: u32,
+    /// Launch logs kept per profile
+    #[serde(default = "default_launch_log_max_launches")]
+    pub max_launches: usize,
+}
+
+impl Default for LaunchLogConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: default_launch_log_max_file_size_mb(),
+            max_files_per_launch: default_launch_log_max_files_per_launch(),
+            retention_days: default_launch_log_retention_days(),
+            max_launches: default_launch_log_max_launches(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +125,22 @@ pub struct LauncherConfig {
     pub hooks: Hooks,
     #[serde(default = "default_hide_on_process_start")]
     pub hide_on_process_start: bool,
+    #[serde(default)]
+    pub mirrors: MirrorConfig,
+    /// Launch from cached metadata only, without any network requests or token refreshes
+    #[serde(default)]
+    pub offline_mode: bool,
+    /// Refuse to launch when the mod check finds missing dependencies or incompatible mods
+    #[serde(default = "default_block_launch_on_mod_issues")]
+    pub block_launch_on_mod_issues: bool,
+    #[serde(default)]
+    pub launch_logs: LaunchLogConfig,
+    /// Java runtime downloaded for profiles without a custom Java path
+    #[serde(default = "default_java_distribution")]
+    pub java_distribution: JavaDistribution,
+    /// Key for the CurseForge API, needed to resolve the files of CurseForge modpacks
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
 }
 
 fn default_config_version() -> u32 {
@@ -78,6 +171,38 @@ fn default_hide_on_process_start() -> bool {
     false
 }
 
+fn default_fallback_to_upstream() -> bool {
+    true
+}
+
+fn default_block_launch_on_mod_issues() -> bool {
+    true
+}
+
+fn default_java_distribution() -> JavaDistribution {
+    JavaDistribution::Zulu
+}
+
+fn default_abort_on_pre_launch_failure() -> bool {
+    true
+}
+
+fn default_launch_log_max_file_size_mb() -> u64 {
+    10
+}
+
+fn default_launch_log_max_files_per_launch() -> usize {
+    5
+}
+
+fn default_launch_log_retention_days() -> u32 {
+    14
+}
+
+fn default_launch_log_max_launches() -> usize {
+    20
+}
+
 impl Default for LauncherConfig {
     fn default() -> Self {
         Self {
@@ -93,6 +218,12 @@ impl Default for LauncherConfig {
             last_played_profile: None,
             hooks: Hooks::default(),
             hide_on_process_start: default_hide_on_process_start(),
+            mirrors: MirrorConfig::default(),
+            offline_mode: false,
+            block_launch_on_mod_issues: default_block_launch_on_mod_issues(),
+            launch_logs: LaunchLogConfig::default(),
+            java_distribution: default_java_distribution(),
+            curseforge_api_key: None,
         }
     }
 }
@@ -137,6 +268,9 @@ impl ConfigManager {
                 info!("Successfully loaded launcher configuration");
                 debug!("Loaded config: {:?}", loaded_config);
 
+                mirror_utils::apply_mirror_config(&loaded_config.mirrors);
+                offline_utils::set_offline_mode(loaded_config.offline_mode);
+
                 // Update the stored config
                 let mut config = self.config.write().await;
                 *config = loaded_config;
@@ -202,6 +336,12 @@ impl ConfigManager {
                 && current.last_played_profile == new_config.last_played_profile
                 && current.hooks == new_config.hooks
                 && current.hide_on_process_start == new_config.hide_on_process_start
+                && current.mirrors == new_config.mirrors
+                && current.offline_mode == new_config.offline_mode
+                && current.block_launch_on_mod_issues == new_config.block_launch_on_mod_issues
+                && current.launch_logs == new_config.launch_logs
+                && current.java_distribution == new_config.java_distribution
+                && current.curseforge_api_key == new_config.curseforge_api_key
             {
                 debug!("No config changes detected, skipping save");
                 false
@@ -276,6 +416,45 @@ impl ConfigManager {
                         current.hide_on_process_start, new_config.hide_on_process_start
                     );
                 }
+                if current.mirrors != new_config.mirrors {
+                    info!(
+                        "Changing mirrors: {:?} -> {:?}",
+                        current.mirrors, new_config.mirrors
+                    );
+                    mirror_utils::apply_mirror_config(&new_config.mirrors);
+                }
+                if current.offline_mode != new_config.offline_mode {
+                    info!(
+                        "Changing offline mode: {} -> {}",
+                        current.offline_mode, new_config.offline_mode
+                    );
+                    offline_utils::set_offline_mode(new_config.offline_mode);
+                }
+                if current.block_launch_on_mod_issues != new_config.block_launch_on_mod_issues {
+                    info!(
+                        "Changing block launch on mod issues: {} -> {}",
+                        current.block_launch_on_mod_issues, new_config.block_launch_on_mod_issues
+                    );
+                }
+                if current.java_distribution != new_config.java_distribution {
+                    info!(
+                        "Changing Java distribution: {:?} -> {:?}",
+                        current.java_distribution, new_config.java_distribution
+                    );
+                }
+                if current.launch_logs != new_config.launch_logs {
+                    info!(
+                        "Changing launch logs: {:?} -> {:?}",
+                        current.launch_logs, new_config.launch_logs
+                    );
+                }
+                if current.curseforge_api_key != new_config.curseforge_api_key {
+                    // The key itself is not logged
+                    info!(
+                        "Changing CurseForge API key (set: {})",
+                        new_config.curseforge_api_key.is_some()
+                    );
+                }
 
                 // Update config while preserving version
                 *config = LauncherConfig {
@@ -291,6 +470,12 @@ impl ConfigManager {
                     last_played_profile: new_config.last_played_profile,
                     hooks: new_config.hooks,
                     hide_on_process_start: new_config.hide_on_process_start,
+                    mirrors: new_config.mirrors.clone(),
+                    offline_mode: new_config.offline_mode,
+                    block_launch_on_mod_issues: new_config.block_launch_on_mod_issues,
+                    launch_logs: new_config.launch_logs.clone(),
+                    java_distribution: new_config.java_distribution.clone(),
+                    curseforge_api_key: new_config.curseforge_api_key.clone(),
                 };
 
                 true
@@ -320,7 +505,7 @@ impl ConfigManager {
 
 #[async_trait]
 impl PostInitializationHandler for ConfigManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ConfigManager: on_state_ready called. Loading configuration...");
         self.load_config_internal().await?;
         info!("ConfigManager: Successfully loaded configuration in on_state_ready.");
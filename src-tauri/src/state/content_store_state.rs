@@ -0,0 +1,380 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::post_init::PostInitializationHandler;
+use crate::utils::hash_utils;
+use async_trait::async_trait;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+
+const STORE_DIR_NAME: &str = "store";
+const BLOBS_DIR_NAME: &str = "blobs";
+const INDEX_FILENAME: &str = "index.json";
+
+/// How a file from the store ended up at its target location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MaterializeMethod {
+    /// Same inode as the blob, no extra disk space
+    Hardlink,
+    /// Copy-on-write clone (btrfs, APFS, ReFS, ...) or a plain copy as last resort
+    Copy,
+}
+
+/// Persisted reference tracking: blob sha1 -> paths the blob was materialized to.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ContentStoreIndex {
+    #[serde(default)]
+    pub refs: HashMap<String, BTreeSet<PathBuf>>,
+}
+
+/// Result of a garbage collection run.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ContentStoreGcReport {
+    pub removed_blobs: usize,
+    pub freed_bytes: u64,
+    pub pruned_references: usize,
+}
+
+/// Size information about the store.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ContentStoreStats {
+    pub blob_count: usize,
+    pub total_bytes: u64,
+    pub reference_count: usize,
+}
+
+/// SHA1-keyed content store under `meta_dir()/store`.
+///
+/// Files (mod jars, NRC assets) are put into the store once and then hardlinked into
+/// the profiles instead of being copied. If hardlinking is not possible (different
+/// volume, unsupported filesystem) a reflink or plain copy is used.
+pub struct ContentStoreManager {
+    root: PathBuf,
+    index: Arc<RwLock<ContentStoreIndex>>,
+    save_lock: Mutex<()>,
+    // Shared by ingest/link/release, exclusive for GC, so GC never sees half copied blobs
+    // or blobs that were ingested but not linked yet
+    store_lock: RwLock<()>,
+}
+
+impl ContentStoreManager {
+    pub fn new(root: PathBuf) -> Result<Self> {
+        info!(
+            "ContentStoreManager: Initializing with root: {:?} (index loading deferred)",
+            root
+        );
+        Ok(Self {
+            root,
+            index: Arc::new(RwLock::new(ContentStoreIndex::default())),
+            save_lock: Mutex::new(()),
+            store_lock: RwLock::new(()),
+        })
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join(INDEX_FILENAME)
+    }
+
+    /// Path of the blob for a sha1 (sharded by the first two hex chars like the asset objects).
+    pub fn blob_path(&self, sha1: &str) -> PathBuf {
+        let shard = sha1.get(..2).unwrap_or("00");
+        self.root.join(BLOBS_DIR_NAME).join(shard).join(sha1)
+    }
+
+    async fn load_index(&self) -> Result<()> {
+        let path = self.index_path();
+        if !path.exists() {
+            info!("Content store index not found, starting with an empty index");
+            return Ok(());
+        }
+
+        let data = fs::read_to_string(&path).await?;
+        match serde_json::from_str::<ContentStoreIndex>(&data) {
+            Ok(loaded) => {
+                info!(
+                    "Loaded content store index with {} referenced blobs",
+                    loaded.refs.len()
+                );
+                *self.index.write().await = loaded;
+            }
+            Err(e) => {
+                // The blobs are still valid; a broken index only means GC has to be conservative
+                error!("Failed to parse content store index: {}", e);
+                warn!("Using an empty content store index");
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_index(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        fs::create_dir_all(&self.root).await?;
+        let data = {
+            let index = self.index.read().await;
+            serde_json::to_string(&*index)?
+        };
+        // Write to a temp file first so a crash never leaves a half written index
+        let tmp_path = self.root.join(format!("{}.tmp", INDEX_FILENAME));
+        fs::write(&tmp_path, data).await?;
+        fs::rename(&tmp_path, self.index_path()).await?;
+        debug!("Saved content store index");
+        Ok(())
+    }
+
+    /// Puts a file into the store (if not already present) and returns its sha1.
+    /// The blob is a hardlink to `source` so cached files are not stored twice, a copy only
+    /// if that fails (e.g. another drive). Sources (mod cache, assets) are only ever
+    /// replaced via rename, never written in place, so the blob keeps its content.
+    /// Callers hold `store_lock`, otherwise GC may delete the blob before it is linked.
+    async fn ingest(&self, source: &Path) -> Result<String> {
+        let sha1 = hash_utils::calculate_sha1(&source.to_path_buf()).await?;
+        let blob = self.blob_path(&sha1);
+
+        if !blob.exists() {
+            if let Some(parent) = blob.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            // Link/copy to a temp name and rename, concurrent ingests of the same file are fine
+            let tmp_blob = blob.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+            if let Err(e) = fs::hard_link(source, &tmp_blob).await {
+                debug!(
+                    "Hardlink {:?} -> {:?} failed ({}), falling back to reflink/copy",
+                    source, tmp_blob, e
+                );
+                copy_file(source, &tmp_blob).await?;
+            }
+            if let Err(e) = fs::rename(&tmp_blob, &blob).await {
+                let _ = fs::remove_file(&tmp_blob).await;
+                if !blob.exists() {
+                    return Err(AppError::Io(e));
+                }
+            }
+            debug!("Ingested {:?} into content store as {}", source, sha1);
+        }
+
+        Ok(sha1)
+    }
+
+    /// Places the blob `sha1` at `target`, replacing whatever is there.
+    /// Does not persist the index, see `link_files` for the batched variant.
+    async fn materialize_unsaved(&self, sha1: &str, target: &Path) -> Result<MaterializeMethod> {
+        let blob = self.blob_path(sha1);
+        if !blob.exists() {
+            return Err(AppError::FileNotFound(blob));
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        // Never write through an existing (possibly hardlinked) file
+        if fs::symlink_metadata(target).await.is_ok() {
+            fs::remove_file(target).await?;
+        }
+
+        let method = match fs::hard_link(&blob, target).await {
+            Ok(()) => MaterializeMethod::Hardlink,
+            Err(e) => {
+                debug!(
+                    "Hardlink {:?} -> {:?} failed ({}), falling back to reflink/copy",
+                    blob, target, e
+                );
+                copy_file(&blob, target).await?;
+                MaterializeMethod::Copy
+            }
+        };
+
+        self.index
+            .write()
+            .await
+            .refs
+            .entry(sha1.to_string())
+            .or_default()
+            .insert(target.to_path_buf());
+
+        Ok(method)
+    }
+
+    /// Ingests every source and materializes it at its target. Persists the index once.
+    pub async fn link_files(&self, files: &[(PathBuf, PathBuf)]) -> Result<Vec<MaterializeMethod>> {
+        let _store_guard = self.store_lock.read().await;
+        let mut methods = Vec::with_capacity(files.len());
+        for (source, target) in files {
+            let sha1 = self.ingest(source).await?;
+            methods.push(self.materialize_unsaved(&sha1, target).await?);
+        }
+        if !files.is_empty() {
+            self.save_index().await?;
+        }
+        Ok(methods)
+    }
+
+    /// Deletes the blob `sha1` if its content no longer matches its name (e.g. a hardlinked
+    /// profile file was written in place), so the next link ingests a good copy.
+    /// Returns whether a blob was removed.
+    pub async fn remove_corrupt_blob(&self, sha1: &str) -> Result<bool> {
+        let _store_guard = self.store_lock.write().await;
+        let blob = self.blob_path(sha1);
+        if !blob.exists() {
+            return Ok(false);
+        }
+        if hash_utils::calculate_sha1(&blob).await? == sha1 {
+            return Ok(false);
+        }
+        warn!("Removing corrupt content store blob {}", sha1);
+        fs::remove_file(&blob).await?;
+        Ok(true)
+    }
+
+    /// Forgets references to the given paths (e.g. after removing mods from a profile).
+    pub async fn release_paths(&self, paths: &[PathBuf]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let _store_guard = self.store_lock.read().await;
+        {
+            let mut index = self.index.write().await;
+            for refs in index.refs.values_mut() {
+                for path in paths {
+                    refs.remove(path);
+                }
+            }
+            index.refs.retain(|_, refs| !refs.is_empty());
+        }
+        self.save_index().await
+    }
+
+    /// Removes references whose target no longer exists (or was replaced by a file of a
+    /// different size) and deletes all blobs that are not referenced by any profile.
+    /// Waits for running ingests and links and blocks new ones until it is done.
+    pub async fn garbage_collect(&self) -> Result<ContentStoreGcReport> {
+        let _store_guard = self.store_lock.write().await;
+        info!(
+            "Running content store garbage collection in {:?}",
+            self.root
+        );
+        let mut report = ContentStoreGcReport::default();
+
+        // 1. Prune dead references
+        let snapshot = self.index.read().await.refs.clone();
+        let mut live_refs: HashMap<String, BTreeSet<PathBuf>> = HashMap::new();
+        for (sha1, paths) in snapshot {
+            let blob_len = match fs::metadata(self.blob_path(&sha1)).await {
+                Ok(meta) => meta.len(),
+                Err(_) => {
+                    report.pruned_references += paths.len();
+                    continue;
+                }
+            };
+            for path in paths {
+                match fs::metadata(&path).await {
+                    Ok(meta) if meta.len() == blob_len => {
+                        live_refs.entry(sha1.clone()).or_default().insert(path);
+                    }
+                    _ => report.pruned_references += 1,
+                }
+            }
+        }
+
+        // 2. Delete unreferenced blobs
+        let blobs_dir = self.root.join(BLOBS_DIR_NAME);
+        if blobs_dir.exists() {
+            let mut shards = fs::read_dir(&blobs_dir).await?;
+            while let Some(shard) = shards.next_entry().await? {
+                if !shard.path().is_dir() {
+                    continue;
+                }
+                let mut blobs = fs::read_dir(shard.path()).await?;
+                while let Some(blob) = blobs.next_entry().await? {
+                    let name = blob.file_name().to_string_lossy().to_string();
+                    if live_refs.contains_key(&name) {
+                        continue;
+                    }
+                    let size = blob.metadata().await.map(|m| m.len()).unwrap_or(0);
+                    match fs::remove_file(blob.path()).await {
+                        // Temp files left behind by an interrupted ingest are not blobs
+                        Ok(()) if !is_blob_name(&name) => report.freed_bytes += size,
+                        Ok(()) => {
+                            report.removed_blobs += 1;
+                            report.freed_bytes += size;
+                        }
+                        Err(e) => warn!("Failed to remove blob {:?}: {}", blob.path(), e),
+                    }
+                }
+            }
+        }
+
+        self.index.write().await.refs = live_refs;
+        self.save_index().await?;
+
+        info!(
+            "Content store GC finished: removed {} blobs ({} bytes), pruned {} references",
+            report.removed_blobs, report.freed_bytes, report.pruned_references
+        );
+        Ok(report)
+    }
+
+    /// Counts blobs, their total size and the tracked references.
+    pub async fn stats(&self) -> Result<ContentStoreStats> {
+        let mut stats = ContentStoreStats {
+            reference_count: self.index.read().await.refs.values().map(|r| r.len()).sum(),
+            ..Default::default()
+        };
+
+        let blobs_dir = self.root.join(BLOBS_DIR_NAME);
+        if !blobs_dir.exists() {
+            return Ok(stats);
+        }
+        let mut shards = fs::read_dir(&blobs_dir).await?;
+        while let Some(shard) = shards.next_entry().await? {
+            if !shard.path().is_dir() {
+                continue;
+            }
+            let mut blobs = fs::read_dir(shard.path()).await?;
+            while let Some(blob) = blobs.next_entry().await? {
+                if !is_blob_name(&blob.file_name().to_string_lossy()) {
+                    continue;
+                }
+                stats.blob_count += 1;
+                stats.total_bytes += blob.metadata().await.map(|m| m.len()).unwrap_or(0);
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Blobs are named by their sha1, everything else in a shard is a temp file.
+fn is_blob_name(name: &str) -> bool {
+    name.len() == 40 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Reflink (copy-on-write) if the filesystem supports it, plain copy otherwise.
+async fn copy_file(source: &Path, target: &Path) -> Result<()> {
+    let source = source.to_path_buf();
+    let target = target.to_path_buf();
+    tokio::task::spawn_blocking(move || reflink_copy::reflink_or_copy(&source, &target))
+        .await?
+        .map_err(|e| {
+            error!("Failed to copy file into/out of content store: {}", e);
+            AppError::Io(e)
+        })?;
+    Ok(())
+}
+
+#[async_trait]
+impl PostInitializationHandler for ContentStoreManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("ContentStoreManager: on_state_ready called. Loading index...");
+        self.load_index().await?;
+        Ok(())
+    }
+}
+
+/// Get the default root of the content store
+pub fn default_content_store_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(STORE_DIR_NAME)
+}
@@ -1,12 +1,16 @@
 use crate::error::Result;
+use crate::minecraft::crash_analyzer::CrashAnalysis;
 use crate::state::process_state::ProcessMetadata;
 use dashmap::DashMap;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::Emitter;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
+const EVENT_BROADCAST_CAPACITY: usize = 1024;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
@@ -38,9 +42,15 @@ pub enum EventType {
     Error,
     LaunchSuccessful,
     CrashReportContentAvailable,
+    VerifyingProfile,
+    BisectingMods,
+    BackingUpWorld,
+    RestoringWorld,
+    RenderingWorldMap,
+    ServerStatusChanged,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct EventPayload {
     pub event_id: Uuid,
     pub event_type: EventType,
@@ -58,6 +68,8 @@ pub struct MinecraftProcessExitedPayload {
     pub success: bool,
     pub process_metadata: Option<ProcessMetadata>,
     pub crash_report_content: Option<String>,
+    /// Only for crashes
+    pub crash_analysis: Option<CrashAnalysis>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -77,14 +89,17 @@ struct EventInfo {
 pub struct EventState {
     app: Option<Arc<tauri::AppHandle>>,
     active_events: DashMap<Uuid, EventInfo>,
+    local_sender: broadcast::Sender<EventPayload>,
 }
 
 impl EventState {
     pub fn new(app: Option<Arc<tauri::AppHandle>>) -> Self {
         info!("Initializing EventState...");
+        let (local_sender, _) = broadcast::channel(EVENT_BROADCAST_CAPACITY);
         let state = Self {
             app,
             active_events: DashMap::new(),
+            local_sender,
         };
         info!("Successfully initialized EventState.");
         state
@@ -103,6 +118,9 @@ impl EventState {
             );
         }
 
+        // Forward to in-process listeners (e.g. the CLI). Having no receivers is fine.
+        let _ = self.local_sender.send(payload.clone());
+
         // Emit the event to the frontend
         if let Some(app) = &self.app {
             app.emit("state_event", payload)
@@ -112,6 +130,12 @@ impl EventState {
         Ok(())
     }
 
+    /// Subscribes to all events emitted after this call.
+    /// Slow receivers may miss events (see `broadcast::error::RecvError::Lagged`).
+    pub fn subscribe(&self) -> broadcast::Receiver<EventPayload> {
+        self.local_sender.subscribe()
+    }
+
     /// Specific helper to emit a TriggerProfileUpdate event.
     pub async fn trigger_profile_update(&self, profile_id: Uuid) -> Result<()> {
         let payload = EventPayload {
@@ -0,0 +1,265 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::post_init::PostInitializationHandler;
+use crate::utils::mc_utils;
+use crate::utils::servers_dat_utils::{self, FavoriteSyncChanges, ServerSyncResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+const FAVORITE_SERVERS_FILENAME: &str = "favorite_servers.json";
+
+/// A server kept by the launcher that can be pushed into the server list of any profile.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FavoriteServer {
+    pub id: Uuid,
+    pub name: String,
+    pub address: String,
+    /// Addresses the server had before. Entries with one of them are updated on sync.
+    #[serde(default)]
+    pub previous_addresses: Vec<String>,
+    #[serde(default)]
+    pub icon_base64: Option<String>,
+    /// Push this server into every profile right before it launches
+    #[serde(default)]
+    pub auto_sync: bool,
+    #[serde(default = "chrono::Utc::now")]
+    pub added_at: DateTime<Utc>,
+}
+
+/// Changes to a favorite. Unset fields stay as they are, an empty `icon_base64` removes
+/// the icon.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FavoriteServerUpdate {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub icon_base64: Option<String>,
+    pub auto_sync: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct FavoriteServersDatabase {
+    #[serde(default)]
+    favorites: Vec<FavoriteServer>,
+}
+
+/// Launcher-wide favorite servers, stored in `favorite_servers.json`.
+pub struct FavoriteServersManager {
+    database: RwLock<FavoriteServersDatabase>,
+    path: PathBuf,
+    save_lock: Mutex<()>,
+}
+
+fn validate(name: &str, address: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Server name must not be empty".to_string(),
+        ));
+    }
+    mc_utils::parse_minecraft_address(address.trim()).map_err(AppError::InvalidInput)?;
+    Ok(())
+}
+
+impl FavoriteServersManager {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        info!(
+            "FavoriteServersManager: Initializing with path: {:?} (loading deferred)",
+            path
+        );
+        Ok(Self {
+            database: RwLock::new(FavoriteServersDatabase::default()),
+            path,
+            save_lock: Mutex::new(()),
+        })
+    }
+
+    async fn load(&self) -> Result<()> {
+        if !self.path.exists() {
+            info!("Favorite servers file not found, starting with an empty list");
+            return Ok(());
+        }
+        let data = fs::read_to_string(&self.path).await?;
+        match serde_json::from_str::<FavoriteServersDatabase>(&data) {
+            Ok(database) => {
+                info!("Loaded {} favorite servers", database.favorites.len());
+                *self.database.write().await = database;
+            }
+            Err(e) => {
+                // Keep the broken file around instead of overwriting it on the next save
+                error!("Failed to parse favorite servers file: {}", e);
+                let broken = self.path.with_extension("json.broken");
+                if let Err(e) = fs::rename(&self.path, &broken).await {
+                    warn!("Failed to move broken favorite servers file aside: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn save(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string_pretty(&*self.database.read().await)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, data).await?;
+        fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+
+    pub async fn list_favorites(&self) -> Vec<FavoriteServer> {
+        self.database.read().await.favorites.clone()
+    }
+
+    pub async fn add_favorite(
+        &self,
+        name: &str,
+        address: &str,
+        icon_base64: Option<String>,
+        auto_sync: bool,
+    ) -> Result<FavoriteServer> {
+        validate(name, address)?;
+        let favorite = FavoriteServer {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            address: address.trim().to_string(),
+            previous_addresses: Vec::new(),
+            icon_base64: icon_base64.filter(|icon| !icon.is_empty()),
+            auto_sync,
+            added_at: Utc::now(),
+        };
+        self.database.write().await.favorites.push(favorite.clone());
+        self.save().await?;
+        info!(
+            "Added favorite server '{}' ({})",
+            favorite.name, favorite.address
+        );
+        Ok(favorite)
+    }
+
+    /// Updates a favorite. A changed address is remembered so the next sync fixes profiles
+    /// that still have the old one.
+    pub async fn update_favorite(
+        &self,
+        id: Uuid,
+        update: FavoriteServerUpdate,
+    ) -> Result<FavoriteServer> {
+        let mut database = self.database.write().await;
+        let favorite = database
+            .favorites
+            .iter_mut()
+            .find(|f| f.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Favorite server {}", id)))?;
+        let name = update
+            .name
+            .as_deref()
+            .unwrap_or(&favorite.name)
+            .trim()
+            .to_string();
+        let address = update
+            .address
+            .as_deref()
+            .unwrap_or(&favorite.address)
+            .trim()
+            .to_string();
+        validate(&name, &address)?;
+
+        if address != favorite.address {
+            let old = std::mem::replace(&mut favorite.address, address);
+            info!(
+                "Favorite server '{}' moved from {} to {}",
+                favorite.name, old, favorite.address
+            );
+            if !favorite.previous_addresses.contains(&old) {
+                favorite.previous_addresses.push(old);
+            }
+            let current = favorite.address.clone();
+            favorite.previous_addresses.retain(|a| *a != current);
+        }
+        favorite.name = name;
+        if let Some(icon) = update.icon_base64 {
+            favorite.icon_base64 = Some(icon).filter(|icon| !icon.is_empty());
+        }
+        if let Some(auto_sync) = update.auto_sync {
+            favorite.auto_sync = auto_sync;
+        }
+        let favorite = favorite.clone();
+        drop(database);
+        self.save().await?;
+        Ok(favorite)
+    }
+
+    pub async fn remove_favorite(&self, id: Uuid) -> Result<bool> {
+        let mut database = self.database.write().await;
+        let len = database.favorites.len();
+        database.favorites.retain(|f| f.id != id);
+        let removed = database.favorites.len() < len;
+        drop(database);
+        if removed {
+            self.save().await?;
+        }
+        Ok(removed)
+    }
+
+    /// Pushes favorites (all if `favorite_ids` is `None`) into the server lists of the given
+    /// profiles (all user profiles if `None`).
+    pub async fn sync_to_profiles(
+        &self,
+        favorite_ids: Option<&[Uuid]>,
+        profile_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<ServerSyncResult>> {
+        let favorites: Vec<FavoriteServer> = self
+            .list_favorites()
+            .await
+            .into_iter()
+            .filter(|f| favorite_ids.is_none_or(|ids| ids.contains(&f.id)))
+            .collect();
+        let results =
+            servers_dat_utils::sync_favorites_to_profiles(&favorites, profile_ids).await?;
+        info!(
+            "Synced {} favorite servers into {} profiles",
+            favorites.len(),
+            results.len()
+        );
+        Ok(results)
+    }
+
+    /// Pushes the auto-sync favorites into the server list of an instance before launch.
+    pub async fn sync_before_launch(&self, instance_path: &Path) -> Result<FavoriteSyncChanges> {
+        let favorites: Vec<FavoriteServer> = self
+            .list_favorites()
+            .await
+            .into_iter()
+            .filter(|f| f.auto_sync)
+            .collect();
+        if favorites.is_empty() {
+            return Ok(FavoriteSyncChanges::default());
+        }
+        servers_dat_utils::modify_servers_dat(instance_path, |root| {
+            servers_dat_utils::apply_favorites(root, &favorites)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for FavoriteServersManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("FavoriteServersManager: on_state_ready called. Loading favorites...");
+        self.load().await?;
+        Ok(())
+    }
+}
+
+pub fn default_favorite_servers_path() -> PathBuf {
+    LAUNCHER_DIRECTORY
+        .root_dir()
+        .join(FAVORITE_SERVERS_FILENAME)
+}
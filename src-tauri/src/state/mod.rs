@@ -1,13 +1,18 @@
 pub mod config_state;
+pub mod content_store_state;
 pub mod discord_state;
 pub mod event_state;
+pub mod favorite_servers_state;
 pub mod norisk_packs_state;
 pub mod norisk_versions_state;
 pub mod post_init;
 pub mod process_state;
 pub mod profile_state;
+pub mod server_monitor_state;
+pub mod session_state;
 pub mod skin_state;
 pub mod cape_state;
 pub mod state_manager;
+pub mod world_backup_state;
 
 pub use state_manager::State;
@@ -166,7 +166,7 @@ impl NoriskPackManager {
 
 #[async_trait]
 impl PostInitializationHandler for NoriskPackManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("NoriskPackManager: on_state_ready called. Loading configuration...");
         let loaded_config = self.load_config_internal(&self.config_path.clone()).await?;
         let mut config_guard = self.config.write().await;
@@ -172,7 +172,7 @@ impl NoriskVersionManager {
 
 #[async_trait]
 impl PostInitializationHandler for NoriskVersionManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("NoriskVersionManager: on_state_ready called. Loading configuration...");
         // Load initial config. If loading fails critically (e.g., IO error other than NotFound), propagate the error.
         // If parsing fails or file not found, use default. This logic is now effectively in load_config_internal.
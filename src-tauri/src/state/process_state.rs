@@ -1,9 +1,16 @@
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::crash_analyzer;
 use crate::state::event_state::{
     EventPayload, EventState, EventType, MinecraftProcessExitedPayload,
 };
+use crate::state::profile_state::ProfileKind;
 use crate::state::{self, post_init::PostInitializationHandler, State};
+use crate::utils::hook_utils::{self, HookContext};
+use crate::utils::launch_log_utils::{self, LaunchLogWriter, Log4jLineParser};
+use crate::utils::process_stats_utils::{
+    self, ProcessSample, ProcessStats, ProcessStatsRecord, ProcessStatsWindow,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -12,12 +19,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::process::ExitStatus;
+use std::process::{ExitStatus, Stdio};
 use std::sync::Arc;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
 use tauri::Manager;
 use tokio::fs::{self as async_fs, File};
-use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncSeekExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStderr, ChildStdin, ChildStdout};
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -29,13 +37,20 @@ use notify::{
     event::CreateKind, Config as NotifyConfig, Event as NotifyEvent, EventKind as NotifyEventKind,
     RecommendedWatcher, RecursiveMode, Watcher,
 };
-use tokio::sync::mpsc; // Für den Channel
+use tokio::sync::{broadcast, mpsc}; // Für den Channel
 
 const PROCESSES_FILENAME: &str = "processes.json";
 const NOTIFY_EVENT_CHANNEL_BUFFER: usize = 100;
+const PROCESS_EXIT_CHANNEL_BUFFER: usize = 16;
+const OUTPUT_CHANNEL_BUFFER: usize = 1024;
+const PROCESS_STATS_HISTORY_FILENAME: &str = "process_stats_history.json";
+/// Finished processes kept in the stats history
+const PROCESS_STATS_HISTORY_LIMIT: usize = 100;
+/// How long a server gets to save its worlds after `stop` before it is killed
+const SERVER_STOP_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub struct ProcessManager {
-    app_handle: Arc<tauri::AppHandle>,
+    app_handle: Option<Arc<tauri::AppHandle>>,
     processes: Arc<RwLock<HashMap<Uuid, Process>>>,
     processes_file_path: PathBuf,
     save_lock: Mutex<()>,
@@ -44,6 +59,19 @@ pub struct ProcessManager {
     notify_event_tx: mpsc::Sender<CrashReportNotification>,
     active_watchers: Arc<RwLock<HashMap<Uuid, RecommendedWatcher>>>,
     crash_report_contents: Arc<DashMap<Uuid, String>>,
+    process_exit_tx: broadcast::Sender<ProcessExit>,
+    process_stats: Arc<DashMap<Uuid, ProcessStatsWindow>>,
+    stats_history_path: PathBuf,
+    stats_history_lock: Mutex<()>,
+    // Konsoleneingabe laufender Server
+    server_inputs: Arc<Mutex<HashMap<Uuid, ChildStdin>>>,
+}
+
+/// Sent when a game process exits, see `ProcessManager::subscribe_process_exits`.
+#[derive(Debug, Clone)]
+pub struct ProcessExit {
+    pub process_id: Uuid,
+    pub exit_code: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +89,15 @@ pub struct ProcessMetadata {
     pub norisk_pack: Option<String>,
     pub profile_name: Option<String>,
     pub post_exit_hook: Option<String>,
+    /// `-Xmx` the game was started with
+    #[serde(default)]
+    pub max_heap_mb: Option<u32>,
+    /// Launch log with the captured stdout/stderr, see `launch_log_utils`
+    #[serde(default)]
+    pub launch_log_id: Option<String>,
+    /// Game client or dedicated server
+    #[serde(default)]
+    pub kind: ProfileKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -76,6 +113,8 @@ pub enum ProcessState {
 struct Process {
     metadata: ProcessMetadata,
     last_log_position: Arc<Mutex<u64>>,
+    // stdout/stderr werden direkt gelesen, latest.log muss nicht getailt werden
+    output_captured: bool,
 }
 
 // Kapselt die Nachricht, die vom notify event handler zum ProcessManager geschickt wird
@@ -88,7 +127,7 @@ struct CrashReportNotification {
 impl ProcessManager {
     pub async fn new(
         processes_file_path: PathBuf,
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
     ) -> Result<Self> {
         log::info!(
             "Initializing ProcessManager with state file: {:?}",
@@ -99,6 +138,7 @@ impl ProcessManager {
         let launching_processes = Arc::new(DashMap::new());
         let active_watchers = Arc::new(RwLock::new(HashMap::new()));
         let crash_report_contents = Arc::new(DashMap::new());
+        let (process_exit_tx, _) = broadcast::channel(PROCESS_EXIT_CHANNEL_BUFFER);
 
         // Create the channel. The receiver part (rx) will be handled/stored or recreated
         // appropriately when its consuming task is spawned in on_state_ready.
@@ -106,7 +146,7 @@ impl ProcessManager {
             mpsc::channel::<CrashReportNotification>(NOTIFY_EVENT_CHANNEL_BUFFER);
 
         Ok(Self {
-            app_handle: Arc::clone(&app_handle),
+            app_handle,
             processes,
             processes_file_path,
             save_lock,
@@ -114,6 +154,13 @@ impl ProcessManager {
             notify_event_tx, // Store the sender
             active_watchers,
             crash_report_contents,
+            process_exit_tx,
+            process_stats: Arc::new(DashMap::new()),
+            stats_history_path: LAUNCHER_DIRECTORY
+                .meta_dir()
+                .join(PROCESS_STATS_HISTORY_FILENAME),
+            stats_history_lock: Mutex::new(()),
+            server_inputs: Arc::new(Mutex::new(HashMap::new())),
             // notify_event_rx: Mutex::new(Some(notify_event_rx_placeholder)), // Example of how to store rx
         })
     }
@@ -272,6 +319,8 @@ impl ProcessManager {
                         let process_entry = Process {
                             metadata: metadata.clone(), // metadata hier klonen
                             last_log_position: Arc::new(Mutex::new(0)),
+                            // Nach einem Launcher-Neustart gibt es keine Pipes mehr
+                            output_captured: false,
                         };
                         processes_map_writer.insert(process_entry.metadata.id, process_entry);
                         log::debug!(
@@ -521,6 +570,7 @@ impl ProcessManager {
         norisk_pack: Option<String>,
         profile_name: Option<String>,
         post_exit_hook: Option<String>,
+        kind: ProfileKind,
     ) -> Result<Uuid> {
         log::info!("Attempting to start process for profile {}", profile_id);
 
@@ -542,6 +592,17 @@ impl ProcessManager {
             command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
         }
 
+        let max_heap_mb =
+            process_stats_utils::parse_max_heap_mb(command.get_args().filter_map(|a| a.to_str()));
+
+        // Output before log4j is set up (JVM errors, missing main class, native crashes)
+        // never reaches latest.log, so read stdout/stderr ourselves.
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        // Server bekommen ihre Konsolenbefehle über stdin
+        if kind == ProfileKind::Server {
+            command.stdin(Stdio::piped());
+        }
+
         let mut tokio_command = tokio::process::Command::from(command);
         let mut child = tokio_command.spawn().map_err(|e| {
             log::error!(
@@ -556,6 +617,39 @@ impl ProcessManager {
             AppError::ProcessSpawnFailed("Could not get PID".to_string())
         })?;
         let process_id = Uuid::new_v4();
+        if let Some(stdin) = child.stdin.take() {
+            self.server_inputs.lock().await.insert(process_id, stdin);
+        }
+
+        let instance_path = match State::get().await {
+            Ok(global_state) => global_state
+                .profile_manager
+                .get_profile_instance_path(profile_id)
+                .await
+                .map_err(|e| {
+                    log::error!(
+                        "Could not get instance path for new process {}: {}",
+                        process_id,
+                        e
+                    );
+                    e
+                })
+                .ok(),
+            Err(_) => None,
+        };
+        let launch_log = match &instance_path {
+            Some(instance_path) => {
+                Self::create_launch_log(&launch_log_utils::launch_logs_dir(instance_path)).await
+            }
+            None => None,
+        };
+        let launch_log_id = launch_log.as_ref().map(|w| w.launch_id().to_string());
+        tokio::spawn(Self::capture_output(
+            process_id,
+            child.stdout.take(),
+            child.stderr.take(),
+            launch_log,
+        ));
 
         let metadata = ProcessMetadata {
             id: process_id,
@@ -571,6 +665,9 @@ impl ProcessManager {
             norisk_pack,
             profile_name: profile_name.clone(),
             post_exit_hook,
+            max_heap_mb,
+            launch_log_id,
+            kind,
         };
 
         log::info!(
@@ -582,6 +679,7 @@ impl ProcessManager {
         let process_entry = Process {
             metadata: metadata.clone(),
             last_log_position: Arc::new(Mutex::new(0)),
+            output_captured: true,
         };
 
         {
@@ -589,45 +687,49 @@ impl ProcessManager {
             processes_map.insert(process_id, process_entry);
         }
 
-        // Watcher für Crash-Reports starten
-        // Hier brauchen wir den globalen State für den ProfileManager
-        if let Ok(global_state) = State::get().await {
-            match global_state
-                .profile_manager
-                .get_profile_instance_path(profile_id)
-                .await
-            {
-                Ok(instance_path) => {
-                    let crash_reports_path = instance_path.join("crash-reports");
-                    if let Err(e) = self
-                        .start_crash_report_watcher(process_id, &crash_reports_path)
-                        .await
-                    {
-                        log::error!(
-                            "Failed to start crash report watcher for new process {}: {}",
-                            process_id,
-                            e
-                        );
-                        // Prozessstart nicht unbedingt abbrechen, aber loggen.
-                    }
-                }
-                Err(e) => {
+        // Spielsitzung für die Spielzeit-Statistik festhalten, Server zählen nicht als Spielzeit
+        match State::get().await {
+            Ok(_) if kind == ProfileKind::Server => {}
+            Ok(state) => {
+                if let Err(e) = state.session_manager.start_session(&metadata).await {
                     log::error!(
-                        "Could not get instance path for new process {} to start watcher: {}",
+                        "Failed to record game session for process {}: {}",
                         process_id,
                         e
                     );
                 }
             }
+            Err(e) => log::error!(
+                "Failed to get state to record game session for process {}: {}",
+                process_id,
+                e
+            ),
+        }
+
+        // Watcher für Crash-Reports starten
+        if let Some(instance_path) = &instance_path {
+            let crash_reports_path = instance_path.join("crash-reports");
+            if let Err(e) = self
+                .start_crash_report_watcher(process_id, &crash_reports_path)
+                .await
+            {
+                log::error!(
+                    "Failed to start crash report watcher for new process {}: {}",
+                    process_id,
+                    e
+                );
+                // Prozessstart nicht unbedingt abbrechen, aber loggen.
+            }
         } else {
             log::error!(
-                "Could not get global state to start watcher for new process {}.",
+                "No instance path to start crash report watcher for new process {}.",
                 process_id
             );
         }
 
         // --- BEGIN Discord State Update ---
         match State::get().await {
+            Ok(_) if kind == ProfileKind::Server => {}
             Ok(state) => {
                 log::debug!(
                     "Notifying Discord manager about game process {} start.",
@@ -653,9 +755,14 @@ impl ProcessManager {
         // Hide main window if configured to do so
         if let Ok(global_state) = State::get().await {
             let launcher_config = global_state.config_manager.get_config().await;
-            if launcher_config.hide_on_process_start {
+            // Die Konsole eines Servers soll erreichbar bleiben
+            if launcher_config.hide_on_process_start && kind == ProfileKind::Client {
                 log::info!("Hiding main window as configured (hide_on_process_start = true)");
-                if let Some(main_window) = self.app_handle.get_webview_window("main") {
+                if let Some(main_window) = self
+                    .app_handle
+                    .as_ref()
+                    .and_then(|app| app.get_webview_window("main"))
+                {
                     if let Err(e) = main_window.hide() {
                         log::error!("Failed to hide main window: {}", e);
                     } else {
@@ -674,6 +781,9 @@ impl ProcessManager {
         self.schedule_auto_open_log_window(process_id);
 
         let processes_arc_clone = Arc::clone(&self.processes);
+        let process_exit_tx = self.process_exit_tx.clone();
+        let process_stats_clone = Arc::clone(&self.process_stats);
+        let server_inputs_clone = Arc::clone(&self.server_inputs);
         // Klon für active_watchers und den Manager selbst (oder dessen relevante Teile)
         let active_watchers_clone_for_monitor = Arc::clone(&self.active_watchers);
         // Der Monitor-Task benötigt eine Möglichkeit, stop_crash_report_watcher aufzurufen.
@@ -688,8 +798,6 @@ impl ProcessManager {
         // Alternative: Der Monitor-Task entfernt nur aus processes, und periodic_process_check räumt Watcher auf.
         // ODER: Der Monitor Task sendet eine "ProcessEnded" Nachricht, auf die der PM reagiert.
 
-        let app_handle_clone_for_monitor = Arc::clone(&self.app_handle);
-
         tokio::spawn(async move {
             // State holen, um Zugriff auf den ProcessManager für das Stoppen des Watchers zu haben.
             // Das ist etwas umständlich. Besser wäre es, wenn stop_crash_report_watcher
@@ -725,8 +833,14 @@ impl ProcessManager {
                 }
             };
 
+            server_inputs_clone.lock().await.remove(&process_id);
             let exit_code: Option<i32> = exit_status.and_then(|s| s.code());
             let mut success: bool = exit_code == Some(0);
+            // Fehler heißt nur, dass gerade niemand zuhört
+            let _ = process_exit_tx.send(ProcessExit {
+                process_id,
+                exit_code,
+            });
 
             let was_intentionally_stopped = {
                 let processes_map = processes_arc_clone.read().await;
@@ -752,6 +866,24 @@ impl ProcessManager {
                     .map(|p_entry| p_entry.metadata.clone())
             };
 
+            // Stats abschließen, bevor der Sampler das Fenster als verwaist verwirft
+            let stats_record = match (
+                process_stats_clone.remove(&process_id),
+                &exiting_process_metadata_clone,
+            ) {
+                (Some((_, window)), Some(metadata)) => Some(ProcessStatsRecord {
+                    process_id,
+                    profile_id,
+                    profile_name: metadata.profile_name.clone(),
+                    minecraft_version: metadata.minecraft_version.clone(),
+                    started_at: metadata.start_time,
+                    ended_at: Utc::now(),
+                    exit_code,
+                    summary: window.summary(),
+                }),
+                _ => None,
+            };
+
             // Try to get crash content if it was processed very fast. No extensive polling here.
             let crash_content_for_payload: Option<String> = {
                 if let Ok(state) = &state_for_monitor_res {
@@ -766,6 +898,26 @@ impl ProcessManager {
                 }
             };
 
+            // Bekannte Crash-Ursachen direkt mitschicken, solange die Prozess-Infos noch da sind
+            let crash_analysis = if success {
+                None
+            } else {
+                let pid = exiting_process_metadata_clone.as_ref().map(|m| m.pid);
+                match crash_analyzer::analyze_profile(
+                    profile_id,
+                    crash_content_for_payload.clone(),
+                    pid,
+                )
+                .await
+                {
+                    Ok(analysis) => Some(analysis),
+                    Err(e) => {
+                        log::warn!("Crash analysis for process {} failed: {}", process_id, e);
+                        None
+                    }
+                }
+            };
+
             // Event an UI senden
             if let Ok(state) = &state_for_monitor_res {
                 // Re-access state for this block, or ensure it's still valid
@@ -776,6 +928,7 @@ impl ProcessManager {
                     success,
                     process_metadata: exiting_process_metadata_clone,
                     crash_report_content: crash_content_for_payload,
+                    crash_analysis,
                 };
                 let specific_payload_json = serde_json::to_string(&specific_payload)
                     .unwrap_or_else(|e| {
@@ -841,6 +994,22 @@ impl ProcessManager {
                     .process_manager
                     .stop_crash_report_watcher(process_id)
                     .await;
+                if let Some(record) = stats_record {
+                    if let Err(e) = state.process_manager.save_stats_record(record).await {
+                        log::error!(
+                            "Failed to save resource stats of process {}: {}",
+                            process_id,
+                            e
+                        );
+                    }
+                }
+                if let Err(e) = state
+                    .session_manager
+                    .finish_session(process_id, exit_code, !success, false)
+                    .await
+                {
+                    log::error!("Failed to record end of game session {}: {}", process_id, e);
+                }
                 if let Err(e) = state.process_manager.save_processes().await {
                     log::error!("Monitor task for process {} failed to save processes state after removal: {}. In-memory map updated, but persistence failed.", process_id, e);
                 } else {
@@ -850,6 +1019,14 @@ impl ProcessManager {
                     );
                 }
 
+                // Gespielte Welten sichern, bevor der Post-Exit-Hook läuft
+                if let Some(process) = &removed_process_metadata {
+                    state
+                        .world_backup_manager
+                        .backup_worlds_after_exit(profile_id, process.metadata.start_time)
+                        .await;
+                }
+
                 // Execute post-exit hook if process was successful
                 Self::execute_post_exit_hook_if_needed(
                     success,
@@ -871,6 +1048,23 @@ impl ProcessManager {
     pub async fn stop_process(&self, process_id: Uuid) -> Result<()> {
         log::info!("Attempting to stop process {}", process_id);
 
+        // Server über die Konsole herunterfahren, damit die Welten gespeichert werden
+        if self.server_inputs.lock().await.contains_key(&process_id) {
+            match self.stop_server_gracefully(process_id).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => log::warn!(
+                    "Server {} did not stop within {}s, killing it",
+                    process_id,
+                    SERVER_STOP_TIMEOUT.as_secs()
+                ),
+                Err(e) => log::warn!(
+                    "Failed to stop server {} via its console, killing it: {}",
+                    process_id,
+                    e
+                ),
+            }
+        }
+
         let mut kill_successful = false;
         let mut pid_for_error: u32 = 0;
 
@@ -933,6 +1127,55 @@ impl ProcessManager {
         }
     }
 
+    /// Sends `stop` to a server and waits until it exited. Returns `false` on timeout.
+    async fn stop_server_gracefully(&self, process_id: Uuid) -> Result<bool> {
+        let mut exits = self.subscribe_process_exits();
+        {
+            let mut processes_map = self.processes.write().await;
+            match processes_map.get_mut(&process_id) {
+                Some(process) => process.metadata.state = ProcessState::Stopping,
+                None => return Err(AppError::ProcessNotFound(process_id)),
+            }
+        }
+        self.send_server_command(process_id, "stop").await?;
+        let exited = tokio::time::timeout(SERVER_STOP_TIMEOUT, async {
+            loop {
+                match exits.recv().await {
+                    Ok(exit) if exit.process_id == process_id => return true,
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return false,
+                }
+            }
+        })
+        .await;
+        Ok(exited.unwrap_or(false))
+    }
+
+    /// Writes a command line into the console of a running server.
+    pub async fn send_server_command(&self, process_id: Uuid, command: &str) -> Result<()> {
+        let command = command.trim();
+        if command.is_empty() || command.contains(['\n', '\r']) {
+            return Err(AppError::InvalidInput(
+                "Server command must be a single non-empty line".to_string(),
+            ));
+        }
+        let mut server_inputs = self.server_inputs.lock().await;
+        let stdin = server_inputs.get_mut(&process_id).ok_or_else(|| {
+            AppError::NotFound(format!("Console of server process {}", process_id))
+        })?;
+        // Ohne führenden Slash, den kennt die Serverkonsole nicht
+        let line = format!("{}\n", command.strip_prefix('/').unwrap_or(command));
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.flush().await?;
+        log::info!("Sent command to server process {}: {}", process_id, command);
+        Ok(())
+    }
+
+    /// Receives the exit code of every game process that exits after subscribing.
+    pub fn subscribe_process_exits(&self) -> broadcast::Receiver<ProcessExit> {
+        self.process_exit_tx.subscribe()
+    }
+
     pub async fn get_process_metadata(&self, process_id: Uuid) -> Option<ProcessMetadata> {
         let processes_map = self.processes.read().await;
         processes_map
@@ -949,6 +1192,56 @@ impl ProcessManager {
             .collect()
     }
 
+    /// Recent resource samples and run totals of a running process.
+    pub fn get_process_stats(&self, process_id: Uuid) -> Option<ProcessStats> {
+        self.process_stats
+            .get(&process_id)
+            .map(|window| window.stats())
+    }
+
+    /// Resource summaries of finished processes, newest first.
+    pub async fn get_process_stats_history(
+        &self,
+        profile_id: Option<Uuid>,
+    ) -> Result<Vec<ProcessStatsRecord>> {
+        let mut records = self.load_stats_history().await?;
+        records.retain(|r| profile_id.is_none_or(|id| r.profile_id == id));
+        records.reverse();
+        Ok(records)
+    }
+
+    async fn load_stats_history(&self) -> Result<Vec<ProcessStatsRecord>> {
+        if !self.stats_history_path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = async_fs::read_to_string(&self.stats_history_path).await?;
+        Ok(serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable process stats history: {}", e);
+            Vec::new()
+        }))
+    }
+
+    async fn save_stats_record(&self, record: ProcessStatsRecord) -> Result<()> {
+        let _guard = self.stats_history_lock.lock().await;
+        if !record.summary.warnings.is_empty() {
+            log::warn!(
+                "Process {} had resource warnings: {:?}",
+                record.process_id,
+                record.summary.warnings
+            );
+        }
+        let mut records = self.load_stats_history().await?;
+        records.push(record);
+        let overflow = records.len().saturating_sub(PROCESS_STATS_HISTORY_LIMIT);
+        records.drain(..overflow);
+
+        if let Some(parent_dir) = self.stats_history_path.parent() {
+            async_fs::create_dir_all(parent_dir).await?;
+        }
+        async_fs::write(&self.stats_history_path, serde_json::to_string(&records)?).await?;
+        Ok(())
+    }
+
     pub async fn list_processes(&self) -> Vec<ProcessMetadata> {
         let processes_map = self.processes.read().await;
         processes_map
@@ -958,7 +1251,7 @@ impl ProcessManager {
     }
 
     async fn periodic_process_check(
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
         processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>,
         active_watchers_arc: Arc<RwLock<HashMap<Uuid, RecommendedWatcher>>>,
         notify_tx: mpsc::Sender<CrashReportNotification>,
@@ -1055,6 +1348,20 @@ impl ProcessManager {
                     processes_map_writer.remove(id);
                 }
                 drop(processes_map_writer);
+                // Exit code and cause are unknown for processes we did not start in this run
+                for id in &dead_process_ids_from_map {
+                    if let Err(e) = global_state
+                        .session_manager
+                        .finish_session(*id, None, false, true)
+                        .await
+                    {
+                        log::error!(
+                            "Periodic check: Failed to record end of game session {}: {}",
+                            id,
+                            e
+                        );
+                    }
+                }
                 // Speichere Änderungen an der Prozessliste
                 if let Err(e) = global_state.process_manager.save_processes().await {
                     log::error!(
@@ -1094,6 +1401,183 @@ impl ProcessManager {
         }
     }
 
+    /// Prunes old launch logs and starts a new one. Without a launch log the output is
+    /// still streamed to the UI.
+    async fn create_launch_log(dir: &Path) -> Option<LaunchLogWriter> {
+        let config = match State::get().await {
+            Ok(state) => state.config_manager.get_config().await.launch_logs,
+            Err(_) => Default::default(),
+        };
+        if let Err(e) = launch_log_utils::prune_launch_logs(dir, &config, Utc::now()).await {
+            log::warn!("Failed to prune launch logs in {:?}: {}", dir, e);
+        }
+        match LaunchLogWriter::create(dir, chrono::Local::now(), &config).await {
+            Ok(writer) => Some(writer),
+            Err(e) => {
+                log::error!("Failed to create launch log in {:?}: {}", dir, e);
+                None
+            }
+        }
+    }
+
+    /// Reads stdout and stderr of a game process until it exits, writes the lines to the
+    /// launch log and sends them as `MinecraftOutput` events.
+    async fn capture_output(
+        process_id: Uuid,
+        stdout: Option<ChildStdout>,
+        stderr: Option<ChildStderr>,
+        mut launch_log: Option<LaunchLogWriter>,
+    ) {
+        fn spawn_reader(reader: impl AsyncRead + Unpin + Send + 'static, tx: mpsc::Sender<String>) {
+            tokio::spawn(async move {
+                let mut reader = BufReader::new(reader);
+                let mut buffer = Vec::new();
+                // The client logs XML events to stdout, the launch log and UI get plain lines
+                let mut parser = Log4jLineParser::default();
+                loop {
+                    buffer.clear();
+                    match reader.read_until(b'\n', &mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => {
+                            let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+                            for line in parser.push_line(&line) {
+                                if tx.send(line).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                }
+                for line in parser.finish() {
+                    if tx.send(line).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        // Beide Streams landen in Ankunftsreihenfolge in einer Datei
+        let (tx, mut rx) = mpsc::channel::<String>(OUTPUT_CHANNEL_BUFFER);
+        if let Some(stdout) = stdout {
+            spawn_reader(stdout, tx.clone());
+        }
+        if let Some(stderr) = stderr {
+            spawn_reader(stderr, tx.clone());
+        }
+        drop(tx);
+
+        let state = State::get().await.ok();
+        while let Some(line) = rx.recv().await {
+            if let Some(writer) = launch_log.as_mut() {
+                if let Err(e) = writer.write_line(&line).await {
+                    log::error!(
+                        "Failed to write launch log of process {}: {}. Only streaming output from now on.",
+                        process_id,
+                        e
+                    );
+                    launch_log = None;
+                }
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(state) = &state {
+                let payload = EventPayload {
+                    event_id: Uuid::new_v4(),
+                    event_type: EventType::MinecraftOutput,
+                    target_id: Some(process_id),
+                    message: line,
+                    progress: None,
+                    error: None,
+                };
+                if let Err(e) = state.event_state.emit(payload).await {
+                    log::error!("Failed to emit output of process {}: {}", process_id, e);
+                }
+            }
+        }
+        if let Some(writer) = launch_log.as_mut() {
+            if let Err(e) = writer.flush().await {
+                log::warn!(
+                    "Failed to flush launch log of process {}: {}",
+                    process_id,
+                    e
+                );
+            }
+        }
+        log::debug!("Output capture finished for process {}", process_id);
+    }
+
+    /// Samples memory, CPU, threads and disk I/O of all running game processes.
+    async fn periodic_stats_sampler(
+        processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>,
+        process_stats: Arc<DashMap<Uuid, ProcessStatsWindow>>,
+    ) {
+        let mut interval = interval(Duration::from_secs(
+            process_stats_utils::SAMPLE_INTERVAL_SECS,
+        ));
+        // Das System-Objekt bleibt erhalten, CPU-Auslastung braucht zwei Messungen
+        let mut sys = System::new();
+        let refresh_kind = ProcessRefreshKind::nothing()
+            .with_memory()
+            .with_cpu()
+            .with_disk_usage()
+            .with_tasks();
+        log::info!("Starting periodic process stats sampler task.");
+
+        loop {
+            interval.tick().await;
+            let running: Vec<(Uuid, Pid, Option<u32>)> = {
+                let processes_map = processes_arc.read().await;
+                processes_map
+                    .values()
+                    .map(|p| {
+                        (
+                            p.metadata.id,
+                            Pid::from(p.metadata.pid as usize),
+                            p.metadata.max_heap_mb,
+                        )
+                    })
+                    .collect()
+            };
+            process_stats.retain(|id, _| running.iter().any(|(running_id, _, _)| running_id == id));
+            if running.is_empty() {
+                continue;
+            }
+
+            let pids: Vec<Pid> = running.iter().map(|(_, pid, _)| *pid).collect();
+            sys.refresh_memory();
+            sys.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, refresh_kind);
+
+            for (id, pid, max_heap_mb) in running {
+                let Some(process) = sys.process(pid) else {
+                    continue;
+                };
+                let disk_usage = process.disk_usage();
+                let sample = ProcessSample {
+                    timestamp: Utc::now(),
+                    memory_bytes: process.memory(),
+                    virtual_memory_bytes: process.virtual_memory(),
+                    cpu_percent: process.cpu_usage(),
+                    thread_count: process.tasks().map(|tasks| tasks.len() as u32),
+                    disk_read_bytes: disk_usage.read_bytes,
+                    disk_written_bytes: disk_usage.written_bytes,
+                    system_available_memory_bytes: sys.available_memory(),
+                    system_used_swap_bytes: sys.used_swap(),
+                };
+                process_stats
+                    .entry(id)
+                    .or_insert_with(|| {
+                        ProcessStatsWindow::new(
+                            process_stats_utils::SAMPLE_WINDOW_SIZE,
+                            max_heap_mb,
+                            sys.total_memory(),
+                        )
+                    })
+                    .push(sample);
+            }
+        }
+    }
+
     async fn periodic_log_tailer(processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>) {
         let mut interval = interval(Duration::from_secs(1)); // Log-Tailing kann weiterhin häufig sein
         log::info!("Starting periodic log tailing task (crash reports handled by notify).");
@@ -1124,8 +1608,9 @@ impl ProcessManager {
             let processes_to_tail: Vec<(Uuid, Uuid, Arc<Mutex<u64>>)> = processes_map_reader
                 .iter()
                 .filter(|(_, process_entry)| {
-                    process_entry.metadata.state == ProcessState::Running
-                        || process_entry.metadata.state == ProcessState::Starting
+                    !process_entry.output_captured
+                        && (process_entry.metadata.state == ProcessState::Running
+                            || process_entry.metadata.state == ProcessState::Starting)
                 })
                 .map(|(id, process_entry)| {
                     (
@@ -1344,6 +1829,27 @@ impl ProcessManager {
             .profile_manager
             .get_profile_instance_path(profile_id)
             .await?; // Access profile manager
+
+        // Captured processes have everything (including output before log4j) in their launch log
+        let output_captured = self
+            .processes
+            .read()
+            .await
+            .get(&process_id)
+            .is_some_and(|p| p.output_captured);
+        if let (true, Some(launch_log_id)) = (output_captured, &process_metadata.launch_log_id) {
+            let launch_logs_dir = launch_log_utils::launch_logs_dir(&instance_path);
+            match launch_log_utils::read_launch_log(&launch_logs_dir, launch_log_id).await {
+                Ok(content) => return Ok(content),
+                Err(e) => log::warn!(
+                    "Failed to read launch log {} of process {}, using latest.log: {}",
+                    launch_log_id,
+                    process_id,
+                    e
+                ),
+            }
+        }
+
         let log_path = instance_path.join("logs").join("latest.log");
         log::debug!("Constructed log path for full read: {:?}", log_path);
 
@@ -1478,40 +1984,19 @@ impl ProcessManager {
         };
 
         // Execute hook without waiting for completion (fire and forget)
-        let hook_command = hook.clone();
-        let game_dir_clone = game_directory.clone();
-        tokio::spawn(async move {
-            let mut cmd = hook_command.split(' ');
-            let command = match cmd.next() {
-                Some(c) => c,
-                None => return,
-            };
-
-            match std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
-                .current_dir(&game_dir_clone)
-                .spawn()
-            {
-                Ok(_) => {
-                    log::info!(
-                        "Post-exit hook spawned successfully for process {}",
-                        process_id
-                    );
-                }
-                Err(e) => {
-                    log::error!(
-                        "Failed to spawn post-exit hook for process {}: {}",
-                        process_id,
-                        e
-                    );
-                }
-            }
-        });
+        let hook_context = HookContext::new(game_directory, profile.id, &profile.name)
+            .with_pid(removed_process.metadata.pid);
+        log::info!("Spawning post-exit hook for process {}", process_id);
+        hook_utils::spawn_hook(hook, &hook_context, &profile.settings.env_vars);
     }
 
     // Private helper to schedule the auto-opening of the log window
     fn schedule_auto_open_log_window(&self, process_id: Uuid) {
-        let app_handle_clone = Arc::clone(&self.app_handle);
+        // Without a Tauri app (headless/CLI) there is no log window to open
+        let app_handle_clone = match &self.app_handle {
+            Some(app) => Arc::clone(app),
+            None => return,
+        };
 
         tokio::spawn(async move {
             match crate::state::State::get().await {
@@ -1556,7 +2041,7 @@ impl ProcessManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProcessManager {
-    async fn on_state_ready(&self, app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         log::info!("ProcessManager: on_state_ready called. Performing post-initialization tasks.");
 
         // For process_crash_report_events: The task requires the receive end of an mpsc channel.
@@ -1571,7 +2056,7 @@ impl PostInitializationHandler for ProcessManager {
 
         let manager_clone_periodic_check_processes = Arc::clone(&self.processes);
         let manager_clone_periodic_check_watchers = Arc::clone(&self.active_watchers);
-        let app_handle_for_periodic_check = Arc::clone(&app_handle);
+        let app_handle_for_periodic_check = app_handle.clone();
         let notify_tx_for_periodic_check = self.notify_event_tx.clone();
 
         tokio::spawn(Self::periodic_process_check(
@@ -1586,6 +2071,12 @@ impl PostInitializationHandler for ProcessManager {
         tokio::spawn(Self::periodic_log_tailer(tailer_processes_arc));
         log::info!("ProcessManager: Spawned periodic_log_tailer task.");
 
+        tokio::spawn(Self::periodic_stats_sampler(
+            Arc::clone(&self.processes),
+            Arc::clone(&self.process_stats),
+        ));
+        log::info!("ProcessManager: Spawned periodic_stats_sampler task.");
+
         log::info!("ProcessManager: Successfully completed on_state_ready.");
         Ok(())
     }
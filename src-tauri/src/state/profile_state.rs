@@ -4,7 +4,9 @@ use crate::error::Result;
 use crate::integrations::modrinth::{self, ModrinthDependencyType, ModrinthVersion};
 use crate::state::post_init::PostInitializationHandler;
 use crate::utils::hash_utils;
+use crate::utils::mod_metadata::{self, ModMetadata};
 use crate::utils::path_utils;
+use crate::utils::world_backup_utils::{BackupFormat, BackupRetention};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use futures::future::BoxFuture;
@@ -12,7 +14,7 @@ use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri_plugin_dialog::FilePath;
 use tokio::fs;
@@ -29,6 +31,8 @@ pub enum ModSource {
     Url {
         url: String,
         file_name: Option<String>,
+        #[serde(default)]
+        file_hash_sha1: Option<String>, // Optional SHA1 hash for verification
     },
     Maven {
         coordinates: String,
@@ -57,6 +61,384 @@ pub struct Mod {
     pub game_versions: Option<Vec<String>>, // Changed: List of supported Minecraft versions
     pub file_name_override: Option<String>, // Optional: To store the actual filename on disk if needed
     pub associated_loader: Option<ModLoader>, // Optional: Tracks the loader this mod was originally intended for
+    #[serde(default)]
+    pub installed_as_dependency: bool, // Nur als Abhängigkeit einer anderen Mod installiert, nicht vom Nutzer gewählt
+    #[serde(default)]
+    pub dependencies: Vec<Uuid>, // IDs der Mods in diesem Profil, die diese Mod benötigt
+}
+
+/// Records that `parent_id` requires `dependency_id`. Returns whether the graph changed.
+fn link_mod_dependency(profile: &mut Profile, parent_id: Uuid, dependency_id: Uuid) -> bool {
+    if parent_id == dependency_id {
+        return false;
+    }
+    match profile.mods.iter_mut().find(|m| m.id == parent_id) {
+        Some(parent) if !parent.dependencies.contains(&dependency_id) => {
+            parent.dependencies.push(dependency_id);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// A Modrinth version picked for an install, before anything is added to the profile.
+#[derive(Debug, Clone)]
+pub struct ModrinthInstallCandidate {
+    pub project_id: String,
+    pub version_id: String,
+    pub file_name: String,
+    pub download_url: String,
+    pub file_hash_sha1: Option<String>,
+    pub name: Option<String>,
+    pub version_number: Option<String>,
+    pub loaders: Option<Vec<String>>,
+    pub game_versions: Option<Vec<String>>,
+    /// Details of the version, `None` if they weren't fetched
+    pub version_info: Option<ModrinthVersion>,
+    /// Indices of the candidates of the same install that require this one
+    pub required_by: Vec<usize>,
+}
+
+impl ModrinthInstallCandidate {
+    /// Candidate for the primary file of a version, `None` if it has none.
+    pub fn from_version(version: &ModrinthVersion) -> Option<Self> {
+        let primary_file = version.files.iter().find(|f| f.primary)?;
+        Some(Self {
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            file_name: primary_file.filename.clone(),
+            download_url: primary_file.url.clone(),
+            file_hash_sha1: primary_file.hashes.sha1.clone(),
+            name: Some(version.name.clone()),
+            version_number: Some(version.version_number.clone()),
+            loaders: Some(version.loaders.clone()),
+            game_versions: Some(version.game_versions.clone()),
+            version_info: Some(version.clone()),
+            required_by: Vec::new(),
+        })
+    }
+
+    fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.project_id)
+    }
+
+    fn source(&self) -> ModSource {
+        ModSource::Modrinth {
+            project_id: self.project_id.clone(),
+            version_id: self.version_id.clone(),
+            file_name: self.file_name.clone(),
+            download_url: self.download_url.clone(),
+            file_hash_sha1: self.file_hash_sha1.clone(),
+        }
+    }
+}
+
+/// Checks Modrinth "incompatible" entries in both directions between the candidates of an
+/// install, the Modrinth mods in the profile and each other. The versions of the installed mods
+/// are looked up by sha1 in `installed_versions`. Another version of the same project is an
+/// update, not a conflict.
+pub fn check_modrinth_conflicts(
+    profile: &Profile,
+    candidates: &[ModrinthInstallCandidate],
+    installed_versions: &HashMap<String, ModrinthVersion>,
+) -> Result<()> {
+    // (project id, version id, name, version details)
+    let mut present: Vec<(&str, &str, String, Option<&ModrinthVersion>)> = Vec::new();
+    for installed in &profile.mods {
+        if let ModSource::Modrinth {
+            project_id,
+            version_id,
+            file_hash_sha1,
+            ..
+        } = &installed.source
+        {
+            let name = installed
+                .display_name
+                .clone()
+                .unwrap_or_else(|| project_id.clone());
+            let details = file_hash_sha1
+                .as_ref()
+                .and_then(|hash| installed_versions.get(hash));
+            present.push((project_id, version_id, name, details));
+        }
+    }
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        let others = candidates[..index].iter().map(|c| {
+            (
+                c.project_id.as_str(),
+                c.version_id.as_str(),
+                c.display_name().to_string(),
+                c.version_info.as_ref(),
+            )
+        });
+        let conflicts: Vec<String> = present
+            .iter()
+            .cloned()
+            .chain(others)
+            .filter(|(project_id, version_id, _, details)| {
+                *project_id != candidate.project_id
+                    && (candidate.version_info.as_ref().is_some_and(|v| {
+                        v.dependencies
+                            .iter()
+                            .any(|d| d.excludes(project_id, version_id))
+                    }) || details.is_some_and(|v| {
+                        v.dependencies
+                            .iter()
+                            .any(|d| d.excludes(&candidate.project_id, &candidate.version_id))
+                    }))
+            })
+            .map(|(_, _, name, _)| name)
+            .collect();
+
+        if !conflicts.is_empty() {
+            warn!(
+                "Refusing to add {} to profile {}: incompatible with {:?}",
+                candidate.display_name(),
+                profile.id,
+                conflicts
+            );
+            return Err(AppError::ModIncompatible {
+                profile_id: profile.id,
+                mod_name: candidate.display_name().to_string(),
+                conflicts: conflicts.join(", "),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Adds the candidates of an install to the profile and links them to the mods that require
+/// them. The first candidate is the mod that was picked, `required_by` the mod in the profile
+/// that needs it. Candidates already in the profile are kept. Returns whether anything changed.
+pub fn apply_modrinth_install(
+    profile: &mut Profile,
+    candidates: &[ModrinthInstallCandidate],
+    required_by: Option<Uuid>,
+) -> bool {
+    let mut changed = false;
+    let mut ids: Vec<Uuid> = Vec::with_capacity(candidates.len());
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        let source = candidate.source();
+        let as_dependency = index > 0 || required_by.is_some();
+        if let Some(existing) = profile.mods.iter_mut().find(|m| m.source == source) {
+            info!(
+                "Mod {} already exists in profile {}. Skipping addition.",
+                candidate.display_name(),
+                profile.id
+            );
+            // Explizit installiert -> nicht mehr als verwaiste Abhängigkeit entfernen
+            if !as_dependency && existing.installed_as_dependency {
+                existing.installed_as_dependency = false;
+                changed = true;
+            }
+            ids.push(existing.id);
+        } else {
+            info!(
+                "Adding mod {} ({}) to profile {}",
+                candidate.display_name(),
+                candidate
+                    .version_number
+                    .as_deref()
+                    .unwrap_or(&candidate.version_id),
+                profile.id
+            );
+            let new_mod = Mod {
+                id: Uuid::new_v4(),
+                source,
+                enabled: true,
+                display_name: candidate
+                    .name
+                    .clone()
+                    .or_else(|| Some(candidate.file_name.clone())),
+                version: candidate.version_number.clone(),
+                game_versions: candidate.game_versions.clone(),
+                file_name_override: None,
+                associated_loader: candidate
+                    .loaders
+                    .as_ref()
+                    .and_then(|l| l.first().and_then(|s| ModLoader::from_str(s).ok())),
+                installed_as_dependency: as_dependency,
+                dependencies: Vec::new(),
+            };
+            ids.push(new_mod.id);
+            profile.mods.push(new_mod);
+            changed = true;
+        }
+    }
+
+    if let (Some(parent_id), Some(root_id)) = (required_by, ids.first()) {
+        changed |= link_mod_dependency(profile, parent_id, *root_id);
+    }
+    for (index, candidate) in candidates.iter().enumerate() {
+        for parent in &candidate.required_by {
+            changed |= link_mod_dependency(profile, ids[*parent], ids[index]);
+        }
+    }
+    changed
+}
+
+/// Points a Modrinth mod of the profile at a new version after checking it against the other
+/// mods, and links the required dependencies already in the profile. Nothing is changed on an
+/// error. Returns the required dependencies (project id, version id) that still need installing.
+pub fn apply_modrinth_mod_update(
+    profile: &mut Profile,
+    mod_id: Uuid,
+    new_version_details: &ModrinthVersion,
+    installed_versions: &HashMap<String, ModrinthVersion>,
+) -> Result<Vec<(String, Option<String>)>> {
+    let profile_id = profile.id;
+    let mod_to_update = profile
+        .mods
+        .iter()
+        .find(|m| m.id == mod_id)
+        .ok_or_else(|| {
+            error!(
+                "Mod instance with ID {} not found in profile {} during update.",
+                mod_id, profile_id
+            );
+            AppError::ModNotFoundInProfile { profile_id, mod_id }
+        })?;
+
+    match &mod_to_update.source {
+        ModSource::Modrinth {
+            project_id: old_project_id,
+            ..
+        } if old_project_id != &new_version_details.project_id => {
+            error!(
+                "Project ID mismatch when updating mod {}! Expected '{}', got '{}'. Aborting update.",
+                mod_id, old_project_id, new_version_details.project_id
+            );
+            return Err(AppError::Other(format!(
+                "Project ID mismatch for mod {}",
+                mod_id
+            )));
+        }
+        ModSource::Modrinth { .. } => {}
+        _ => {
+            error!(
+                "Mod instance {} in profile {} is not a Modrinth mod.",
+                mod_id, profile_id
+            );
+            return Err(AppError::Other(format!(
+                "Mod {} is not a Modrinth mod",
+                mod_id
+            )));
+        }
+    }
+
+    let candidate =
+        ModrinthInstallCandidate::from_version(new_version_details).ok_or_else(|| {
+            error!(
+                "No primary file found for Modrinth version {} (ID: {})",
+                new_version_details.name, new_version_details.id
+            );
+            AppError::ModrinthPrimaryFileNotFound {
+                version_id: new_version_details.id.clone(),
+            }
+        })?;
+    check_modrinth_conflicts(
+        profile,
+        std::slice::from_ref(&candidate),
+        installed_versions,
+    )?;
+
+    info!(
+        "Checking required dependencies for new version {}...",
+        new_version_details.id
+    );
+    let mut missing_deps = Vec::new();
+    let mut present_deps = Vec::new();
+    for dependency in &new_version_details.dependencies {
+        if dependency.dependency_type != ModrinthDependencyType::Required {
+            continue;
+        }
+        let Some(dep_project_id) = &dependency.project_id else {
+            warn!(
+                "Required dependency found without a project_id in version {}: {:?}",
+                new_version_details.id, dependency
+            );
+            continue;
+        };
+        let existing = profile.mods.iter().find(|m| {
+            matches!(&m.source, ModSource::Modrinth { project_id, .. } if project_id == dep_project_id)
+        });
+        match existing {
+            Some(existing) => {
+                info!(
+                    "Required dependency project '{}' found in profile.",
+                    dep_project_id
+                );
+                present_deps.push(existing.id);
+            }
+            None => {
+                info!(
+                    "Required dependency project '{}' is missing in profile {}. Will install it automatically.",
+                    dep_project_id, profile_id
+                );
+                missing_deps.push((dep_project_id.clone(), dependency.version_id.clone()));
+            }
+        }
+    }
+
+    if let Some(mod_to_update) = profile.mods.iter_mut().find(|m| m.id == mod_id) {
+        info!(
+            "Updating mod instance {} from version {} to {} using file '{}'",
+            mod_id,
+            mod_to_update.version.as_deref().unwrap_or("?"),
+            new_version_details.version_number,
+            candidate.file_name
+        );
+        mod_to_update.source = candidate.source();
+        mod_to_update.version = candidate.version_number.clone();
+        mod_to_update.game_versions = candidate.game_versions.clone();
+        mod_to_update.associated_loader = new_version_details
+            .loaders
+            .first()
+            .and_then(|s| ModLoader::from_str(s).ok());
+    }
+    for dependency_id in present_deps {
+        link_mod_dependency(profile, mod_id, dependency_id);
+    }
+    info!("Mod instance {} updated successfully in memory.", mod_id);
+    Ok(missing_deps)
+}
+
+/// Mods that were pulled in as dependencies of `removed` (directly or transitively) and that
+/// no remaining, explicitly installed mod needs anymore. Cycles between dependencies are fine.
+pub fn find_orphaned_dependencies(mods: &[Mod], removed: &[Uuid]) -> Vec<Uuid> {
+    let reachable = |start: Vec<Uuid>, skip: &[Uuid]| {
+        let mut seen = HashSet::new();
+        let mut queue = start;
+        while let Some(id) = queue.pop() {
+            if skip.contains(&id) || !seen.insert(id) {
+                continue;
+            }
+            if let Some(m) = mods.iter().find(|m| m.id == id) {
+                queue.extend(m.dependencies.iter().copied());
+            }
+        }
+        seen
+    };
+
+    let roots = mods
+        .iter()
+        .filter(|m| !m.installed_as_dependency && !removed.contains(&m.id))
+        .map(|m| m.id)
+        .collect();
+    let still_needed = reachable(roots, removed);
+    let pulled_in = reachable(removed.to_vec(), &[]);
+
+    mods.iter()
+        .filter(|m| {
+            m.installed_as_dependency
+                && !removed.contains(&m.id)
+                && pulled_in.contains(&m.id)
+                && !still_needed.contains(&m.id)
+        })
+        .map(|m| m.id)
+        .collect()
 }
 
 // New struct to uniquely identify a Norisk Pack mod within a specific context
@@ -131,6 +513,12 @@ pub struct Profile {
     #[serde(default)]
     pub background: Option<ProfileBanner>,
     pub norisk_information: Option<NoriskInformation>,
+    /// Client (default) or dedicated server, see `server_installer`
+    #[serde(default)]
+    pub kind: ProfileKind,
+    /// Server profiles only: the client profile "join this server" launches
+    #[serde(default)]
+    pub linked_client_profile_id: Option<Uuid>,
 }
 
 fn default_true() -> bool {
@@ -147,6 +535,15 @@ pub struct NoriskInformation {
     pub copy_initial_mc_data: bool,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileKind {
+    #[default]
+    Client,
+    /// Runs a dedicated Minecraft server in the profile directory
+    Server,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum ModLoader {
@@ -194,6 +591,43 @@ pub struct ProfileSettings {
     pub extra_game_args: Vec<String>, // Zusätzliche Argumente für das Spiel
     #[serde(default)] // Für Abwärtskompatibilität
     pub custom_jvm_args: Option<String>, // Zusätzliche JVM-Argumente als String
+    #[serde(default)]
+    pub hooks: ProfileHooks, // Überschreibt die globalen Hooks
+    #[serde(default)]
+    pub env_vars: HashMap<String, String>, // Zusätzliche Umgebungsvariablen für Spiel und Hooks
+    #[serde(default)]
+    pub world_backups: WorldBackupSettings, // Automatische Welt-Backups
+}
+
+/// Automatic world backups of a profile, see `WorldBackupManager`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorldBackupSettings {
+    /// Back up the worlds played in a session when the game exits
+    #[serde(default = "default_true")]
+    pub backup_on_exit: bool,
+    /// Back up the worlds of a running game every N minutes
+    #[serde(default)]
+    pub interval_minutes: Option<u32>,
+    #[serde(default)]
+    pub format: BackupFormat,
+    #[serde(default)]
+    pub retention: BackupRetention,
+}
+
+/// Per-profile hooks. Set fields override the global `Hooks` of the launcher config,
+/// an empty string disables the global hook for this profile.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProfileHooks {
+    #[serde(default)]
+    pub pre_launch: Option<String>,
+    #[serde(default)]
+    pub wrapper: Option<String>,
+    #[serde(default)]
+    pub post_exit: Option<String>,
+    #[serde(default)]
+    pub pre_launch_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub abort_on_pre_launch_failure: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -228,9 +662,10 @@ impl Default for ProfileState {
 
 #[derive(Serialize, Clone, Debug)]
 pub struct CustomModInfo {
-    pub filename: String, // Base filename (e.g., OptiFine.jar)
-    pub is_enabled: bool, // True if the file doesn't end with .disabled
-    pub path: PathBuf,    // Full path to the file in custom_mods directory
+    pub filename: String,              // Base filename (e.g., OptiFine.jar)
+    pub is_enabled: bool,              // True if the file doesn't end with .disabled
+    pub path: PathBuf,                 // Full path to the file in custom_mods directory
+    pub metadata: Option<ModMetadata>, // Aus der Jar gelesen (fabric.mod.json, mods.toml, ...)
 }
 
 // Profile Manager
@@ -489,278 +924,238 @@ impl ProfileManager {
         }
     }
 
-    // Add a mod specifically sourced from Modrinth - Internal function with dependency logic
-    // Use BoxFuture for recursion
-    fn add_modrinth_mod_internal<'a>(
+    // Resolves the dependency tree of the candidate at `index` into `candidates` without
+    // touching the profile. Use BoxFuture for recursion
+    fn resolve_modrinth_install<'a>(
         &'a self,
-        profile_id: Uuid,
-        project_id: String,
-        version_id: String,
-        file_name: String,
-        download_url: String,
-        file_hash_sha1: Option<String>,
-        // Optional details for better Mod struct population
-        mod_name: Option<String>,
-        version_number: Option<String>,
-        // Loaders and game versions associated with *this specific version* being added
-        loaders: Option<Vec<String>>,
-        game_versions: Option<Vec<String>>,
+        profile: &'a Profile,
+        index: usize,
         // Flag to control dependency fetching
         add_dependencies: bool,
-        // Internal parameter to prevent infinite loops
-        visited_mods: HashSet<(String, String)>,
-    ) -> BoxFuture<'a, Result<()>> {
-        // Return type is BoxFuture
+        // Version details are only needed for the dependencies and the incompatibility check
+        fetch_details: bool,
+        candidates: &'a mut Vec<ModrinthInstallCandidate>,
+    ) -> BoxFuture<'a, ()> {
         Box::pin(async move {
-            let display_name_log = mod_name.as_deref().unwrap_or(&project_id);
-            let version_log = version_number.as_deref().unwrap_or(&version_id);
+            let display_name_log = candidates[index].display_name().to_string();
+            let version_log = candidates[index]
+                .version_number
+                .clone()
+                .unwrap_or_else(|| candidates[index].version_id.clone());
             info!(
                 "Processing Modrinth mod {} (Version {}) for profile {}. Add dependencies: {}",
-                display_name_log, version_log, profile_id, add_dependencies
+                display_name_log, version_log, profile.id, add_dependencies
             );
 
-            let mod_key = (project_id.clone(), version_id.clone());
-            if visited_mods.contains(&mod_key) {
-                info!(
-                    "Skipping already processed mod/dependency: {} ({})",
-                    display_name_log, version_log
-                );
-                return Ok(());
-            }
-            let mut visited_mods_clone = visited_mods.clone();
-            visited_mods_clone.insert(mod_key);
-
-            let source = ModSource::Modrinth {
-                project_id: project_id.clone(),
-                version_id: version_id.clone(),
-                file_name: file_name.clone(),
-                download_url: download_url.clone(),
-                file_hash_sha1: file_hash_sha1.clone(),
-            };
-
-            let mut needs_save = false;
-            {
-                let mut profiles = self.profiles.write().await;
-                if let Some(profile) = profiles.get_mut(&profile_id) {
-                    if !profile.mods.iter().any(|m| m.source == source) {
-                        info!(
-                            "Adding mod {} ({}) to profile {}",
-                            display_name_log, version_log, profile_id
-                        );
-
-                        let new_mod = Mod {
-                            id: Uuid::new_v4(),
-                            source: source.clone(),
-                            enabled: true,
-                            display_name: mod_name.clone().or_else(|| Some(file_name.clone())),
-                            version: version_number.clone(),
-                            game_versions: game_versions.clone(),
-                            file_name_override: None,
-                            associated_loader: loaders
-                                .clone()
-                                .and_then(|l| l.first().and_then(|s| ModLoader::from_str(s).ok())),
-                        };
-                        profile.mods.push(new_mod);
-                        needs_save = true;
-                    } else {
-                        info!(
-                            "Mod {} ({}) already exists in profile {}. Skipping addition.",
-                            display_name_log, version_log, profile_id
+            if candidates[index].version_info.is_none() && (add_dependencies || fetch_details) {
+                match modrinth::get_version_details(candidates[index].version_id.clone()).await {
+                    Ok(details) => candidates[index].version_info = Some(details),
+                    Err(e) => {
+                        warn!(
+                            "Failed to fetch version details for {} ({}): {}. Skipping incompatibility and dependency checks.",
+                            display_name_log, version_log, e
                         );
                     }
-                } else {
-                    return Err(AppError::ProfileNotFound(profile_id));
                 }
             }
 
-            if needs_save {
-                self.save_profiles().await?;
+            if !add_dependencies {
                 info!(
-                    "Profile saved after adding mod {} ({})",
+                    "Skipping dependency check for {} ({}) as requested.",
                     display_name_log, version_log
                 );
+                return;
             }
+            let Some(version_info) = candidates[index].version_info.clone() else {
+                warn!(
+                    "Could not resolve dependencies for version ID '{}' of project '{}' without version details.",
+                    candidates[index].version_id, candidates[index].project_id
+                );
+                return;
+            };
 
-            if add_dependencies {
+            info!(
+                "Found {} dependencies for {} ({})",
+                version_info.dependencies.len(),
+                display_name_log,
+                version_log
+            );
+            let profile_loader_str = profile.loader.as_str().to_string();
+            let profile_game_version = profile.game_version.clone();
+
+            for dependency in version_info.dependencies.iter() {
+                if dependency.dependency_type != ModrinthDependencyType::Required {
+                    // Optional/Incompatible/Embedded dependencies are ignored for now
+                    continue;
+                }
                 info!(
-                    "Fetching dependencies for {} ({})",
-                    display_name_log, version_log
+                    "Processing required dependency: Project={:?}, Version={:?}",
+                    dependency.project_id, dependency.version_id
                 );
 
-                let profile_details = self.get_profile(profile_id).await?;
-                let profile_loader_str = profile_details.loader.as_str().to_string();
-                let profile_game_version = profile_details.game_version.clone();
-
-                match modrinth::get_mod_versions(project_id.clone(), None, None).await {
-                    Ok(versions) => {
-                        if let Some(version_info) =
-                            versions.into_iter().find(|v| v.id == version_id)
-                        {
-                            info!(
-                                "Found {} dependencies for {} ({})",
-                                version_info.dependencies.len(),
-                                display_name_log,
-                                version_log
+                let selected_dep_version = if let Some(dep_project_id) = &dependency.project_id {
+                    info!(
+                        "Attempting to find compatible version for dependency project '{}'",
+                        dep_project_id
+                    );
+                    // Fetch dependency versions compatible with the profile's loader, but *without* filtering by game version yet.
+                    // Game version filtering will happen below based on the *parent mod's* requirements.
+                    let dep_versions = match modrinth::get_mod_versions(
+                        dep_project_id.clone(),
+                        Some(vec![profile_loader_str.clone()]),
+                        None,
+                    )
+                    .await
+                    {
+                        Ok(dep_versions) => dep_versions,
+                        Err(e) => {
+                            error!(
+                                "Failed to fetch versions for dependency project '{}': {}",
+                                dep_project_id, e
                             );
+                            continue;
+                        }
+                    };
 
-                            for dependency in version_info.dependencies {
-                                if dependency.dependency_type == ModrinthDependencyType::Required {
-                                    info!("Processing required dependency: Project={:?}, Version={:?}", dependency.project_id, dependency.version_id);
-
-                                    if let Some(dep_project_id) = dependency.project_id {
-                                        info!("Attempting to find compatible version for dependency project '{}'", dep_project_id);
-
-                                        let target_version_id = dependency.version_id;
-
-                                        // Fetch dependency versions compatible with the profile's loader, but *without* filtering by game version yet.
-                                        // Game version filtering will happen below based on the *parent mod's* requirements.
-                                        match modrinth::get_mod_versions(
-                                            dep_project_id.clone(), 
-                                            Some(vec![profile_loader_str.clone()]), 
-                                            None // <-- Removed game_version filter here
-                                        ).await {
-                                            Ok(dep_versions) => {
-                                                let mut best_dep_version: Option<&ModrinthVersion> = None;
-
-                                                // If a specific dependency version was requested, try to find that first.
-                                                if let Some(tv_id) = &target_version_id { // Borrow tv_id
-                                                    best_dep_version = dep_versions.iter().find(|v| &v.id == tv_id);
-                                                    if best_dep_version.is_none() {
-                                                        warn!("Requested dependency version '{}' not found or not compatible with profile's loader for project '{}'. Trying to find best alternative.", tv_id, dep_project_id);
-                                                    }
-                                                }
-                                                
-                                                // If no specific version requested or found, find the best compatible version.
-                                                if best_dep_version.is_none() {
-                                                    
-                                                    // Determine the target game versions for filtering: use the PARENT mod's versions (from version_info) if available (non-empty), else fallback to profile's.
-                                                    let target_game_versions_for_dep: Vec<String> = if !version_info.game_versions.is_empty() {
-                                                        // Use the parent mod's game versions if the list is not empty
-                                                        version_info.game_versions.clone()
-                                                    } else {
-                                                         // Otherwise, fallback to the profile's game version
-                                                         warn!("Parent mod {} ({}) did not provide specific game versions in its fetched data (version_info) or list was empty. Falling back to profile game version '{}' for dependency '{}' lookup.", display_name_log, version_log, profile_game_version, dep_project_id);
-                                                        vec![profile_game_version.clone()]
-                                                    };
-                                                    
-                                                     // Attempt 1: Find the latest version supporting any of the *target* game versions.
-                                                     best_dep_version = dep_versions.iter()
-                                                         .filter(|dep_v| {
-                                                            // Check if the dependency version supports AT LEAST ONE of the target game versions
-                                                            target_game_versions_for_dep.iter().any(|target_gv| dep_v.game_versions.contains(target_gv))
-                                                         })
-                                                         .max_by_key(|v| &v.date_published);
-
-                                                     // Attempt 2: If no match for target game versions, fall back to the overall latest compatible version (loader match only).
-                                                     if best_dep_version.is_none() {
-                                                         warn!("Could not find dependency version matching target game versions {:?} for project '{}'. Falling back to latest version compatible with loader '{}'.", target_game_versions_for_dep, dep_project_id, profile_loader_str);
-                                                         best_dep_version = dep_versions.iter()
-                                                             .max_by_key(|v| &v.date_published);
-                                                     }
-                                                 }
-
-                                                 if let Some(selected_dep_version) = best_dep_version {
-                                                     info!("Selected version '{}' ({}) for dependency '{}'", selected_dep_version.name, selected_dep_version.id, dep_project_id);
-                                                     
-                                                     if let Some(primary_file) = selected_dep_version.files.iter().find(|f| f.primary) {
-                                                         match self.add_modrinth_mod_internal(
-                                                             profile_id,
-                                                             selected_dep_version.project_id.clone(),
-                                                             selected_dep_version.id.clone(),
-                                                             primary_file.filename.clone(),
-                                                             primary_file.url.clone(),
-                                                             primary_file.hashes.sha1.clone(),
-                                                             Some(selected_dep_version.name.clone()),
-                                                             Some(selected_dep_version.version_number.clone()),
-                                                             Some(selected_dep_version.loaders.clone()),
-                                                             Some(selected_dep_version.game_versions.clone()),
-                                                             true,
-                                                             visited_mods_clone.clone(),
-                                                         ).await {
-                                                             Ok(_) => info!("Successfully processed dependency '{}'", dep_project_id),
-                                                             Err(e) => error!("Failed processing dependency '{}': {}", dep_project_id, e),
-                                                         }
-                                                     } else {
-                                                          error!("Could not find primary file for dependency version {} ({})", selected_dep_version.name, selected_dep_version.id);
-                                                     }
-                                                 } else {
-                                                     warn!("Could not find a compatible version for dependency project '{}' matching loader '{}' and game version '{}'. Dependency may be missing.", dep_project_id, profile_loader_str, profile_game_version);
-                                                 }
-                                             },
-                                             Err(e) => error!("Failed to fetch versions for dependency project '{}': {}", dep_project_id, e),
-                                         }
-                                    } else {
-                                        if let Some(dep_version_id_only) = dependency.version_id {
-                                            warn!("Dependency has only version_id ('{}'). Attempting to fetch details directly.", dep_version_id_only);
-                                            match modrinth::get_version_details(
-                                                dep_version_id_only.clone(),
-                                            )
-                                            .await
-                                            {
-                                                Ok(dep_version_details) => {
-                                                    info!("Successfully fetched details for version '{}': Project='{}'", dep_version_id_only, dep_version_details.project_id);
-                                                    if let Some(primary_file) = dep_version_details
-                                                        .files
-                                                        .iter()
-                                                        .find(|f| f.primary)
-                                                    {
-                                                        match self.add_modrinth_mod_internal(
-                                                             profile_id,
-                                                             dep_version_details.project_id.clone(),
-                                                             dep_version_details.id.clone(),
-                                                             primary_file.filename.clone(),
-                                                             primary_file.url.clone(),
-                                                             primary_file.hashes.sha1.clone(),
-                                                             Some(dep_version_details.name.clone()),
-                                                             Some(dep_version_details.version_number.clone()),
-                                                             Some(dep_version_details.loaders.clone()),
-                                                             Some(dep_version_details.game_versions.clone()),
-                                                             true,
-                                                             visited_mods_clone.clone(),
-                                                         ).await {
-                                                             Ok(_) => info!("Successfully processed dependency by version_id '{}'", dep_version_id_only),
-                                                             Err(e) => error!("Failed processing dependency by version_id '{}': {}", dep_version_id_only, e),
-                                                         }
-                                                    } else {
-                                                        error!("Could not find primary file for dependency version fetched by ID '{}'", dep_version_id_only);
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    error!("Failed to fetch details for dependency version_id '{}': {}. Cannot add dependency.", dep_version_id_only, e);
-                                                }
-                                            }
-                                        } else {
-                                            error!("Required dependency is missing project_id and version_id. Cannot resolve. File: {:?}", dependency.file_name);
-                                        }
-                                    }
-                                } else {
-                                    // Optional/Incompatible/Embedded dependencies are ignored for now
-                                    // info!("Ignoring non-required dependency type: {:?}", dependency.dependency_type);
-                                }
-                            }
+                    let mut best_dep_version: Option<&ModrinthVersion> = None;
+
+                    // If a specific dependency version was requested, try to find that first.
+                    if let Some(tv_id) = &dependency.version_id {
+                        best_dep_version = dep_versions.iter().find(|v| &v.id == tv_id);
+                        if best_dep_version.is_none() {
+                            warn!("Requested dependency version '{}' not found or not compatible with profile's loader for project '{}'. Trying to find best alternative.", tv_id, dep_project_id);
+                        }
+                    }
+
+                    // If no specific version requested or found, find the best compatible version.
+                    if best_dep_version.is_none() {
+                        // Use the PARENT mod's game versions if available (non-empty), else fallback to profile's.
+                        let target_game_versions_for_dep: Vec<String> = if !version_info
+                            .game_versions
+                            .is_empty()
+                        {
+                            version_info.game_versions.clone()
                         } else {
-                            warn!("Could not find details for version ID '{}' of project '{}' on Modrinth after fetching versions.", version_id, project_id);
+                            warn!("Parent mod {} ({}) did not provide specific game versions in its fetched data (version_info) or list was empty. Falling back to profile game version '{}' for dependency '{}' lookup.", display_name_log, version_log, profile_game_version, dep_project_id);
+                            vec![profile_game_version.clone()]
+                        };
+
+                        // Attempt 1: Find the latest version supporting any of the *target* game versions.
+                        best_dep_version = dep_versions
+                            .iter()
+                            .filter(|dep_v| {
+                                target_game_versions_for_dep
+                                    .iter()
+                                    .any(|target_gv| dep_v.game_versions.contains(target_gv))
+                            })
+                            .max_by_key(|v| &v.date_published);
+
+                        // Attempt 2: If no match for target game versions, fall back to the overall latest compatible version (loader match only).
+                        if best_dep_version.is_none() {
+                            warn!("Could not find dependency version matching target game versions {:?} for project '{}'. Falling back to latest version compatible with loader '{}'.", target_game_versions_for_dep, dep_project_id, profile_loader_str);
+                            best_dep_version =
+                                dep_versions.iter().max_by_key(|v| &v.date_published);
                         }
                     }
-                    Err(e) => {
-                        error!(
-                            "Failed to fetch versions for project '{}' to resolve dependencies: {}",
-                            project_id, e
-                        );
+
+                    match best_dep_version {
+                        Some(selected) => selected.clone(),
+                        None => {
+                            warn!("Could not find a compatible version for dependency project '{}' matching loader '{}' and game version '{}'. Dependency may be missing.", dep_project_id, profile_loader_str, profile_game_version);
+                            continue;
+                        }
                     }
-                }
-            } else {
+                } else if let Some(dep_version_id_only) = &dependency.version_id {
+                    warn!(
+                        "Dependency has only version_id ('{}'). Attempting to fetch details directly.",
+                        dep_version_id_only
+                    );
+                    match modrinth::get_version_details(dep_version_id_only.clone()).await {
+                        Ok(dep_version_details) => dep_version_details,
+                        Err(e) => {
+                            error!("Failed to fetch details for dependency version_id '{}': {}. Cannot add dependency.", dep_version_id_only, e);
+                            continue;
+                        }
+                    }
+                } else {
+                    error!(
+                        "Required dependency is missing project_id and version_id. Cannot resolve. File: {:?}",
+                        dependency.file_name
+                    );
+                    continue;
+                };
+
                 info!(
-                    "Skipping dependency check for {} ({}) as requested.",
-                    display_name_log, version_log
+                    "Selected version '{}' ({}) for dependency '{}'",
+                    selected_dep_version.name,
+                    selected_dep_version.id,
+                    selected_dep_version.project_id
                 );
-            }
 
-            Ok(())
+                // Already part of this install (shared dependency or cycle), only record the link
+                if let Some(existing) = candidates.iter_mut().find(|c| {
+                    c.project_id == selected_dep_version.project_id
+                        && c.version_id == selected_dep_version.id
+                }) {
+                    if !existing.required_by.contains(&index) {
+                        existing.required_by.push(index);
+                    }
+                    continue;
+                }
+
+                match ModrinthInstallCandidate::from_version(&selected_dep_version) {
+                    Some(mut candidate) => {
+                        candidate.required_by.push(index);
+                        candidates.push(candidate);
+                        let dep_index = candidates.len() - 1;
+                        self.resolve_modrinth_install(
+                            profile,
+                            dep_index,
+                            true,
+                            fetch_details,
+                            candidates,
+                        )
+                        .await;
+                    }
+                    None => error!(
+                        "Could not find primary file for dependency version {} ({})",
+                        selected_dep_version.name, selected_dep_version.id
+                    ),
+                }
+            }
         })
     }
 
-    // Public wrapper function to add a Modrinth mod and its dependencies
+    // Versions of the Modrinth mods in the profile keyed by sha1, for the incompatibility check
+    async fn fetch_installed_modrinth_versions(
+        &self,
+        profile: &Profile,
+    ) -> HashMap<String, ModrinthVersion> {
+        let hashes: Vec<String> = profile
+            .mods
+            .iter()
+            .filter_map(|m| match &m.source {
+                ModSource::Modrinth { file_hash_sha1, .. } => file_hash_sha1.clone(),
+                _ => None,
+            })
+            .collect();
+        if hashes.is_empty() {
+            return HashMap::new();
+        }
+        match modrinth::get_versions_by_hashes(hashes, "sha1").await {
+            Ok(versions) => versions,
+            Err(e) => {
+                warn!(
+                    "Could not fetch installed mod versions of profile {} to check incompatibilities: {}",
+                    profile.id, e
+                );
+                HashMap::new()
+            }
+        }
+    }
+
+    // Public wrapper function to add a Modrinth mod and its dependencies. The whole dependency
+    // tree is resolved and checked first, the profile is only changed if nothing conflicts.
     pub async fn add_modrinth_mod(
         &self,
         profile_id: Uuid,
@@ -775,22 +1170,59 @@ impl ProfileManager {
         loaders: Option<Vec<String>>,
         game_versions: Option<Vec<String>>,
         add_dependencies: bool, // Allow caller to decide
+        ignore_incompatibilities: bool,
+        required_by: Option<Uuid>, // Mod that needs this one, recorded in the dependency graph
     ) -> Result<()> {
-        self.add_modrinth_mod_internal(
-            profile_id,
+        let profile = self.get_profile(profile_id).await?;
+        let mut candidates = vec![ModrinthInstallCandidate {
             project_id,
             version_id,
             file_name,
             download_url,
             file_hash_sha1,
-            mod_name,
+            name: mod_name,
             version_number,
             loaders,
             game_versions,
+            version_info: None,
+            required_by: Vec::new(),
+        }];
+        self.resolve_modrinth_install(
+            &profile,
+            0,
             add_dependencies,
-            HashSet::new(),
+            !ignore_incompatibilities,
+            &mut candidates,
         )
-        .await
+        .await;
+
+        let installed_versions = if ignore_incompatibilities {
+            HashMap::new()
+        } else {
+            self.fetch_installed_modrinth_versions(&profile).await
+        };
+
+        let changed = {
+            let mut profiles = self.profiles.write().await;
+            let profile = profiles
+                .get_mut(&profile_id)
+                .ok_or(AppError::ProfileNotFound(profile_id))?;
+            if !ignore_incompatibilities {
+                check_modrinth_conflicts(profile, &candidates, &installed_versions)?;
+            }
+            apply_modrinth_install(profile, &candidates, required_by)
+        };
+
+        if changed {
+            self.save_profiles().await?;
+            info!(
+                "Profile {} saved after adding {} ({} mods resolved)",
+                profile_id,
+                candidates[0].display_name(),
+                candidates.len()
+            );
+        }
+        Ok(())
     }
 
     // Set the enabled status of a specific mod within a profile
@@ -835,6 +1267,38 @@ impl ProfileManager {
         }
     }
 
+    // Set the enabled status of several mods at once with a single save
+    pub async fn set_mods_enabled(
+        &self,
+        profile_id: Uuid,
+        enabled_states: &HashMap<Uuid, bool>,
+    ) -> Result<()> {
+        let mut profiles = self.profiles.write().await;
+        let profile = profiles
+            .get_mut(&profile_id)
+            .ok_or(AppError::ProfileNotFound(profile_id))?;
+
+        let mut changed = 0;
+        for mod_entry in profile.mods.iter_mut() {
+            if let Some(enabled) = enabled_states.get(&mod_entry.id) {
+                if mod_entry.enabled != *enabled {
+                    mod_entry.enabled = *enabled;
+                    changed += 1;
+                }
+            }
+        }
+        drop(profiles);
+
+        if changed > 0 {
+            self.save_profiles().await?;
+            info!(
+                "Updated enabled status of {} mods in profile {}",
+                changed, profile_id
+            );
+        }
+        Ok(())
+    }
+
     // Remove a specific mod from a profile
     pub async fn delete_mod(&self, profile_id: Uuid, mod_id: Uuid) -> Result<()> {
         info!("Deleting mod {} from profile {}", mod_id, profile_id);
@@ -847,6 +1311,9 @@ impl ProfileManager {
             let final_len = profile.mods.len();
 
             if final_len < initial_len {
+                for remaining in profile.mods.iter_mut() {
+                    remaining.dependencies.retain(|id| *id != mod_id);
+                }
                 drop(profiles);
                 self.save_profiles().await?;
                 info!(
@@ -865,6 +1332,64 @@ impl ProfileManager {
         }
     }
 
+    // Dependencies of a mod that nothing else in the profile needs, shown before deleting it
+    pub async fn get_orphaned_dependencies(
+        &self,
+        profile_id: Uuid,
+        mod_id: Uuid,
+    ) -> Result<Vec<Mod>> {
+        let profile = self.get_profile(profile_id).await?;
+        let orphaned = find_orphaned_dependencies(&profile.mods, &[mod_id]);
+        Ok(profile
+            .mods
+            .into_iter()
+            .filter(|m| orphaned.contains(&m.id))
+            .collect())
+    }
+
+    // Remove a mod together with its orphaned dependencies, returns the removed dependencies
+    pub async fn delete_mod_with_orphaned_dependencies(
+        &self,
+        profile_id: Uuid,
+        mod_id: Uuid,
+    ) -> Result<Vec<Mod>> {
+        info!(
+            "Deleting mod {} and its orphaned dependencies from profile {}",
+            mod_id, profile_id
+        );
+
+        let mut profiles = self.profiles.write().await;
+        let profile = profiles
+            .get_mut(&profile_id)
+            .ok_or(AppError::ProfileNotFound(profile_id))?;
+        if !profile.mods.iter().any(|m| m.id == mod_id) {
+            return Err(AppError::ModNotFoundInProfile { profile_id, mod_id });
+        }
+
+        let mut to_remove = find_orphaned_dependencies(&profile.mods, &[mod_id]);
+        to_remove.push(mod_id);
+        let (removed, kept): (Vec<Mod>, Vec<Mod>) = profile
+            .mods
+            .drain(..)
+            .partition(|m| to_remove.contains(&m.id));
+        profile.mods = kept;
+        for remaining in profile.mods.iter_mut() {
+            remaining.dependencies.retain(|id| !to_remove.contains(id));
+        }
+        drop(profiles);
+        self.save_profiles().await?;
+
+        let removed_dependencies: Vec<Mod> =
+            removed.into_iter().filter(|m| m.id != mod_id).collect();
+        info!(
+            "Deleted mod {} and {} orphaned dependencies from profile {}",
+            mod_id,
+            removed_dependencies.len(),
+            profile_id
+        );
+        Ok(removed_dependencies)
+    }
+
     // Set the enabled/disabled status of a specific mod within a Norisk Pack for a profile's specific context
     pub async fn set_norisk_mod_status(
         &self,
@@ -937,8 +1462,8 @@ impl ProfileManager {
     }
 
     /// Updates the version of a specific Modrinth mod instance within a profile,
-    /// after checking for the presence of required dependencies (by project ID).
-    /// Automatically adds missing dependencies.
+    /// after checking it against the other mods and for the presence of required
+    /// dependencies (by project ID). Automatically adds missing dependencies.
     pub async fn update_profile_modrinth_mod_version(
         &self,
         profile_id: Uuid,
@@ -950,136 +1475,22 @@ impl ProfileManager {
             mod_id, profile_id, new_version_details.name, new_version_details.id
         );
 
-        let mut profiles = self.profiles.write().await;
-
-        let profile = profiles.get_mut(&profile_id).ok_or_else(|| {
-            error!(
-                "Profile {} not found during mod update attempt.",
-                profile_id
-            );
-            AppError::ProfileNotFound(profile_id)
-        })?;
+        let snapshot = self.get_profile(profile_id).await?;
+        let installed_versions = self.fetch_installed_modrinth_versions(&snapshot).await;
 
-        info!(
-            "Checking required dependencies for new version {}...",
-            new_version_details.id
-        );
-        let existing_project_ids: HashSet<String> = profile
-            .mods
-            .iter()
-            .filter_map(|m| match &m.source {
-                ModSource::Modrinth { project_id, .. } => Some(project_id.clone()),
-                _ => None,
-            })
-            .collect();
-
-        // Track missing dependencies to install them later
-        let mut missing_deps = Vec::new();
-
-        for dependency in &new_version_details.dependencies {
-            if dependency.dependency_type == ModrinthDependencyType::Required {
-                if let Some(dep_project_id) = &dependency.project_id {
-                    if !existing_project_ids.contains(dep_project_id) {
-                        info!(
-                            "Required dependency project '{}' is missing in profile {}. Will install it automatically.",
-                            dep_project_id, profile_id
-                        );
-                        missing_deps.push((dep_project_id.clone(), dependency.version_id.clone()));
-                    } else {
-                        info!(
-                            "Required dependency project '{}' found in profile.",
-                            dep_project_id
-                        );
-                    }
-                } else {
-                    warn!(
-                        "Required dependency found without a project_id in version {}: {:?}",
-                        new_version_details.id, dependency
-                    );
-                }
-            }
-        }
-
-        // Now update the mod
-        let mod_to_update_index = profile.mods.iter().position(|m| m.id == mod_id);
-
-        if let Some(index) = mod_to_update_index {
-            let mod_to_update = &mut profile.mods[index];
-
-            if let ModSource::Modrinth {
-                project_id: old_project_id,
-                ..
-            } = &mod_to_update.source
-            {
-                if old_project_id != &new_version_details.project_id {
-                    error!(
-                        "Project ID mismatch when updating mod {}! Expected '{}', got '{}'. Aborting update.",
-                         mod_id, old_project_id, new_version_details.project_id
-                    );
-                    return Err(AppError::Other(format!(
-                        "Project ID mismatch for mod {}",
-                        mod_id
-                    )));
-                }
-
-                match new_version_details.files.iter().find(|f| f.primary) {
-                    Some(primary_file) => {
-                        info!(
-                            "Updating mod instance {} from version {} to {} using file '{}'",
-                            mod_id,
-                            mod_to_update.version.as_deref().unwrap_or("?"),
-                            new_version_details.version_number,
-                            primary_file.filename
-                        );
-
-                        mod_to_update.source = ModSource::Modrinth {
-                            project_id: new_version_details.project_id.clone(),
-                            version_id: new_version_details.id.clone(),
-                            file_name: primary_file.filename.clone(),
-                            download_url: primary_file.url.clone(),
-                            file_hash_sha1: primary_file.hashes.sha1.clone(),
-                        };
-
-                        mod_to_update.version = Some(new_version_details.version_number.clone());
-                        mod_to_update.game_versions =
-                            Some(new_version_details.game_versions.clone());
-                        mod_to_update.associated_loader = new_version_details
-                            .loaders
-                            .first()
-                            .and_then(|s| ModLoader::from_str(s).ok());
-
-                        info!("Mod instance {} updated successfully in memory.", mod_id);
-                    }
-                    None => {
-                        error!(
-                            "No primary file found for Modrinth version {} (ID: {})",
-                            new_version_details.name, new_version_details.id
-                        );
-                        return Err(AppError::ModrinthPrimaryFileNotFound {
-                            version_id: new_version_details.id.clone(),
-                        });
-                    }
-                }
-            } else {
+        let missing_deps = {
+            let mut profiles = self.profiles.write().await;
+            let profile = profiles.get_mut(&profile_id).ok_or_else(|| {
                 error!(
-                    "Mod instance {} in profile {} is not a Modrinth mod.",
-                    mod_id, profile_id
+                    "Profile {} not found during mod update attempt.",
+                    profile_id
                 );
-                return Err(AppError::Other(format!(
-                    "Mod {} is not a Modrinth mod",
-                    mod_id
-                )));
-            }
-        } else {
-            error!(
-                "Mod instance with ID {} not found in profile {} during update.",
-                mod_id, profile_id
-            );
-            return Err(AppError::ModNotFoundInProfile { profile_id, mod_id });
-        }
+                AppError::ProfileNotFound(profile_id)
+            })?;
+            apply_modrinth_mod_update(profile, mod_id, new_version_details, &installed_versions)?
+        };
 
         // Save changes to the profile first
-        drop(profiles);
         self.save_profiles().await?;
         info!(
             "Profile {} saved after updating mod {}.",
@@ -1115,6 +1526,8 @@ impl ProfileManager {
                                     Some(dep_version.loaders.clone()),
                                     Some(dep_version.game_versions.clone()),
                                     false, // don't recursively add dependencies here
+                                    false,
+                                    Some(mod_id),
                                 )
                                 .await
                             {
@@ -1161,6 +1574,8 @@ impl ProfileManager {
                                     Some(best_version.loaders.clone()),
                                     Some(best_version.game_versions.clone()),
                                     false, // don't recursively add dependencies here
+                                    false,
+                                    Some(mod_id),
                                 )
                                 .await
                             {
@@ -1316,6 +1731,62 @@ impl ProfileManager {
         Ok(custom_mods_dir)
     }
 
+    /// Fills the missing `display_name` and `version` of mods not from Modrinth from the
+    /// metadata of their jars in `mod_cache_dir`. Returns the number of updated mods.
+    pub async fn fill_mod_metadata_from_cache(
+        &self,
+        profile_id: Uuid,
+        mod_cache_dir: &Path,
+    ) -> Result<usize> {
+        let profile = self.get_profile(profile_id).await?;
+        let mut found: Vec<(Uuid, ModMetadata)> = Vec::new();
+        for mod_info in &profile.mods {
+            if matches!(mod_info.source, ModSource::Modrinth { .. })
+                || (mod_info.display_name.is_some() && mod_info.version.is_some())
+            {
+                continue;
+            }
+            let Ok(filename) = get_profile_mod_filename(&mod_info.source) else {
+                continue;
+            };
+            let jar_path = mod_cache_dir.join(&filename);
+            if !jar_path.exists() {
+                continue;
+            }
+            match mod_metadata::read_jar_metadata(&jar_path, false).await {
+                Ok(jar_metadata) => {
+                    if let Some(metadata) = jar_metadata.primary_for_loader(&profile.loader) {
+                        found.push((mod_info.id, metadata.clone()));
+                    }
+                }
+                Err(e) => warn!("Failed to read mod metadata of {:?}: {}", jar_path, e),
+            }
+        }
+
+        let mut updated = 0;
+        {
+            let mut profiles = self.profiles.write().await;
+            let Some(profile) = profiles.get_mut(&profile_id) else {
+                return Ok(0);
+            };
+            for (mod_id, metadata) in &found {
+                if let Some(mod_info) = profile.mods.iter_mut().find(|m| m.id == *mod_id) {
+                    if fill_mod_from_metadata(mod_info, metadata) {
+                        updated += 1;
+                    }
+                }
+            }
+        }
+        if updated > 0 {
+            info!(
+                "Filled name/version of {} mods of profile {} from their jars",
+                updated, profile_id
+            );
+            self.save_profiles().await?;
+        }
+        Ok(updated)
+    }
+
     /// Lists relevant custom mods found in the profile's `custom_mods` directory.
     /// Only includes files ending in `.jar` or `.jar.disabled`.
     pub async fn list_custom_mods(&self, profile: &Profile) -> Result<Vec<CustomModInfo>> {
@@ -1386,10 +1857,20 @@ impl ProfileManager {
                     };
 
                     if let Some(base_filename) = base_filename_opt {
+                        let metadata = match mod_metadata::read_jar_metadata(&path, false).await {
+                            Ok(jar_metadata) => {
+                                jar_metadata.primary_for_loader(&profile.loader).cloned()
+                            }
+                            Err(e) => {
+                                log::warn!("Failed to read mod metadata of {:?}: {}", path, e);
+                                None
+                            }
+                        };
                         custom_mods.push(CustomModInfo {
                             filename: base_filename,
                             is_enabled,
                             path: path.clone(),
+                            metadata,
                         });
                     } else {
                         log::trace!(
@@ -1626,6 +2107,8 @@ impl ProfileManager {
                                     Some(modrinth_version.loaders.clone()),
                                     Some(modrinth_version.game_versions.clone()),
                                     false, // add_dependencies = true
+                                    true,  // files are already in the profile
+                                    None,
                                 )
                                 .await
                             {
@@ -1751,7 +2234,7 @@ impl ProfileManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProfileManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ProfileManager: on_state_ready called. Loading profiles...");
         let loaded_profiles = self
             .load_profiles_internal(&self.profiles_path.clone())
@@ -1760,6 +2243,14 @@ impl PostInitializationHandler for ProfileManager {
         *profiles_guard = loaded_profiles;
         drop(profiles_guard);
         info!("ProfileManager: Successfully loaded profiles in on_state_ready.");
+
+        // Mods, die eine abgebrochene Bisektion deaktiviert hat, wieder aktivieren
+        if let Err(e) = crate::minecraft::mod_bisect::restore_interrupted_sessions(self).await {
+            warn!(
+                "ProfileManager: Failed to restore mods of interrupted bisections: {}",
+                e
+            );
+        }
         Ok(())
     }
 }
@@ -1769,7 +2260,7 @@ pub fn get_profile_mod_filename(source: &ModSource) -> crate::error::Result<Stri
     match source {
         ModSource::Modrinth { file_name, .. } => Ok(file_name.clone()),
         ModSource::Local { file_name } => Ok(file_name.clone()),
-        ModSource::Url { file_name, url } => file_name.clone().ok_or_else(|| {
+        ModSource::Url { file_name, url, .. } => file_name.clone().ok_or_else(|| {
             crate::error::AppError::Other(format!("Filename missing for URL mod source: {}", url))
         }),
         ModSource::Maven { coordinates, .. } => Err(crate::error::AppError::Other(format!(
@@ -1783,6 +2274,21 @@ pub fn get_profile_mod_filename(source: &ModSource) -> crate::error::Result<Stri
     }
 }
 
+/// Sets the `display_name` and `version` a mod entry is missing from its jar metadata.
+/// Returns whether anything changed.
+pub fn fill_mod_from_metadata(mod_info: &mut Mod, metadata: &ModMetadata) -> bool {
+    let mut changed = false;
+    if mod_info.display_name.is_none() {
+        mod_info.display_name = Some(metadata.display_name().to_string());
+        changed = true;
+    }
+    if mod_info.version.is_none() && metadata.version.is_some() {
+        mod_info.version = metadata.version.clone();
+        changed = true;
+    }
+    changed
+}
+
 pub fn default_profile_path() -> PathBuf {
     LAUNCHER_DIRECTORY.data_dir().join("profiles")
 }
@@ -1797,6 +2303,20 @@ impl Default for ProfileSettings {
             fullscreen: false,
             extra_game_args: Vec::new(),
             custom_jvm_args: None, // Standardmäßig keine benutzerdefinierten JVM-Args
+            hooks: ProfileHooks::default(),
+            env_vars: HashMap::new(),
+            world_backups: WorldBackupSettings::default(),
+        }
+    }
+}
+
+impl Default for WorldBackupSettings {
+    fn default() -> Self {
+        Self {
+            backup_on_exit: true,
+            interval_minutes: None,
+            format: BackupFormat::default(),
+            retention: BackupRetention::default(),
         }
     }
 }
@@ -0,0 +1,443 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::state_manager::State;
+use crate::utils::mc_utils::{self, ServerPingInfo};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use futures::future::join_all;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{Mutex, Notify, RwLock};
+use tokio::time::Duration;
+use uuid::Uuid;
+
+const SERVER_MONITOR_FILENAME: &str = "server_monitor.json";
+const SERVER_HISTORY_FILENAME: &str = "server_monitor_history.json";
+const MIN_INTERVAL_SECS: u64 = 10;
+/// Failed polls in a row before a server counts as down, so one lost ping doesn't flap
+pub const OFFLINE_AFTER_FAILURES: u32 = 2;
+
+fn default_interval_secs() -> u64 {
+    60
+}
+
+fn default_history_hours() -> u32 {
+    24
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonitoredServer {
+    pub id: Uuid,
+    pub name: String,
+    pub address: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerMonitorConfig {
+    #[serde(default)]
+    pub servers: Vec<MonitoredServer>,
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    /// How long samples are kept
+    #[serde(default = "default_history_hours")]
+    pub history_hours: u32,
+}
+
+impl Default for ServerMonitorConfig {
+    fn default() -> Self {
+        Self {
+            servers: Vec::new(),
+            interval_secs: default_interval_secs(),
+            history_hours: default_history_hours(),
+        }
+    }
+}
+
+/// One poll of a server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServerSample {
+    pub timestamp: DateTime<Utc>,
+    pub online: bool,
+    pub latency_ms: Option<u64>,
+    pub players_online: Option<u32>,
+    pub players_max: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ServerSample {
+    pub fn from_ping(info: &ServerPingInfo) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            online: info.error.is_none(),
+            latency_ms: info.latency_ms.filter(|_| info.error.is_none()),
+            players_online: info.players_online,
+            players_max: info.players_max,
+            error: info.error.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerHistory {
+    pub samples: Vec<ServerSample>,
+    /// Last known state, `None` until the server was polled
+    pub online: Option<bool>,
+    #[serde(default)]
+    failures: u32,
+}
+
+impl ServerHistory {
+    /// Adds a sample and returns the new state if the server went up or down.
+    /// The first known state is not a change.
+    pub fn record(&mut self, sample: ServerSample) -> Option<bool> {
+        let online = if sample.online {
+            self.failures = 0;
+            Some(true)
+        } else {
+            self.failures += 1;
+            if self.failures >= OFFLINE_AFTER_FAILURES {
+                Some(false)
+            } else {
+                self.online
+            }
+        };
+        self.samples.push(sample);
+        let previous = std::mem::replace(&mut self.online, online);
+        match (previous, online) {
+            (Some(previous), Some(online)) if previous != online => Some(online),
+            _ => None,
+        }
+    }
+
+    /// Drops samples older than `before`.
+    pub fn prune(&mut self, before: DateTime<Utc>) {
+        self.samples.retain(|s| s.timestamp >= before);
+    }
+
+    /// Share of successful polls (0.0 - 1.0), `None` without samples.
+    pub fn uptime(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let online = self.samples.iter().filter(|s| s.online).count();
+        Some(online as f64 / self.samples.len() as f64)
+    }
+}
+
+/// Current state of a monitored server for the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitoredServerStatus {
+    #[serde(flatten)]
+    pub server: MonitoredServer,
+    pub online: Option<bool>,
+    pub last_sample: Option<ServerSample>,
+    pub uptime: Option<f64>,
+}
+
+/// Polls a configurable set of servers in the background and keeps their latency and
+/// player count history. Up/down changes are emitted as `ServerStatusChanged` events.
+pub struct ServerMonitorManager {
+    config: RwLock<ServerMonitorConfig>,
+    history: RwLock<HashMap<Uuid, ServerHistory>>,
+    config_path: PathBuf,
+    history_path: PathBuf,
+    save_lock: Mutex<()>,
+    poll_lock: Mutex<()>,
+    // Wakes the poll loop when servers or the interval change
+    wake: Notify,
+}
+
+async fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, serde_json::to_string(value)?).await?;
+    fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+impl ServerMonitorManager {
+    pub fn new(config_path: PathBuf, history_path: PathBuf) -> Result<Self> {
+        info!(
+            "ServerMonitorManager: Initializing with path: {:?} (loading deferred)",
+            config_path
+        );
+        Ok(Self {
+            config: RwLock::new(ServerMonitorConfig::default()),
+            history: RwLock::new(HashMap::new()),
+            config_path,
+            history_path,
+            save_lock: Mutex::new(()),
+            poll_lock: Mutex::new(()),
+            wake: Notify::new(),
+        })
+    }
+
+    async fn load(&self) -> Result<()> {
+        if self.config_path.exists() {
+            let data = fs::read_to_string(&self.config_path).await?;
+            match serde_json::from_str::<ServerMonitorConfig>(&data) {
+                Ok(config) => {
+                    info!("Loaded {} monitored servers", config.servers.len());
+                    *self.config.write().await = config;
+                }
+                Err(e) => {
+                    error!("Failed to parse server monitor config: {}", e);
+                    let broken = self.config_path.with_extension("json.broken");
+                    if let Err(e) = fs::rename(&self.config_path, &broken).await {
+                        warn!("Failed to move broken server monitor config aside: {}", e);
+                    }
+                }
+            }
+        }
+        // The history is only a cache, a broken file just starts over
+        if self.history_path.exists() {
+            let data = fs::read_to_string(&self.history_path).await?;
+            match serde_json::from_str::<HashMap<Uuid, ServerHistory>>(&data) {
+                Ok(history) => *self.history.write().await = history,
+                Err(e) => warn!("Ignoring unreadable server monitor history: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_config(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        let config = self.config.read().await.clone();
+        write_json(&self.config_path, &config).await
+    }
+
+    async fn save_history(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        let history = self.history.read().await.clone();
+        write_json(&self.history_path, &history).await
+    }
+
+    pub async fn get_config(&self) -> ServerMonitorConfig {
+        self.config.read().await.clone()
+    }
+
+    pub async fn list_servers(&self) -> Vec<MonitoredServerStatus> {
+        let config = self.config.read().await;
+        let history = self.history.read().await;
+        config
+            .servers
+            .iter()
+            .map(|server| {
+                let history = history.get(&server.id);
+                MonitoredServerStatus {
+                    server: server.clone(),
+                    online: history.and_then(|h| h.online),
+                    last_sample: history.and_then(|h| h.samples.last().cloned()),
+                    uptime: history.and_then(|h| h.uptime()),
+                }
+            })
+            .collect()
+    }
+
+    pub async fn add_server(&self, name: &str, address: &str) -> Result<MonitoredServer> {
+        if name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "Server name must not be empty".to_string(),
+            ));
+        }
+        mc_utils::parse_minecraft_address(address.trim()).map_err(AppError::InvalidInput)?;
+        let server = MonitoredServer {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            address: address.trim().to_string(),
+            enabled: true,
+        };
+        self.config.write().await.servers.push(server.clone());
+        self.save_config().await?;
+        info!("Monitoring server '{}' ({})", server.name, server.address);
+        self.wake.notify_one();
+        Ok(server)
+    }
+
+    pub async fn set_server_enabled(&self, id: Uuid, enabled: bool) -> Result<()> {
+        let mut config = self.config.write().await;
+        let server = config
+            .servers
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Monitored server {}", id)))?;
+        server.enabled = enabled;
+        drop(config);
+        self.save_config().await?;
+        self.wake.notify_one();
+        Ok(())
+    }
+
+    pub async fn remove_server(&self, id: Uuid) -> Result<bool> {
+        let mut config = self.config.write().await;
+        let len = config.servers.len();
+        config.servers.retain(|s| s.id != id);
+        let removed = config.servers.len() < len;
+        drop(config);
+        if removed {
+            self.history.write().await.remove(&id);
+            self.save_config().await?;
+            self.save_history().await?;
+        }
+        Ok(removed)
+    }
+
+    pub async fn set_schedule(&self, interval_secs: u64, history_hours: Option<u32>) -> Result<()> {
+        if interval_secs < MIN_INTERVAL_SECS {
+            return Err(AppError::InvalidInput(format!(
+                "Interval must be at least {} seconds",
+                MIN_INTERVAL_SECS
+            )));
+        }
+        let mut config = self.config.write().await;
+        config.interval_secs = interval_secs;
+        if let Some(history_hours) = history_hours {
+            config.history_hours = history_hours.max(1);
+        }
+        drop(config);
+        self.save_config().await?;
+        self.wake.notify_one();
+        Ok(())
+    }
+
+    /// Samples of a server, optionally only those after `since`.
+    pub async fn get_history(
+        &self,
+        id: Uuid,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ServerSample>> {
+        if !self.config.read().await.servers.iter().any(|s| s.id == id) {
+            return Err(AppError::NotFound(format!("Monitored server {}", id)));
+        }
+        Ok(self
+            .history
+            .read()
+            .await
+            .get(&id)
+            .map(|h| {
+                h.samples
+                    .iter()
+                    .filter(|s| since.is_none_or(|since| s.timestamp > since))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Pings all enabled servers once, records the samples and emits events for servers
+    /// that went up or down.
+    pub async fn poll_all(&self) -> Result<Vec<MonitoredServerStatus>> {
+        let _guard = self.poll_lock.lock().await;
+        let config = self.get_config().await;
+        let servers: Vec<&MonitoredServer> = config.servers.iter().filter(|s| s.enabled).collect();
+        let pings = join_all(
+            servers
+                .iter()
+                .map(|server| mc_utils::ping_server_status(&server.address)),
+        )
+        .await;
+
+        let prune_before = Utc::now() - ChronoDuration::hours(config.history_hours as i64);
+        let mut changes = Vec::new();
+        {
+            let mut history = self.history.write().await;
+            history.retain(|id, _| config.servers.iter().any(|s| s.id == *id));
+            for (server, ping) in servers.iter().zip(&pings) {
+                let entry = history.entry(server.id).or_default();
+                let sample = ServerSample::from_ping(ping);
+                if let Some(online) = entry.record(sample.clone()) {
+                    changes.push((*server, online, sample.error));
+                }
+                entry.prune(prune_before);
+            }
+        }
+        self.save_history().await?;
+
+        if !changes.is_empty() {
+            let state = State::get().await?;
+            for (server, online, error) in changes {
+                info!(
+                    "Monitored server '{}' ({}) is now {}",
+                    server.name,
+                    server.address,
+                    if online { "online" } else { "offline" }
+                );
+                let payload = EventPayload {
+                    event_id: Uuid::new_v4(),
+                    event_type: EventType::ServerStatusChanged,
+                    target_id: Some(server.id),
+                    message: format!(
+                        "{} is {}",
+                        server.name,
+                        if online { "online" } else { "offline" }
+                    ),
+                    progress: None,
+                    error,
+                };
+                if let Err(e) = state.emit_event(payload).await {
+                    warn!("Failed to emit server status event: {}", e);
+                }
+            }
+        }
+        Ok(self.list_servers().await)
+    }
+
+    async fn run_monitor_loop() {
+        info!("Starting server monitor task.");
+        loop {
+            let state = match State::get().await {
+                Ok(state) => state,
+                Err(e) => {
+                    error!("Server monitor: Failed to get global state: {}", e);
+                    return;
+                }
+            };
+            let monitor = &state.server_monitor_manager;
+            let config = monitor.get_config().await;
+            if config.servers.iter().any(|s| s.enabled) {
+                debug!("Polling {} monitored servers", config.servers.len());
+                if let Err(e) = monitor.poll_all().await {
+                    error!("Server monitor poll failed: {}", e);
+                }
+            }
+            // A changed configuration polls right away
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(config.interval_secs.max(MIN_INTERVAL_SECS))) => {}
+                _ = monitor.wake.notified() => {}
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for ServerMonitorManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("ServerMonitorManager: on_state_ready called. Loading monitored servers...");
+        self.load().await?;
+        tokio::spawn(Self::run_monitor_loop());
+        Ok(())
+    }
+}
+
+pub fn default_server_monitor_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(SERVER_MONITOR_FILENAME)
+}
+
+pub fn default_server_history_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(SERVER_HISTORY_FILENAME)
+}
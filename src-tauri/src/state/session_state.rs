@@ -0,0 +1,426 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::Result;
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::process_state::ProcessMetadata;
+use crate::state::state_manager::State;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Local, Utc};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+const SESSIONS_FILENAME: &str = "sessions.json";
+
+/// One game run, from the start of the process until it exited.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameSession {
+    /// Same as the id of the game process
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub profile_name: Option<String>,
+    pub account_uuid: Option<String>,
+    pub account_name: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub modloader: Option<String>,
+    pub modloader_version: Option<String>,
+    pub norisk_pack: Option<String>,
+    /// Fingerprint of the pack's mods at launch, see `NoriskModpacksConfig::pack_fingerprint`
+    pub norisk_pack_version: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the game is running or if the exit was missed
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_secs: Option<u64>,
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+    /// The exit was not observed because the launcher was closed. If the launcher found the
+    /// game still running after a restart, `ended_at` is when it noticed the exit, otherwise
+    /// the session has no end and no duration.
+    #[serde(default)]
+    pub exit_unknown: bool,
+}
+
+impl GameSession {
+    pub fn from_process(metadata: &ProcessMetadata, norisk_pack_version: Option<String>) -> Self {
+        Self {
+            id: metadata.id,
+            profile_id: metadata.profile_id,
+            profile_name: metadata.profile_name.clone(),
+            account_uuid: metadata.account_uuid.clone(),
+            account_name: metadata.account_name.clone(),
+            minecraft_version: metadata.minecraft_version.clone(),
+            modloader: metadata.modloader.clone(),
+            modloader_version: metadata.modloader_version.clone(),
+            norisk_pack: metadata.norisk_pack.clone(),
+            norisk_pack_version,
+            started_at: metadata.start_time,
+            ended_at: None,
+            duration_secs: None,
+            exit_code: None,
+            crashed: false,
+            exit_unknown: false,
+        }
+    }
+
+    pub fn finish(&mut self, ended_at: DateTime<Utc>, exit_code: Option<i32>, crashed: bool) {
+        self.ended_at = Some(ended_at);
+        self.duration_secs = Some((ended_at - self.started_at).num_seconds().max(0) as u64);
+        self.exit_code = exit_code;
+        self.crashed = crashed;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none() && !self.exit_unknown
+    }
+
+    /// Played seconds, up to `now` for a running session
+    pub fn played_secs(&self, now: DateTime<Utc>) -> u64 {
+        match self.duration_secs {
+            Some(duration) => duration,
+            None if self.is_running() => (now - self.started_at).num_seconds().max(0) as u64,
+            None => 0,
+        }
+    }
+}
+
+/// Restricts sessions for queries. Unset fields match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SessionFilter {
+    pub profile_id: Option<Uuid>,
+    pub account_uuid: Option<String>,
+    pub norisk_pack: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl SessionFilter {
+    pub fn matches(&self, session: &GameSession) -> bool {
+        self.profile_id.is_none_or(|id| session.profile_id == id)
+            && self
+                .account_uuid
+                .as_ref()
+                .is_none_or(|uuid| session.account_uuid.as_ref() == Some(uuid))
+            && self
+                .norisk_pack
+                .as_ref()
+                .is_none_or(|pack| session.norisk_pack.as_ref() == Some(pack))
+            && self.since.is_none_or(|since| session.started_at >= since)
+            && self.until.is_none_or(|until| session.started_at < until)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlaytimeEntry {
+    /// Profile id, account uuid or ISO week ("2025-W05")
+    pub key: String,
+    pub name: Option<String>,
+    pub sessions: usize,
+    pub total_secs: u64,
+}
+
+/// Stability of one pack version: how many of its finished sessions crashed.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PackVersionStats {
+    pub norisk_pack: String,
+    pub norisk_pack_version: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub sessions: usize,
+    pub crashes: usize,
+    /// 0.0 - 1.0 over sessions with an observed exit
+    pub crash_rate: f64,
+    pub total_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SessionAggregates {
+    pub sessions: usize,
+    pub total_secs: u64,
+    pub by_profile: Vec<PlaytimeEntry>,
+    pub by_account: Vec<PlaytimeEntry>,
+    /// By the week (local time) a session started in, oldest first
+    pub by_week: Vec<PlaytimeEntry>,
+    pub by_pack_version: Vec<PackVersionStats>,
+}
+
+fn add_playtime(
+    entries: &mut BTreeMap<String, PlaytimeEntry>,
+    key: String,
+    name: Option<&String>,
+    secs: u64,
+) {
+    let entry = entries.entry(key.clone()).or_insert_with(|| PlaytimeEntry {
+        key,
+        name: None,
+        sessions: 0,
+        total_secs: 0,
+    });
+    entry.sessions += 1;
+    entry.total_secs += secs;
+    // The newest name wins (sessions are in start order)
+    if name.is_some() {
+        entry.name = name.cloned();
+    }
+}
+
+fn by_playtime(entries: BTreeMap<String, PlaytimeEntry>) -> Vec<PlaytimeEntry> {
+    let mut entries: Vec<PlaytimeEntry> = entries.into_values().collect();
+    entries.sort_by(|a, b| b.total_secs.cmp(&a.total_secs).then(a.key.cmp(&b.key)));
+    entries
+}
+
+/// Total playtime per profile, account and week and the crash rate per pack version.
+pub fn aggregate_sessions(sessions: &[GameSession], now: DateTime<Utc>) -> SessionAggregates {
+    let mut total_secs = 0;
+    let mut by_profile = BTreeMap::new();
+    let mut by_account = BTreeMap::new();
+    let mut by_week = BTreeMap::new();
+    let mut by_pack_version: BTreeMap<(String, Option<String>, Option<String>), PackVersionStats> =
+        BTreeMap::new();
+
+    for session in sessions {
+        let secs = session.played_secs(now);
+        total_secs += secs;
+        add_playtime(
+            &mut by_profile,
+            session.profile_id.to_string(),
+            session.profile_name.as_ref(),
+            secs,
+        );
+        if let Some(account_uuid) = &session.account_uuid {
+            add_playtime(
+                &mut by_account,
+                account_uuid.clone(),
+                session.account_name.as_ref(),
+                secs,
+            );
+        }
+        let week = session.started_at.with_timezone(&Local).iso_week();
+        add_playtime(
+            &mut by_week,
+            format!("{}-W{:02}", week.year(), week.week()),
+            None,
+            secs,
+        );
+
+        if let Some(pack) = &session.norisk_pack {
+            let key = (
+                pack.clone(),
+                session.norisk_pack_version.clone(),
+                session.minecraft_version.clone(),
+            );
+            let stats = by_pack_version
+                .entry(key)
+                .or_insert_with(|| PackVersionStats {
+                    norisk_pack: pack.clone(),
+                    norisk_pack_version: session.norisk_pack_version.clone(),
+                    minecraft_version: session.minecraft_version.clone(),
+                    sessions: 0,
+                    crashes: 0,
+                    crash_rate: 0.0,
+                    total_secs: 0,
+                });
+            stats.total_secs += secs;
+            // Running sessions and unknown exits say nothing about stability
+            if !session.is_running() && !session.exit_unknown {
+                stats.sessions += 1;
+                if session.crashed {
+                    stats.crashes += 1;
+                }
+                stats.crash_rate = stats.crashes as f64 / stats.sessions as f64;
+            }
+        }
+    }
+
+    SessionAggregates {
+        sessions: sessions.len(),
+        total_secs,
+        by_profile: by_playtime(by_profile),
+        by_account: by_playtime(by_account),
+        by_week: by_week.into_values().collect(),
+        by_pack_version: by_pack_version.into_values().collect(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct SessionDatabase {
+    #[serde(default)]
+    sessions: Vec<GameSession>,
+}
+
+/// Records every game run in `sessions.json` for playtime and stability statistics.
+pub struct SessionManager {
+    database: RwLock<SessionDatabase>,
+    path: PathBuf,
+    save_lock: Mutex<()>,
+}
+
+impl SessionManager {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        info!(
+            "SessionManager: Initializing with path: {:?} (loading deferred)",
+            path
+        );
+        Ok(Self {
+            database: RwLock::new(SessionDatabase::default()),
+            path,
+            save_lock: Mutex::new(()),
+        })
+    }
+
+    async fn load(&self) -> Result<()> {
+        if !self.path.exists() {
+            info!("Sessions file not found, starting with an empty history");
+            return Ok(());
+        }
+        let data = fs::read_to_string(&self.path).await?;
+        match serde_json::from_str::<SessionDatabase>(&data) {
+            Ok(database) => {
+                info!("Loaded {} game sessions", database.sessions.len());
+                *self.database.write().await = database;
+            }
+            Err(e) => {
+                // Keep the broken file around instead of overwriting it on the next save
+                error!("Failed to parse sessions file: {}", e);
+                let broken = self.path.with_extension("json.broken");
+                if let Err(e) = fs::rename(&self.path, &broken).await {
+                    warn!("Failed to move broken sessions file aside: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn save(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string(&*self.database.read().await)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, data).await?;
+        fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+
+    /// Records the start of a game process.
+    pub async fn start_session(&self, metadata: &ProcessMetadata) -> Result<()> {
+        let norisk_pack_version = match (&metadata.norisk_pack, &metadata.minecraft_version) {
+            (Some(pack), Some(version)) => {
+                let loader = metadata.modloader.as_deref().unwrap_or("vanilla");
+                let packs = State::get().await?.norisk_pack_manager.get_config().await;
+                packs
+                    .pack_fingerprint(pack, version, loader)
+                    .map_err(|e| warn!("No fingerprint for pack {}: {}", pack, e))
+                    .ok()
+            }
+            _ => None,
+        };
+        let session = GameSession::from_process(metadata, norisk_pack_version);
+        {
+            let mut database = self.database.write().await;
+            database.sessions.retain(|s| s.id != session.id);
+            database.sessions.push(session);
+        }
+        self.save().await
+    }
+
+    /// Records the exit of a game process. `exit_code` is `None` if the exit was not observed.
+    pub async fn finish_session(
+        &self,
+        process_id: Uuid,
+        exit_code: Option<i32>,
+        crashed: bool,
+        exit_unknown: bool,
+    ) -> Result<()> {
+        {
+            let mut database = self.database.write().await;
+            let Some(session) = database
+                .sessions
+                .iter_mut()
+                .find(|s| s.id == process_id && s.is_running())
+            else {
+                return Ok(());
+            };
+            session.finish(Utc::now(), exit_code, crashed);
+            session.exit_unknown = exit_unknown;
+            info!(
+                "Game session {} of profile {:?} ended after {}s (crashed: {})",
+                process_id,
+                session.profile_name,
+                session.duration_secs.unwrap_or(0),
+                crashed
+            );
+        }
+        self.save().await
+    }
+
+    /// Marks sessions whose game is no longer running as ended at an unknown time, e.g. the
+    /// launcher was closed while the game ran and the game exited before the next start.
+    async fn close_abandoned_sessions(&self, running: &[Uuid]) -> Result<()> {
+        let mut abandoned = 0;
+        {
+            let mut database = self.database.write().await;
+            for session in database.sessions.iter_mut() {
+                if session.is_running() && !running.contains(&session.id) {
+                    session.exit_unknown = true;
+                    abandoned += 1;
+                }
+            }
+        }
+        if abandoned > 0 {
+            warn!("Closed {} game sessions with an unknown end", abandoned);
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    /// Sessions matching the filter, newest first.
+    pub async fn list_sessions(&self, filter: &SessionFilter) -> Vec<GameSession> {
+        let database = self.database.read().await;
+        database
+            .sessions
+            .iter()
+            .rev()
+            .filter(|s| filter.matches(s))
+            .cloned()
+            .collect()
+    }
+
+    pub async fn aggregate(&self, filter: &SessionFilter) -> SessionAggregates {
+        let database = self.database.read().await;
+        let sessions: Vec<GameSession> = database
+            .sessions
+            .iter()
+            .filter(|s| filter.matches(s))
+            .cloned()
+            .collect();
+        aggregate_sessions(&sessions, Utc::now())
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for SessionManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("SessionManager: on_state_ready called. Loading game sessions...");
+        self.load().await?;
+        // The ProcessManager has already reloaded the games that are still running
+        let running: Vec<Uuid> = State::get()
+            .await?
+            .process_manager
+            .list_processes()
+            .await
+            .iter()
+            .map(|p| p.id)
+            .collect();
+        self.close_abandoned_sessions(&running).await?;
+        Ok(())
+    }
+}
+
+pub fn default_sessions_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(SESSIONS_FILENAME)
+}
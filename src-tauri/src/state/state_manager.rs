@@ -2,15 +2,24 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::minecraft_auth::MinecraftAuthStore;
 use crate::state::config_state::ConfigManager;
+use crate::state::content_store_state::{default_content_store_path, ContentStoreManager};
 use crate::state::discord_state::DiscordManager;
 use crate::state::event_state::{EventPayload, EventState};
+use crate::state::favorite_servers_state::{
+    default_favorite_servers_path, FavoriteServersManager,
+};
 use crate::state::norisk_packs_state::{default_norisk_packs_path, NoriskPackManager};
 use crate::state::norisk_versions_state::{default_norisk_versions_path, NoriskVersionManager};
 use crate::state::post_init::PostInitializationHandler;
 use crate::state::process_state::{default_processes_path, ProcessManager};
 use crate::state::profile_state::ProfileManager;
+use crate::state::server_monitor_state::{
+    default_server_history_path, default_server_monitor_path, ServerMonitorManager,
+};
+use crate::state::session_state::{default_sessions_path, SessionManager};
 use crate::state::skin_state::{default_skins_path, SkinManager};
 use crate::state::cape_state::{default_capes_path, CapeManager};
+use crate::state::world_backup_state::{default_world_backups_path, WorldBackupManager};
 use std::sync::Arc;
 use tokio::sync::{OnceCell, Semaphore};
 
@@ -30,24 +39,44 @@ pub struct State {
     pub skin_manager: SkinManager,
     pub cape_manager: CapeManager,
     pub discord_manager: DiscordManager,
+    pub content_store: ContentStoreManager,
+    pub world_backup_manager: WorldBackupManager,
+    pub favorite_servers_manager: FavoriteServersManager,
+    pub server_monitor_manager: ServerMonitorManager,
+    pub session_manager: SessionManager,
     pub io_semaphore: Arc<Semaphore>,
 }
 
 impl State {
     // Initialize the global state
     pub async fn init(app: Arc<tauri::AppHandle>) -> Result<()> {
+        Self::init_internal(Some(app)).await
+    }
+
+    /// Initializes the global state without a Tauri app (e.g. for the CLI).
+    /// Events are only delivered to `EventState::subscribe` receivers.
+    pub async fn init_headless() -> Result<()> {
+        Self::init_internal(None).await
+    }
+
+    async fn init_internal(app: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         let initial_state_arc = LAUNCHER_STATE
             .get_or_try_init(|| async {
                 log::info!("State::init - Starting primary initialization of managers (Phase 1 - Lightweight Instantiation)...");
                 let config_manager = ConfigManager::new()?;
                 let discord_manager = DiscordManager::new(false).await?;
                 let io_semaphore = Arc::new(Semaphore::new(10));
-                let event_state = EventState::new(Some(app.clone()));
+                let event_state = EventState::new(app.clone());
                 let minecraft_account_manager_v2 = MinecraftAuthStore::new().await?;
                 let norisk_pack_manager = NoriskPackManager::new(default_norisk_packs_path())?;
                 let norisk_version_manager = NoriskVersionManager::new(default_norisk_versions_path())?;
                 let skin_manager = SkinManager::new(default_skins_path())?;
                 let cape_manager = CapeManager::new(default_capes_path())?;
+                let content_store = ContentStoreManager::new(default_content_store_path())?;
+                let world_backup_manager = WorldBackupManager::new(default_world_backups_path())?;
+                let favorite_servers_manager = FavoriteServersManager::new(default_favorite_servers_path())?;
+                let server_monitor_manager = ServerMonitorManager::new(default_server_monitor_path(), default_server_history_path())?;
+                let session_manager = SessionManager::new(default_sessions_path())?;
                 let profile_manager = ProfileManager::new(LAUNCHER_DIRECTORY.root_dir().join("profiles.json"))?;
                 let process_manager = ProcessManager::new(default_processes_path(), app.clone()).await?;
 
@@ -64,6 +93,11 @@ impl State {
                     skin_manager,
                     cape_manager,
                     discord_manager,
+                    content_store,
+                    world_backup_manager,
+                    favorite_servers_manager,
+                    server_monitor_manager,
+                    session_manager,
                     io_semaphore,
                 }))
             })
@@ -132,6 +166,36 @@ impl State {
             .await?;
         log::info!("State::init - CapeManager post-initialization complete.");
 
+        initial_state_arc
+            .content_store
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - ContentStoreManager post-initialization complete.");
+
+        initial_state_arc
+            .world_backup_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - WorldBackupManager post-initialization complete.");
+
+        initial_state_arc
+            .favorite_servers_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - FavoriteServersManager post-initialization complete.");
+
+        initial_state_arc
+            .server_monitor_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - ServerMonitorManager post-initialization complete.");
+
+        initial_state_arc
+            .session_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - SessionManager post-initialization complete.");
+
         initial_state_arc
             .norisk_pack_manager
             .print_current_config()
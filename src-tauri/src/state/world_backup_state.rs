@@ -0,0 +1,520 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::State;
+use crate::utils::world_backup_utils::{self, BackupTrigger, WorldBackup};
+use crate::utils::world_utils;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const BACKUPS_DIR_NAME: &str = "backups";
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Backups of the worlds of all profiles under `root_dir()/backups/<profile>/<world>`.
+///
+/// Backups are taken manually, when the game exits and on a schedule while it runs.
+/// Automatic backups are pruned with the retention policy of the profile.
+pub struct WorldBackupManager {
+    root: PathBuf,
+    /// One backup/restore at a time per world
+    world_locks: DashMap<(Uuid, String), Arc<Mutex<()>>>,
+    /// When the scheduler last backed up a profile
+    last_scheduled: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+}
+
+impl WorldBackupManager {
+    pub fn new(root: PathBuf) -> Result<Self> {
+        info!("WorldBackupManager: Initializing with root: {:?}", root);
+        Ok(Self {
+            root,
+            world_locks: DashMap::new(),
+            last_scheduled: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn backup_dir(&self, profile_id: Uuid, world_folder: &str) -> PathBuf {
+        self.root.join(profile_id.to_string()).join(world_folder)
+    }
+
+    fn world_lock(&self, profile_id: Uuid, world_folder: &str) -> Arc<Mutex<()>> {
+        self.world_locks
+            .entry((profile_id, world_folder.to_string()))
+            .or_default()
+            .clone()
+    }
+
+    async fn saves_path(profile_id: Uuid) -> Result<PathBuf> {
+        let state = State::get().await?;
+        Ok(state
+            .profile_manager
+            .get_profile_instance_path(profile_id)
+            .await?
+            .join("saves"))
+    }
+
+    fn validate_world_folder(world_folder: &str) -> Result<()> {
+        if world_folder.is_empty()
+            || world_folder.contains(['/', '\\'])
+            || world_folder == "."
+            || world_folder == ".."
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid world folder name '{}'",
+                world_folder
+            )));
+        }
+        Ok(())
+    }
+
+    /// Backs up a world. Worlds of a running game are backed up too, the backup is then
+    /// marked as `taken_while_running`. Automatic backups apply the retention policy.
+    pub async fn create_backup(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        trigger: BackupTrigger,
+    ) -> Result<WorldBackup> {
+        Self::validate_world_folder(world_folder)?;
+        let state = State::get().await?;
+        let profile = state.profile_manager.get_profile(profile_id).await?;
+        let world_path = Self::saves_path(profile_id).await?.join(world_folder);
+        if !world_path.join("level.dat").is_file() {
+            return Err(AppError::WorldNotFound {
+                profile_id,
+                world_folder: world_folder.to_string(),
+            });
+        }
+
+        let lock = self.world_lock(profile_id, world_folder);
+        let _guard = lock.lock().await;
+
+        let taken_while_running = match world_utils::check_world_session_lock(&world_path).await {
+            Ok(()) => false,
+            Err(AppError::WorldLocked { .. }) => true,
+            Err(e) => return Err(e),
+        };
+        let event_id = Uuid::new_v4();
+        emit_progress(
+            event_id,
+            EventType::BackingUpWorld,
+            profile_id,
+            format!("Backing up world '{}'...", world_folder),
+            0.0,
+            None,
+        )
+        .await;
+
+        let settings = profile.settings.world_backups;
+        let backup_dir = self.backup_dir(profile_id, world_folder);
+        let result = tokio::task::spawn_blocking({
+            let backup_dir = backup_dir.clone();
+            move || {
+                world_backup_utils::create_backup(
+                    &world_path,
+                    &backup_dir,
+                    settings.format,
+                    trigger,
+                    taken_while_running,
+                )
+            }
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("World backup task failed: {}", e)))
+        .and_then(|r| r);
+
+        let backup = match result {
+            Ok(backup) => backup,
+            Err(e) => {
+                emit_progress(
+                    event_id,
+                    EventType::BackingUpWorld,
+                    profile_id,
+                    format!("Backup of world '{}' failed", world_folder),
+                    1.0,
+                    Some(e.to_string()),
+                )
+                .await;
+                return Err(e);
+            }
+        };
+
+        if trigger != BackupTrigger::Manual {
+            if let Err(e) = self.apply_retention(&backup_dir, &settings.retention).await {
+                warn!(
+                    "Failed to prune old backups of world '{}' in profile {}: {}",
+                    world_folder, profile_id, e
+                );
+            }
+        }
+
+        emit_progress(
+            event_id,
+            EventType::BackingUpWorld,
+            profile_id,
+            format!("Backed up world '{}'", world_folder),
+            1.0,
+            None,
+        )
+        .await;
+        Ok(backup)
+    }
+
+    async fn apply_retention(
+        &self,
+        backup_dir: &Path,
+        retention: &world_backup_utils::BackupRetention,
+    ) -> Result<()> {
+        let backup_dir = backup_dir.to_path_buf();
+        let retention = retention.clone();
+        tokio::task::spawn_blocking(move || {
+            let backups = world_backup_utils::list_backups(&backup_dir)?;
+            for id in world_backup_utils::select_expired_backups(&backups, &retention) {
+                world_backup_utils::delete_backup(&backup_dir, &id)?;
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("Backup retention task failed: {}", e)))?
+    }
+
+    /// Backups of a world, newest first.
+    pub async fn list_backups(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+    ) -> Result<Vec<WorldBackup>> {
+        Self::validate_world_folder(world_folder)?;
+        let backup_dir = self.backup_dir(profile_id, world_folder);
+        tokio::task::spawn_blocking(move || world_backup_utils::list_backups(&backup_dir))
+            .await
+            .map_err(|e| AppError::Other(format!("Listing backups failed: {}", e)))?
+    }
+
+    pub async fn delete_backup(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        backup_id: &str,
+    ) -> Result<()> {
+        Self::validate_world_folder(world_folder)?;
+        let lock = self.world_lock(profile_id, world_folder);
+        let _guard = lock.lock().await;
+        let backup_dir = self.backup_dir(profile_id, world_folder);
+        let backup_id = backup_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            world_backup_utils::delete_backup(&backup_dir, &backup_id)
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("Deleting backup failed: {}", e)))?
+    }
+
+    /// Restores a backup. Without `new_world_name` the world is replaced; its current state is
+    /// backed up first. With a name the backup becomes a new world next to it.
+    /// Returns the folder the backup was restored to.
+    pub async fn restore_backup(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        backup_id: &str,
+        new_world_name: Option<String>,
+    ) -> Result<String> {
+        Self::validate_world_folder(world_folder)?;
+        let saves_path = Self::saves_path(profile_id).await?;
+        fs::create_dir_all(&saves_path).await?;
+        let world_path = saves_path.join(world_folder);
+        let backup_dir = self.backup_dir(profile_id, world_folder);
+        let event_id = Uuid::new_v4();
+
+        let target_folder = match &new_world_name {
+            Some(name) => world_utils::find_unique_world_folder_name(&saves_path, name).await?,
+            None => {
+                if world_path.exists() {
+                    if let Err(e) = world_utils::check_world_session_lock(&world_path).await {
+                        return Err(match e {
+                            AppError::WorldLocked { .. } => AppError::WorldLocked {
+                                profile_id,
+                                world_folder: world_folder.to_string(),
+                            },
+                            other => other,
+                        });
+                    }
+                    if world_path.join("level.dat").is_file() {
+                        self.create_backup(profile_id, world_folder, BackupTrigger::BeforeRestore)
+                            .await?;
+                    }
+                }
+                world_folder.to_string()
+            }
+        };
+
+        let lock = self.world_lock(profile_id, world_folder);
+        let _guard = lock.lock().await;
+        emit_progress(
+            event_id,
+            EventType::RestoringWorld,
+            profile_id,
+            format!("Restoring backup of world '{}'...", world_folder),
+            0.0,
+            None,
+        )
+        .await;
+
+        let target_path = saves_path.join(&target_folder);
+        let result = self
+            .restore_into(
+                &backup_dir,
+                backup_id,
+                &target_path,
+                new_world_name.is_none(),
+            )
+            .await;
+        let result = match (result, &new_world_name) {
+            (Ok(()), Some(name)) => {
+                world_utils::modify_level_dat_name(&target_path.join("level.dat"), name).await
+            }
+            (result, _) => result,
+        };
+
+        match &result {
+            Ok(()) => {
+                info!(
+                    "Restored backup {} of world '{}' in profile {} to '{}'",
+                    backup_id, world_folder, profile_id, target_folder
+                );
+                emit_progress(
+                    event_id,
+                    EventType::RestoringWorld,
+                    profile_id,
+                    format!("Restored world '{}'", target_folder),
+                    1.0,
+                    None,
+                )
+                .await;
+            }
+            Err(e) => {
+                emit_progress(
+                    event_id,
+                    EventType::RestoringWorld,
+                    profile_id,
+                    format!("Restoring world '{}' failed", world_folder),
+                    1.0,
+                    Some(e.to_string()),
+                )
+                .await;
+            }
+        }
+        result.map(|_| target_folder)
+    }
+
+    /// Restores next to the target and swaps directories at the end, the old world is only
+    /// deleted once the restored one is in place.
+    async fn restore_into(
+        &self,
+        backup_dir: &Path,
+        backup_id: &str,
+        target_path: &Path,
+        replace: bool,
+    ) -> Result<()> {
+        let backup_dir = backup_dir.to_path_buf();
+        let backup_id = backup_id.to_string();
+        let target_path = target_path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            if !replace || !target_path.exists() {
+                return world_backup_utils::restore_backup(&backup_dir, &backup_id, &target_path);
+            }
+            let file_name = target_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy();
+            let restored_path = target_path.with_file_name(format!(".{}.restored", file_name));
+            let replaced_path = target_path.with_file_name(format!(".{}.replaced", file_name));
+            for leftover in [&restored_path, &replaced_path] {
+                if leftover.exists() {
+                    std::fs::remove_dir_all(leftover)?;
+                }
+            }
+            world_backup_utils::restore_backup(&backup_dir, &backup_id, &restored_path)?;
+            std::fs::rename(&target_path, &replaced_path)?;
+            if let Err(e) = std::fs::rename(&restored_path, &target_path) {
+                let _ = std::fs::rename(&replaced_path, &target_path);
+                let _ = std::fs::remove_dir_all(&restored_path);
+                return Err(e.into());
+            }
+            if let Err(e) = std::fs::remove_dir_all(&replaced_path) {
+                warn!("Failed to remove replaced world {:?}: {}", replaced_path, e);
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("World restore task failed: {}", e)))?
+    }
+
+    /// Backs up the worlds that were played in a session, called when the game exits.
+    pub async fn backup_worlds_after_exit(&self, profile_id: Uuid, session_start: DateTime<Utc>) {
+        let state = match State::get().await {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        let settings = match state.profile_manager.get_profile(profile_id).await {
+            Ok(profile) => profile.settings.world_backups,
+            Err(e) => {
+                warn!(
+                    "No world backups after exit, profile {} not found: {}",
+                    profile_id, e
+                );
+                return;
+            }
+        };
+        if !settings.backup_on_exit {
+            return;
+        }
+        self.backup_worlds_modified_since(profile_id, session_start, BackupTrigger::GameExit)
+            .await;
+    }
+
+    async fn backup_worlds_modified_since(
+        &self,
+        profile_id: Uuid,
+        since: DateTime<Utc>,
+        trigger: BackupTrigger,
+    ) {
+        let saves_path = match Self::saves_path(profile_id).await {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Cannot back up worlds of profile {}: {}", profile_id, e);
+                return;
+            }
+        };
+        for world_folder in played_worlds(&saves_path, since).await {
+            if let Err(e) = self.create_backup(profile_id, &world_folder, trigger).await {
+                error!(
+                    "Automatic backup of world '{}' in profile {} failed: {}",
+                    world_folder, profile_id, e
+                );
+            }
+        }
+    }
+
+    /// Backs up the worlds of running games whose profile has a backup interval.
+    async fn run_scheduled_backups(&self) -> Result<()> {
+        let state = State::get().await?;
+        let now = Utc::now();
+        let mut sessions: HashMap<Uuid, DateTime<Utc>> = HashMap::new();
+        for process in state.process_manager.list_processes().await {
+            let start = sessions
+                .entry(process.profile_id)
+                .or_insert(process.start_time);
+            *start = (*start).min(process.start_time);
+        }
+
+        for (profile_id, session_start) in sessions {
+            let Some(interval) = state
+                .profile_manager
+                .get_profile(profile_id)
+                .await
+                .ok()
+                .and_then(|p| p.settings.world_backups.interval_minutes)
+                .filter(|m| *m > 0)
+            else {
+                continue;
+            };
+            let interval = chrono::Duration::minutes(interval as i64);
+            let since = {
+                let last_scheduled = self.last_scheduled.lock().await;
+                last_scheduled
+                    .get(&profile_id)
+                    .copied()
+                    .unwrap_or(session_start)
+                    .max(session_start)
+            };
+            if now - since < interval {
+                continue;
+            }
+            self.last_scheduled.lock().await.insert(profile_id, now);
+            info!("Running scheduled world backups for profile {}", profile_id);
+            self.backup_worlds_modified_since(profile_id, since, BackupTrigger::Scheduled)
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Worlds whose level.dat was written since `since`. The game saves it regularly while a
+/// world is open, so this finds the worlds played in a session.
+async fn played_worlds(saves_path: &Path, since: DateTime<Utc>) -> Vec<String> {
+    let mut worlds = Vec::new();
+    let Ok(mut entries) = fs::read_dir(saves_path).await else {
+        return worlds;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = fs::metadata(entry.path().join("level.dat")).await else {
+            continue;
+        };
+        let modified = metadata.modified().ok().map(DateTime::<Utc>::from);
+        if modified.is_some_and(|m| m >= since) {
+            worlds.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    worlds.sort();
+    worlds
+}
+
+async fn emit_progress(
+    event_id: Uuid,
+    event_type: EventType,
+    profile_id: Uuid,
+    message: String,
+    progress: f64,
+    error: Option<String>,
+) {
+    if let Ok(state) = State::get().await {
+        let _ = state
+            .emit_event(EventPayload {
+                event_id,
+                event_type,
+                target_id: Some(profile_id),
+                message,
+                progress: Some(progress),
+                error,
+            })
+            .await;
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for WorldBackupManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("WorldBackupManager: on_state_ready called. Starting backup scheduler...");
+        tokio::spawn(async {
+            let mut interval = tokio::time::interval(SCHEDULER_TICK);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match State::get().await {
+                    Ok(state) => {
+                        if let Err(e) = state.world_backup_manager.run_scheduled_backups().await {
+                            warn!("Scheduled world backups failed: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("World backup scheduler could not get state: {}", e),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Get the default root of the world backups
+pub fn default_world_backups_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(BACKUPS_DIR_NAME)
+}
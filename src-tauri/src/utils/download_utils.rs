@@ -0,0 +1,505 @@
+use crate::config::HTTP_CLIENT;
+use crate::error::{AppError, Result};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::state_manager::State;
+use crate::utils::mirror_utils;
+use futures::StreamExt;
+use log::{debug, info, warn};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
+
+const PART_EXTENSION: &str = "part";
+const DEFAULT_MAX_RETRIES: u32 = 5;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Where byte-level progress of a download is reported to.
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub event_id: Uuid,
+    pub event_type: EventType,
+    pub target_id: Option<Uuid>,
+    pub label: String,
+}
+
+impl DownloadProgress {
+    pub fn new(event_type: EventType, target_id: Option<Uuid>, label: impl Into<String>) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            event_type,
+            target_id,
+            label: label.into(),
+        }
+    }
+}
+
+/// What `VerifiedDownload::download` had to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadOutcome {
+    /// The target already existed and passed verification
+    AlreadyPresent,
+    /// The file was (re)downloaded; `resumed` is true if a previous `.part` file was continued
+    Downloaded { bytes: u64, resumed: bool },
+}
+
+/// A single file download shared by all download services.
+///
+/// Streams into `<target>.part`, continues an existing `.part` file with an HTTP `Range`
+/// request, retries with exponential backoff and only moves the file to `target` after the
+/// size and hashes (if known) have been verified. Configured mirrors (`mirror_utils`) are
+/// tried in order before the original URL.
+///
+/// ```ignore
+/// VerifiedDownload::new(&info.url, &target_path)
+///     .sha1(Some(&info.sha1))
+///     .size(Some(info.size as u64))
+///     .download()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct VerifiedDownload {
+    url: String,
+    target: PathBuf,
+    sha1: Option<String>,
+    sha256: Option<String>,
+    size: Option<u64>,
+    progress: Option<DownloadProgress>,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl VerifiedDownload {
+    pub fn new(url: impl Into<String>, target: impl Into<PathBuf>) -> Self {
+        Self {
+            url: url.into(),
+            target: target.into(),
+            sha1: None,
+            sha256: None,
+            size: None,
+            progress: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+        }
+    }
+
+    pub fn sha1(mut self, sha1: Option<&str>) -> Self {
+        self.sha1 = sha1.filter(|s| !s.is_empty()).map(|s| s.to_lowercase());
+        self
+    }
+
+    pub fn sha256(mut self, sha256: Option<&str>) -> Self {
+        self.sha256 = sha256.filter(|s| !s.is_empty()).map(|s| s.to_lowercase());
+        self
+    }
+
+    /// Expected size in bytes. Sizes of 0 are treated as unknown (some metadata uses 0 for that).
+    pub fn size(mut self, size: Option<u64>) -> Self {
+        self.size = size.filter(|s| *s > 0);
+        self
+    }
+
+    pub fn progress(mut self, progress: Option<DownloadProgress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    fn part_path(&self) -> PathBuf {
+        let mut name = self
+            .target
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_default();
+        name.push(".");
+        name.push(PART_EXTENSION);
+        self.target.with_file_name(name)
+    }
+
+    fn has_checksum(&self) -> bool {
+        self.sha1.is_some() || self.sha256.is_some()
+    }
+
+    /// Downloads the file unless `target` already exists and matches.
+    /// Without hash and size any existing file is accepted, like the old per-service helpers did.
+    pub async fn download(&self) -> Result<DownloadOutcome> {
+        if fs::try_exists(&self.target).await? {
+            if !self.has_checksum() && self.size.is_none() {
+                debug!(
+                    "File exists, skipping (nothing to verify): {:?}",
+                    self.target
+                );
+                return Ok(DownloadOutcome::AlreadyPresent);
+            }
+            match self.verify(&self.target).await {
+                Ok(()) => {
+                    debug!("File exists and is valid: {:?}", self.target);
+                    return Ok(DownloadOutcome::AlreadyPresent);
+                }
+                Err(e) => {
+                    warn!(
+                        "Existing file {:?} is invalid ({}), redownloading",
+                        self.target, e
+                    );
+                    fs::remove_file(&self.target).await?;
+                }
+            }
+        }
+
+        if let Some(parent) = self.target.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let part_path = self.part_path();
+        let candidates = mirror_utils::candidate_urls(&self.url);
+        let mut last_error = None;
+        for (index, url) in candidates.iter().enumerate() {
+            let result = if mirror_utils::is_file_url(url) {
+                self.copy_from_local_mirror(url, &part_path).await
+            } else {
+                self.download_with_retries(url, &part_path).await
+            };
+            match result {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) => {
+                    if index + 1 < candidates.len() {
+                        warn!("Download from {} failed ({}), trying next mirror", url, e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| AppError::Download(format!("Failed to download {}", self.url))))
+    }
+
+    async fn download_with_retries(&self, url: &str, part_path: &Path) -> Result<DownloadOutcome> {
+        let mut last_error = None;
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                let backoff = self.backoff_for(attempt);
+                warn!(
+                    "Retrying download of {} in {:?} (attempt {}/{})",
+                    url, backoff, attempt, self.max_retries
+                );
+                tokio::time::sleep(backoff).await;
+            }
+
+            match self.try_download(url, part_path).await {
+                Ok((bytes, resumed)) => match self.verify(part_path).await {
+                    Ok(()) => {
+                        fs::rename(part_path, &self.target).await?;
+                        self.emit_progress(bytes, Some(bytes), true).await;
+                        info!("Downloaded {} -> {:?}", url, self.target);
+                        return Ok(DownloadOutcome::Downloaded { bytes, resumed });
+                    }
+                    Err(e) => {
+                        // A corrupt .part file must never be resumed
+                        warn!("Verification of {} failed: {}", url, e);
+                        let _ = fs::remove_file(part_path).await;
+                        last_error = Some(e);
+                    }
+                },
+                Err(AttemptError::Fatal(e)) => {
+                    let _ = fs::remove_file(part_path).await;
+                    return Err(e);
+                }
+                Err(AttemptError::Retryable(e)) => {
+                    warn!("Download attempt for {} failed: {}", url, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| AppError::Download(format!("Failed to download {}", url))))
+    }
+
+    /// `file://` mirror: copy instead of download, verified like any other source.
+    async fn copy_from_local_mirror(&self, url: &str, part_path: &Path) -> Result<DownloadOutcome> {
+        let source = mirror_utils::file_url_to_path(url)
+            .ok_or_else(|| AppError::Download(format!("Invalid file URL: {}", url)))?;
+        let bytes = fs::copy(&source, part_path).await?;
+        if let Err(e) = self.verify(part_path).await {
+            let _ = fs::remove_file(part_path).await;
+            return Err(e);
+        }
+        fs::rename(part_path, &self.target).await?;
+        self.emit_progress(bytes, Some(bytes), true).await;
+        debug!("Copied {:?} from local mirror -> {:?}", source, self.target);
+        Ok(DownloadOutcome::Downloaded {
+            bytes,
+            resumed: false,
+        })
+    }
+
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(MAX_BACKOFF)
+    }
+
+    /// One request. Returns the total size of the `.part` file and whether it was resumed.
+    async fn try_download(
+        &self,
+        url: &str,
+        part_path: &Path,
+    ) -> std::result::Result<(u64, bool), AttemptError> {
+        let mut offset = match fs::metadata(part_path).await {
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        };
+        if self.size.is_some_and(|size| offset > size) {
+            fs::remove_file(part_path).await.map_err(AttemptError::io)?;
+            offset = 0;
+        }
+        if offset > 0 && self.size == Some(offset) {
+            // Complete from an earlier run that died before the rename
+            return Ok((offset, true));
+        }
+
+        let mut request = HTTP_CLIENT.get(url);
+        if offset > 0 {
+            debug!("Resuming {} at byte {}", url, offset);
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        let response = request.send().await.map_err(|e| {
+            AttemptError::Retryable(AppError::Download(format!(
+                "Request failed for {}: {}",
+                url, e
+            )))
+        })?;
+
+        let status = response.status();
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            // Server disagrees with our partial file, start over on the next attempt
+            let _ = fs::remove_file(part_path).await;
+            return Err(AttemptError::Retryable(AppError::Download(format!(
+                "Server rejected resume of {} at byte {}",
+                url, offset
+            ))));
+        }
+        if !status.is_success() {
+            let error =
+                AppError::Download(format!("Failed to download {}: Status {}", url, status));
+            return Err(if is_retryable_status(status) {
+                AttemptError::Retryable(error)
+            } else {
+                AttemptError::Fatal(error)
+            });
+        }
+
+        // 200 on a range request means the server ignored it and sends the whole file
+        let resumed =
+            offset > 0 && status == StatusCode::PARTIAL_CONTENT && has_content_range(&response);
+        if !resumed {
+            offset = 0;
+        }
+        let total = self.size.or_else(|| {
+            response
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|len| len + offset)
+        });
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(part_path)
+            .await
+            .map_err(AttemptError::io)?;
+
+        let mut written = offset;
+        let mut last_emit = Instant::now();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| {
+                AttemptError::Retryable(AppError::Download(format!(
+                    "Stream error for {} after {} bytes: {}",
+                    url, written, e
+                )))
+            })?;
+            file.write_all(&chunk).await.map_err(AttemptError::io)?;
+            written += chunk.len() as u64;
+
+            if self.size.is_some_and(|size| written > size) {
+                drop(file);
+                let _ = fs::remove_file(part_path).await;
+                return Err(AttemptError::Retryable(AppError::Download(format!(
+                    "{} is larger than the expected {} bytes",
+                    url,
+                    self.size.unwrap_or_default()
+                ))));
+            }
+            if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                self.emit_progress(written, total, false).await;
+                last_emit = Instant::now();
+            }
+        }
+        file.flush().await.map_err(AttemptError::io)?;
+
+        if let Some(size) = self.size {
+            if written < size {
+                // Connection closed early, keep the .part file for the next attempt
+                return Err(AttemptError::Retryable(AppError::Download(format!(
+                    "Incomplete download of {}: {} of {} bytes",
+                    url, written, size
+                ))));
+            }
+        }
+
+        Ok((written, resumed))
+    }
+
+    /// Checks size and hashes of `path` against the expected values.
+    pub async fn verify(&self, path: &Path) -> Result<()> {
+        if let Some(expected) = self.size {
+            let actual = fs::metadata(path).await?.len();
+            if actual != expected {
+                return Err(AppError::Download(format!(
+                    "Size mismatch for {:?}: expected {} bytes, got {}",
+                    path, expected, actual
+                )));
+            }
+        }
+
+        if !self.has_checksum() {
+            return Ok(());
+        }
+
+        let (sha1, sha256) = hash_file(path, self.sha1.is_some(), self.sha256.is_some()).await?;
+        if let (Some(expected), Some(actual)) = (&self.sha1, sha1) {
+            if *expected != actual {
+                return Err(AppError::Download(format!(
+                    "SHA1 mismatch for {:?}: expected {}, got {}",
+                    path, expected, actual
+                )));
+            }
+        }
+        if let (Some(expected), Some(actual)) = (&self.sha256, sha256) {
+            if *expected != actual {
+                return Err(AppError::Download(format!(
+                    "SHA256 mismatch for {:?}: expected {}, got {}",
+                    path, expected, actual
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn emit_progress(&self, written: u64, total: Option<u64>, done: bool) {
+        let Some(progress) = &self.progress else {
+            return;
+        };
+        if !State::initialized() {
+            return;
+        }
+        let Ok(state) = State::get().await else {
+            return;
+        };
+
+        let fraction = match total {
+            _ if done => Some(1.0),
+            Some(total) if total > 0 => Some((written as f64 / total as f64).min(1.0)),
+            _ => None,
+        };
+        let message = match total {
+            Some(total) => format!(
+                "{} ({:.1} / {:.1} MB)",
+                progress.label,
+                written as f64 / 1_048_576.0,
+                total as f64 / 1_048_576.0
+            ),
+            None => format!(
+                "{} ({:.1} MB)",
+                progress.label,
+                written as f64 / 1_048_576.0
+            ),
+        };
+
+        if let Err(e) = state
+            .emit_event(EventPayload {
+                event_id: progress.event_id,
+                event_type: progress.event_type.clone(),
+                target_id: progress.target_id,
+                message,
+                progress: fraction,
+                error: None,
+            })
+            .await
+        {
+            debug!("Failed to emit download progress: {}", e);
+        }
+    }
+}
+
+enum AttemptError {
+    Retryable(AppError),
+    Fatal(AppError),
+}
+
+impl AttemptError {
+    fn io(e: std::io::Error) -> Self {
+        // Disk errors (full, permissions) will not fix themselves by retrying
+        AttemptError::Fatal(AppError::Io(e))
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn has_content_range(response: &reqwest::Response) -> bool {
+    response.headers().contains_key(CONTENT_RANGE)
+}
+
+/// Streams the file once and computes the requested hashes (lowercase hex).
+async fn hash_file(
+    path: &Path,
+    want_sha1: bool,
+    want_sha256: bool,
+) -> Result<(Option<String>, Option<String>)> {
+    let mut file = fs::File::open(path).await?;
+    let mut sha1 = want_sha1.then(Sha1::new);
+    let mut sha256 = want_sha256.then(Sha256::new);
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        if let Some(hasher) = sha1.as_mut() {
+            hasher.update(&buffer[..n]);
+        }
+        if let Some(hasher) = sha256.as_mut() {
+            hasher.update(&buffer[..n]);
+        }
+    }
+
+    Ok((
+        sha1.map(|h| hex::encode(h.finalize())),
+        sha256.map(|h| hex::encode(h.finalize())),
+    ))
+}
@@ -0,0 +1,209 @@
+use crate::error::{AppError, Result};
+use crate::state::config_state::Hooks;
+use crate::state::profile_state::ProfileHooks;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Hooks for one launch after applying the profile overrides to the global hooks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedHooks {
+    pub pre_launch: Option<String>,
+    pub wrapper: Option<String>,
+    pub post_exit: Option<String>,
+    pub pre_launch_timeout: Option<Duration>,
+    pub abort_on_pre_launch_failure: bool,
+}
+
+impl ResolvedHooks {
+    pub fn resolve(global: &Hooks, profile: &ProfileHooks) -> Self {
+        Self {
+            pre_launch: pick_hook(&profile.pre_launch, &global.pre_launch),
+            wrapper: pick_hook(&profile.wrapper, &global.wrapper),
+            post_exit: pick_hook(&profile.post_exit, &global.post_exit),
+            pre_launch_timeout: profile
+                .pre_launch_timeout_secs
+                .or(global.pre_launch_timeout_secs)
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            abort_on_pre_launch_failure: profile
+                .abort_on_pre_launch_failure
+                .unwrap_or(global.abort_on_pre_launch_failure),
+        }
+    }
+}
+
+/// A profile value wins, an empty profile value disables the global hook.
+fn pick_hook(profile: &Option<String>, global: &Option<String>) -> Option<String> {
+    match profile {
+        Some(value) if value.trim().is_empty() => None,
+        Some(value) => Some(value.clone()),
+        None => global.clone().filter(|value| !value.trim().is_empty()),
+    }
+}
+
+/// Values for the placeholders in hook and wrapper commands:
+/// `{instance_dir}`, `{profile_name}`, `{profile_id}`, `{java_path}` (pre-launch and wrapper)
+/// and `{pid}` (post-exit).
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub instance_dir: PathBuf,
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub java_path: Option<PathBuf>,
+    /// Only known once the game runs (post-exit hook)
+    pub pid: Option<u32>,
+}
+
+impl HookContext {
+    pub fn new(instance_dir: PathBuf, profile_id: Uuid, profile_name: impl Into<String>) -> Self {
+        Self {
+            instance_dir,
+            profile_id,
+            profile_name: profile_name.into(),
+            java_path: None,
+            pid: None,
+        }
+    }
+
+    pub fn with_java_path(mut self, java_path: PathBuf) -> Self {
+        self.java_path = Some(java_path);
+        self
+    }
+
+    pub fn with_pid(mut self, pid: u32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// Replaces the known placeholders. Unknown values (e.g. `{pid}` before launch)
+    /// are left in place so they can be filled in later.
+    pub fn substitute(&self, template: &str) -> String {
+        let mut result = template
+            .replace("{instance_dir}", &self.instance_dir.to_string_lossy())
+            .replace("{profile_name}", &self.profile_name)
+            .replace("{profile_id}", &self.profile_id.to_string());
+        if let Some(java_path) = &self.java_path {
+            result = result.replace("{java_path}", &java_path.to_string_lossy());
+        }
+        if let Some(pid) = self.pid {
+            result = result.replace("{pid}", &pid.to_string());
+        }
+        result
+    }
+}
+
+/// Splits a command line into program and arguments. Single and double quotes group
+/// words, so paths with spaces work: `"C:/My Scripts/backup.bat" {instance_dir}`.
+pub fn split_command_line(line: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    parts.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Builds the command for a hook with placeholders substituted, running in the instance
+/// directory with the profile's environment variables.
+fn build_hook_command(
+    hook: &str,
+    context: &HookContext,
+    env_vars: &HashMap<String, String>,
+) -> Result<tokio::process::Command> {
+    let parts = split_command_line(hook);
+    let (program, args) = parts
+        .split_first()
+        .ok_or_else(|| AppError::Other("Hook command is empty".to_string()))?;
+
+    let mut command = tokio::process::Command::new(context.substitute(program));
+    command
+        .args(args.iter().map(|arg| context.substitute(arg)))
+        .envs(env_vars)
+        .current_dir(&context.instance_dir);
+    Ok(command)
+}
+
+/// Runs the pre-launch hook and waits for it. Fails if it can't be started, exits with a
+/// non-zero code or exceeds `timeout` (the hook is killed in that case).
+pub async fn run_pre_launch_hook(
+    hook: &str,
+    context: &HookContext,
+    env_vars: &HashMap<String, String>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    info!("Executing pre-launch hook: {}", hook);
+    let mut child = build_hook_command(hook, context, env_vars)?
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(AppError::Io)?;
+
+    let status = match timeout {
+        Some(limit) => match tokio::time::timeout(limit, child.wait()).await {
+            Ok(status) => status.map_err(AppError::Io)?,
+            Err(_) => {
+                let _ = child.kill().await;
+                return Err(AppError::Other(format!(
+                    "Pre-launch hook timed out after {} seconds",
+                    limit.as_secs()
+                )));
+            }
+        },
+        None => child.wait().await.map_err(AppError::Io)?,
+    };
+
+    if !status.success() {
+        return Err(AppError::Other(format!(
+            "Pre-launch hook failed with exit code: {}",
+            status.code().unwrap_or(-1)
+        )));
+    }
+    info!("Pre-launch hook executed successfully");
+    Ok(())
+}
+
+/// Starts a hook without waiting for it (post-exit).
+pub fn spawn_hook(hook: &str, context: &HookContext, env_vars: &HashMap<String, String>) {
+    let mut command = match build_hook_command(hook, context, env_vars) {
+        Ok(command) => command,
+        Err(e) => {
+            warn!("Skipping hook '{}': {}", hook, e);
+            return;
+        }
+    };
+    match command.spawn() {
+        Ok(mut child) => {
+            info!("Hook spawned successfully: {}", hook);
+            // Reap the child in the background so it doesn't linger as a zombie
+            tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+        }
+        Err(e) => error!("Failed to spawn hook '{}': {}", hook, e),
+    }
+}
@@ -438,7 +438,7 @@ pub async fn find_best_java_for_minecraft(mc_version: &str) -> Result<Option<Jav
 }
 
 /// Parses the major version from a Java version string
-fn parse_java_major_version(version: &str) -> Option<u32> {
+pub(crate) fn parse_java_major_version(version: &str) -> Option<u32> {
     // Handle different version formats:
     // 1.8.0_292 (Java 8)
     // 11.0.11 (Java 11)
@@ -0,0 +1,330 @@
+use crate::error::{AppError, Result};
+use crate::state::config_state::LaunchLogConfig;
+use chrono::{DateTime, Local, Utc};
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+
+/// Launch logs live in `<instance>/logs/launches`, next to the game's own logs
+pub fn launch_logs_dir(instance_path: &Path) -> PathBuf {
+    instance_path.join("logs").join("launches")
+}
+
+/// File of a part of a launch log: `<id>.log` for the first, `<id>.<n>.log` for later ones.
+fn part_path(dir: &Path, launch_id: &str, part: usize) -> PathBuf {
+    if part == 0 {
+        dir.join(format!("{}.log", launch_id))
+    } else {
+        dir.join(format!("{}.{}.log", launch_id, part))
+    }
+}
+
+/// Splits a launch log file name into launch id and part number.
+fn parse_part_name(file_name: &str) -> Option<(&str, usize)> {
+    let stem = file_name.strip_suffix(".log")?;
+    match stem.rsplit_once('.') {
+        Some((launch_id, part)) => Some((launch_id, part.parse().ok()?)),
+        None => Some((stem, 0)),
+    }
+}
+
+fn validate_launch_id(launch_id: &str) -> Result<()> {
+    if launch_id.is_empty()
+        || !launch_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid launch log id: {}",
+            launch_id
+        )));
+    }
+    Ok(())
+}
+
+/// Writes the output of one launch, continuing in a new file when the current one is full.
+pub struct LaunchLogWriter {
+    dir: PathBuf,
+    launch_id: String,
+    max_file_bytes: u64,
+    max_files: usize,
+    file: File,
+    part: usize,
+    written: u64,
+    // Oldest part after the first one that still exists
+    oldest_part: usize,
+}
+
+impl LaunchLogWriter {
+    /// Creates the first file of a new launch log named after the start time.
+    pub async fn create(
+        dir: &Path,
+        started_at: DateTime<Local>,
+        config: &LaunchLogConfig,
+    ) -> Result<Self> {
+        fs::create_dir_all(dir).await?;
+        let stamp = started_at.format("%Y-%m-%d_%H-%M-%S").to_string();
+        // Two launches in the same second get a suffix
+        let mut launch_id = stamp.clone();
+        let mut suffix = 1;
+        while fs::try_exists(part_path(dir, &launch_id, 0)).await? {
+            suffix += 1;
+            launch_id = format!("{}_{}", stamp, suffix);
+        }
+        let file = File::create(part_path(dir, &launch_id, 0)).await?;
+        debug!("Writing launch log {:?}", part_path(dir, &launch_id, 0));
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            launch_id,
+            max_file_bytes: config.max_file_size_mb.max(1) * 1024 * 1024,
+            max_files: config.max_files_per_launch.max(2),
+            file,
+            part: 0,
+            written: 0,
+            oldest_part: 1,
+        })
+    }
+
+    pub fn launch_id(&self) -> &str {
+        &self.launch_id
+    }
+
+    pub async fn write_line(&mut self, line: &str) -> Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.written > 0 && self.written + len > self.max_file_bytes {
+            self.rotate().await?;
+        }
+        self.file.write_all(line.as_bytes()).await?;
+        self.file.write_all(b"\n").await?;
+        self.written += len;
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> Result<()> {
+        self.file.flush().await?;
+        self.part += 1;
+        self.file = File::create(part_path(&self.dir, &self.launch_id, self.part)).await?;
+        self.written = 0;
+        // The first part keeps the startup output, the oldest of the others goes
+        while 1 + self.part - self.oldest_part + 1 > self.max_files {
+            let oldest = part_path(&self.dir, &self.launch_id, self.oldest_part);
+            if let Err(e) = fs::remove_file(&oldest).await {
+                warn!("Failed to remove old launch log part {:?}: {}", oldest, e);
+            }
+            self.oldest_part += 1;
+        }
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> Result<()> {
+        self.file.flush().await?;
+        Ok(())
+    }
+}
+
+const LOG4J_EVENT_START: &str = "<log4j:Event";
+const LOG4J_EVENT_END: &str = "</log4j:Event>";
+
+/// Turns the log4j XML events that Mojang's logging config (`-Dlog4j.configurationFile`)
+/// writes to stdout back into `[12:34:56] [Render thread/INFO]: message` lines, like in
+/// latest.log. Everything outside of events is passed through as is. One per stream.
+#[derive(Debug, Default)]
+pub struct Log4jLineParser {
+    event: Option<String>,
+}
+
+impl Log4jLineParser {
+    /// Feeds one line of output. Returns the finished lines, none while inside an event.
+    pub fn push_line(&mut self, line: &str) -> Vec<String> {
+        if self.event.is_none() && !line.trim_start().starts_with(LOG4J_EVENT_START) {
+            return vec![line.to_string()];
+        }
+        let event = self.event.get_or_insert_with(String::new);
+        event.push_str(line);
+        event.push('\n');
+        if !line.contains(LOG4J_EVENT_END) {
+            return Vec::new();
+        }
+
+        let event = self.event.take().unwrap_or_default();
+        format_log4j_event(&event).unwrap_or_else(|| event.lines().map(str::to_string).collect())
+    }
+
+    /// Returns what is left of an unfinished event, e.g. when the process died mid-event.
+    pub fn finish(&mut self) -> Vec<String> {
+        self.event
+            .take()
+            .map(|event| event.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn format_log4j_event(event: &str) -> Option<Vec<String>> {
+    let start = event.find(LOG4J_EVENT_START)? + LOG4J_EVENT_START.len();
+    let open_tag = &event[start..start + event[start..].find('>')?];
+    let attribute = |name: &str| {
+        let value_start = open_tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+        let value = &open_tag[value_start..value_start + open_tag[value_start..].find('"')?];
+        Some(
+            quick_xml::escape::unescape(value)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| value.to_string()),
+        )
+    };
+    let time = attribute("timestamp")
+        .and_then(|millis| millis.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_millis)
+        .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    let thread = attribute("thread").unwrap_or_default();
+    let level = attribute("level").unwrap_or_default();
+
+    let message = log4j_element_text(event, "log4j:Message")?;
+    let mut lines: Vec<String> = message.lines().map(str::to_string).collect();
+    let first = if lines.is_empty() {
+        String::new()
+    } else {
+        lines.remove(0)
+    };
+    lines.insert(0, format!("[{}] [{}/{}]: {}", time, thread, level, first));
+    if let Some(throwable) = log4j_element_text(event, "log4j:Throwable") {
+        lines.extend(throwable.lines().map(str::to_string));
+    }
+    Some(lines)
+}
+
+/// Text of `<name>...</name>`, CDATA sections are joined (log4j splits on `]]>`).
+fn log4j_element_text(event: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let start = event.find(&open)? + open.len();
+    let inner = &event[start..start + event[start..].find(&format!("</{}>", name))?];
+    if !inner.contains("<![CDATA[") {
+        return Some(
+            quick_xml::escape::unescape(inner)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| inner.to_string()),
+        );
+    }
+    let mut text = String::new();
+    let mut rest = inner;
+    while let Some(cdata_start) = rest.find("<![CDATA[") {
+        rest = &rest[cdata_start + "<![CDATA[".len()..];
+        let cdata_end = rest.find("]]>").unwrap_or(rest.len());
+        text.push_str(&rest[..cdata_end]);
+        rest = &rest[(cdata_end + 3).min(rest.len())..];
+    }
+    Some(text)
+}
+
+/// A launch log with all of its files.
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchLogInfo {
+    pub id: String,
+    pub size_bytes: u64,
+    pub files: usize,
+    pub modified: DateTime<Utc>,
+}
+
+/// Launch logs in a directory, newest first.
+pub async fn list_launch_logs(dir: &Path) -> Result<Vec<LaunchLogInfo>> {
+    let mut logs: BTreeMap<String, LaunchLogInfo> = BTreeMap::new();
+    if !fs::try_exists(dir).await? {
+        return Ok(Vec::new());
+    }
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((launch_id, _)) = parse_part_name(&file_name) else {
+            continue;
+        };
+        if validate_launch_id(launch_id).is_err() {
+            continue;
+        }
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified: DateTime<Utc> = metadata.modified().map(Into::into).unwrap_or_default();
+        let info = logs
+            .entry(launch_id.to_string())
+            .or_insert_with(|| LaunchLogInfo {
+                id: launch_id.to_string(),
+                size_bytes: 0,
+                files: 0,
+                modified,
+            });
+        info.size_bytes += metadata.len();
+        info.files += 1;
+        info.modified = info.modified.max(modified);
+    }
+    let mut logs: Vec<LaunchLogInfo> = logs.into_values().collect();
+    logs.sort_by(|a, b| b.modified.cmp(&a.modified).then(b.id.cmp(&a.id)));
+    Ok(logs)
+}
+
+/// All files of a launch log joined in order.
+pub async fn read_launch_log(dir: &Path, launch_id: &str) -> Result<String> {
+    validate_launch_id(launch_id)?;
+    let mut parts = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if let Some((id, part)) = parse_part_name(&file_name) {
+            if id == launch_id {
+                parts.push(part);
+            }
+        }
+    }
+    if parts.is_empty() {
+        return Err(AppError::NotFound(format!("Launch log {}", launch_id)));
+    }
+    parts.sort_unstable();
+
+    let mut content = Vec::new();
+    for (i, part) in parts.iter().enumerate() {
+        // Deleted parts leave a gap between the first and the kept ones
+        if i > 0 && *part != parts[i - 1] + 1 {
+            content.extend_from_slice(b"[...]\n");
+        }
+        content.extend(fs::read(part_path(dir, launch_id, *part)).await?);
+    }
+    Ok(String::from_utf8_lossy(&content).to_string())
+}
+
+/// Deletes launch logs beyond `max_launches` and older than `retention_days`.
+/// Returns the number of deleted launch logs.
+pub async fn prune_launch_logs(
+    dir: &Path,
+    config: &LaunchLogConfig,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let cutoff = now - chrono::Duration::days(config.retention_days as i64);
+    let expired: Vec<LaunchLogInfo> = list_launch_logs(dir)
+        .await?
+        .into_iter()
+        .enumerate()
+        .filter(|(i, log)| *i >= config.max_launches.max(1) || log.modified < cutoff)
+        .map(|(_, log)| log)
+        .collect();
+    if expired.is_empty() {
+        return Ok(0);
+    }
+
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((launch_id, _)) = parse_part_name(&file_name) else {
+            continue;
+        };
+        if expired.iter().any(|log| log.id == launch_id) {
+            if let Err(e) = fs::remove_file(entry.path()).await {
+                warn!("Failed to remove launch log {:?}: {}", entry.path(), e);
+            }
+        }
+    }
+    info!("Pruned {} launch logs in {:?}", expired.len(), dir);
+    Ok(expired.len())
+}
@@ -0,0 +1,485 @@
+use crate::error::{AppError, Result};
+use crate::state::State;
+use crate::utils::world_utils;
+use fastnbt::Value;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{Read, Write};
+use std::path::Path;
+use tokio::fs;
+use uuid::Uuid;
+
+const LEVEL_DAT: &str = "level.dat";
+/// Copy of level.dat taken before the launcher changes it
+const LEVEL_DAT_BACKUP: &str = "level.dat_launcher_backup";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SpawnPoint {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Everything level.dat says about a world.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LevelDatDetails {
+    pub level_name: Option<String>,
+    pub data_version: Option<i32>,
+    pub version_name: Option<String>,
+    pub seed: Option<i64>,
+    pub spawn: Option<SpawnPoint>,
+    pub game_type: Option<i32>,
+    pub difficulty: Option<i8>,
+    pub difficulty_locked: Option<bool>,
+    pub hardcore: Option<bool>,
+    pub allow_commands: Option<bool>,
+    pub last_played: Option<i64>,
+    /// Values as Minecraft shows them in `/gamerule`, e.g. "true" or "3"
+    pub game_rules: BTreeMap<String, String>,
+    pub enabled_datapacks: Vec<String>,
+    pub disabled_datapacks: Vec<String>,
+    /// Dimensions listed in the world generation settings (1.16+). In `WorldDetails` also
+    /// the dimensions the world folder has data for.
+    pub dimensions: Vec<String>,
+}
+
+/// level.dat plus what the world folder shows.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldDetails {
+    pub folder_name: String,
+    #[serde(flatten)]
+    pub level: LevelDatDetails,
+    pub size_bytes: u64,
+    /// Players that have joined the world, from `playerdata`
+    pub player_count: u32,
+    pub locked: bool,
+}
+
+/// The fields of level.dat that can be changed. Unset fields stay as they are.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LevelDatEdit {
+    #[serde(default)]
+    pub level_name: Option<String>,
+    /// 0 = peaceful, 1 = easy, 2 = normal, 3 = hard
+    #[serde(default)]
+    pub difficulty: Option<i8>,
+    /// 0 = survival, 1 = creative, 2 = adventure, 3 = spectator
+    #[serde(default)]
+    pub game_type: Option<i32>,
+    #[serde(default)]
+    pub allow_commands: Option<bool>,
+    /// Only rules the world already has can be changed, values must keep their type
+    #[serde(default)]
+    pub game_rules: HashMap<String, String>,
+}
+
+fn decode(level_dat_gz: &[u8]) -> Result<Value> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(level_dat_gz).read_to_end(&mut decompressed)?;
+    Ok(fastnbt::from_bytes(&decompressed)?)
+}
+
+fn encode(root: &Value) -> Result<Vec<u8>> {
+    let bytes = fastnbt::to_bytes(root)
+        .map_err(|e| AppError::Other(format!("NBT serialization error: {}", e)))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&bytes)?;
+    Ok(encoder.finish()?)
+}
+
+fn data_compound(root: &Value) -> Result<&HashMap<String, Value>> {
+    match root {
+        Value::Compound(root) => match root.get("Data") {
+            Some(Value::Compound(data)) => Ok(data),
+            _ => Err(AppError::ParseError(
+                "level.dat has no Data compound".to_string(),
+            )),
+        },
+        _ => Err(AppError::ParseError(
+            "level.dat root is not a compound".to_string(),
+        )),
+    }
+}
+
+fn data_compound_mut(root: &mut Value) -> Result<&mut HashMap<String, Value>> {
+    match root {
+        Value::Compound(root) => match root.get_mut("Data") {
+            Some(Value::Compound(data)) => Ok(data),
+            _ => Err(AppError::ParseError(
+                "level.dat has no Data compound".to_string(),
+            )),
+        },
+        _ => Err(AppError::ParseError(
+            "level.dat root is not a compound".to_string(),
+        )),
+    }
+}
+
+fn get<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Compound(map) => map.get(key),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Byte(v) => Some(*v as i64),
+        Value::Short(v) => Some(*v as i64),
+        Value::Int(v) => Some(*v as i64),
+        Value::Long(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::List(items)) => items.iter().filter_map(as_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Game rule values are strings in older worlds and typed in newer ones.
+fn game_rule_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(v) => Some(v.clone()),
+        Value::Byte(v) => Some((*v != 0).to_string()),
+        other => as_i64(other).map(|v| v.to_string()),
+    }
+}
+
+/// Reads the details of a gzipped level.dat.
+pub fn parse_level_dat_details(level_dat_gz: &[u8]) -> Result<LevelDatDetails> {
+    let root = decode(level_dat_gz)?;
+    let data = data_compound(&root)?;
+    let field = |key: &str| data.get(key);
+    let flag = |key: &str| field(key).and_then(as_i64).map(|v| v != 0);
+
+    let seed = field("WorldGenSettings")
+        .and_then(|settings| get(settings, "seed"))
+        .or_else(|| field("RandomSeed"))
+        .and_then(as_i64);
+
+    // Newer versions store the spawn as `spawn: {pos: [I; x, y, z], ...}`
+    let spawn = match field("spawn").and_then(|spawn| get(spawn, "pos")) {
+        Some(Value::IntArray(pos)) if pos.len() == 3 => Some(SpawnPoint {
+            x: pos[0],
+            y: pos[1],
+            z: pos[2],
+        }),
+        _ => match (
+            field("SpawnX").and_then(as_i64),
+            field("SpawnY").and_then(as_i64),
+            field("SpawnZ").and_then(as_i64),
+        ) {
+            (Some(x), Some(y), Some(z)) => Some(SpawnPoint {
+                x: x as i32,
+                y: y as i32,
+                z: z as i32,
+            }),
+            _ => None,
+        },
+    };
+
+    let game_rules = match field("GameRules").or_else(|| field("game_rules")) {
+        Some(Value::Compound(rules)) => rules
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), game_rule_string(value)?)))
+            .collect(),
+        _ => BTreeMap::new(),
+    };
+
+    let mut dimensions: Vec<String> =
+        match field("WorldGenSettings").and_then(|settings| get(settings, "dimensions")) {
+            Some(Value::Compound(dimensions)) => dimensions.keys().cloned().collect(),
+            _ => Vec::new(),
+        };
+    dimensions.sort();
+
+    Ok(LevelDatDetails {
+        level_name: field("LevelName").and_then(as_string),
+        data_version: field("DataVersion").and_then(as_i64).map(|v| v as i32),
+        version_name: field("Version")
+            .and_then(|v| get(v, "Name"))
+            .and_then(as_string),
+        seed,
+        spawn,
+        game_type: field("GameType").and_then(as_i64).map(|v| v as i32),
+        difficulty: field("Difficulty").and_then(as_i64).map(|v| v as i8),
+        difficulty_locked: flag("DifficultyLocked"),
+        hardcore: flag("hardcore"),
+        allow_commands: flag("allowCommands"),
+        last_played: field("LastPlayed").and_then(as_i64),
+        game_rules,
+        enabled_datapacks: string_list(field("DataPacks").and_then(|p| get(p, "Enabled"))),
+        disabled_datapacks: string_list(field("DataPacks").and_then(|p| get(p, "Disabled"))),
+        dimensions,
+    })
+}
+
+/// Converts a game rule value to the type the world stores the rule with.
+fn game_rule_value(name: &str, current: &Value, new_value: &str) -> Result<Value> {
+    let new_value = new_value.trim();
+    let invalid = || {
+        AppError::InvalidInput(format!(
+            "Invalid value '{}' for game rule '{}'",
+            new_value, name
+        ))
+    };
+    let current_text = game_rule_string(current).unwrap_or_default();
+    let is_bool = matches!(current_text.as_str(), "true" | "false");
+    match current {
+        Value::String(_) if is_bool => match new_value {
+            "true" | "false" => Ok(Value::String(new_value.to_string())),
+            _ => Err(invalid()),
+        },
+        Value::String(_) => new_value
+            .parse::<i64>()
+            .map(|v| Value::String(v.to_string()))
+            .map_err(|_| invalid()),
+        Value::Byte(_) => match new_value {
+            "true" => Ok(Value::Byte(1)),
+            "false" => Ok(Value::Byte(0)),
+            _ => Err(invalid()),
+        },
+        Value::Short(_) => new_value.parse().map(Value::Short).map_err(|_| invalid()),
+        Value::Int(_) => new_value.parse().map(Value::Int).map_err(|_| invalid()),
+        Value::Long(_) => new_value.parse().map(Value::Long).map_err(|_| invalid()),
+        _ => Err(AppError::InvalidInput(format!(
+            "Game rule '{}' can't be edited",
+            name
+        ))),
+    }
+}
+
+/// Applies an edit to a gzipped level.dat and returns the new file. Nothing is changed if any
+/// value is invalid.
+pub fn apply_level_dat_edit(level_dat_gz: &[u8], edit: &LevelDatEdit) -> Result<Vec<u8>> {
+    if let Some(difficulty) = edit.difficulty {
+        if !(0..=3).contains(&difficulty) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid difficulty {}",
+                difficulty
+            )));
+        }
+    }
+    if let Some(game_type) = edit.game_type {
+        if !(0..=3).contains(&game_type) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid game type {}",
+                game_type
+            )));
+        }
+    }
+    if let Some(name) = &edit.level_name {
+        if name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "The world name can't be empty".to_string(),
+            ));
+        }
+    }
+
+    let mut root = decode(level_dat_gz)?;
+    let data = data_compound_mut(&mut root)?;
+
+    if !edit.game_rules.is_empty() {
+        let rules_key = if data.contains_key("GameRules") {
+            "GameRules"
+        } else {
+            "game_rules"
+        };
+        let Some(Value::Compound(rules)) = data.get_mut(rules_key) else {
+            return Err(AppError::InvalidInput(
+                "The world has no game rules".to_string(),
+            ));
+        };
+        // Validate all rules before changing any
+        let mut new_values = Vec::with_capacity(edit.game_rules.len());
+        for (name, value) in &edit.game_rules {
+            let current = rules
+                .get(name)
+                .ok_or_else(|| AppError::InvalidInput(format!("Unknown game rule '{}'", name)))?;
+            new_values.push((name.clone(), game_rule_value(name, current, value)?));
+        }
+        rules.extend(new_values);
+    }
+
+    if let Some(name) = &edit.level_name {
+        data.insert(
+            "LevelName".to_string(),
+            Value::String(name.trim().to_string()),
+        );
+    }
+    if let Some(difficulty) = edit.difficulty {
+        data.insert("Difficulty".to_string(), Value::Byte(difficulty));
+    }
+    if let Some(allow_commands) = edit.allow_commands {
+        data.insert(
+            "allowCommands".to_string(),
+            Value::Byte(allow_commands as i8),
+        );
+    }
+    if let Some(game_type) = edit.game_type {
+        data.insert("GameType".to_string(), Value::Int(game_type));
+        // The singleplayer player keeps their own game mode, which wins over the world's
+        if let Some(Value::Compound(player)) = data.get_mut("Player") {
+            player.insert("playerGameType".to_string(), Value::Int(game_type));
+        }
+    }
+
+    encode(&root)
+}
+
+fn dimension_of_folder(relative: &str) -> Option<String> {
+    match relative {
+        "region" => Some("minecraft:overworld".to_string()),
+        "DIM-1" => Some("minecraft:the_nether".to_string()),
+        "DIM1" => Some("minecraft:the_end".to_string()),
+        other => other
+            .strip_prefix("DIM")
+            .and_then(|n| n.parse::<i32>().ok())
+            .map(|_| other.to_string()),
+    }
+}
+
+/// Walks the world folder for its size, player count and dimensions with data.
+async fn scan_world_folder(world_path: &Path) -> Result<(u64, u32, BTreeSet<String>)> {
+    let mut size = 0;
+    let mut dimensions = BTreeSet::new();
+    let mut pending = vec![world_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+
+    let mut entries = fs::read_dir(world_path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.path().is_dir() {
+            if let Some(dimension) = dimension_of_folder(&name) {
+                dimensions.insert(dimension);
+            }
+        }
+    }
+    if let Ok(mut namespaces) = fs::read_dir(world_path.join("dimensions")).await {
+        while let Some(namespace) = namespaces.next_entry().await? {
+            let Ok(mut names) = fs::read_dir(namespace.path()).await else {
+                continue;
+            };
+            while let Some(name) = names.next_entry().await? {
+                if name.path().is_dir() {
+                    dimensions.insert(format!(
+                        "{}:{}",
+                        namespace.file_name().to_string_lossy(),
+                        name.file_name().to_string_lossy()
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut player_count = 0;
+    if let Ok(mut players) = fs::read_dir(world_path.join("playerdata")).await {
+        while let Some(player) = players.next_entry().await? {
+            if player.path().extension().is_some_and(|e| e == "dat") {
+                player_count += 1;
+            }
+        }
+    }
+    Ok((size, player_count, dimensions))
+}
+
+pub(crate) async fn world_path(profile_id: Uuid, world_folder: &str) -> Result<std::path::PathBuf> {
+    if world_folder.is_empty() || world_folder.contains(['/', '\\']) || world_folder == ".." {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid world folder name '{}'",
+            world_folder
+        )));
+    }
+    let state = State::get().await?;
+    let path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?
+        .join("saves")
+        .join(world_folder);
+    if !path.join(LEVEL_DAT).is_file() {
+        return Err(AppError::WorldNotFound {
+            profile_id,
+            world_folder: world_folder.to_string(),
+        });
+    }
+    Ok(path)
+}
+
+/// Reads all details of a world.
+pub async fn get_world_details(profile_id: Uuid, world_folder: &str) -> Result<WorldDetails> {
+    let world_path = world_path(profile_id, world_folder).await?;
+    let mut level = parse_level_dat_details(&fs::read(world_path.join(LEVEL_DAT)).await?)?;
+    let (size_bytes, player_count, mut dimensions) = scan_world_folder(&world_path).await?;
+    dimensions.extend(level.dimensions.drain(..));
+    level.dimensions = dimensions.into_iter().collect();
+    let locked = matches!(
+        world_utils::check_world_session_lock(&world_path).await,
+        Err(AppError::WorldLocked { .. })
+    );
+    Ok(WorldDetails {
+        folder_name: world_folder.to_string(),
+        level,
+        size_bytes,
+        player_count,
+        locked,
+    })
+}
+
+/// Changes level.dat of a world that isn't open. The previous level.dat is kept as
+/// `level.dat_launcher_backup`.
+pub async fn edit_world(
+    profile_id: Uuid,
+    world_folder: &str,
+    edit: &LevelDatEdit,
+) -> Result<WorldDetails> {
+    let world_path = world_path(profile_id, world_folder).await?;
+    if let Err(e) = world_utils::check_world_session_lock(&world_path).await {
+        return Err(match e {
+            AppError::WorldLocked { .. } => AppError::WorldLocked {
+                profile_id,
+                world_folder: world_folder.to_string(),
+            },
+            other => other,
+        });
+    }
+
+    let level_dat_path = world_path.join(LEVEL_DAT);
+    let current = fs::read(&level_dat_path).await?;
+    let updated = apply_level_dat_edit(&current, edit)?;
+
+    fs::write(world_path.join(LEVEL_DAT_BACKUP), &current).await?;
+    let tmp_path = world_path.join(format!("{}.tmp", LEVEL_DAT));
+    fs::write(&tmp_path, &updated).await?;
+    fs::rename(&tmp_path, &level_dat_path).await?;
+    info!(
+        "Edited level.dat of world '{}' in profile {}: {:?}",
+        world_folder, profile_id, edit
+    );
+
+    get_world_details(profile_id, world_folder).await
+}
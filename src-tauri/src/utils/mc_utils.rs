@@ -14,6 +14,7 @@ use serde::Deserialize;
 use serde::Serialize; // Added Serialize directly
                       // To represent NBT Compound
 use futures::future::try_join_all;
+use once_cell::sync::Lazy;
 use std::env;
 use std::io::{Cursor, Read}; // Needed for reading NBT from bytes and decompression
 use std::net::SocketAddr;
@@ -41,6 +42,12 @@ use std::path::Path;
 
 // Referenziere unsere server_ping-Modul, das sich im gleichen Verzeichnis befindet
 
+const DEFAULT_SERVER_PORT: u16 = 25565;
+
+// Shared so repeated pings (server monitor) benefit from the DNS cache
+static DNS_RESOLVER: Lazy<TokioAsyncResolver> =
+    Lazy::new(|| TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default()));
+
 // --- Struct for World Info ---
 #[derive(Debug, Clone, Serialize)]
 pub struct WorldInfo {
@@ -988,8 +995,15 @@ pub struct ServerInfo {
 /// Parses a Minecraft server address string (e.g., "example.com", "example.com:25566", "[::1]:25565")
 /// into host and port, handling default port and IPv6 bracket notation.
 /// Inspired by Modrinth Launcher's implementation.
-fn parse_minecraft_address(address: &str) -> std::result::Result<(String, u16), String> {
-    let default_port = 25565;
+pub(crate) fn parse_minecraft_address(
+    address: &str,
+) -> std::result::Result<(String, u16), String> {
+    let (host, port) = split_minecraft_address(address)?;
+    Ok((host, port.unwrap_or(DEFAULT_SERVER_PORT)))
+}
+
+/// Like `parse_minecraft_address`, but without filling in the default port.
+fn split_minecraft_address(address: &str) -> std::result::Result<(String, Option<u16>), String> {
     let (host_part, port_str) = if address.starts_with('[') {
         // IPv6 Address like [::1] or [::1]:25566
         let close_bracket_index = match address.rfind(']') {
@@ -1045,10 +1059,10 @@ fn parse_minecraft_address(address: &str) -> std::result::Result<(String, u16),
 
     let port = match port_str {
         Some(p_str) => match p_str.parse::<u16>() {
-            Ok(p) => p,
+            Ok(p) => Some(p),
             Err(_) => return Err(format!("Unparseable port number: {}", p_str)),
         },
-        None => default_port,
+        None => None,
     };
 
     // Basic validation: host part should not be empty
@@ -1059,6 +1073,70 @@ fn parse_minecraft_address(address: &str) -> std::result::Result<(String, u16),
     Ok((host_part.to_string(), port))
 }
 
+/// Where to connect to for a server address.
+#[derive(Debug, Clone)]
+pub struct ResolvedServerAddress {
+    /// Host and port after the SRV lookup, sent in the handshake
+    pub host: String,
+    pub port: u16,
+    pub socket_address: SocketAddr,
+}
+
+/// Resolves a server address like Minecraft does: the `_minecraft._tcp` SRV record is only
+/// looked up if the address has no explicit port.
+pub async fn resolve_server_address(address: &str) -> Result<ResolvedServerAddress> {
+    let (host, port) = split_minecraft_address(address.trim())
+        .map_err(|e| AppError::InvalidInput(format!("Invalid address format: {}", e)))?;
+
+    let mut target = (host.clone(), port.unwrap_or(DEFAULT_SERVER_PORT));
+    if port.is_none() && host.parse::<std::net::IpAddr>().is_err() {
+        let srv_query = format!("_minecraft._tcp.{}", host);
+        debug!("[Server Ping] Attempting SRV lookup for: {}", srv_query);
+        match DNS_RESOLVER.srv_lookup(srv_query.as_str()).await {
+            Ok(srv) => {
+                if let Some(record) = srv
+                    .iter()
+                    .min_by_key(|r| (r.priority(), std::cmp::Reverse(r.weight())))
+                {
+                    let srv_host = record.target().to_utf8();
+                    target = (srv_host.trim_end_matches('.').to_string(), record.port());
+                    debug!(
+                        "[Server Ping] SRV lookup successful: Target = {}:{}",
+                        target.0, target.1
+                    );
+                }
+            }
+            Err(e) => debug!(
+                "[Server Ping] SRV lookup for '{}' failed: {}. Using parsed host/port.",
+                srv_query, e
+            ),
+        }
+    }
+
+    let ip = match target.0.parse::<std::net::IpAddr>() {
+        Ok(ip) => ip,
+        Err(_) => DNS_RESOLVER
+            .lookup_ip(target.0.as_str())
+            .await
+            .map_err(|e| {
+                AppError::Other(format!("Failed to resolve hostname '{}': {}", target.0, e))
+            })?
+            .iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::Other(format!(
+                    "DNS lookup for '{}' returned no IP addresses",
+                    target.0
+                ))
+            })?,
+    };
+    Ok(ResolvedServerAddress {
+        socket_address: SocketAddr::new(ip, target.1),
+        host: target.0,
+        port: target.1,
+    })
+}
+
 /// Lists the multiplayer servers found in the profile's servers.dat file.
 pub async fn get_profile_servers(profile_id: Uuid) -> Result<Vec<ServerInfo>> {
     info!("[Servers] Getting servers for profile {}", profile_id);
@@ -1211,6 +1289,8 @@ pub struct ServerPingInfo {
     pub players_max: Option<u32>,
     pub favicon_base64: Option<String>, // Base64 PNG string (without data:image/png;base64,)
     pub latency_ms: Option<u64>,
+    /// Answered the pre-1.7 server list ping only
+    pub legacy: bool,
     #[serde(skip_serializing_if = "Option::is_none")] // Don't include error if None
     pub error: Option<String>,
 }
@@ -1229,6 +1309,7 @@ impl ServerPingInfo {
             players_max: None,
             favicon_base64: None,
             latency_ms: latency, // Include latency if measured before error
+            legacy: false,
             error: Some(error_msg),
         }
     }
@@ -1267,6 +1348,7 @@ impl ServerPingInfo {
             players_max: status.players.as_ref().map(|p| p.max as u32),
             favicon_base64,
             latency_ms: status.ping.map(|p| p as u64),
+            legacy: status.legacy,
             error: None,
         }
     }
@@ -1307,75 +1389,20 @@ fn extract_text_from_json(value: &serde_json::Value) -> Option<String> {
 pub async fn ping_server_status(address: &str) -> ServerPingInfo {
     info!("[Server Ping] Pinging server address: {}", address);
 
-    // Parse the address
-    let (host, port) = match parse_minecraft_address(address) {
-        Ok((h, p)) => (h, p),
-        Err(e) => {
-            return ServerPingInfo::error(address, format!("Invalid address format: {}", e), None);
-        }
-    };
-
     // Resolve the server (including SRV records)
-    let resolver = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
-
-    // SRV Lookup
-    let srv_query = format!("_minecraft._tcp.{}", host);
-    info!("[Server Ping] Attempting SRV lookup for: {}", srv_query);
-
-    let (target_host, target_port) = match resolver.srv_lookup(srv_query.as_str()).await {
-        Ok(srv) => {
-            if let Some(record) = srv
-                .iter()
-                .min_by_key(|r| (r.priority(), std::cmp::Reverse(r.weight())))
-            {
-                let srv_host = record.target().to_utf8();
-                let srv_port = record.port();
-                info!(
-                    "[Server Ping] SRV lookup successful: Target = {}:{}",
-                    srv_host, srv_port
-                );
-                (srv_host, srv_port)
-            } else {
-                info!("[Server Ping] SRV lookup for '{}' returned no records. Using parsed host/port.", srv_query);
-                (host.to_string(), port)
-            }
-        }
-        Err(e) => {
-            warn!(
-                "[Server Ping] SRV lookup for '{}' failed: {}. Falling back to parsed host/port.",
-                srv_query, e
-            );
-            (host.to_string(), port)
-        }
+    let resolved = match resolve_server_address(address).await {
+        Ok(resolved) => resolved,
+        Err(e) => return ServerPingInfo::error(address, e.to_string(), None),
     };
-
-    // Resolve IP address
-    let ip_lookup = resolver.lookup_ip(target_host.as_str()).await;
-    let socket_address = match ip_lookup {
-        Ok(lookup) => match lookup.iter().next() {
-            Some(ip) => SocketAddr::new(ip, target_port),
-            None => {
-                return ServerPingInfo::error(
-                    address,
-                    format!("DNS lookup for '{}' returned no IP addresses", target_host),
-                    None,
-                );
-            }
-        },
-        Err(e) => {
-            return ServerPingInfo::error(
-                address,
-                format!("Failed to resolve hostname '{}': {}", target_host, e),
-                None,
-            );
-        }
-    };
-
-    info!("[Server Ping] Resolved to: {}", socket_address);
+    info!("[Server Ping] Resolved to: {}", resolved.socket_address);
 
     // Ping the server using our server_ping implementation
-    match super::server_ping::get_server_status(&socket_address, (&target_host, target_port), None)
-        .await
+    match super::server_ping::get_server_status(
+        &resolved.socket_address,
+        (&resolved.host, resolved.port),
+        None,
+    )
+    .await
     {
         Ok(status) => ServerPingInfo::from_server_status(status),
         Err(e) => ServerPingInfo::error(address, format!("Server ping failed: {}", e), None),
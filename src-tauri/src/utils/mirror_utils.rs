@@ -0,0 +1,183 @@
+use crate::config::HTTP_CLIENT;
+use crate::error::{AppError, Result};
+use crate::state::config_state::MirrorConfig;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use reqwest::{Method, RequestBuilder, Response};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Active mirror configuration. Set from the `LauncherConfig` on load and on every change,
+/// so code paths without access to the `State` (download services, API structs) can use it.
+static ACTIVE_MIRRORS: Lazy<RwLock<MirrorConfig>> =
+    Lazy::new(|| RwLock::new(MirrorConfig::default()));
+
+/// Replaces the active mirror configuration.
+pub fn apply_mirror_config(config: &MirrorConfig) {
+    let rule_count = config.rules.len();
+    match ACTIVE_MIRRORS.write() {
+        Ok(mut active) => *active = config.clone(),
+        Err(poisoned) => *poisoned.into_inner() = config.clone(),
+    }
+    info!(
+        "Applied mirror configuration ({} rules, fallback to upstream: {})",
+        rule_count, config.fallback_to_upstream
+    );
+}
+
+fn active_config() -> MirrorConfig {
+    match ACTIVE_MIRRORS.read() {
+        Ok(active) => active.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// All URLs to try for `url`, in order: the mirrors of the most specific matching rule,
+/// then the original URL (unless `fallback_to_upstream` is disabled).
+/// Without a matching rule this is just `[url]`.
+pub fn candidate_urls(url: &str) -> Vec<String> {
+    let config = active_config();
+    let rule = config
+        .rules
+        .iter()
+        .filter(|rule| !rule.upstream.is_empty() && url.starts_with(&rule.upstream))
+        .max_by_key(|rule| rule.upstream.len());
+
+    let Some(rule) = rule else {
+        return vec![url.to_string()];
+    };
+
+    let suffix = &url[rule.upstream.len()..];
+    let mut candidates: Vec<String> = rule
+        .mirrors
+        .iter()
+        .map(|mirror| join_url(mirror, suffix))
+        .collect();
+    if config.fallback_to_upstream || candidates.is_empty() {
+        candidates.push(url.to_string());
+    }
+    debug!("Mirror candidates for {}: {:?}", url, candidates);
+    candidates
+}
+
+pub fn is_file_url(url: &str) -> bool {
+    url.starts_with("file://")
+}
+
+/// `file:///srv/mirror/a.jar` -> `/srv/mirror/a.jar`, `file:///C:/mirror/a.jar` -> `C:/mirror/a.jar`
+pub fn file_url_to_path(url: &str) -> Option<PathBuf> {
+    let parsed = url::Url::parse(url).ok()?;
+    if parsed.scheme() != "file" {
+        return None;
+    }
+    parsed.to_file_path().ok()
+}
+
+fn join_url(prefix: &str, suffix: &str) -> String {
+    match (prefix.ends_with('/'), suffix.starts_with('/')) {
+        (true, true) => format!("{}{}", prefix, &suffix[1..]),
+        (false, false) if !suffix.is_empty() && !suffix.starts_with('?') => {
+            format!("{}/{}", prefix, suffix)
+        }
+        _ => format!("{}{}", prefix, suffix),
+    }
+}
+
+/// GETs `url` through the mirror list and returns the body of the first candidate that works.
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
+    let mut last_error = None;
+    for candidate in candidate_urls(url) {
+        match fetch_candidate(&candidate).await {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => {
+                warn!("Fetching {} failed: {}", candidate, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| AppError::Download(format!("Failed to fetch {}", url))))
+}
+
+/// Sends a request to `url` through the mirror list. The next candidate is tried on
+/// connection errors and server errors, the response of the last candidate is returned as is.
+/// `file://` mirrors can only answer GET requests. `build` adds headers and bodies.
+pub async fn send_with_fallback(
+    method: Method,
+    url: &str,
+    build: impl Fn(RequestBuilder) -> RequestBuilder,
+) -> Result<Response> {
+    let candidates = candidate_urls(url);
+    let last_index = candidates.len().saturating_sub(1);
+    let mut last_error = None;
+    for (index, candidate) in candidates.into_iter().enumerate() {
+        if is_file_url(&candidate) {
+            if method != Method::GET {
+                debug!("Skipping file mirror {} for {} request", candidate, method);
+                continue;
+            }
+            match fetch_candidate(&candidate).await {
+                Ok(bytes) => return Ok(Response::from(http::Response::new(bytes))),
+                Err(e) => {
+                    warn!("Fetching {} failed: {}", candidate, e);
+                    last_error = Some(e);
+                }
+            }
+            continue;
+        }
+
+        match build(HTTP_CLIENT.request(method.clone(), &candidate))
+            .send()
+            .await
+        {
+            Ok(response) if !response.status().is_server_error() || index == last_index => {
+                return Ok(response)
+            }
+            Ok(response) => {
+                warn!(
+                    "Request to {} failed: Status {}",
+                    candidate,
+                    response.status()
+                );
+                last_error = Some(AppError::Download(format!(
+                    "Request to {} failed: Status {}",
+                    candidate,
+                    response.status()
+                )));
+            }
+            Err(e) => {
+                warn!("Request to {} failed: {}", candidate, e);
+                last_error = Some(AppError::Download(format!(
+                    "Request failed for {}: {}",
+                    candidate, e
+                )));
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| AppError::Download(format!("No usable mirror for {}", url))))
+}
+
+async fn fetch_candidate(url: &str) -> Result<Vec<u8>> {
+    if is_file_url(url) {
+        let path = file_url_to_path(url)
+            .ok_or_else(|| AppError::Download(format!("Invalid file URL: {}", url)))?;
+        return Ok(tokio::fs::read(&path).await?);
+    }
+
+    let response = HTTP_CLIENT
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| AppError::Download(format!("Request failed for {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(AppError::Download(format!(
+            "Request to {} failed: Status {}",
+            url,
+            response.status()
+        )));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::Download(format!("Failed to read response of {}: {}", url, e)))?;
+    Ok(bytes.to_vec())
+}
@@ -1,16 +1,30 @@
 pub mod datapack_utils; // DataPack-Utils für das Scannen und Verwalten von DataPacks
 pub mod debug_utils;
+pub mod download_utils; // Shared downloader: resume via Range, retries, hash/size verification
 pub mod file_utils; // Utilities for file operations like reading archives
 pub mod hash_utils;
+pub mod hook_utils; // Launch hooks (pre-launch, wrapper, post-exit) with placeholders
 pub mod java_detector; // Java detector to find Java installations
+pub mod launch_log_utils; // Per-launch stdout/stderr files with size-based rotation and retention
+pub mod level_dat_utils; // World details from level.dat and safe editing of selected fields
 pub mod mc_utils; // Utilities for Minecraft-related operations
+pub mod mod_metadata; // Reads fabric.mod.json, quilt.mod.json, mods.toml and mcmod.info from mod jars
+pub mod mirror_utils; // URL rewriting to configured mirrors (incl. file:// local caches)
+pub mod offline_utils; // Offline mode flag and persisted metadata for launching without network
 pub mod path_utils; // Deklariert das neue Modul und macht seinen Inhalt (wenn `pub`) nutzbar
+pub mod process_stats_utils; // Rolling resource samples (memory, CPU, threads, disk I/O) of game processes
 pub mod profile_utils; // Utility-Funktionen für Profilinhalte wie das Installieren von Modrinth-Content
 pub mod resourcepack_utils; // ResourcePack-Utils für das Scannen und Verwalten von ResourcePacks
 pub mod server_ping; // Server-Ping-Modul für die Kommunikation mit Minecraft-Servern
+pub mod server_properties_utils; // server.properties and eula.txt of dedicated server profiles
+pub mod servers_dat_utils; // Editing servers.dat and pushing launcher favorites into it
 pub mod shaderpack_utils; // ShaderPack-Utils für das Scannen und Verwalten von ShaderPacks
 pub mod system_info; // <-- Hinzufügen
 pub mod updater_utils;
+pub mod version_range; // Fabric predicates and maven ranges as declared in mod metadata
+pub mod world_backup_utils; // Archive and incremental (chunk-deduplicated) world backups
+pub mod world_map_utils; // Top-down map tiles rendered from region files, cached per world
+pub mod world_transfer_utils; // World zip import/export and DataVersion/mod dimension checks
 pub mod world_utils; // <-- Hinzugefügt
 
 // Füge hier ggf. andere Util-Module hinzu
@@ -0,0 +1,811 @@
+use crate::error::{AppError, Result};
+use crate::state::profile_state::ModLoader;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// How deep jar-in-jar nesting is followed.
+const MAX_NESTING_DEPTH: usize = 3;
+
+const FABRIC_MOD_JSON: &str = "fabric.mod.json";
+const QUILT_MOD_JSON: &str = "quilt.mod.json";
+const FORGE_MODS_TOML: &str = "META-INF/mods.toml";
+const NEOFORGE_MODS_TOML: &str = "META-INF/neoforge.mods.toml";
+const MCMOD_INFO: &str = "mcmod.info";
+const JARJAR_METADATA: &str = "META-INF/jarjar/metadata.json";
+const MANIFEST: &str = "META-INF/MANIFEST.MF";
+
+/// Which metadata file a mod was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModMetadataFormat {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+    /// `mcmod.info` of pre-1.13 Forge mods
+    LegacyForge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModEnvironment {
+    Client,
+    Server,
+    Both,
+    /// The format doesn't declare it (Forge)
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    Required,
+    Optional,
+    /// Must not be installed together (`breaks`, `incompatible`)
+    Incompatible,
+    /// Works, but causes known issues (`conflicts`, `discouraged`)
+    Discouraged,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModDependency {
+    pub mod_id: String,
+    /// Version predicate in the format's own syntax (`>=0.15`, `[47,)`), `None` means any
+    pub version_range: Option<String>,
+    pub kind: DependencyKind,
+    pub environment: ModEnvironment,
+}
+
+/// One mod declared in a jar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModMetadata {
+    pub format: ModMetadataFormat,
+    pub mod_id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub environment: ModEnvironment,
+    /// Icon path inside the jar as declared by the metadata
+    pub icon: Option<String>,
+    /// Base64 encoded icon, only filled if requested
+    pub icon_data: Option<String>,
+    pub dependencies: Vec<ModDependency>,
+    /// Additional mod ids this mod provides (Fabric `provides`)
+    pub provides: Vec<String>,
+}
+
+impl ModMetadata {
+    /// Name for display, falls back to the mod id.
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.mod_id)
+    }
+}
+
+/// Everything a jar declares, including the jars nested inside it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JarMetadata {
+    pub mods: Vec<ModMetadata>,
+    pub nested: Vec<NestedJarMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NestedJarMetadata {
+    /// Path of the nested jar inside its parent
+    pub path: String,
+    pub metadata: JarMetadata,
+}
+
+impl JarMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty() && self.nested.is_empty()
+    }
+
+    /// All mods of this jar followed by those of the nested jars.
+    pub fn all_mods(&self) -> Vec<&ModMetadata> {
+        let mut mods: Vec<&ModMetadata> = self.mods.iter().collect();
+        for nested in &self.nested {
+            mods.extend(nested.metadata.all_mods());
+        }
+        mods
+    }
+
+    /// The mods the given loader would load from this jar (one metadata file can declare
+    /// several). Multi-loader jars carry several metadata files, Quilt falls back to Fabric
+    /// metadata and NeoForge to `mods.toml`.
+    pub fn loadable_mods(&self, loader: &ModLoader) -> Vec<&ModMetadata> {
+        let preference: &[ModMetadataFormat] = match loader {
+            ModLoader::Fabric => &[ModMetadataFormat::Fabric],
+            ModLoader::Quilt => &[ModMetadataFormat::Quilt, ModMetadataFormat::Fabric],
+            ModLoader::Forge => &[ModMetadataFormat::Forge, ModMetadataFormat::LegacyForge],
+            ModLoader::NeoForge => &[ModMetadataFormat::NeoForge, ModMetadataFormat::Forge],
+            ModLoader::Vanilla => &[],
+        };
+        let format = preference
+            .iter()
+            .find(|format| self.mods.iter().any(|m| m.format == **format));
+        match format {
+            Some(format) => self.mods.iter().filter(|m| m.format == *format).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn loadable_by(&self, loader: &ModLoader) -> Option<&ModMetadata> {
+        self.loadable_mods(loader).into_iter().next()
+    }
+
+    /// Like [`JarMetadata::loadable_by`], but falls back to any declared mod for display.
+    pub fn primary_for_loader(&self, loader: &ModLoader) -> Option<&ModMetadata> {
+        self.loadable_by(loader).or_else(|| self.mods.first())
+    }
+}
+
+/// Reads the mod metadata of a jar. Malformed metadata files are logged and skipped,
+/// only an unreadable archive is an error. Icons are loaded as Base64 if `include_icons` is set.
+pub async fn read_jar_metadata(path: &Path, include_icons: bool) -> Result<JarMetadata> {
+    if !path.exists() {
+        return Err(AppError::FileNotFound(path.to_path_buf()));
+    }
+    let path_buf = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path_buf)?;
+        read_jar_metadata_from_reader(BufReader::new(file), include_icons, 0)
+            .map_err(|e| AppError::ArchiveReadError(format!("{}: {}", path_buf.display(), e)))
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Metadata task failed: {}", e)))?
+}
+
+/// Same as [`read_jar_metadata`] for a jar already in memory.
+#[allow(dead_code)] // Only used by the tests and the library
+pub fn read_jar_metadata_from_bytes(bytes: &[u8], include_icons: bool) -> Result<JarMetadata> {
+    read_jar_metadata_from_reader(Cursor::new(bytes), include_icons, 0)
+}
+
+fn read_jar_metadata_from_reader<R: Read + Seek>(
+    reader: R,
+    include_icons: bool,
+    depth: usize,
+) -> Result<JarMetadata> {
+    let mut archive = ZipArchive::new(reader)?;
+    let mut metadata = JarMetadata::default();
+    let mut nested_paths: Vec<String> = Vec::new();
+
+    if let Some(content) = read_entry_string(&mut archive, FABRIC_MOD_JSON) {
+        match parse_fabric_mod_json(&content) {
+            Ok((fabric_mod, jars)) => {
+                metadata.mods.push(fabric_mod);
+                nested_paths.extend(jars);
+            }
+            Err(e) => warn!("Skipping invalid {}: {}", FABRIC_MOD_JSON, e),
+        }
+    }
+
+    if let Some(content) = read_entry_string(&mut archive, QUILT_MOD_JSON) {
+        match parse_quilt_mod_json(&content) {
+            Ok((quilt_mod, jars)) => {
+                metadata.mods.push(quilt_mod);
+                nested_paths.extend(jars);
+            }
+            Err(e) => warn!("Skipping invalid {}: {}", QUILT_MOD_JSON, e),
+        }
+    }
+
+    let toml_files = [
+        (NEOFORGE_MODS_TOML, Some(ModMetadataFormat::NeoForge)),
+        (FORGE_MODS_TOML, None),
+    ];
+    for (toml_path, format) in toml_files {
+        if let Some(content) = read_entry_string(&mut archive, toml_path) {
+            let manifest = read_entry_string(&mut archive, MANIFEST)
+                .map(|m| parse_manifest(&m))
+                .unwrap_or_default();
+            match parse_mods_toml(&content, format, &manifest) {
+                Ok(mods) => metadata.mods.extend(mods),
+                Err(e) => warn!("Skipping invalid {}: {}", toml_path, e),
+            }
+        }
+    }
+
+    if let Some(content) = read_entry_string(&mut archive, MCMOD_INFO) {
+        match parse_mcmod_info(&content) {
+            Ok(mods) => metadata.mods.extend(mods),
+            Err(e) => warn!("Skipping invalid {}: {}", MCMOD_INFO, e),
+        }
+    }
+
+    if let Some(content) = read_entry_string(&mut archive, JARJAR_METADATA) {
+        match serde_json::from_str::<JarJarMetadata>(&content) {
+            Ok(jarjar) => nested_paths.extend(jarjar.jars.into_iter().map(|j| j.path)),
+            Err(e) => warn!("Skipping invalid {}: {}", JARJAR_METADATA, e),
+        }
+    }
+
+    if include_icons {
+        for mod_metadata in &mut metadata.mods {
+            if let Some(icon) = &mod_metadata.icon {
+                mod_metadata.icon_data =
+                    read_entry_bytes(&mut archive, icon.trim_start_matches('/'))
+                        .map(|bytes| STANDARD.encode(bytes));
+            }
+        }
+    }
+
+    if depth < MAX_NESTING_DEPTH {
+        nested_paths.sort();
+        nested_paths.dedup();
+        for nested_path in nested_paths {
+            let Some(bytes) = read_entry_bytes(&mut archive, &nested_path) else {
+                debug!("Declared nested jar {} not found", nested_path);
+                continue;
+            };
+            match read_jar_metadata_from_reader(Cursor::new(bytes), include_icons, depth + 1) {
+                Ok(nested) if !nested.is_empty() => metadata.nested.push(NestedJarMetadata {
+                    path: nested_path,
+                    metadata: nested,
+                }),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to read nested jar {}: {}", nested_path, e),
+            }
+        }
+    }
+
+    Ok(metadata)
+}
+
+fn read_entry_bytes<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<Vec<u8>> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut buffer = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut buffer).ok()?;
+    Some(buffer)
+}
+
+fn read_entry_string<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    read_entry_bytes(archive, name).map(|bytes| {
+        String::from_utf8_lossy(&bytes)
+            .trim_start_matches('\u{feff}')
+            .to_string()
+    })
+}
+
+/// Parses JSON the way mod loaders tolerate it: raw line breaks and tabs inside
+/// strings are common in hand-written metadata.
+fn parse_lenient_json<T: for<'de> Deserialize<'de>>(content: &str) -> Result<T> {
+    match serde_json::from_str(content) {
+        Ok(value) => Ok(value),
+        Err(_) => {
+            let cleaned: String = content
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            Ok(serde_json::from_str(&cleaned)?)
+        }
+    }
+}
+
+/// Unresolved build placeholders like `${version}` are treated as missing.
+fn clean_value(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && !v.contains("${"))
+}
+
+/// Version predicates can be a string or a list of alternatives.
+fn version_predicate(value: Option<serde_json::Value>) -> Option<String> {
+    match value? {
+        serde_json::Value::String(s) if s == "*" => None,
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Array(values) => {
+            let parts: Vec<String> = values
+                .into_iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            if parts.is_empty() || parts.iter().any(|p| p == "*") {
+                None
+            } else {
+                Some(parts.join(" || "))
+            }
+        }
+        _ => None,
+    }
+}
+
+// --- Fabric ---
+
+#[derive(Deserialize)]
+struct FabricModJson {
+    id: String,
+    version: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    authors: Vec<FabricPerson>,
+    environment: Option<String>,
+    icon: Option<FabricIcon>,
+    #[serde(default)]
+    depends: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    recommends: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    suggests: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    conflicts: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    breaks: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    provides: Vec<String>,
+    #[serde(default)]
+    jars: Vec<FabricJar>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FabricPerson {
+    Name(String),
+    Object { name: String },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FabricIcon {
+    Path(String),
+    /// Size in pixels -> path
+    Sizes(HashMap<String, String>),
+}
+
+impl FabricIcon {
+    fn best_path(self) -> Option<String> {
+        match self {
+            FabricIcon::Path(path) => Some(path),
+            FabricIcon::Sizes(sizes) => sizes
+                .into_iter()
+                .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or(0))
+                .map(|(_, path)| path),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FabricJar {
+    file: String,
+}
+
+fn fabric_environment(environment: Option<&str>) -> ModEnvironment {
+    match environment {
+        Some("client") => ModEnvironment::Client,
+        Some("server") | Some("dedicated_server") => ModEnvironment::Server,
+        _ => ModEnvironment::Both,
+    }
+}
+
+fn parse_fabric_mod_json(content: &str) -> Result<(ModMetadata, Vec<String>)> {
+    let json: FabricModJson = parse_lenient_json(content)?;
+
+    let mut dependencies = Vec::new();
+    let groups = [
+        (json.depends, DependencyKind::Required),
+        (json.recommends, DependencyKind::Optional),
+        (json.suggests, DependencyKind::Optional),
+        (json.conflicts, DependencyKind::Discouraged),
+        (json.breaks, DependencyKind::Incompatible),
+    ];
+    for (entries, kind) in groups {
+        let mut entries: Vec<_> = entries.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        for (mod_id, versions) in entries {
+            dependencies.push(ModDependency {
+                mod_id,
+                version_range: version_predicate(Some(versions)),
+                kind,
+                environment: ModEnvironment::Both,
+            });
+        }
+    }
+
+    let metadata = ModMetadata {
+        format: ModMetadataFormat::Fabric,
+        mod_id: json.id,
+        name: clean_value(json.name),
+        version: clean_value(json.version),
+        description: clean_value(json.description),
+        authors: json
+            .authors
+            .into_iter()
+            .map(|author| match author {
+                FabricPerson::Name(name) | FabricPerson::Object { name } => name,
+            })
+            .collect(),
+        environment: fabric_environment(json.environment.as_deref()),
+        icon: json.icon.and_then(FabricIcon::best_path),
+        icon_data: None,
+        dependencies,
+        provides: json.provides,
+    };
+    Ok((metadata, json.jars.into_iter().map(|j| j.file).collect()))
+}
+
+// --- Quilt ---
+
+#[derive(Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoader,
+    minecraft: Option<QuiltMinecraft>,
+}
+
+#[derive(Deserialize)]
+struct QuiltLoader {
+    id: String,
+    version: Option<String>,
+    #[serde(default)]
+    metadata: QuiltMetadata,
+    #[serde(default)]
+    depends: Vec<QuiltDependency>,
+    #[serde(default)]
+    breaks: Vec<QuiltDependency>,
+    #[serde(default)]
+    provides: Vec<QuiltProvides>,
+    #[serde(default)]
+    jars: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct QuiltMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    /// Name -> role
+    #[serde(default)]
+    contributors: HashMap<String, String>,
+    icon: Option<FabricIcon>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuiltDependency {
+    Id(String),
+    Object {
+        id: String,
+        versions: Option<serde_json::Value>,
+        #[serde(default)]
+        optional: bool,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuiltProvides {
+    Id(String),
+    Object { id: String },
+}
+
+#[derive(Deserialize)]
+struct QuiltMinecraft {
+    environment: Option<String>,
+}
+
+/// Quilt ids may be namespaced with a maven group (`org.quiltmc:quilt_loader`).
+fn quilt_mod_id(id: String) -> String {
+    match id.split_once(':') {
+        Some((_, id)) => id.to_string(),
+        None => id,
+    }
+}
+
+fn parse_quilt_mod_json(content: &str) -> Result<(ModMetadata, Vec<String>)> {
+    let json: QuiltModJson = parse_lenient_json(content)?;
+    let loader = json.quilt_loader;
+
+    let mut dependencies = Vec::new();
+    let groups = [(loader.depends, false), (loader.breaks, true)];
+    for (entries, is_breaks) in groups {
+        for dependency in entries {
+            let (id, versions, optional) = match dependency {
+                QuiltDependency::Id(id) => (id, None, false),
+                QuiltDependency::Object {
+                    id,
+                    versions,
+                    optional,
+                } => (id, versions, optional),
+            };
+            let kind = match (is_breaks, optional) {
+                (true, _) => DependencyKind::Incompatible,
+                (false, true) => DependencyKind::Optional,
+                (false, false) => DependencyKind::Required,
+            };
+            dependencies.push(ModDependency {
+                mod_id: quilt_mod_id(id),
+                version_range: version_predicate(versions),
+                kind,
+                environment: ModEnvironment::Both,
+            });
+        }
+    }
+
+    let mut authors: Vec<String> = loader.metadata.contributors.into_keys().collect();
+    authors.sort();
+
+    let metadata = ModMetadata {
+        format: ModMetadataFormat::Quilt,
+        mod_id: loader.id,
+        name: clean_value(loader.metadata.name),
+        version: clean_value(loader.version),
+        description: clean_value(loader.metadata.description),
+        authors,
+        environment: fabric_environment(
+            json.minecraft
+                .as_ref()
+                .and_then(|m| m.environment.as_deref()),
+        ),
+        icon: loader.metadata.icon.and_then(FabricIcon::best_path),
+        icon_data: None,
+        dependencies,
+        provides: loader
+            .provides
+            .into_iter()
+            .map(|p| match p {
+                QuiltProvides::Id(id) | QuiltProvides::Object { id } => quilt_mod_id(id),
+            })
+            .collect(),
+    };
+    Ok((metadata, loader.jars))
+}
+
+// --- Forge / NeoForge ---
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsToml {
+    #[serde(default)]
+    mods: Vec<ModsTomlEntry>,
+    #[serde(default)]
+    dependencies: HashMap<String, Vec<ModsTomlDependency>>,
+    logo_file: Option<String>,
+    authors: Option<StringOrList>,
+    #[serde(default)]
+    client_side_only: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlEntry {
+    mod_id: String,
+    version: Option<String>,
+    display_name: Option<String>,
+    description: Option<String>,
+    authors: Option<StringOrList>,
+    logo_file: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrList {
+    String(String),
+    List(Vec<String>),
+}
+
+impl StringOrList {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            StringOrList::String(s) => vec![s],
+            StringOrList::List(list) => list,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlDependency {
+    mod_id: String,
+    /// Forge and early NeoForge
+    mandatory: Option<bool>,
+    /// NeoForge 20.5+: required, optional, incompatible, discouraged
+    #[serde(rename = "type")]
+    dependency_type: Option<String>,
+    version_range: Option<String>,
+    side: Option<String>,
+}
+
+/// Main attributes of `META-INF/MANIFEST.MF`.
+fn parse_manifest(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn parse_mods_toml(
+    content: &str,
+    format: Option<ModMetadataFormat>,
+    manifest: &HashMap<String, String>,
+) -> Result<Vec<ModMetadata>> {
+    let toml: ModsToml = toml::from_str(content)
+        .map_err(|e| AppError::Other(format!("Failed to parse mods.toml: {}", e)))?;
+
+    // Old NeoForge versions still used mods.toml
+    let format = format.unwrap_or_else(|| {
+        let depends_on_neoforge = toml
+            .dependencies
+            .values()
+            .flatten()
+            .any(|d| d.mod_id == "neoforge");
+        if depends_on_neoforge {
+            ModMetadataFormat::NeoForge
+        } else {
+            ModMetadataFormat::Forge
+        }
+    });
+    let environment = if toml.client_side_only {
+        ModEnvironment::Client
+    } else {
+        ModEnvironment::Unknown
+    };
+    let jar_version = manifest.get("Implementation-Version").cloned();
+    let shared_authors = toml.authors.map(StringOrList::into_vec).unwrap_or_default();
+
+    let mut dependencies_by_mod = toml.dependencies;
+    let mods = toml
+        .mods
+        .into_iter()
+        .map(|entry| {
+            let version = entry.version.map(|v| match &jar_version {
+                Some(jar_version) => v.replace("${file.jarVersion}", jar_version),
+                None => v,
+            });
+            let dependencies = dependencies_by_mod
+                .remove(&entry.mod_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|d| ModDependency {
+                    kind: match d.dependency_type.as_deref().map(str::to_lowercase) {
+                        Some(t) if t == "required" => DependencyKind::Required,
+                        Some(t) if t == "incompatible" => DependencyKind::Incompatible,
+                        Some(t) if t == "discouraged" => DependencyKind::Discouraged,
+                        Some(_) => DependencyKind::Optional,
+                        None if d.mandatory.unwrap_or(true) => DependencyKind::Required,
+                        None => DependencyKind::Optional,
+                    },
+                    mod_id: d.mod_id,
+                    version_range: clean_value(d.version_range),
+                    environment: match d.side.as_deref() {
+                        Some("CLIENT") => ModEnvironment::Client,
+                        Some("SERVER") => ModEnvironment::Server,
+                        _ => ModEnvironment::Both,
+                    },
+                })
+                .collect();
+            let authors = entry
+                .authors
+                .map(StringOrList::into_vec)
+                .unwrap_or_else(|| shared_authors.clone());
+
+            ModMetadata {
+                format,
+                mod_id: entry.mod_id,
+                name: clean_value(entry.display_name),
+                version: clean_value(version),
+                description: clean_value(entry.description),
+                authors,
+                environment,
+                icon: clean_value(entry.logo_file.or_else(|| toml.logo_file.clone())),
+                icon_data: None,
+                dependencies,
+                provides: Vec::new(),
+            }
+        })
+        .collect();
+    Ok(mods)
+}
+
+#[derive(Deserialize)]
+struct JarJarMetadata {
+    #[serde(default)]
+    jars: Vec<JarJarEntry>,
+}
+
+#[derive(Deserialize)]
+struct JarJarEntry {
+    path: String,
+}
+
+// --- Legacy Forge ---
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum McModInfo {
+    List(Vec<McModInfoEntry>),
+    /// modListVersion 2
+    Wrapped {
+        #[serde(rename = "modList")]
+        mod_list: Vec<McModInfoEntry>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct McModInfoEntry {
+    modid: String,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    author_list: Vec<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    logo_file: Option<String>,
+    #[serde(default)]
+    required_mods: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Legacy dependencies look like `modid@[1.0,)`.
+fn split_legacy_dependency(value: &str) -> (String, Option<String>) {
+    match value.split_once('@') {
+        Some((id, range)) => (id.to_string(), Some(range.to_string())),
+        None => (value.to_string(), None),
+    }
+}
+
+fn parse_mcmod_info(content: &str) -> Result<Vec<ModMetadata>> {
+    let entries = match parse_lenient_json::<McModInfo>(content)? {
+        McModInfo::List(entries) => entries,
+        McModInfo::Wrapped { mod_list } => mod_list,
+    };
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let required: Vec<(String, Option<String>)> = entry
+                .required_mods
+                .iter()
+                .map(|d| split_legacy_dependency(d))
+                .collect();
+            let mut dependencies: Vec<ModDependency> = required
+                .iter()
+                .map(|(mod_id, version_range)| ModDependency {
+                    mod_id: mod_id.clone(),
+                    version_range: version_range.clone(),
+                    kind: DependencyKind::Required,
+                    environment: ModEnvironment::Both,
+                })
+                .collect();
+            // `dependencies` only orders loading, so they are optional unless also required
+            for (mod_id, version_range) in entry
+                .dependencies
+                .iter()
+                .map(|d| split_legacy_dependency(d))
+            {
+                if !required.iter().any(|(id, _)| *id == mod_id) {
+                    dependencies.push(ModDependency {
+                        mod_id,
+                        version_range,
+                        kind: DependencyKind::Optional,
+                        environment: ModEnvironment::Both,
+                    });
+                }
+            }
+
+            let mut authors = entry.author_list;
+            authors.extend(entry.authors);
+
+            ModMetadata {
+                format: ModMetadataFormat::LegacyForge,
+                mod_id: entry.modid,
+                name: clean_value(entry.name),
+                version: clean_value(entry.version),
+                description: clean_value(entry.description),
+                authors,
+                environment: ModEnvironment::Unknown,
+                icon: clean_value(entry.logo_file),
+                icon_data: None,
+                dependencies,
+                provides: Vec::new(),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,113 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::utils::mirror_utils;
+use log::{debug, info, warn};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::fs;
+use uuid::Uuid;
+
+const METADATA_CACHE_DIR: &str = "metadata_cache";
+
+/// Mirrors `LauncherConfig::offline_mode`, readable without the `State`.
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_offline_mode(offline: bool) {
+    let previous = OFFLINE_MODE.swap(offline, Ordering::SeqCst);
+    if previous != offline {
+        info!(
+            "Offline mode {}",
+            if offline { "enabled" } else { "disabled" }
+        );
+    }
+}
+
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::SeqCst)
+}
+
+/// Root of the persisted metadata (version manifest, piston meta, loader versions).
+pub fn metadata_cache_dir() -> PathBuf {
+    LAUNCHER_DIRECTORY.meta_dir().join(METADATA_CACHE_DIR)
+}
+
+/// Fetches `url` (through the mirrors) and stores the raw response at `cache_path`.
+/// Offline, or if the request fails, the cached copy is returned instead.
+/// The response is only cached if it parses.
+pub async fn fetch_json_cached<T: DeserializeOwned>(url: &str, cache_path: &Path) -> Result<T> {
+    fetch_parsed_cached(url, cache_path, |bytes| Ok(serde_json::from_slice(bytes)?)).await
+}
+
+/// Text variant of `fetch_json_cached`.
+pub async fn fetch_text_cached(url: &str, cache_path: &Path) -> Result<String> {
+    fetch_parsed_cached(url, cache_path, |bytes| {
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| AppError::Download(format!("Response of {} is not UTF-8: {}", url, e)))
+    })
+    .await
+}
+
+/// A fresh response only replaces the cached copy after `parse` accepted it, so a broken
+/// response never overwrites the last good metadata.
+async fn fetch_parsed_cached<T>(
+    url: &str,
+    cache_path: &Path,
+    parse: impl Fn(&[u8]) -> Result<T>,
+) -> Result<T> {
+    if is_offline_mode() {
+        debug!("Offline mode, reading {} from {:?}", url, cache_path);
+        return parse(&read_cache(url, cache_path).await?);
+    }
+
+    let error = match mirror_utils::fetch_bytes(url).await {
+        Ok(bytes) => match parse(&bytes) {
+            Ok(parsed) => {
+                if let Err(e) = write_cache(cache_path, &bytes).await {
+                    warn!("Failed to cache metadata {:?}: {}", cache_path, e);
+                }
+                return Ok(parsed);
+            }
+            Err(e) => e,
+        },
+        Err(e) => e,
+    };
+
+    if cache_path.exists() {
+        warn!(
+            "Fetching {} failed ({}), falling back to cached copy {:?}",
+            url, error, cache_path
+        );
+        parse(&read_cache(url, cache_path).await?)
+    } else {
+        Err(error)
+    }
+}
+
+async fn read_cache(url: &str, cache_path: &Path) -> Result<Vec<u8>> {
+    if !cache_path.exists() {
+        return Err(AppError::OfflineUnavailable(format!(
+            "{} was never downloaded (expected cache at {:?})",
+            url, cache_path
+        )));
+    }
+    Ok(fs::read(cache_path).await?)
+}
+
+async fn write_cache(cache_path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    // Unique per write, `with_extension` would map "1.21.json" and "1.21.1.json" to one file
+    let file_name = cache_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = cache_path.with_file_name(format!("{}.tmp-{}", file_name, Uuid::new_v4()));
+    fs::write(&tmp_path, bytes).await?;
+    if let Err(e) = fs::rename(&tmp_path, cache_path).await {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(AppError::Io(e));
+    }
+    Ok(())
+}
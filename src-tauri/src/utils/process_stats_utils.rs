@@ -0,0 +1,229 @@
+use crate::integrations::launcher_import::parse_memory_value;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// How often running game processes are sampled
+pub const SAMPLE_INTERVAL_SECS: u64 = 5;
+/// Samples kept per process (10 minutes)
+pub const SAMPLE_WINDOW_SIZE: usize = 120;
+
+const MB: u64 = 1024 * 1024;
+/// Swap that has to be added while the game runs before we call it swapping
+const SWAP_GROWTH_THRESHOLD: u64 = 256 * MB;
+/// Free system memory below this share counts as memory pressure
+const LOW_MEMORY_RATIO: f64 = 0.05;
+/// `-Xmx` above this share of the system memory leaves too little for the OS and native memory
+const MAX_HEAP_RATIO: f64 = 0.75;
+
+/// One measurement of a game process (plus the system memory at that time).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProcessSample {
+    pub timestamp: DateTime<Utc>,
+    /// Resident set size
+    pub memory_bytes: u64,
+    pub virtual_memory_bytes: u64,
+    /// 100 = one fully used core
+    pub cpu_percent: f32,
+    /// Only available on Linux
+    pub thread_count: Option<u32>,
+    /// Bytes read/written since the previous sample
+    pub disk_read_bytes: u64,
+    pub disk_written_bytes: u64,
+    pub system_available_memory_bytes: u64,
+    pub system_used_swap_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResourceWarning {
+    /// The system ran out of memory and moved memory to swap while the game ran
+    Swapping {
+        swap_growth_bytes: u64,
+        min_available_memory_bytes: u64,
+    },
+    /// `-Xmx` is too large for the memory of this system
+    HeapTooLarge {
+        max_heap_mb: u32,
+        total_memory_mb: u64,
+    },
+}
+
+/// Aggregates over the whole run of a process, not only the kept samples.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProcessStatsSummary {
+    pub first_sample_at: Option<DateTime<Utc>>,
+    pub last_sample_at: Option<DateTime<Utc>>,
+    pub sample_count: u64,
+    pub avg_memory_bytes: u64,
+    pub peak_memory_bytes: u64,
+    pub avg_cpu_percent: f32,
+    pub peak_cpu_percent: f32,
+    pub peak_thread_count: Option<u32>,
+    pub disk_read_bytes: u64,
+    pub disk_written_bytes: u64,
+    pub max_heap_mb: Option<u32>,
+    pub total_memory_bytes: u64,
+    pub warnings: Vec<ResourceWarning>,
+}
+
+/// Summary of a finished game process, kept in the process stats history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessStatsRecord {
+    pub process_id: Uuid,
+    pub profile_id: Uuid,
+    pub profile_name: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub exit_code: Option<i32>,
+    #[serde(flatten)]
+    pub summary: ProcessStatsSummary,
+}
+
+/// Live stats of a running process.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessStats {
+    pub samples: Vec<ProcessSample>,
+    pub summary: ProcessStatsSummary,
+}
+
+/// Rolling window of samples of one process plus running totals.
+#[derive(Debug, Clone)]
+pub struct ProcessStatsWindow {
+    samples: VecDeque<ProcessSample>,
+    capacity: usize,
+    max_heap_mb: Option<u32>,
+    total_memory_bytes: u64,
+    first_sample_at: Option<DateTime<Utc>>,
+    sample_count: u64,
+    memory_sum: u128,
+    cpu_sum: f64,
+    peak_memory_bytes: u64,
+    peak_cpu_percent: f32,
+    peak_thread_count: Option<u32>,
+    disk_read_bytes: u64,
+    disk_written_bytes: u64,
+    min_swap_bytes: Option<u64>,
+    max_swap_bytes: u64,
+    min_available_memory_bytes: Option<u64>,
+}
+
+impl ProcessStatsWindow {
+    pub fn new(capacity: usize, max_heap_mb: Option<u32>, total_memory_bytes: u64) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            max_heap_mb,
+            total_memory_bytes,
+            first_sample_at: None,
+            sample_count: 0,
+            memory_sum: 0,
+            cpu_sum: 0.0,
+            peak_memory_bytes: 0,
+            peak_cpu_percent: 0.0,
+            peak_thread_count: None,
+            disk_read_bytes: 0,
+            disk_written_bytes: 0,
+            min_swap_bytes: None,
+            max_swap_bytes: 0,
+            min_available_memory_bytes: None,
+        }
+    }
+
+    pub fn push(&mut self, sample: ProcessSample) {
+        self.first_sample_at.get_or_insert(sample.timestamp);
+        self.sample_count += 1;
+        self.memory_sum += sample.memory_bytes as u128;
+        self.cpu_sum += sample.cpu_percent as f64;
+        self.peak_memory_bytes = self.peak_memory_bytes.max(sample.memory_bytes);
+        self.peak_cpu_percent = self.peak_cpu_percent.max(sample.cpu_percent);
+        if let Some(threads) = sample.thread_count {
+            self.peak_thread_count = Some(self.peak_thread_count.unwrap_or(0).max(threads));
+        }
+        self.disk_read_bytes += sample.disk_read_bytes;
+        self.disk_written_bytes += sample.disk_written_bytes;
+        self.min_swap_bytes = Some(
+            self.min_swap_bytes
+                .map_or(sample.system_used_swap_bytes, |min| {
+                    min.min(sample.system_used_swap_bytes)
+                }),
+        );
+        self.max_swap_bytes = self.max_swap_bytes.max(sample.system_used_swap_bytes);
+        self.min_available_memory_bytes = Some(
+            self.min_available_memory_bytes
+                .map_or(sample.system_available_memory_bytes, |min| {
+                    min.min(sample.system_available_memory_bytes)
+                }),
+        );
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn samples(&self) -> Vec<ProcessSample> {
+        self.samples.iter().cloned().collect()
+    }
+
+    fn warnings(&self) -> Vec<ResourceWarning> {
+        let mut warnings = Vec::new();
+        let swap_growth = self.max_swap_bytes - self.min_swap_bytes.unwrap_or(0);
+        if let Some(min_available) = self.min_available_memory_bytes {
+            let low_memory =
+                (min_available as f64) < self.total_memory_bytes as f64 * LOW_MEMORY_RATIO;
+            if swap_growth >= SWAP_GROWTH_THRESHOLD && low_memory {
+                warnings.push(ResourceWarning::Swapping {
+                    swap_growth_bytes: swap_growth,
+                    min_available_memory_bytes: min_available,
+                });
+            }
+        }
+        if let Some(max_heap_mb) = self.max_heap_mb {
+            let total_memory_mb = self.total_memory_bytes / MB;
+            if total_memory_mb > 0 && max_heap_mb as f64 > total_memory_mb as f64 * MAX_HEAP_RATIO {
+                warnings.push(ResourceWarning::HeapTooLarge {
+                    max_heap_mb,
+                    total_memory_mb,
+                });
+            }
+        }
+        warnings
+    }
+
+    pub fn summary(&self) -> ProcessStatsSummary {
+        let count = self.sample_count.max(1);
+        ProcessStatsSummary {
+            first_sample_at: self.first_sample_at,
+            last_sample_at: self.samples.back().map(|s| s.timestamp),
+            sample_count: self.sample_count,
+            avg_memory_bytes: (self.memory_sum / count as u128) as u64,
+            peak_memory_bytes: self.peak_memory_bytes,
+            avg_cpu_percent: (self.cpu_sum / count as f64) as f32,
+            peak_cpu_percent: self.peak_cpu_percent,
+            peak_thread_count: self.peak_thread_count,
+            disk_read_bytes: self.disk_read_bytes,
+            disk_written_bytes: self.disk_written_bytes,
+            max_heap_mb: self.max_heap_mb,
+            total_memory_bytes: self.total_memory_bytes,
+            warnings: self.warnings(),
+        }
+    }
+
+    pub fn stats(&self) -> ProcessStats {
+        ProcessStats {
+            samples: self.samples(),
+            summary: self.summary(),
+        }
+    }
+}
+
+/// The `-Xmx` (in MB) from JVM arguments. The JVM uses the last one if there are several.
+pub fn parse_max_heap_mb<'a>(args: impl IntoIterator<Item = &'a str>) -> Option<u32> {
+    args.into_iter()
+        .filter_map(|arg| arg.strip_prefix("-Xmx"))
+        .filter_map(parse_memory_value)
+        .last()
+}
@@ -1088,7 +1088,7 @@ fn sanitize_profile_for_export(profile: &Profile) -> Profile {
 }
 
 /// Collect all files recursively (like Modrinth's add_all_recursive_folder_paths)
-fn collect_all_files_recursive<'a>(
+pub(crate) fn collect_all_files_recursive<'a>(
     dir_path: &'a Path,
     file_list: &'a mut Vec<PathBuf>,
 ) -> BoxFuture<'a, Result<()>> {
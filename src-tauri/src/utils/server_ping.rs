@@ -23,6 +23,9 @@ pub struct ServerStatus {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ping: Option<i64>,
+    /// The server only answered the legacy (pre-1.7) server list ping
+    #[serde(skip)]
+    pub legacy: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -50,11 +53,127 @@ pub async fn get_server_status(
     original_address: (&str, u16),
     protocol_version: Option<i32>,
 ) -> Result<ServerStatus> {
-    select! {
+    let result = select! {
         res = modern::status(address, original_address, protocol_version) => res,
         _ = time::sleep(Duration::from_secs(30)) => Err(AppError::Other(
             format!("Ping of {}:{} timed out", original_address.0, original_address.1)
         ))
+    };
+    match result {
+        Ok(status) => Ok(status),
+        // Pre-1.7 servers and some proxies only answer the legacy server list ping
+        Err(e) => match time::timeout(
+            Duration::from_secs(10),
+            legacy::status(address, original_address),
+        )
+        .await
+        {
+            Ok(Ok(status)) => Ok(status),
+            _ => Err(e),
+        },
+    }
+}
+
+/// Parses the kick message a server answers the legacy ping with: `§1\0protocol\0version\0
+/// motd\0online\0max` since 1.4, `motd§online§max` before.
+pub fn parse_legacy_response(response: &str) -> Result<ServerStatus> {
+    let invalid = || AppError::Other(format!("Invalid legacy ping response: {:?}", response));
+    let (protocol, version, motd, online, max) = match response.strip_prefix("\u{a7}1\0") {
+        Some(fields) => {
+            let fields: Vec<&str> = fields.split('\0').collect();
+            let [protocol, version, motd, online, max] = fields[..] else {
+                return Err(invalid());
+            };
+            (protocol.parse().ok(), Some(version), motd, online, max)
+        }
+        None => {
+            let mut fields = response.rsplitn(3, '\u{a7}');
+            let (Some(max), Some(online), Some(motd)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid());
+            };
+            (None, None, motd, online, max)
+        }
+    };
+    Ok(ServerStatus {
+        description: Some(serde_json::value::to_raw_value(motd)?),
+        players: Some(ServerPlayers {
+            max: max.trim().parse().map_err(|_| invalid())?,
+            online: online.trim().parse().map_err(|_| invalid())?,
+            sample: Vec::new(),
+        }),
+        version: version.map(|name| ServerVersion {
+            name: name.to_string(),
+            protocol: protocol.unwrap_or(-1),
+        }),
+        favicon: None,
+        enforces_secure_chat: false,
+        ping: None,
+        legacy: true,
+    })
+}
+
+mod legacy {
+    use super::ServerStatus;
+    use crate::error::{AppError, Result};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpStream, ToSocketAddrs};
+
+    /// Protocol version sent in the MC|PingHost payload (1.6.4)
+    const PROTOCOL_VERSION: u8 = 74;
+
+    fn write_string(out: &mut Vec<u8>, value: &str) {
+        let units: Vec<u16> = value.encode_utf16().collect();
+        out.extend_from_slice(&(units.len() as u16).to_be_bytes());
+        units
+            .iter()
+            .for_each(|unit| out.extend_from_slice(&unit.to_be_bytes()));
+    }
+
+    /// `0xFE 0x01` followed by the MC|PingHost plugin message that 1.6 servers expect.
+    /// Older servers ignore everything after `0xFE 0x01`.
+    fn request(original_address: (&str, u16)) -> Vec<u8> {
+        let (host, port) = original_address;
+        let mut payload = vec![PROTOCOL_VERSION];
+        write_string(&mut payload, host);
+        payload.extend_from_slice(&(port as i32).to_be_bytes());
+
+        let mut packet = vec![0xFE, 0x01, 0xFA];
+        write_string(&mut packet, "MC|PingHost");
+        packet.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        packet.extend(payload);
+        packet
+    }
+
+    pub async fn status(
+        address: &impl ToSocketAddrs,
+        original_address: (&str, u16),
+    ) -> Result<ServerStatus> {
+        let start_time = Instant::now();
+        let mut stream = TcpStream::connect(address).await?;
+        stream.write_all(&request(original_address)).await?;
+        stream.flush().await?;
+
+        // The answer is a kick packet with a UTF-16 string
+        if stream.read_u8().await? != 0xFF {
+            return Err(AppError::Other(
+                "Unexpected legacy ping response".to_string(),
+            ));
+        }
+        let length = stream.read_u16().await? as usize;
+        let mut data = vec![0_u8; length * 2];
+        stream.read_exact(&mut data).await?;
+        let elapsed = start_time.elapsed();
+
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+            .collect();
+        let mut status = super::parse_legacy_response(&String::from_utf16_lossy(&units))?;
+        status.ping = Some(elapsed.as_millis() as i64);
+        Ok(status)
     }
 }
 
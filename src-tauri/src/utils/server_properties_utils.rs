@@ -0,0 +1,223 @@
+use crate::error::Result;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use tokio::fs;
+
+pub const SERVER_PROPERTIES_FILE: &str = "server.properties";
+pub const EULA_FILE: &str = "eula.txt";
+pub const DEFAULT_SERVER_PORT: u16 = 25565;
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+
+#[derive(Debug, Clone, PartialEq)]
+enum PropertyLine {
+    /// Comments, blank lines and anything we don't understand, written back as is
+    Other(String),
+    Entry {
+        key: String,
+        value: String,
+    },
+}
+
+/// A `server.properties` file. Comments and the order of keys are kept, so changing a value
+/// doesn't rewrite the rest of the file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerProperties {
+    lines: Vec<PropertyLine>,
+}
+
+/// Resolves the escapes of Java properties files (`\uXXXX`, `\:`, `\=`, `\\`, ...).
+fn unescape(value: &str) -> String {
+    fn push(c: char, result: &mut Vec<u16>) {
+        let mut buffer = [0u16; 2];
+        result.extend_from_slice(c.encode_utf16(&mut buffer));
+    }
+
+    // Collected as UTF-16, characters outside the BMP come as two escaped surrogates
+    let mut result: Vec<u16> = Vec::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            push(c, &mut result);
+            continue;
+        }
+        match chars.next() {
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                match u16::from_str_radix(&hex, 16) {
+                    Ok(unit) if hex.len() == 4 => result.push(unit),
+                    _ => hex.chars().for_each(|c| push(c, &mut result)),
+                }
+            }
+            Some('t') => push('\t', &mut result),
+            Some('n') => push('\n', &mut result),
+            Some('r') => push('\r', &mut result),
+            Some('f') => push('\u{c}', &mut result),
+            Some(other) => push(other, &mut result),
+            None => {}
+        }
+    }
+    String::from_utf16_lossy(&result)
+}
+
+/// Escapes a value the way `java.util.Properties` writes it. Non-ASCII characters become
+/// `\uXXXX`, which every server version reads, independent of the file encoding.
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\t' => result.push_str("\\t"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\u{c}' => result.push_str("\\f"),
+            ' ' if i == 0 => result.push_str("\\ "),
+            c if c.is_ascii() => result.push(c),
+            c => {
+                let mut buffer = [0u16; 2];
+                for unit in c.encode_utf16(&mut buffer) {
+                    result.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Splits `key=value` / `key:value` at the first unescaped separator.
+fn split_entry(line: &str) -> Option<(String, String)> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '=' | ':' if !escaped => {
+                let key = unescape(line[..i].trim());
+                let value = unescape(line[i + 1..].trim_start());
+                return (!key.is_empty()).then_some((key, value));
+            }
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+impl ServerProperties {
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim_start();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+                    return PropertyLine::Other(line.to_string());
+                }
+                match split_entry(trimmed) {
+                    Some((key, value)) => PropertyLine::Entry { key, value },
+                    None => PropertyLine::Other(line.to_string()),
+                }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        // Like `java.util.Properties`, the last occurrence wins
+        self.lines.iter().rev().find_map(|line| match line {
+            PropertyLine::Entry { key: k, value } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Changes the value of a key in place or appends it if the file doesn't have it yet.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let existing = self.lines.iter_mut().rev().find_map(|line| match line {
+            PropertyLine::Entry { key: k, value } if k == key => Some(value),
+            _ => None,
+        });
+        match existing {
+            Some(existing) => *existing = value.to_string(),
+            None => self.lines.push(PropertyLine::Entry {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    pub fn entries(&self) -> BTreeMap<String, String> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                PropertyLine::Entry { key, value } => Some((key.clone(), value.clone())),
+                PropertyLine::Other(_) => None,
+            })
+            .collect()
+    }
+
+    /// The port clients connect to, 25565 if unset or invalid.
+    pub fn server_port(&self) -> u16 {
+        self.get("server-port")
+            .and_then(|port| port.trim().parse().ok())
+            .filter(|port| *port != 0)
+            .unwrap_or(DEFAULT_SERVER_PORT)
+    }
+}
+
+impl fmt::Display for ServerProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                PropertyLine::Other(line) => writeln!(f, "{}", line)?,
+                PropertyLine::Entry { key, value } => {
+                    writeln!(f, "{}={}", escape(key), escape(value))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads `server.properties` of a server directory. A server that never ran has none yet.
+pub async fn read_server_properties(server_dir: &Path) -> Result<ServerProperties> {
+    let path = server_dir.join(SERVER_PROPERTIES_FILE);
+    if !fs::try_exists(&path).await? {
+        return Ok(ServerProperties::default());
+    }
+    let content = fs::read(&path).await?;
+    Ok(ServerProperties::parse(&String::from_utf8_lossy(&content)))
+}
+
+pub async fn write_server_properties(
+    server_dir: &Path,
+    properties: &ServerProperties,
+) -> Result<()> {
+    fs::create_dir_all(server_dir).await?;
+    let path = server_dir.join(SERVER_PROPERTIES_FILE);
+    let tmp_path = path.with_extension("properties.tmp");
+    fs::write(&tmp_path, properties.to_string()).await?;
+    fs::rename(&tmp_path, &path).await?;
+    Ok(())
+}
+
+/// Whether `eula.txt` of a server directory says `eula=true`.
+pub async fn is_eula_accepted(server_dir: &Path) -> Result<bool> {
+    let path = server_dir.join(EULA_FILE);
+    if !fs::try_exists(&path).await? {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(&path).await?;
+    Ok(ServerProperties::parse(&content)
+        .get("eula")
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("true")))
+}
+
+/// Writes the `eula.txt` the server would otherwise stop at on its first start.
+pub async fn accept_eula(server_dir: &Path) -> Result<()> {
+    fs::create_dir_all(server_dir).await?;
+    let content = format!(
+        "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({}).\n#{}\neula=true\n",
+        EULA_URL,
+        Utc::now().to_rfc2822()
+    );
+    fs::write(server_dir.join(EULA_FILE), content).await?;
+    Ok(())
+}
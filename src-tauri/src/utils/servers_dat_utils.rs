@@ -0,0 +1,449 @@
+use crate::error::{AppError, Result};
+use crate::state::favorite_servers_state::FavoriteServer;
+use crate::state::State;
+use crate::utils::mc_utils::{self, ServerInfo};
+use dashmap::DashMap;
+use fastnbt::Value;
+use flate2::read::GzDecoder;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub const SERVERS_DAT: &str = "servers.dat";
+/// Minecraft keeps the previous list under this name as well.
+const SERVERS_DAT_OLD: &str = "servers.dat_old";
+
+static SERVERS_DAT_LOCKS: Lazy<DashMap<PathBuf, Arc<Mutex<()>>>> = Lazy::new(DashMap::new);
+
+/// Changes to one entry of the server list. Unset fields stay as they are, an empty
+/// `icon_base64` removes the icon.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ServerEntryEdit {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub icon_base64: Option<String>,
+    /// 0 = prompt, 1 = enabled, 2 = disabled
+    pub accepts_textures: Option<u8>,
+}
+
+/// What pushing the favorites into one server list changed.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct FavoriteSyncChanges {
+    pub added: usize,
+    pub updated: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerSyncResult {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    #[serde(flatten)]
+    pub changes: FavoriteSyncChanges,
+    pub error: Option<String>,
+}
+
+/// Parses servers.dat. It is uncompressed NBT, some tools write it gzipped though.
+/// Empty input yields an empty list.
+pub fn parse_servers_dat(bytes: &[u8]) -> Result<Value> {
+    if bytes.is_empty() {
+        return Ok(empty_server_list());
+    }
+    let root: Value = match fastnbt::from_bytes(bytes) {
+        Ok(root) => root,
+        Err(e) => {
+            let mut decompressed = Vec::new();
+            if GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .is_err()
+            {
+                return Err(AppError::Nbt(e));
+            }
+            fastnbt::from_bytes(&decompressed)?
+        }
+    };
+    let mut root = match root {
+        Value::Compound(root) => root,
+        _ => {
+            return Err(AppError::ParseError(
+                "servers.dat root is not a compound".to_string(),
+            ))
+        }
+    };
+    // A list without entries may be missing entirely
+    root.entry("servers".to_string())
+        .or_insert_with(|| Value::List(Vec::new()));
+    Ok(Value::Compound(root))
+}
+
+fn empty_server_list() -> Value {
+    Value::Compound(HashMap::from([(
+        "servers".to_string(),
+        Value::List(Vec::new()),
+    )]))
+}
+
+/// Serializes the list back as uncompressed NBT, the way Minecraft writes it.
+pub fn write_servers_dat(root: &Value) -> Result<Vec<u8>> {
+    Ok(fastnbt::to_bytes(root)?)
+}
+
+fn servers_mut(root: &mut Value) -> Result<&mut Vec<Value>> {
+    match root {
+        Value::Compound(root) => match root.get_mut("servers") {
+            Some(Value::List(servers)) => Ok(servers),
+            _ => Err(AppError::ParseError(
+                "servers.dat has no server list".to_string(),
+            )),
+        },
+        _ => Err(AppError::ParseError(
+            "servers.dat root is not a compound".to_string(),
+        )),
+    }
+}
+
+fn string_tag(entry: &HashMap<String, Value>, key: &str) -> Option<String> {
+    match entry.get(key) {
+        Some(Value::String(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn byte_tag(entry: &HashMap<String, Value>, key: &str) -> Option<u8> {
+    match entry.get(key) {
+        Some(Value::Byte(value)) => Some(*value as u8),
+        _ => None,
+    }
+}
+
+/// The entries of a server list in the format `get_servers_for_profile` returns.
+pub fn server_list(root: &Value) -> Vec<ServerInfo> {
+    let Value::Compound(root) = root else {
+        return Vec::new();
+    };
+    let Some(Value::List(servers)) = root.get("servers") else {
+        return Vec::new();
+    };
+    servers
+        .iter()
+        .filter_map(|entry| match entry {
+            Value::Compound(entry) => Some(ServerInfo {
+                name: string_tag(entry, "name"),
+                address: string_tag(entry, "ip"),
+                icon_base64: string_tag(entry, "icon"),
+                accepts_textures: byte_tag(entry, "acceptTextures"),
+                previews_chat: byte_tag(entry, "previewsChat"),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn validate_address(address: &str) -> Result<String> {
+    let address = address.trim();
+    mc_utils::parse_minecraft_address(address).map_err(AppError::InvalidInput)?;
+    Ok(address.to_string())
+}
+
+fn check_index(servers: &[Value], index: usize) -> Result<()> {
+    if index >= servers.len() {
+        return Err(AppError::InvalidInput(format!(
+            "Server index {} out of range ({} servers)",
+            index,
+            servers.len()
+        )));
+    }
+    Ok(())
+}
+
+fn server_entry(name: &str, address: &str, icon: Option<&str>) -> Value {
+    let mut entry = HashMap::from([
+        ("name".to_string(), Value::String(name.to_string())),
+        ("ip".to_string(), Value::String(address.to_string())),
+        ("hidden".to_string(), Value::Byte(0)),
+    ]);
+    if let Some(icon) = icon.filter(|icon| !icon.is_empty()) {
+        entry.insert("icon".to_string(), Value::String(icon.to_string()));
+    }
+    Value::Compound(entry)
+}
+
+/// Adds a server at `index` (default: the end of the list) and returns its position.
+pub fn add_server(
+    root: &mut Value,
+    name: &str,
+    address: &str,
+    index: Option<usize>,
+) -> Result<usize> {
+    let address = validate_address(address)?;
+    let servers = servers_mut(root)?;
+    let index = index.unwrap_or(servers.len()).min(servers.len());
+    servers.insert(index, server_entry(name.trim(), &address, None));
+    Ok(index)
+}
+
+/// Edits one entry. Tags the launcher doesn't know about are kept.
+pub fn edit_server(root: &mut Value, index: usize, edit: &ServerEntryEdit) -> Result<()> {
+    let address = edit.address.as_deref().map(validate_address).transpose()?;
+    if edit.accepts_textures.is_some_and(|value| value > 2) {
+        return Err(AppError::InvalidInput(
+            "accepts_textures must be 0, 1 or 2".to_string(),
+        ));
+    }
+    let servers = servers_mut(root)?;
+    check_index(servers, index)?;
+    let Value::Compound(entry) = &mut servers[index] else {
+        return Err(AppError::ParseError(format!(
+            "Server entry {} is not a compound",
+            index
+        )));
+    };
+    if let Some(name) = &edit.name {
+        entry.insert("name".to_string(), Value::String(name.trim().to_string()));
+    }
+    if let Some(address) = address {
+        entry.insert("ip".to_string(), Value::String(address));
+    }
+    match edit.icon_base64.as_deref() {
+        Some("") => {
+            entry.remove("icon");
+        }
+        Some(icon) => {
+            entry.insert("icon".to_string(), Value::String(icon.to_string()));
+        }
+        None => {}
+    }
+    match edit.accepts_textures {
+        // "prompt" is stored by leaving the tag out
+        Some(0) => {
+            entry.remove("acceptTextures");
+        }
+        Some(value) => {
+            entry.insert(
+                "acceptTextures".to_string(),
+                Value::Byte((value == 1) as i8),
+            );
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+pub fn remove_server(root: &mut Value, index: usize) -> Result<()> {
+    let servers = servers_mut(root)?;
+    check_index(servers, index)?;
+    servers.remove(index);
+    Ok(())
+}
+
+/// Moves an entry from `from` to position `to`.
+pub fn move_server(root: &mut Value, from: usize, to: usize) -> Result<()> {
+    let servers = servers_mut(root)?;
+    check_index(servers, from)?;
+    check_index(servers, to)?;
+    let entry = servers.remove(from);
+    servers.insert(to, entry);
+    Ok(())
+}
+
+/// Lower case and without the default port, so `Play.Example.com:25565` matches
+/// `play.example.com`.
+fn normalize_address(address: &str) -> String {
+    let address = address.trim().trim_end_matches('.').to_lowercase();
+    address
+        .strip_suffix(":25565")
+        .map(|host| host.trim_end_matches('.').to_string())
+        .unwrap_or(address)
+}
+
+/// Pushes favorites into a server list. Entries with the favorite's current or one of its
+/// previous addresses are updated to the current name and address, missing favorites are
+/// added at the top in favorite order. Other entries are left alone.
+pub fn apply_favorites(
+    root: &mut Value,
+    favorites: &[FavoriteServer],
+) -> Result<FavoriteSyncChanges> {
+    let servers = servers_mut(root)?;
+    let mut changes = FavoriteSyncChanges::default();
+    let mut insert_at = 0;
+    for favorite in favorites {
+        let known: Vec<String> = std::iter::once(&favorite.address)
+            .chain(favorite.previous_addresses.iter())
+            .map(|address| normalize_address(address))
+            .collect();
+        let mut found = false;
+        for server in servers.iter_mut() {
+            let Value::Compound(entry) = server else {
+                continue;
+            };
+            let matches =
+                string_tag(entry, "ip").is_some_and(|ip| known.contains(&normalize_address(&ip)));
+            if !matches {
+                continue;
+            }
+            found = true;
+            let mut changed = false;
+            if string_tag(entry, "ip").as_deref() != Some(favorite.address.as_str()) {
+                entry.insert("ip".to_string(), Value::String(favorite.address.clone()));
+                changed = true;
+            }
+            if string_tag(entry, "name").as_deref() != Some(favorite.name.as_str()) {
+                entry.insert("name".to_string(), Value::String(favorite.name.clone()));
+                changed = true;
+            }
+            if let Some(icon) = &favorite.icon_base64 {
+                if string_tag(entry, "icon").as_ref() != Some(icon) {
+                    entry.insert("icon".to_string(), Value::String(icon.clone()));
+                    changed = true;
+                }
+            }
+            if changed {
+                changes.updated += 1;
+            }
+        }
+        if !found {
+            servers.insert(
+                insert_at,
+                server_entry(
+                    &favorite.name,
+                    &favorite.address,
+                    favorite.icon_base64.as_deref(),
+                ),
+            );
+            insert_at += 1;
+            changes.added += 1;
+        }
+    }
+    Ok(changes)
+}
+
+/// Reads servers.dat of an instance, applies `change` and writes the list back. The previous
+/// file is kept as `servers.dat_old`. Nothing is written if `change` fails or changes nothing.
+pub async fn modify_servers_dat<T>(
+    instance_path: &Path,
+    change: impl FnOnce(&mut Value) -> Result<T>,
+) -> Result<T> {
+    let path = instance_path.join(SERVERS_DAT);
+    let lock = SERVERS_DAT_LOCKS.entry(path.clone()).or_default().clone();
+    let _guard = lock.lock().await;
+
+    let existing = match fs::read(&path).await {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let mut root = parse_servers_dat(existing.as_deref().unwrap_or_default())?;
+    let before = root.clone();
+    let result = change(&mut root)?;
+    if root == before {
+        return Ok(result);
+    }
+
+    fs::create_dir_all(instance_path).await?;
+    if let Some(existing) = &existing {
+        fs::write(instance_path.join(SERVERS_DAT_OLD), existing).await?;
+    }
+    let tmp_path = instance_path.join(format!("{}.tmp", SERVERS_DAT));
+    fs::write(&tmp_path, write_servers_dat(&root)?).await?;
+    fs::rename(&tmp_path, &path).await?;
+    Ok(result)
+}
+
+/// Instance folder of a user profile or a standard profile.
+async fn profile_instance_path(profile_id: Uuid) -> Result<PathBuf> {
+    let state = State::get().await?;
+    let profile = match state.profile_manager.get_profile(profile_id).await {
+        Ok(profile) => profile,
+        Err(AppError::ProfileNotFound(_)) => state
+            .norisk_version_manager
+            .get_profile_by_id(profile_id)
+            .await
+            .ok_or(AppError::ProfileNotFound(profile_id))?,
+        Err(e) => return Err(e),
+    };
+    state
+        .profile_manager
+        .calculate_instance_path_for_profile(&profile)
+}
+
+/// Changes the server list of a profile and returns the new list.
+pub async fn modify_profile_servers(
+    profile_id: Uuid,
+    change: impl FnOnce(&mut Value) -> Result<()>,
+) -> Result<Vec<ServerInfo>> {
+    let instance_path = profile_instance_path(profile_id).await?;
+    let servers = modify_servers_dat(&instance_path, |root| {
+        change(root)?;
+        Ok(server_list(root))
+    })
+    .await?;
+    info!(
+        "[Servers] Updated servers.dat of profile {} ({} servers)",
+        profile_id,
+        servers.len()
+    );
+    Ok(servers)
+}
+
+/// Pushes favorites into the server lists of the given profiles (all user profiles if
+/// `None`). A failing profile doesn't stop the others.
+pub async fn sync_favorites_to_profiles(
+    favorites: &[FavoriteServer],
+    profile_ids: Option<&[Uuid]>,
+) -> Result<Vec<ServerSyncResult>> {
+    let state = State::get().await?;
+    let profiles = match profile_ids {
+        Some(ids) => {
+            let mut profiles = Vec::with_capacity(ids.len());
+            for id in ids {
+                profiles.push(match state.profile_manager.get_profile(*id).await {
+                    Ok(profile) => profile,
+                    Err(AppError::ProfileNotFound(_)) => state
+                        .norisk_version_manager
+                        .get_profile_by_id(*id)
+                        .await
+                        .ok_or(AppError::ProfileNotFound(*id))?,
+                    Err(e) => return Err(e),
+                });
+            }
+            profiles
+        }
+        None => state.profile_manager.list_profiles().await?,
+    };
+
+    let mut results = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        let result = match state
+            .profile_manager
+            .calculate_instance_path_for_profile(&profile)
+        {
+            Ok(instance_path) => {
+                modify_servers_dat(&instance_path, |root| apply_favorites(root, favorites)).await
+            }
+            Err(e) => Err(e),
+        };
+        let (changes, error) = match result {
+            Ok(changes) => (changes, None),
+            Err(e) => {
+                warn!(
+                    "[Servers] Failed to sync favorite servers into profile '{}': {}",
+                    profile.name, e
+                );
+                (FavoriteSyncChanges::default(), Some(e.to_string()))
+            }
+        };
+        results.push(ServerSyncResult {
+            profile_id: profile.id,
+            profile_name: profile.name,
+            changes,
+            error,
+        });
+    }
+    Ok(results)
+}
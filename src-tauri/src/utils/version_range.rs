@@ -0,0 +1,258 @@
+use std::cmp::Ordering;
+
+/// Syntax of a version predicate in mod metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSyntax {
+    /// Fabric/Quilt: `>=0.15 <0.16`, `~1.20.1`, `^2.0`, `1.20.x`, alternatives joined with `||`
+    Fabric,
+    /// Forge/NeoForge maven ranges: `[47,)`, `[1.20,1.21)`, `(,2.0]`, a bare version accepts anything
+    Maven,
+}
+
+/// Checks a version against a predicate. `None` if either side can't be interpreted
+/// (e.g. snapshot names), callers should treat that as "unknown" instead of a mismatch.
+pub fn matches(range: &str, version: &str, syntax: RangeSyntax) -> Option<bool> {
+    let version = LooseVersion::parse(version)?;
+    match syntax {
+        RangeSyntax::Fabric => matches_fabric(range, &version),
+        RangeSyntax::Maven => matches_maven(range, &version),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Number(u64),
+    Text(String),
+}
+
+impl Part {
+    fn parse(value: &str) -> Self {
+        match value.parse::<u64>() {
+            Ok(number) => Part::Number(number),
+            Err(_) => Part::Text(value.to_lowercase()),
+        }
+    }
+}
+
+impl Ord for Part {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Part::Number(a), Part::Number(b)) => a.cmp(b),
+            (Part::Number(_), Part::Text(_)) => Ordering::Less,
+            (Part::Text(_), Part::Number(_)) => Ordering::Greater,
+            (Part::Text(a), Part::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Part {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Version compared the way mod loaders do: missing components count as zero
+/// (`1.20` == `1.20.0`) and pre-releases sort before the release (`1.0-beta` < `1.0`).
+#[derive(Debug, Clone)]
+struct LooseVersion {
+    core: Vec<Part>,
+    pre_release: Vec<Part>,
+}
+
+impl LooseVersion {
+    fn parse(version: &str) -> Option<Self> {
+        // Build metadata (`+build.3`, `+1.20.1`) doesn't affect ordering
+        let version = version.trim().split('+').next()?;
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre_release)) => (core, pre_release),
+            None => (version, ""),
+        };
+        let core: Vec<Part> = core.split('.').map(Part::parse).collect();
+        // Snapshot names like 24w10a are not comparable to release versions
+        if !matches!(core.first(), Some(Part::Number(_))) {
+            return None;
+        }
+        let pre_release = if pre_release.is_empty() {
+            Vec::new()
+        } else {
+            pre_release.split('.').map(Part::parse).collect()
+        };
+        Some(Self { core, pre_release })
+    }
+
+    fn core_part(&self, index: usize) -> Part {
+        self.core.get(index).cloned().unwrap_or(Part::Number(0))
+    }
+
+    /// Smallest version above every version starting with the first `length` components
+    /// (`1.20.4` with length 2 -> `1.21`).
+    fn bump(&self, length: usize) -> Self {
+        let mut core: Vec<Part> = (0..length.max(1)).map(|i| self.core_part(i)).collect();
+        if let Some(Part::Number(last)) = core.last_mut() {
+            *last += 1;
+        }
+        Self {
+            core,
+            pre_release: Vec::new(),
+        }
+    }
+}
+
+impl Ord for LooseVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let length = self.core.len().max(other.core.len());
+        for index in 0..length {
+            match self.core_part(index).cmp(&other.core_part(index)) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            }
+        }
+        match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.pre_release.cmp(&other.pre_release),
+        }
+    }
+}
+
+impl PartialEq for LooseVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LooseVersion {}
+
+impl PartialOrd for LooseVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn matches_fabric(range: &str, version: &LooseVersion) -> Option<bool> {
+    let mut any_unknown = false;
+    for alternative in range.split("||") {
+        let mut alternative_result = Some(true);
+        for term in alternative.split_whitespace() {
+            match matches_fabric_term(term, version) {
+                Some(true) => {}
+                Some(false) => {
+                    alternative_result = Some(false);
+                    break;
+                }
+                None => alternative_result = None,
+            }
+        }
+        match alternative_result {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => any_unknown = true,
+        }
+    }
+    if any_unknown {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+fn matches_fabric_term(term: &str, version: &LooseVersion) -> Option<bool> {
+    if term == "*" {
+        return Some(true);
+    }
+    let (operator, target) = ["<=", ">=", "<", ">", "=", "~", "^"]
+        .iter()
+        .find_map(|op| term.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("=", term));
+
+    // X-ranges: `1.20.x` matches every 1.20 version
+    if let Some(prefix) = target
+        .strip_suffix(".x")
+        .or_else(|| target.strip_suffix(".X"))
+        .or_else(|| target.strip_suffix(".*"))
+    {
+        let prefix = LooseVersion::parse(prefix)?;
+        let length = prefix.core.len();
+        let in_range = version >= &prefix && version < &prefix.bump(length);
+        return Some(match operator {
+            "=" => in_range,
+            ">=" => version >= &prefix,
+            ">" => version >= &prefix.bump(length),
+            "<" => version < &prefix,
+            "<=" => version < &prefix.bump(length),
+            _ => in_range,
+        });
+    }
+
+    let target = LooseVersion::parse(target)?;
+    Some(match operator {
+        "<=" => version <= &target,
+        ">=" => version >= &target,
+        "<" => version < &target,
+        ">" => version > &target,
+        // Same minor version (same major if only the major is given)
+        "~" => version >= &target && version < &target.bump(target.core.len().min(2)),
+        // Same major version
+        "^" => version >= &target && version < &target.bump(1),
+        _ => version == &target,
+    })
+}
+
+fn matches_maven(range: &str, version: &LooseVersion) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    // A bare version is only a recommendation in maven ranges
+    if !range.starts_with('[') && !range.starts_with('(') {
+        return Some(true);
+    }
+
+    // `[1.0,2.0),[3.0,)` is a union of intervals
+    let mut rest = range;
+    while !rest.is_empty() {
+        let close = rest.find([']', ')'])?;
+        let interval = &rest[..=close];
+        if matches_maven_interval(interval, version)? {
+            return Some(true);
+        }
+        rest = rest[close + 1..].trim_start_matches([',', ' ']);
+    }
+    Some(false)
+}
+
+fn matches_maven_interval(interval: &str, version: &LooseVersion) -> Option<bool> {
+    let lower_inclusive = interval.starts_with('[');
+    let upper_inclusive = interval.ends_with(']');
+    let inner = interval.get(1..interval.len() - 1)?;
+
+    let Some((lower, upper)) = inner.split_once(',') else {
+        // `[1.0]` is an exact version
+        return Some(version == &LooseVersion::parse(inner)?);
+    };
+
+    if !lower.trim().is_empty() {
+        let lower = LooseVersion::parse(lower)?;
+        let above = if lower_inclusive {
+            version >= &lower
+        } else {
+            version > &lower
+        };
+        if !above {
+            return Some(false);
+        }
+    }
+    if !upper.trim().is_empty() {
+        let upper = LooseVersion::parse(upper)?;
+        let below = if upper_inclusive {
+            version <= &upper
+        } else {
+            version < &upper
+        };
+        if !below {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
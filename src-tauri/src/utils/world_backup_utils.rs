@@ -0,0 +1,579 @@
+use crate::error::{AppError, Result};
+use crate::utils::hash_utils;
+use chrono::{DateTime, Datelike, Local, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use uuid::Uuid;
+
+/// Incremental backups split files into chunks of this size, unchanged chunks are stored once.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+const CHUNKS_DIR: &str = "chunks";
+const ARCHIVE_EXTENSION: &str = "zip";
+const MANIFEST_SUFFIX: &str = ".manifest.json";
+const METADATA_EXTENSION: &str = "json";
+/// Held open by the running game and meaningless in a backup
+const SKIPPED_FILES: &[&str] = &["session.lock"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFormat {
+    /// A zip archive per backup
+    #[default]
+    Archive,
+    /// Deduplicated chunks shared between the backups of a world, for large worlds
+    Incremental,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupTrigger {
+    Manual,
+    GameExit,
+    Scheduled,
+    /// Taken automatically before a backup is restored over the world
+    BeforeRestore,
+}
+
+/// Which automatic backups are kept. Manual backups are never pruned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BackupRetention {
+    /// The newest N backups
+    #[serde(default)]
+    pub keep_last: u32,
+    /// The newest backup of each of the last N days that have backups
+    #[serde(default)]
+    pub keep_daily: u32,
+    /// The newest backup of each of the last N weeks that have backups
+    #[serde(default)]
+    pub keep_weekly: u32,
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        Self {
+            keep_last: 5,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WorldBackup {
+    pub id: String,
+    pub world_folder: String,
+    pub created_at: DateTime<Utc>,
+    pub trigger: BackupTrigger,
+    pub format: BackupFormat,
+    pub file_count: u64,
+    /// Size of the world when it was backed up
+    pub world_size_bytes: u64,
+    /// Disk space the backup added: the archive, or the new chunks of an incremental backup
+    pub stored_size_bytes: u64,
+    /// The game was running, files may have been written during the backup
+    #[serde(default)]
+    pub taken_while_running: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct BackupManifest {
+    files: Vec<ManifestFile>,
+    #[serde(default)]
+    dirs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+    modified_ms: i64,
+    /// SHA1 of each chunk, in order
+    chunks: Vec<String>,
+}
+
+struct WorldFile {
+    relative_path: String,
+    path: PathBuf,
+    size: u64,
+    modified_ms: i64,
+}
+
+/// Backs up a world folder into `backup_dir`. Blocking, run it with `spawn_blocking`.
+pub fn create_backup(
+    world_path: &Path,
+    backup_dir: &Path,
+    format: BackupFormat,
+    trigger: BackupTrigger,
+    taken_while_running: bool,
+) -> Result<WorldBackup> {
+    let world_folder = world_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid world path {:?}", world_path)))?;
+    if !world_path.is_dir() {
+        return Err(AppError::FileNotFound(world_path.to_path_buf()));
+    }
+    fs::create_dir_all(backup_dir)?;
+
+    let created_at = Utc::now();
+    let id = format!(
+        "{}-{}",
+        created_at.format("%Y%m%d-%H%M%S"),
+        &Uuid::new_v4().simple().to_string()[..8]
+    );
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    collect_world_files(world_path, "", &mut files, &mut dirs)?;
+
+    let stored_size_bytes = match format {
+        BackupFormat::Archive => write_archive(backup_dir, &id, &files, &dirs)?,
+        BackupFormat::Incremental => write_incremental(backup_dir, &id, &files, dirs)?,
+    };
+
+    let backup = WorldBackup {
+        id,
+        world_folder,
+        created_at,
+        trigger,
+        format,
+        file_count: files.len() as u64,
+        world_size_bytes: files.iter().map(|f| f.size).sum(),
+        stored_size_bytes,
+        taken_while_running,
+    };
+    // The metadata is written last, a backup without it doesn't exist
+    write_atomically(
+        &metadata_path(backup_dir, &backup.id),
+        &serde_json::to_vec_pretty(&backup)?,
+    )?;
+    info!(
+        "Backed up world '{}' as {} ({:?}, {} files, {} bytes stored)",
+        backup.world_folder, backup.id, backup.format, backup.file_count, backup.stored_size_bytes
+    );
+    Ok(backup)
+}
+
+/// All backups in `backup_dir`, newest first.
+pub fn list_backups(backup_dir: &Path) -> Result<Vec<WorldBackup>> {
+    let mut backups = Vec::new();
+    if !backup_dir.is_dir() {
+        return Ok(backups);
+    }
+    for entry in fs::read_dir(backup_dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !path.is_file()
+            || name.ends_with(MANIFEST_SUFFIX)
+            || path.extension().is_none_or(|e| e != METADATA_EXTENSION)
+        {
+            continue;
+        }
+        match fs::read(&path)
+            .map_err(AppError::from)
+            .and_then(|bytes| Ok(serde_json::from_slice::<WorldBackup>(&bytes)?))
+        {
+            Ok(backup) => backups.push(backup),
+            Err(e) => warn!("Ignoring unreadable backup metadata {:?}: {}", path, e),
+        }
+    }
+    backups.sort_by_key(|b| Reverse(b.created_at));
+    Ok(backups)
+}
+
+/// Restores a backup into `target_world_path`, which must not exist yet. The files are
+/// extracted next to it first, so a failed restore never leaves a half written world.
+pub fn restore_backup(backup_dir: &Path, backup_id: &str, target_world_path: &Path) -> Result<()> {
+    let backup = read_backup(backup_dir, backup_id)?;
+    if target_world_path.exists() {
+        return Err(AppError::InvalidOperation(format!(
+            "Restore target {:?} already exists",
+            target_world_path
+        )));
+    }
+    let target_name = target_world_path.file_name().ok_or_else(|| {
+        AppError::InvalidInput(format!("Invalid world path {:?}", target_world_path))
+    })?;
+    let temp_path = target_world_path.with_file_name(format!(
+        ".{}.restoring-{}",
+        target_name.to_string_lossy(),
+        backup.id
+    ));
+    if temp_path.exists() {
+        fs::remove_dir_all(&temp_path)?;
+    }
+    fs::create_dir_all(&temp_path)?;
+
+    let result = match backup.format {
+        BackupFormat::Archive => extract_archive(backup_dir, &backup.id, &temp_path),
+        BackupFormat::Incremental => restore_incremental(backup_dir, &backup.id, &temp_path),
+    };
+    if let Err(e) = result.and_then(|_| Ok(fs::rename(&temp_path, target_world_path)?)) {
+        let _ = fs::remove_dir_all(&temp_path);
+        return Err(e);
+    }
+    info!(
+        "Restored backup {} of '{}' to {:?}",
+        backup.id, backup.world_folder, target_world_path
+    );
+    Ok(())
+}
+
+/// Deletes a backup. Chunks only it used are removed too.
+pub fn delete_backup(backup_dir: &Path, backup_id: &str) -> Result<()> {
+    let backup = read_backup(backup_dir, backup_id)?;
+    // Metadata first, so an interrupted delete leaves no broken backup behind
+    fs::remove_file(metadata_path(backup_dir, &backup.id))?;
+    let data_path = match backup.format {
+        BackupFormat::Archive => archive_path(backup_dir, &backup.id),
+        BackupFormat::Incremental => manifest_path(backup_dir, &backup.id),
+    };
+    if data_path.exists() {
+        fs::remove_file(data_path)?;
+    }
+    if backup.format == BackupFormat::Incremental {
+        remove_unused_chunks(backup_dir)?;
+    }
+    info!("Deleted backup {} of '{}'", backup.id, backup.world_folder);
+    Ok(())
+}
+
+/// Removes chunks no incremental backup refers to anymore, returns the freed bytes.
+pub fn remove_unused_chunks(backup_dir: &Path) -> Result<u64> {
+    let chunks_dir = backup_dir.join(CHUNKS_DIR);
+    if !chunks_dir.is_dir() {
+        return Ok(0);
+    }
+    let mut used = HashSet::new();
+    for backup in list_backups(backup_dir)? {
+        if backup.format == BackupFormat::Incremental {
+            let manifest = read_manifest(backup_dir, &backup.id)?;
+            used.extend(manifest.files.into_iter().flat_map(|f| f.chunks));
+        }
+    }
+
+    let mut freed = 0;
+    for shard in fs::read_dir(&chunks_dir)? {
+        let shard = shard?.path();
+        if !shard.is_dir() {
+            continue;
+        }
+        for chunk in fs::read_dir(&shard)? {
+            let chunk = chunk?;
+            let name = chunk.file_name().to_string_lossy().into_owned();
+            if !used.contains(&name) {
+                freed += chunk.metadata().map(|m| m.len()).unwrap_or(0);
+                fs::remove_file(chunk.path())?;
+            }
+        }
+    }
+    Ok(freed)
+}
+
+/// Ids of the automatic backups the retention policy doesn't keep. The newest automatic
+/// backup is always kept.
+pub fn select_expired_backups(backups: &[WorldBackup], retention: &BackupRetention) -> Vec<String> {
+    let mut automatic: Vec<&WorldBackup> = backups
+        .iter()
+        .filter(|b| b.trigger != BackupTrigger::Manual)
+        .collect();
+    automatic.sort_by_key(|b| Reverse(b.created_at));
+
+    let mut keep: HashSet<&str> = automatic
+        .iter()
+        .take((retention.keep_last as usize).max(1))
+        .map(|b| b.id.as_str())
+        .collect();
+
+    let mut keep_newest_per = |limit: u32, period: &dyn Fn(&WorldBackup) -> (i32, u32)| {
+        let mut seen = HashSet::new();
+        for backup in &automatic {
+            if seen.len() >= limit as usize {
+                break;
+            }
+            if seen.insert(period(backup)) {
+                keep.insert(backup.id.as_str());
+            }
+        }
+    };
+    keep_newest_per(retention.keep_daily, &|b| {
+        let local = b.created_at.with_timezone(&Local);
+        (local.year(), local.ordinal())
+    });
+    keep_newest_per(retention.keep_weekly, &|b| {
+        let week = b.created_at.with_timezone(&Local).iso_week();
+        (week.year(), week.week())
+    });
+
+    automatic
+        .iter()
+        .filter(|b| !keep.contains(b.id.as_str()))
+        .map(|b| b.id.clone())
+        .collect()
+}
+
+fn read_backup(backup_dir: &Path, backup_id: &str) -> Result<WorldBackup> {
+    // Ids end up in file names
+    if backup_id.is_empty() || backup_id.contains(['/', '\\', '.']) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid backup id '{}'",
+            backup_id
+        )));
+    }
+    let path = metadata_path(backup_dir, backup_id);
+    if !path.exists() {
+        return Err(AppError::NotFound(format!("Backup {}", backup_id)));
+    }
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+fn metadata_path(backup_dir: &Path, id: &str) -> PathBuf {
+    backup_dir.join(format!("{}.{}", id, METADATA_EXTENSION))
+}
+
+fn archive_path(backup_dir: &Path, id: &str) -> PathBuf {
+    backup_dir.join(format!("{}.{}", id, ARCHIVE_EXTENSION))
+}
+
+fn manifest_path(backup_dir: &Path, id: &str) -> PathBuf {
+    backup_dir.join(format!("{}{}", id, MANIFEST_SUFFIX))
+}
+
+fn chunk_path(backup_dir: &Path, sha1: &str) -> PathBuf {
+    let shard = sha1.get(..2).unwrap_or("00");
+    backup_dir.join(CHUNKS_DIR).join(shard).join(sha1)
+}
+
+fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, data)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn collect_world_files(
+    dir: &Path,
+    prefix: &str,
+    files: &mut Vec<WorldFile>,
+    dirs: &mut Vec<String>,
+) -> Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative_path = format!("{}{}", prefix, name);
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            dirs.push(relative_path.clone());
+            collect_world_files(&entry.path(), &format!("{}/", relative_path), files, dirs)?;
+        } else if file_type.is_file() && !SKIPPED_FILES.contains(&name.as_str()) {
+            let metadata = entry.metadata()?;
+            let modified_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0);
+            files.push(WorldFile {
+                relative_path,
+                path: entry.path(),
+                size: metadata.len(),
+                modified_ms,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn write_archive(backup_dir: &Path, id: &str, files: &[WorldFile], dirs: &[String]) -> Result<u64> {
+    let path = archive_path(backup_dir, id);
+    let tmp_path = path.with_extension("zip.tmp");
+    let write = || -> Result<()> {
+        let mut writer = zip::ZipWriter::new(BufWriter::new(File::create(&tmp_path)?));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for dir in dirs {
+            writer.add_directory(dir.as_str(), options)?;
+        }
+        for file in files {
+            writer.start_file(
+                file.relative_path.as_str(),
+                options.large_file(file.size >= u32::MAX as u64),
+            )?;
+            io::copy(&mut BufReader::new(File::open(&file.path)?), &mut writer)?;
+        }
+        writer.finish()?.flush()?;
+        Ok(())
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    fs::rename(&tmp_path, &path)?;
+    Ok(fs::metadata(&path)?.len())
+}
+
+fn extract_archive(backup_dir: &Path, id: &str, target: &Path) -> Result<()> {
+    let mut archive =
+        zip::ZipArchive::new(BufReader::new(File::open(archive_path(backup_dir, id))?))?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(relative_path) = entry.enclosed_name() else {
+            warn!("Skipping unsafe path '{}' in backup {}", entry.name(), id);
+            continue;
+        };
+        let path = target.join(relative_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut BufWriter::new(File::create(&path)?))?;
+    }
+    Ok(())
+}
+
+fn read_manifest(backup_dir: &Path, id: &str) -> Result<BackupManifest> {
+    Ok(serde_json::from_slice(&fs::read(manifest_path(
+        backup_dir, id,
+    ))?)?)
+}
+
+fn write_incremental(
+    backup_dir: &Path,
+    id: &str,
+    files: &[WorldFile],
+    dirs: Vec<String>,
+) -> Result<u64> {
+    // Files that didn't change since the last incremental backup aren't read again
+    let previous: HashMap<String, ManifestFile> = list_backups(backup_dir)?
+        .into_iter()
+        .find(|b| b.format == BackupFormat::Incremental)
+        .and_then(|b| read_manifest(backup_dir, &b.id).ok())
+        .map(|m| m.files.into_iter().map(|f| (f.path.clone(), f)).collect())
+        .unwrap_or_default();
+
+    let mut stored = 0;
+    let mut manifest = BackupManifest {
+        files: Vec::with_capacity(files.len()),
+        dirs,
+    };
+    for file in files {
+        if let Some(unchanged) = previous.get(&file.relative_path).filter(|p| {
+            p.size == file.size
+                && p.modified_ms == file.modified_ms
+                && p.chunks.iter().all(|c| chunk_path(backup_dir, c).exists())
+        }) {
+            manifest.files.push(unchanged.clone());
+            continue;
+        }
+
+        let mut chunks = Vec::new();
+        let mut size = 0;
+        let mut reader = BufReader::new(File::open(&file.path)?);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        loop {
+            let n = read_full(&mut reader, &mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            size += n as u64;
+            let sha1 = hash_utils::calculate_sha1_from_bytes(&buffer[..n]);
+            let path = chunk_path(backup_dir, &sha1);
+            if !path.exists() {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&buffer[..n])?;
+                let compressed = encoder.finish()?;
+                fs::create_dir_all(path.parent().unwrap_or(backup_dir))?;
+                write_atomically(&path, &compressed)?;
+                stored += compressed.len() as u64;
+            }
+            chunks.push(sha1);
+        }
+        manifest.files.push(ManifestFile {
+            path: file.relative_path.clone(),
+            // The game may have written to the file since it was listed
+            size,
+            modified_ms: file.modified_ms,
+            chunks,
+        });
+    }
+
+    let manifest_bytes = serde_json::to_vec(&manifest)?;
+    stored += manifest_bytes.len() as u64;
+    write_atomically(&manifest_path(backup_dir, id), &manifest_bytes)?;
+    Ok(stored)
+}
+
+fn restore_incremental(backup_dir: &Path, id: &str, target: &Path) -> Result<()> {
+    let manifest = read_manifest(backup_dir, id)?;
+    for dir in &manifest.dirs {
+        fs::create_dir_all(target.join(safe_relative_path(dir)?))?;
+    }
+    for file in &manifest.files {
+        let path = target.join(safe_relative_path(&file.path)?);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for sha1 in &file.chunks {
+            let compressed = fs::read(chunk_path(backup_dir, sha1)).map_err(|e| {
+                AppError::Other(format!(
+                    "Backup {} is damaged, chunk {} of '{}' can't be read: {}",
+                    id, sha1, file.path, e
+                ))
+            })?;
+            let mut data = Vec::with_capacity(CHUNK_SIZE);
+            GzDecoder::new(&compressed[..]).read_to_end(&mut data)?;
+            if hash_utils::calculate_sha1_from_bytes(&data) != *sha1 {
+                return Err(AppError::Other(format!(
+                    "Backup {} is damaged, chunk {} of '{}' doesn't match its hash",
+                    id, sha1, file.path
+                )));
+            }
+            writer.write_all(&data)?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Rejects absolute paths and `..` from manifests.
+fn safe_relative_path(path: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "Unsafe path '{}' in backup",
+            path
+        )))
+    }
+}
+
+/// Reads until the buffer is full or the file ends.
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
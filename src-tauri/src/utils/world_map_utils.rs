@@ -0,0 +1,1103 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::State;
+use crate::utils::level_dat_utils::{self, SpawnPoint};
+use dashmap::DashMap;
+use fastnbt::LongArray;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use image::{ImageFormat, RgbaImage};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+use uuid::Uuid;
+
+/// Width and height of a map tile in pixels. At zoom 0 a tile is exactly one region (one
+/// pixel per block), every further zoom level halves the resolution.
+pub const TILE_SIZE: u32 = 512;
+/// A tile at this zoom covers 8x8 regions.
+pub const MAX_ZOOM: u8 = 3;
+
+const REGION_CHUNKS: usize = 32;
+const SECTOR_SIZE: usize = 4096;
+const CACHE_VERSION: u32 = 1;
+const CACHE_INDEX_FILE: &str = "index.json";
+/// The nether roof; rendering starts below it so the map shows the caves instead of bedrock.
+const NETHER_ROOF_Y: i32 = 127;
+const MAX_WATER_DEPTH: i32 = 16;
+
+static RENDER_LOCKS: Lazy<DashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>> = Lazy::new(DashMap::new);
+
+/// One PNG tile of a rendered map. At zoom `n` the tile covers the blocks from
+/// `x * 512 * 2^n` to `(x + 1) * 512 * 2^n` (same for z).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WorldMapTile {
+    pub zoom: u8,
+    pub x: i32,
+    pub z: i32,
+    pub path: PathBuf,
+}
+
+/// Result of updating the tile cache of one dimension.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldMapUpdate {
+    pub tiles: Vec<WorldMapTile>,
+    /// Regions that were (re)rendered because they are new or changed
+    pub rendered_regions: usize,
+    /// Regions whose tiles were reused from the cache
+    pub cached_regions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldMap {
+    pub world_folder: String,
+    pub dimension: String,
+    pub tile_size: u32,
+    pub max_zoom: u8,
+    pub spawn: Option<SpawnPoint>,
+    #[serde(flatten)]
+    pub update: WorldMapUpdate,
+}
+
+// --- Cache index ---
+
+/// Size and modification time of a region file when its tile was rendered.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+struct RegionStamp {
+    size: u64,
+    modified_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MapCacheIndex {
+    version: u32,
+    regions: BTreeMap<String, RegionStamp>,
+}
+
+// --- Chunk NBT (1.18+ at the root, 1.13 - 1.17 below "Level") ---
+
+#[derive(Deserialize)]
+struct ChunkNbt {
+    #[serde(rename = "yPos")]
+    y_pos: Option<i32>,
+    #[serde(rename = "Status")]
+    status: Option<String>,
+    sections: Option<Vec<SectionNbt>>,
+    #[serde(rename = "Heightmaps")]
+    heightmaps: Option<HeightmapsNbt>,
+    #[serde(rename = "Level")]
+    level: Option<LegacyLevelNbt>,
+}
+
+#[derive(Deserialize)]
+struct SectionNbt {
+    #[serde(rename = "Y")]
+    y: i8,
+    block_states: Option<BlockStatesNbt>,
+}
+
+#[derive(Deserialize)]
+struct BlockStatesNbt {
+    palette: Vec<BlockStateNbt>,
+    data: Option<LongArray>,
+}
+
+#[derive(Deserialize)]
+struct LegacyLevelNbt {
+    #[serde(rename = "Status")]
+    status: Option<String>,
+    #[serde(rename = "Sections")]
+    sections: Option<Vec<LegacySectionNbt>>,
+    #[serde(rename = "Heightmaps")]
+    heightmaps: Option<HeightmapsNbt>,
+}
+
+#[derive(Deserialize)]
+struct LegacySectionNbt {
+    #[serde(rename = "Y")]
+    y: i8,
+    // Missing before 1.13 (numeric block ids), those chunks are not rendered
+    #[serde(rename = "Palette")]
+    palette: Option<Vec<BlockStateNbt>>,
+    #[serde(rename = "BlockStates")]
+    block_states: Option<LongArray>,
+}
+
+#[derive(Deserialize)]
+struct BlockStateNbt {
+    #[serde(rename = "Name")]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct HeightmapsNbt {
+    #[serde(rename = "WORLD_SURFACE")]
+    world_surface: Option<LongArray>,
+}
+
+struct Section {
+    palette: Vec<String>,
+    /// 4096 palette indices in YZX order, `None` if the section is a single block
+    indices: Option<Vec<u16>>,
+}
+
+struct Chunk {
+    min_y: i32,
+    min_section: i32,
+    sections: Vec<Option<Section>>,
+    /// Highest non-air block per column (ZX order)
+    surface: Option<Vec<i32>>,
+}
+
+impl Chunk {
+    fn max_y(&self) -> i32 {
+        (self.min_section + self.sections.len() as i32) * 16 - 1
+    }
+
+    fn block(&self, x: usize, y: i32, z: usize) -> Option<&str> {
+        let index = y.div_euclid(16) - self.min_section;
+        if index < 0 {
+            return None;
+        }
+        let section = self.sections.get(index as usize)?.as_ref()?;
+        let palette_index = match &section.indices {
+            Some(indices) => indices[(y.rem_euclid(16) as usize) * 256 + z * 16 + x] as usize,
+            None => 0,
+        };
+        section.palette.get(palette_index).map(String::as_str)
+    }
+}
+
+/// What the map shows for one column: its color and the height used for shading.
+#[derive(Clone, Copy)]
+struct Column {
+    color: [u8; 3],
+    height: i32,
+}
+
+// Statuses of chunks that are fully generated; proto chunks at the border are skipped.
+const FINISHED_STATUSES: &[&str] = &["full", "spawn", "heightmaps", "postprocessed", "fullchunk"];
+
+/// Unpacks `count` values of `bits` bits from a long array. Since 1.16 values never span two
+/// longs, before that they were packed tightly; the array length tells which one it is.
+fn unpack_longs(data: &[i64], bits: usize, count: usize) -> Option<Vec<u16>> {
+    if bits == 0 || bits > 16 {
+        return None;
+    }
+    let mask = (1u64 << bits) - 1;
+    let per_long = 64 / bits;
+    let mut values = Vec::with_capacity(count);
+    if data.len() == count.div_ceil(per_long) {
+        for i in 0..count {
+            let long = data[i / per_long] as u64;
+            values.push(((long >> ((i % per_long) * bits)) & mask) as u16);
+        }
+    } else if data.len() == (count * bits).div_ceil(64) {
+        for i in 0..count {
+            let bit = i * bits;
+            let (long, offset) = (bit / 64, bit % 64);
+            let mut value = (data[long] as u64) >> offset;
+            if offset + bits > 64 {
+                value |= (data[long + 1] as u64) << (64 - offset);
+            }
+            values.push((value & mask) as u16);
+        }
+    } else {
+        return None;
+    }
+    Some(values)
+}
+
+/// Heightmaps use as many bits as the dimension height needs, found via the array length.
+fn unpack_heightmap(data: &[i64]) -> Option<Vec<u16>> {
+    (1..=16).find_map(|bits| {
+        let per_long = 64 / bits;
+        (data.len() == 256usize.div_ceil(per_long) || data.len() == (256 * bits).div_ceil(64))
+            .then(|| unpack_longs(data, bits, 256))
+            .flatten()
+    })
+}
+
+fn decode_section(palette: Vec<BlockStateNbt>, data: Option<LongArray>) -> Option<Section> {
+    if palette.is_empty() {
+        return None;
+    }
+    let palette: Vec<String> = palette.into_iter().map(|b| b.name).collect();
+    let indices = match data {
+        Some(data) if palette.len() > 1 => {
+            let bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(4) as usize;
+            Some(unpack_longs(&data, bits, 4096)?)
+        }
+        _ => None,
+    };
+    Some(Section { palette, indices })
+}
+
+fn decode_chunk(nbt: &[u8]) -> Result<Option<Chunk>> {
+    let chunk: ChunkNbt = fastnbt::from_bytes(nbt)?;
+    let (status, raw_sections, heightmaps, min_y) = match chunk.level {
+        Some(level) => (
+            level.status,
+            level
+                .sections
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|s| Some((s.y as i32, s.palette?, s.block_states)))
+                .collect::<Vec<_>>(),
+            level.heightmaps,
+            0,
+        ),
+        None => (
+            chunk.status,
+            chunk
+                .sections
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|s| {
+                    let states = s.block_states?;
+                    Some((s.y as i32, states.palette, states.data))
+                })
+                .collect(),
+            chunk.heightmaps,
+            chunk.y_pos.unwrap_or(-4) * 16,
+        ),
+    };
+    if let Some(status) = status {
+        let status = status.trim_start_matches("minecraft:");
+        if !FINISHED_STATUSES.contains(&status) {
+            return Ok(None);
+        }
+    }
+    let (Some(min_section), Some(max_section)) = (
+        raw_sections.iter().map(|s| s.0).min(),
+        raw_sections.iter().map(|s| s.0).max(),
+    ) else {
+        return Ok(None);
+    };
+
+    let mut sections: Vec<Option<Section>> = (min_section..=max_section).map(|_| None).collect();
+    for (y, palette, data) in raw_sections {
+        sections[(y - min_section) as usize] = decode_section(palette, data);
+    }
+    let surface = heightmaps
+        .and_then(|h| h.world_surface)
+        .and_then(|data| unpack_heightmap(&data))
+        .map(|values| values.into_iter().map(|v| min_y + v as i32 - 1).collect());
+
+    Ok(Some(Chunk {
+        min_y: min_y.min(min_section * 16),
+        min_section,
+        sections,
+        surface,
+    }))
+}
+
+fn shade(color: [u8; 3], factor: f32) -> [u8; 3] {
+    color.map(|c| (c as f32 * factor).round().clamp(0.0, 255.0) as u8)
+}
+
+fn blend(a: [u8; 3], b: [u8; 3], amount_of_b: f32) -> [u8; 3] {
+    [0, 1, 2].map(|i| (a[i] as f32 * (1.0 - amount_of_b) + b[i] as f32 * amount_of_b) as u8)
+}
+
+fn column(chunk: &Chunk, x: usize, z: usize, has_ceiling: bool) -> Option<Column> {
+    let is_air = |y: i32| {
+        chunk
+            .block(x, y, z)
+            .is_none_or(|name| block_color(name).is_none())
+    };
+    let mut top = match &chunk.surface {
+        Some(surface) if !has_ceiling => surface[z * 16 + x].min(chunk.max_y()),
+        _ => chunk.max_y(),
+    };
+    if has_ceiling {
+        // Skip the roof down to the first open space
+        top = top.min(NETHER_ROOF_Y);
+        let roof = top;
+        while top >= chunk.min_y && !is_air(top) {
+            top -= 1;
+        }
+        if top < chunk.min_y {
+            top = roof;
+        }
+    }
+
+    let mut water: Option<(i32, i32)> = None; // (surface, depth)
+    for y in (chunk.min_y..=top).rev() {
+        let Some(name) = chunk.block(x, y, z) else {
+            continue;
+        };
+        let Some(color) = block_color(name) else {
+            continue;
+        };
+        if is_water(name) {
+            let (surface, depth) = water.get_or_insert((y, 0));
+            *depth += 1;
+            if *depth >= MAX_WATER_DEPTH {
+                return Some(Column {
+                    color: shade(WATER_COLOR, 0.75),
+                    height: *surface,
+                });
+            }
+            continue;
+        }
+        return Some(match water {
+            Some((surface, depth)) => Column {
+                color: blend(color, WATER_COLOR, (0.55 + depth as f32 * 0.03).min(0.9)),
+                height: surface,
+            },
+            None => Column { color, height: y },
+        });
+    }
+    water.map(|(surface, _)| Column {
+        color: WATER_COLOR,
+        height: surface,
+    })
+}
+
+/// Reads the decompressed NBT of one chunk. Chunks too large for the region file are stored
+/// next to it as `c.<x>.<z>.mcc`.
+fn read_chunk_nbt(
+    region: &[u8],
+    index: usize,
+    region_dir: &Path,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Result<Option<Vec<u8>>> {
+    let location = &region[index * 4..index * 4 + 4];
+    let offset = u32::from_be_bytes([0, location[0], location[1], location[2]]) as usize;
+    if offset < 2 || location[3] == 0 {
+        return Ok(None);
+    }
+    let start = offset * SECTOR_SIZE;
+    let header = region
+        .get(start..start + 5)
+        .ok_or_else(|| AppError::ParseError(format!("Chunk {} points outside of region", index)))?;
+    let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let compression = header[4];
+
+    let external;
+    let (compression, payload) = if compression & 0x80 != 0 {
+        external = fs::read(region_dir.join(format!("c.{}.{}.mcc", chunk_x, chunk_z)))?;
+        (compression & 0x7f, external.as_slice())
+    } else {
+        let payload = region
+            .get(start + 5..start + 4 + length.max(1))
+            .ok_or_else(|| AppError::ParseError(format!("Chunk {} is truncated", index)))?;
+        (compression, payload)
+    };
+
+    let mut nbt = Vec::new();
+    match compression {
+        1 => {
+            GzDecoder::new(payload).read_to_end(&mut nbt)?;
+        }
+        2 => {
+            ZlibDecoder::new(payload).read_to_end(&mut nbt)?;
+        }
+        3 => nbt.extend_from_slice(payload),
+        other => {
+            // 4 is LZ4, only used when a server explicitly enables it
+            debug!(
+                "Skipping chunk {} with unsupported compression {}",
+                index, other
+            );
+            return Ok(None);
+        }
+    }
+    Ok(Some(nbt))
+}
+
+/// Parses `r.<x>.<z>.mca` into region coordinates.
+pub fn parse_region_file_name(name: &str) -> Option<(i32, i32)> {
+    let coords = name.strip_prefix("r.")?.strip_suffix(".mca")?;
+    let (x, z) = coords.split_once('.')?;
+    Some((x.parse().ok()?, z.parse().ok()?))
+}
+
+/// Renders one region file into a 512x512 tile, one pixel per block. Missing or unfinished
+/// chunks stay transparent; returns `None` if the region has nothing to show.
+pub fn render_region(region_path: &Path, has_ceiling: bool) -> Result<Option<RgbaImage>> {
+    let name = region_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (region_x, region_z) = parse_region_file_name(&name)
+        .ok_or_else(|| AppError::InvalidInput(format!("Not a region file: {}", name)))?;
+    let region_dir = region_path.parent().unwrap_or(Path::new("."));
+    let data = fs::read(region_path)?;
+    if data.len() < 2 * SECTOR_SIZE {
+        return Ok(None);
+    }
+
+    let size = TILE_SIZE as usize;
+    let mut columns: Vec<Option<Column>> = vec![None; size * size];
+    for index in 0..REGION_CHUNKS * REGION_CHUNKS {
+        let (local_x, local_z) = (index % REGION_CHUNKS, index / REGION_CHUNKS);
+        let chunk_x = region_x * REGION_CHUNKS as i32 + local_x as i32;
+        let chunk_z = region_z * REGION_CHUNKS as i32 + local_z as i32;
+        let chunk = match read_chunk_nbt(&data, index, region_dir, chunk_x, chunk_z)
+            .and_then(|nbt| nbt.map(|nbt| decode_chunk(&nbt)).transpose())
+        {
+            Ok(Some(Some(chunk))) => chunk,
+            Ok(_) => continue,
+            Err(e) => {
+                debug!("Skipping chunk {},{} in {}: {}", chunk_x, chunk_z, name, e);
+                continue;
+            }
+        };
+        for z in 0..16 {
+            for x in 0..16 {
+                columns[(local_z * 16 + z) * size + local_x * 16 + x] =
+                    column(&chunk, x, z, has_ceiling);
+            }
+        }
+    }
+    if columns.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    // Blocks higher than their northern neighbour are lit, lower ones are in shadow
+    let mut pixels = vec![0u8; size * size * 4];
+    for (i, column) in columns.iter().enumerate() {
+        let Some(column) = column else {
+            continue;
+        };
+        let north = if i >= size { columns[i - size] } else { None };
+        let factor = match north {
+            Some(north) if column.height > north.height => 1.12,
+            Some(north) if column.height < north.height => 0.86,
+            _ => 1.0,
+        };
+        let [r, g, b] = shade(column.color, factor);
+        pixels[i * 4..i * 4 + 4].copy_from_slice(&[r, g, b, 255]);
+    }
+    Ok(RgbaImage::from_raw(TILE_SIZE, TILE_SIZE, pixels))
+}
+
+/// Builds a tile of the next zoom level from up to four tiles (`children[dz][dx]`), averaging
+/// 2x2 pixels. Returns `None` if all children are missing.
+pub fn compose_parent_tile(children: [[Option<&RgbaImage>; 2]; 2]) -> Option<RgbaImage> {
+    if children.iter().flatten().all(Option::is_none) {
+        return None;
+    }
+    let half = TILE_SIZE / 2;
+    let mut parent = RgbaImage::new(TILE_SIZE, TILE_SIZE);
+    for (dz, row) in children.iter().enumerate() {
+        for (dx, child) in row.iter().enumerate() {
+            let Some(child) = child else {
+                continue;
+            };
+            for y in 0..half {
+                for x in 0..half {
+                    let (mut sum, mut count) = ([0u32; 3], 0);
+                    for (cx, cy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        let pixel = child.get_pixel(x * 2 + cx, y * 2 + cy);
+                        if pixel[3] > 0 {
+                            (0..3).for_each(|i| sum[i] += pixel[i] as u32);
+                            count += 1;
+                        }
+                    }
+                    if count > 0 {
+                        let [r, g, b] = sum.map(|s| (s / count) as u8);
+                        parent.put_pixel(
+                            dx as u32 * half + x,
+                            dz as u32 * half + y,
+                            image::Rgba([r, g, b, 255]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Some(parent)
+}
+
+fn tile_path(cache_dir: &Path, zoom: u8, x: i32, z: i32) -> PathBuf {
+    cache_dir
+        .join("tiles")
+        .join(zoom.to_string())
+        .join(format!("{}_{}.png", x, z))
+}
+
+fn save_tile(path: &Path, tile: Option<RgbaImage>) -> Result<()> {
+    match tile {
+        Some(tile) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            tile.save_with_format(path, ImageFormat::Png)
+                .map_err(|e| AppError::Other(format!("Failed to write map tile: {}", e)))
+        }
+        None => match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        },
+    }
+}
+
+fn load_tile(path: &Path) -> Option<RgbaImage> {
+    if !path.is_file() {
+        return None;
+    }
+    match image::open(path) {
+        Ok(tile) => Some(tile.into_rgba8()),
+        Err(e) => {
+            warn!("Ignoring unreadable map tile {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn region_stamps(region_dir: &Path) -> Result<BTreeMap<String, RegionStamp>> {
+    let mut stamps = BTreeMap::new();
+    let entries = match fs::read_dir(region_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(stamps),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if parse_region_file_name(&name).is_none() {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_millis() as u64);
+        stamps.insert(
+            name,
+            RegionStamp {
+                size: metadata.len(),
+                modified_ms,
+            },
+        );
+    }
+    Ok(stamps)
+}
+
+fn list_tiles(cache_dir: &Path) -> Vec<WorldMapTile> {
+    let mut tiles = Vec::new();
+    for zoom in 0..=MAX_ZOOM {
+        let Ok(entries) = fs::read_dir(cache_dir.join("tiles").join(zoom.to_string())) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let coords = name
+                .strip_suffix(".png")
+                .and_then(|c| c.split_once('_'))
+                .and_then(|(x, z)| Some((x.parse().ok()?, z.parse().ok()?)));
+            if let Some((x, z)) = coords {
+                tiles.push(WorldMapTile {
+                    zoom,
+                    x,
+                    z,
+                    path: entry.path(),
+                });
+            }
+        }
+    }
+    tiles.sort_by_key(|t| (t.zoom, t.z, t.x));
+    tiles
+}
+
+/// Brings the tiles in `cache_dir` up to date with the region files in `region_dir`. Only
+/// regions whose size or modification time changed since the last run are rendered again,
+/// together with the zoomed out tiles containing them. Regions are rendered on all cores.
+pub fn update_map_tiles(
+    region_dir: &Path,
+    cache_dir: &Path,
+    has_ceiling: bool,
+    on_progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<WorldMapUpdate> {
+    let index_path = cache_dir.join(CACHE_INDEX_FILE);
+    let previous = fs::read(&index_path)
+        .ok()
+        .and_then(|data| serde_json::from_slice::<MapCacheIndex>(&data).ok())
+        .filter(|index| index.version == CACHE_VERSION)
+        .unwrap_or_default();
+    let current = region_stamps(region_dir)?;
+
+    let changed: Vec<&String> = current
+        .iter()
+        .filter(|(name, stamp)| previous.regions.get(*name) != Some(*stamp))
+        .map(|(name, _)| name)
+        .collect();
+    let removed: Vec<&String> = previous
+        .regions
+        .keys()
+        .filter(|name| !current.contains_key(*name))
+        .collect();
+
+    // Render the changed regions
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let failed: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    let workers = std::thread::available_parallelism()
+        .map_or(2, |n| n.get())
+        .min(changed.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(name) = changed.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let (x, z) = parse_region_file_name(name).unwrap_or_default();
+                    let result = render_region(&region_dir.join(name), has_ceiling)
+                        .and_then(|tile| save_tile(&tile_path(cache_dir, 0, x, z), tile));
+                    if let Err(e) = result {
+                        warn!("Failed to render region {}: {}", name, e);
+                        failed.lock().unwrap().push(name.to_string());
+                    }
+                    on_progress(done.fetch_add(1, Ordering::Relaxed) + 1, changed.len());
+                }
+            });
+        }
+    });
+    for name in &removed {
+        if let Some((x, z)) = parse_region_file_name(name) {
+            save_tile(&tile_path(cache_dir, 0, x, z), None)?;
+        }
+    }
+
+    // Rebuild the zoomed out tiles above everything that changed
+    let mut dirty: BTreeSet<(i32, i32)> = changed
+        .iter()
+        .chain(removed.iter())
+        .filter_map(|name| parse_region_file_name(name))
+        .collect();
+    for zoom in 1..=MAX_ZOOM {
+        dirty = dirty.iter().map(|(x, z)| (x >> 1, z >> 1)).collect();
+        for &(x, z) in &dirty {
+            let load = |dx: i32, dz: i32| {
+                load_tile(&tile_path(cache_dir, zoom - 1, x * 2 + dx, z * 2 + dz))
+            };
+            let children = [[load(0, 0), load(1, 0)], [load(0, 1), load(1, 1)]];
+            let refs = children
+                .each_ref()
+                .map(|row| row.each_ref().map(Option::as_ref));
+            save_tile(&tile_path(cache_dir, zoom, x, z), compose_parent_tile(refs))?;
+        }
+    }
+
+    // Failed regions are left out of the index so they're tried again next time
+    let failed = failed.into_inner().unwrap();
+    let index = MapCacheIndex {
+        version: CACHE_VERSION,
+        regions: current
+            .iter()
+            .filter(|(name, _)| !failed.contains(name))
+            .map(|(name, stamp)| (name.clone(), *stamp))
+            .collect(),
+    };
+    fs::create_dir_all(cache_dir)?;
+    let tmp_path = cache_dir.join(format!("{}.tmp", CACHE_INDEX_FILE));
+    fs::write(&tmp_path, serde_json::to_vec(&index)?)?;
+    fs::rename(&tmp_path, &index_path)?;
+
+    Ok(WorldMapUpdate {
+        tiles: list_tiles(cache_dir),
+        rendered_regions: changed.len(),
+        cached_regions: current.len() - changed.len(),
+    })
+}
+
+/// Folder of a dimension inside a world, e.g. `DIM-1` for the nether or
+/// `dimensions/<namespace>/<path>` for datapack and mod dimensions.
+pub fn dimension_dir(world_path: &Path, dimension: &str) -> Result<PathBuf> {
+    match dimension {
+        "minecraft:overworld" => Ok(world_path.to_path_buf()),
+        "minecraft:the_nether" => Ok(world_path.join("DIM-1")),
+        "minecraft:the_end" => Ok(world_path.join("DIM1")),
+        other => {
+            let valid = other.split_once(':').is_some_and(|(namespace, path)| {
+                !namespace.is_empty()
+                    && path.split('/').all(|part| !part.is_empty() && part != "..")
+                    && other
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "_-.:/".contains(c))
+            });
+            if !valid {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid dimension '{}'",
+                    dimension
+                )));
+            }
+            let (namespace, path) = other.split_once(':').unwrap_or_default();
+            Ok(path.split('/').fold(
+                world_path.join("dimensions").join(namespace),
+                |dir, part| dir.join(part),
+            ))
+        }
+    }
+}
+
+fn world_map_cache_dir(profile_id: Uuid, world_folder: &str) -> PathBuf {
+    LAUNCHER_DIRECTORY
+        .meta_dir()
+        .join("world_maps")
+        .join(profile_id.to_string())
+        .join(world_folder)
+}
+
+/// Renders (or updates) the map of a world dimension and returns its tiles. Defaults to the
+/// overworld.
+pub async fn render_world_map(
+    profile_id: Uuid,
+    world_folder: &str,
+    dimension: Option<&str>,
+) -> Result<WorldMap> {
+    let dimension = dimension.unwrap_or("minecraft:overworld").to_string();
+    let world_path = level_dat_utils::world_path(profile_id, world_folder).await?;
+    let region_dir = dimension_dir(&world_path, &dimension)?.join("region");
+    let cache_dir =
+        world_map_cache_dir(profile_id, world_folder).join(dimension.replace([':', '/'], "_"));
+    let has_ceiling = dimension == "minecraft:the_nether";
+
+    // Two renders of the same map would write the same tiles
+    let lock = RENDER_LOCKS.entry(cache_dir.clone()).or_default().clone();
+    let _guard = lock.lock().await;
+
+    info!(
+        "Rendering map of world '{}' ({}) in profile {}",
+        world_folder, dimension, profile_id
+    );
+    let event_id = Uuid::new_v4();
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel::<(usize, usize)>();
+    let message_world = world_folder.to_string();
+    let progress_task = tokio::spawn(async move {
+        let Ok(state) = State::get().await else {
+            return;
+        };
+        while let Some((done, total)) = progress_rx.recv().await {
+            let _ = state
+                .emit_event(EventPayload {
+                    event_id,
+                    event_type: EventType::RenderingWorldMap,
+                    target_id: Some(profile_id),
+                    message: format!(
+                        "Rendering map of '{}': region {}/{}",
+                        message_world, done, total
+                    ),
+                    progress: Some(done as f64 / total.max(1) as f64),
+                    error: None,
+                })
+                .await;
+        }
+    });
+
+    let update = tokio::task::spawn_blocking(move || {
+        update_map_tiles(&region_dir, &cache_dir, has_ceiling, &|done, total| {
+            let _ = progress_tx.send((done, total));
+        })
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Map render task failed: {}", e)))??;
+    let _ = progress_task.await;
+    info!(
+        "Map of world '{}' ({}): {} regions rendered, {} from cache",
+        world_folder, dimension, update.rendered_regions, update.cached_regions
+    );
+
+    let spawn = if dimension == "minecraft:overworld" {
+        tokio::fs::read(world_path.join("level.dat"))
+            .await
+            .ok()
+            .and_then(|data| level_dat_utils::parse_level_dat_details(&data).ok())
+            .and_then(|details| details.spawn)
+    } else {
+        None
+    };
+
+    Ok(WorldMap {
+        world_folder: world_folder.to_string(),
+        dimension,
+        tile_size: TILE_SIZE,
+        max_zoom: MAX_ZOOM,
+        spawn,
+        update,
+    })
+}
+
+/// Removes all rendered maps of a world.
+pub async fn clear_world_map_cache(profile_id: Uuid, world_folder: &str) -> Result<()> {
+    if world_folder.is_empty() || world_folder.contains(['/', '\\']) || world_folder == ".." {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid world folder name '{}'",
+            world_folder
+        )));
+    }
+    let cache_dir = world_map_cache_dir(profile_id, world_folder);
+    match tokio::fs::remove_dir_all(&cache_dir).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+// --- Block colors ---
+
+const WATER_COLOR: [u8; 3] = [63, 118, 228];
+const FOLIAGE_COLOR: [u8; 3] = [90, 140, 50];
+const UNKNOWN_COLOR: [u8; 3] = [128, 128, 128];
+
+fn is_water(name: &str) -> bool {
+    matches!(
+        name.trim_start_matches("minecraft:"),
+        "water" | "bubble_column" | "seagrass" | "tall_seagrass" | "kelp" | "kelp_plant"
+    )
+}
+
+/// Average top colors of common blocks.
+const BLOCK_COLORS: &[(&str, [u8; 3])] = &[
+    ("grass_block", [98, 148, 56]),
+    ("dirt", [134, 96, 67]),
+    ("coarse_dirt", [119, 85, 59]),
+    ("rooted_dirt", [144, 103, 76]),
+    ("podzol", [91, 63, 24]),
+    ("mycelium", [111, 99, 105]),
+    ("dirt_path", [148, 122, 65]),
+    ("farmland", [81, 44, 15]),
+    ("mud", [60, 57, 60]),
+    ("clay", [160, 166, 179]),
+    ("gravel", [131, 127, 126]),
+    ("sand", [219, 207, 163]),
+    ("red_sand", [190, 102, 33]),
+    ("sandstone", [216, 203, 155]),
+    ("red_sandstone", [186, 99, 29]),
+    ("stone", [125, 125, 125]),
+    ("cobblestone", [122, 122, 122]),
+    ("mossy_cobblestone", [110, 118, 94]),
+    ("granite", [149, 103, 85]),
+    ("diorite", [188, 188, 188]),
+    ("andesite", [136, 136, 136]),
+    ("deepslate", [80, 80, 82]),
+    ("tuff", [108, 109, 102]),
+    ("calcite", [223, 224, 220]),
+    ("bedrock", [85, 85, 85]),
+    ("obsidian", [15, 10, 24]),
+    ("crying_obsidian", [32, 10, 60]),
+    ("water", WATER_COLOR),
+    ("lava", [207, 92, 20]),
+    ("ice", [145, 183, 253]),
+    ("packed_ice", [141, 180, 250]),
+    ("blue_ice", [116, 167, 253]),
+    ("snow", [249, 254, 254]),
+    ("snow_block", [249, 254, 254]),
+    ("powder_snow", [248, 253, 253]),
+    ("oak_leaves", [72, 118, 38]),
+    ("spruce_leaves", [60, 95, 60]),
+    ("birch_leaves", [107, 141, 70]),
+    ("jungle_leaves", [48, 128, 20]),
+    ("acacia_leaves", [92, 120, 30]),
+    ("dark_oak_leaves", [50, 90, 25]),
+    ("mangrove_leaves", [70, 110, 40]),
+    ("cherry_leaves", [229, 172, 194]),
+    ("azalea_leaves", [90, 115, 45]),
+    ("flowering_azalea_leaves", [100, 111, 61]),
+    ("oak_log", [109, 85, 50]),
+    ("spruce_log", [58, 37, 16]),
+    ("birch_log", [216, 215, 210]),
+    ("jungle_log", [85, 67, 25]),
+    ("acacia_log", [103, 96, 86]),
+    ("dark_oak_log", [60, 46, 26]),
+    ("mangrove_log", [84, 66, 36]),
+    ("cherry_log", [54, 33, 44]),
+    ("oak_planks", [162, 130, 78]),
+    ("spruce_planks", [114, 84, 48]),
+    ("birch_planks", [192, 175, 121]),
+    ("jungle_planks", [160, 115, 80]),
+    ("acacia_planks", [168, 90, 50]),
+    ("dark_oak_planks", [66, 43, 20]),
+    ("mangrove_planks", [117, 54, 48]),
+    ("cherry_planks", [226, 178, 172]),
+    ("bamboo_planks", [193, 173, 80]),
+    ("crimson_planks", [101, 48, 70]),
+    ("warped_planks", [43, 104, 99]),
+    ("short_grass", FOLIAGE_COLOR),
+    ("grass", FOLIAGE_COLOR),
+    ("tall_grass", FOLIAGE_COLOR),
+    ("fern", FOLIAGE_COLOR),
+    ("large_fern", FOLIAGE_COLOR),
+    ("vine", [70, 110, 40]),
+    ("lily_pad", [32, 128, 48]),
+    ("sugar_cane", [148, 192, 101]),
+    ("cactus", [85, 127, 43]),
+    ("bamboo", [93, 144, 19]),
+    ("dead_bush", [107, 78, 40]),
+    ("sweet_berry_bush", [68, 77, 50]),
+    ("pumpkin", [198, 118, 24]),
+    ("melon", [111, 145, 30]),
+    ("hay_block", [166, 139, 12]),
+    ("moss_block", [89, 109, 45]),
+    ("moss_carpet", [89, 109, 45]),
+    ("dripstone_block", [134, 107, 92]),
+    ("pointed_dripstone", [134, 107, 92]),
+    ("amethyst_block", [133, 97, 191]),
+    ("sculk", [12, 29, 36]),
+    ("mangrove_roots", [74, 59, 38]),
+    ("muddy_mangrove_roots", [70, 58, 45]),
+    ("netherrack", [97, 38, 38]),
+    ("nether_bricks", [44, 21, 26]),
+    ("soul_sand", [81, 62, 50]),
+    ("soul_soil", [75, 57, 46]),
+    ("basalt", [80, 81, 86]),
+    ("blackstone", [42, 36, 41]),
+    ("crimson_nylium", [130, 31, 31]),
+    ("warped_nylium", [43, 114, 101]),
+    ("glowstone", [171, 131, 84]),
+    ("magma_block", [142, 63, 31]),
+    ("nether_wart_block", [114, 2, 2]),
+    ("warped_wart_block", [22, 119, 121]),
+    ("shroomlight", [240, 146, 70]),
+    ("end_stone", [219, 222, 158]),
+    ("end_stone_bricks", [218, 224, 162]),
+    ("purpur_block", [169, 125, 169]),
+    ("chorus_plant", [93, 57, 93]),
+    ("chorus_flower", [151, 120, 151]),
+    ("bricks", [150, 97, 83]),
+    ("stone_bricks", [122, 121, 122]),
+    ("mossy_stone_bricks", [115, 121, 105]),
+    ("terracotta", [152, 94, 67]),
+    ("glass", [175, 213, 219]),
+    ("iron_block", [220, 220, 220]),
+    ("gold_block", [246, 208, 61]),
+    ("diamond_block", [98, 237, 228]),
+    ("emerald_block", [42, 203, 87]),
+    ("lapis_block", [30, 67, 140]),
+    ("redstone_block", [175, 24, 5]),
+    ("coal_block", [16, 15, 15]),
+    ("copper_block", [192, 107, 79]),
+    ("quartz_block", [235, 229, 222]),
+    ("bone_block", [229, 225, 207]),
+    ("slime_block", [111, 192, 91]),
+    ("honey_block", [251, 185, 52]),
+    ("tnt", [219, 68, 52]),
+    ("bookshelf", [117, 94, 59]),
+    ("crafting_table", [120, 73, 42]),
+    ("furnace", [110, 110, 110]),
+    ("chest", [162, 116, 44]),
+    ("barrel", [134, 100, 58]),
+];
+
+const DYE_COLORS: &[(&str, [u8; 3])] = &[
+    ("white", [233, 236, 236]),
+    ("orange", [240, 118, 19]),
+    ("magenta", [189, 68, 179]),
+    ("light_blue", [58, 175, 217]),
+    ("yellow", [248, 197, 39]),
+    ("lime", [112, 185, 25]),
+    ("pink", [237, 141, 172]),
+    ("gray", [62, 68, 71]),
+    ("light_gray", [142, 142, 134]),
+    ("cyan", [21, 137, 145]),
+    ("purple", [121, 42, 172]),
+    ("blue", [53, 57, 157]),
+    ("brown", [114, 71, 40]),
+    ("green", [84, 109, 27]),
+    ("red", [160, 39, 34]),
+    ("black", [20, 21, 25]),
+];
+
+const FLOWERS: &[&str] = &[
+    "dandelion",
+    "poppy",
+    "blue_orchid",
+    "allium",
+    "azure_bluet",
+    "oxeye_daisy",
+    "cornflower",
+    "lily_of_the_valley",
+    "sunflower",
+    "lilac",
+    "rose_bush",
+    "peony",
+    "wither_rose",
+    "torchflower",
+    "pink_petals",
+];
+
+/// Suffixes of shaped variants that are colored like their base block.
+const SHAPE_SUFFIXES: &[&str] = &["_slab", "_stairs", "_wall", "_fence_gate", "_fence"];
+
+/// Map color of a block, `None` for air and small blocks (torches, rails, ...) that the map
+/// looks through. Modded blocks fall back to guesses based on their name.
+pub fn block_color(name: &str) -> Option<[u8; 3]> {
+    let path = name.rsplit(':').next().unwrap_or(name);
+    if matches!(
+        path,
+        "air" | "cave_air" | "void_air" | "barrier" | "light" | "structure_void" | "tripwire"
+    ) || [
+        "torch",
+        "_button",
+        "_sign",
+        "_banner",
+        "rail",
+        "redstone_wire",
+        "lever",
+    ]
+    .iter()
+    .any(|suffix| path.ends_with(suffix))
+    {
+        return None;
+    }
+    if let Some((_, color)) = BLOCK_COLORS.iter().find(|(block, _)| *block == path) {
+        return Some(*color);
+    }
+    if let Some((_, color)) = DYE_COLORS
+        .iter()
+        .filter(|(dye, _)| path.starts_with(&format!("{}_", dye)))
+        .max_by_key(|(dye, _)| dye.len())
+    {
+        return Some(*color);
+    }
+    if FLOWERS.contains(&path) || path.ends_with("_tulip") || path.ends_with("sapling") {
+        return Some(FOLIAGE_COLOR);
+    }
+    // Stairs, slabs, ... look like the block they're made of
+    for suffix in SHAPE_SUFFIXES {
+        if let Some(base) = path.strip_suffix(suffix) {
+            for candidate in [
+                base.to_string(),
+                format!("{}s", base),
+                format!("{}_planks", base),
+                format!("{}_block", base),
+            ] {
+                if let Some((_, color)) = BLOCK_COLORS.iter().find(|(b, _)| *b == candidate) {
+                    return Some(*color);
+                }
+            }
+        }
+    }
+
+    let guesses: &[(&str, [u8; 3])] = &[
+        ("leaves", [60, 110, 35]),
+        ("_log", [102, 81, 51]),
+        ("_wood", [102, 81, 51]),
+        ("_stem", [102, 81, 51]),
+        ("planks", [162, 130, 78]),
+        ("grass", FOLIAGE_COLOR),
+        ("sand", [219, 207, 163]),
+        ("dirt", [134, 96, 67]),
+        ("snow", [249, 254, 254]),
+        ("ice", [145, 183, 253]),
+        ("glass", [175, 213, 219]),
+        ("brick", [150, 97, 83]),
+        ("deepslate", [80, 80, 82]),
+        ("stone", [125, 125, 125]),
+        ("ore", [125, 125, 125]),
+    ];
+    Some(
+        guesses
+            .iter()
+            .find(|(part, _)| path.contains(part))
+            .map_or(UNKNOWN_COLOR, |(_, color)| *color),
+    )
+}
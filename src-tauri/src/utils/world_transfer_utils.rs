@@ -0,0 +1,721 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::minecraft::downloads::mod_resolver;
+use crate::state::profile_state::{ModLoader, Profile};
+use crate::state::State;
+use crate::utils::mod_metadata::{self, JarMetadata};
+use crate::utils::world_utils;
+use fastnbt::Value;
+use flate2::read::GzDecoder;
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const LEVEL_DAT: &str = "level.dat";
+/// Held open by the running game and meaningless elsewhere
+const SKIPPED_FILES: &[&str] = &["session.lock"];
+
+/// DataVersion of each release since 1.9, used when the client jar of a profile isn't
+/// downloaded yet. Snapshots are only known from their jar.
+const RELEASE_DATA_VERSIONS: &[(&str, i32)] = &[
+    ("1.9", 169),
+    ("1.9.1", 175),
+    ("1.9.2", 176),
+    ("1.9.3", 183),
+    ("1.9.4", 184),
+    ("1.10", 510),
+    ("1.10.1", 511),
+    ("1.10.2", 512),
+    ("1.11", 819),
+    ("1.11.1", 921),
+    ("1.11.2", 922),
+    ("1.12", 1139),
+    ("1.12.1", 1241),
+    ("1.12.2", 1343),
+    ("1.13", 1519),
+    ("1.13.1", 1628),
+    ("1.13.2", 1631),
+    ("1.14", 1952),
+    ("1.14.1", 1957),
+    ("1.14.2", 1963),
+    ("1.14.3", 1968),
+    ("1.14.4", 1976),
+    ("1.15", 2225),
+    ("1.15.1", 2227),
+    ("1.15.2", 2230),
+    ("1.16", 2566),
+    ("1.16.1", 2567),
+    ("1.16.2", 2578),
+    ("1.16.3", 2580),
+    ("1.16.4", 2584),
+    ("1.16.5", 2586),
+    ("1.17", 2724),
+    ("1.17.1", 2730),
+    ("1.18", 2860),
+    ("1.18.1", 2865),
+    ("1.18.2", 2975),
+    ("1.19", 3105),
+    ("1.19.1", 3117),
+    ("1.19.2", 3120),
+    ("1.19.3", 3218),
+    ("1.19.4", 3337),
+    ("1.20", 3463),
+    ("1.20.1", 3465),
+    ("1.20.2", 3578),
+    ("1.20.3", 3698),
+    ("1.20.4", 3700),
+    ("1.20.5", 3837),
+    ("1.20.6", 3839),
+    ("1.21", 3953),
+    ("1.21.1", 3955),
+    ("1.21.2", 4080),
+    ("1.21.3", 4082),
+    ("1.21.4", 4189),
+    ("1.21.5", 4325),
+    ("1.21.6", 4435),
+    ("1.21.7", 4438),
+    ("1.21.8", 4440),
+];
+
+/// What level.dat and the world folder say about a world.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorldLevelInfo {
+    pub level_name: Option<String>,
+    /// Missing in worlds from before 1.9
+    pub data_version: Option<i32>,
+    pub version_name: Option<String>,
+    /// Dimensions that don't belong to Minecraft itself, e.g. `twilightforest:twilight_forest`
+    pub mod_dimensions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorldCompatibilityWarning {
+    /// The world was saved by a newer Minecraft version than the target profile uses.
+    /// Minecraft can't downgrade worlds, opening it may corrupt or crash.
+    Downgrade {
+        world_data_version: i32,
+        world_version_name: Option<String>,
+        target_version: String,
+        target_data_version: i32,
+    },
+    /// Dimensions whose mod isn't installed in the target profile, they will be unreachable
+    /// or deleted when the world is opened.
+    MissingModDimensions {
+        dimensions: Vec<String>,
+        mod_ids: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldCompatibilityReport {
+    pub world: WorldLevelInfo,
+    pub target_profile_id: Uuid,
+    pub target_version: String,
+    /// `None` if the DataVersion of the target version isn't known
+    pub target_data_version: Option<i32>,
+    pub warnings: Vec<WorldCompatibilityWarning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorldImportResult {
+    pub folder_name: String,
+    pub compatibility: WorldCompatibilityReport,
+}
+
+/// Reads name, version and dimensions from a gzipped level.dat.
+pub fn parse_level_dat(level_dat_gz: &[u8]) -> Result<WorldLevelInfo> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(level_dat_gz).read_to_end(&mut decompressed)?;
+    let root: Value = fastnbt::from_bytes(&decompressed)?;
+    let Some(Value::Compound(data)) = compound_get(&root, "Data") else {
+        return Err(AppError::ParseError(
+            "level.dat has no Data compound".to_string(),
+        ));
+    };
+
+    let mut info = WorldLevelInfo {
+        level_name: match data.get("LevelName") {
+            Some(Value::String(name)) => Some(name.clone()),
+            _ => None,
+        },
+        data_version: data.get("DataVersion").and_then(nbt_int),
+        version_name: match data.get("Version").and_then(|v| compound_get(v, "Name")) {
+            Some(Value::String(name)) => Some(name.clone()),
+            _ => None,
+        },
+        mod_dimensions: Vec::new(),
+    };
+
+    // 1.16+: every dimension of the world is listed in the world generation settings
+    if let Some(Value::Compound(dimensions)) = data
+        .get("WorldGenSettings")
+        .and_then(|settings| compound_get(settings, "dimensions"))
+    {
+        info.mod_dimensions = dimensions
+            .keys()
+            .filter(|id| is_mod_dimension(id))
+            .cloned()
+            .collect();
+        info.mod_dimensions.sort();
+    }
+    Ok(info)
+}
+
+fn compound_get<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Compound(map) => map.get(key),
+        _ => None,
+    }
+}
+
+fn nbt_int(value: &Value) -> Option<i32> {
+    match value {
+        Value::Int(v) => Some(*v),
+        Value::Short(v) => Some(*v as i32),
+        Value::Long(v) => i32::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+fn is_mod_dimension(id: &str) -> bool {
+    id.split_once(':')
+        .is_some_and(|(namespace, _)| namespace != "minecraft")
+}
+
+/// Dimensions that only exist as folders: `dimensions/<namespace>/<name>` (1.16+) and the
+/// numbered `DIM<n>` folders of old Forge worlds.
+fn mod_dimension_folders(world_path: &Path) -> Vec<String> {
+    let mut dimensions = Vec::new();
+    if let Ok(namespaces) = fs::read_dir(world_path.join("dimensions")) {
+        for namespace in namespaces.flatten().filter(|e| e.path().is_dir()) {
+            let namespace_name = namespace.file_name().to_string_lossy().into_owned();
+            if namespace_name == "minecraft" {
+                continue;
+            }
+            for dimension in fs::read_dir(namespace.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                if dimension.path().is_dir() {
+                    dimensions.push(format!(
+                        "{}:{}",
+                        namespace_name,
+                        dimension.file_name().to_string_lossy()
+                    ));
+                }
+            }
+        }
+    }
+    if let Ok(entries) = fs::read_dir(world_path) {
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_numbered_mod_dimension = name
+                .strip_prefix("DIM")
+                .and_then(|n| n.parse::<i32>().ok())
+                .is_some_and(|n| n != -1 && n != 1);
+            if is_numbered_mod_dimension {
+                dimensions.push(name);
+            }
+        }
+    }
+    dimensions
+}
+
+/// Reads level.dat and the dimension folders of a world on disk.
+pub fn read_world_info(world_path: &Path) -> Result<WorldLevelInfo> {
+    let mut info = parse_level_dat(&fs::read(world_path.join(LEVEL_DAT))?)?;
+    for dimension in mod_dimension_folders(world_path) {
+        if !info.mod_dimensions.contains(&dimension) {
+            info.mod_dimensions.push(dimension);
+        }
+    }
+    info.mod_dimensions.sort();
+    Ok(info)
+}
+
+/// DataVersion of a release from the built-in table.
+pub fn release_data_version(minecraft_version: &str) -> Option<i32> {
+    RELEASE_DATA_VERSIONS
+        .iter()
+        .find(|(version, _)| *version == minecraft_version)
+        .map(|(_, data_version)| *data_version)
+}
+
+/// DataVersion a Minecraft version saves worlds with. Read from `version.json` in the
+/// client jar if it is downloaded, otherwise taken from the release table.
+pub fn data_version_of(minecraft_version: &str) -> Option<i32> {
+    let client_jar = LAUNCHER_DIRECTORY
+        .meta_dir()
+        .join("versions")
+        .join(minecraft_version)
+        .join(format!("{}.jar", minecraft_version));
+    let from_jar = File::open(&client_jar).ok().and_then(|file| {
+        let mut archive = zip::ZipArchive::new(BufReader::new(file)).ok()?;
+        let mut version_json = String::new();
+        archive
+            .by_name("version.json")
+            .ok()?
+            .read_to_string(&mut version_json)
+            .ok()?;
+        let json: serde_json::Value = serde_json::from_str(&version_json).ok()?;
+        json.get("world_version")?.as_i64()?.try_into().ok()
+    });
+    from_jar.or_else(|| release_data_version(minecraft_version))
+}
+
+/// Compares a world with the profile it should be opened in. `target_mod_ids` is `None`
+/// when the mods of the target profile are unknown, then dimensions aren't checked.
+pub fn check_compatibility(
+    world: &WorldLevelInfo,
+    target_version: &str,
+    target_data_version: Option<i32>,
+    target_mod_ids: Option<&HashSet<String>>,
+) -> Vec<WorldCompatibilityWarning> {
+    let mut warnings = Vec::new();
+
+    if let (Some(world_data_version), Some(target_data_version)) =
+        (world.data_version, target_data_version)
+    {
+        if world_data_version > target_data_version {
+            warnings.push(WorldCompatibilityWarning::Downgrade {
+                world_data_version,
+                world_version_name: world.version_name.clone(),
+                target_version: target_version.to_string(),
+                target_data_version,
+            });
+        }
+    }
+
+    if let Some(mod_ids) = target_mod_ids {
+        let mut missing_dimensions = Vec::new();
+        let mut missing_mods = BTreeSet::new();
+        for dimension in &world.mod_dimensions {
+            match dimension.split_once(':') {
+                Some((namespace, _)) if !mod_ids.contains(namespace) => {
+                    missing_dimensions.push(dimension.clone());
+                    missing_mods.insert(namespace.to_string());
+                }
+                Some(_) => {}
+                // Numbered Forge dimensions don't say which mod they are from,
+                // without any mods they are lost for sure
+                None if mod_ids.is_empty() => missing_dimensions.push(dimension.clone()),
+                None => {}
+            }
+        }
+        if !missing_dimensions.is_empty() {
+            warnings.push(WorldCompatibilityWarning::MissingModDimensions {
+                dimensions: missing_dimensions,
+                mod_ids: missing_mods.into_iter().collect(),
+            });
+        }
+    }
+    warnings
+}
+
+/// Mod ids of everything the profile would launch with, including mods nested in jars.
+async fn profile_mod_ids(profile: &Profile) -> Result<HashSet<String>> {
+    let mut mod_ids = HashSet::new();
+    if profile.loader == ModLoader::Vanilla {
+        return Ok(mod_ids);
+    }
+    let state = State::get().await?;
+    let norisk_config = if profile.selected_norisk_pack_id.is_some() {
+        Some(state.norisk_pack_manager.get_config().await)
+    } else {
+        None
+    };
+    let custom_mods = state.profile_manager.list_custom_mods(profile).await?;
+    let target_mods = mod_resolver::resolve_target_mods(
+        profile,
+        norisk_config.as_ref(),
+        Some(&custom_mods),
+        &profile.game_version,
+        profile.loader.as_str(),
+        &LAUNCHER_DIRECTORY.meta_dir().join("mod_cache"),
+    )
+    .await?;
+
+    fn collect(metadata: &JarMetadata, mod_ids: &mut HashSet<String>) {
+        mod_ids.extend(metadata.mods.iter().map(|m| m.mod_id.clone()));
+        for nested in &metadata.nested {
+            collect(&nested.metadata, mod_ids);
+        }
+    }
+    for target in target_mods {
+        match mod_metadata::read_jar_metadata(&target.cache_path, false).await {
+            Ok(metadata) => collect(&metadata, &mut mod_ids),
+            Err(e) => warn!(
+                "[WorldTransfer] Can't read mod ids of {}: {}",
+                target.filename, e
+            ),
+        }
+    }
+    Ok(mod_ids)
+}
+
+async fn compatibility_report(
+    world: WorldLevelInfo,
+    target_profile_id: Uuid,
+) -> Result<WorldCompatibilityReport> {
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(target_profile_id).await?;
+    let target_version = profile.game_version.clone();
+    let target_data_version = {
+        let version = target_version.clone();
+        tokio::task::spawn_blocking(move || data_version_of(&version))
+            .await
+            .map_err(|e| AppError::Other(format!("DataVersion lookup failed: {}", e)))?
+    };
+    let mod_ids = if world.mod_dimensions.is_empty() {
+        None
+    } else {
+        match profile_mod_ids(&profile).await {
+            Ok(ids) => Some(ids),
+            Err(e) => {
+                warn!(
+                    "[WorldTransfer] Can't resolve mods of profile {}: {}",
+                    target_profile_id, e
+                );
+                None
+            }
+        }
+    };
+    let warnings = check_compatibility(
+        &world,
+        &target_version,
+        target_data_version,
+        mod_ids.as_ref(),
+    );
+    for warning in &warnings {
+        warn!(
+            "[WorldTransfer] World '{}' in profile {}: {:?}",
+            world.level_name.as_deref().unwrap_or("?"),
+            target_profile_id,
+            warning
+        );
+    }
+    Ok(WorldCompatibilityReport {
+        world,
+        target_profile_id,
+        target_version,
+        target_data_version,
+        warnings,
+    })
+}
+
+async fn world_path(profile_id: Uuid, world_folder: &str) -> Result<PathBuf> {
+    if world_folder.is_empty() || world_folder.contains(['/', '\\']) || world_folder == ".." {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid world folder name '{}'",
+            world_folder
+        )));
+    }
+    let state = State::get().await?;
+    let path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?
+        .join("saves")
+        .join(world_folder);
+    if !path.join(LEVEL_DAT).is_file() {
+        return Err(AppError::WorldNotFound {
+            profile_id,
+            world_folder: world_folder.to_string(),
+        });
+    }
+    Ok(path)
+}
+
+/// Checks whether a world can be copied into another profile without problems.
+pub async fn check_world_transfer(
+    source_profile_id: Uuid,
+    source_world_folder: &str,
+    target_profile_id: Uuid,
+) -> Result<WorldCompatibilityReport> {
+    let source_path = world_path(source_profile_id, source_world_folder).await?;
+    let world = tokio::task::spawn_blocking(move || read_world_info(&source_path))
+        .await
+        .map_err(|e| AppError::Other(format!("Reading world failed: {}", e)))??;
+    compatibility_report(world, target_profile_id).await
+}
+
+/// Writes a world into a zip with the world folder as its only top level entry.
+pub async fn export_world_to_zip(
+    profile_id: Uuid,
+    world_folder: &str,
+    output_path: &Path,
+) -> Result<PathBuf> {
+    let source_path = world_path(profile_id, world_folder).await?;
+    if let Err(AppError::WorldLocked { .. }) =
+        world_utils::check_world_session_lock(&source_path).await
+    {
+        warn!(
+            "[WorldTransfer] Exporting world '{}' while it is open, the zip may contain unsaved changes",
+            world_folder
+        );
+    }
+    let output = output_path.to_path_buf();
+    let top_folder = world_folder.to_string();
+    tokio::task::spawn_blocking(move || {
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = output.with_extension("zip.tmp");
+        let result = File::create(&tmp_path)
+            .map_err(AppError::from)
+            .and_then(|file| write_world_zip(&source_path, &top_folder, BufWriter::new(file)));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e);
+        }
+        fs::rename(&tmp_path, &output)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("World export task failed: {}", e)))??;
+    info!(
+        "[WorldTransfer] Exported world '{}' of profile {} to {:?}",
+        world_folder, profile_id, output_path
+    );
+    Ok(output_path.to_path_buf())
+}
+
+/// Zips the world at `world_path` below `top_folder/`.
+pub fn write_world_zip<W: Write + Seek>(
+    world_path: &Path,
+    top_folder: &str,
+    writer: W,
+) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(writer);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.add_directory(format!("{}/", top_folder), options)?;
+    let mut pending = vec![(world_path.to_path_buf(), format!("{}/", top_folder))];
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = fs::read_dir(&dir)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let entry_name = format!("{}{}", prefix, name);
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                zip.add_directory(format!("{}/", entry_name), options)?;
+                pending.push((entry.path(), format!("{}/", entry_name)));
+            } else if file_type.is_file() && !SKIPPED_FILES.contains(&name.as_str()) {
+                let large = entry.metadata()?.len() >= u32::MAX as u64;
+                zip.start_file(entry_name, options.large_file(large))?;
+                io::copy(&mut BufReader::new(File::open(entry.path())?), &mut zip)?;
+            }
+        }
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+/// Finds the folder of a world inside a zip: the directory of the shallowest level.dat.
+/// Returns the prefix of its entries, `""` if the world is at the top level.
+pub fn find_world_root<'a>(entry_names: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    entry_names
+        .into_iter()
+        .map(|name| name.replace('\\', "/"))
+        .filter(|name| name == LEVEL_DAT || name.ends_with(&format!("/{}", LEVEL_DAT)))
+        // Skip macOS metadata
+        .filter(|name| !name.starts_with("__MACOSX/"))
+        .min_by_key(|name| (name.matches('/').count(), name.clone()))
+        .map(|name| name[..name.len() - LEVEL_DAT.len()].to_string())
+}
+
+struct WorldZip {
+    root: String,
+    info: WorldLevelInfo,
+}
+
+fn open_world_zip(zip_path: &Path) -> Result<(zip::ZipArchive<BufReader<File>>, WorldZip)> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(File::open(zip_path)?))?;
+    let root = find_world_root(archive.file_names()).ok_or_else(|| {
+        AppError::InvalidInput(format!("{:?} contains no Minecraft world", zip_path))
+    })?;
+    let mut level_dat = Vec::new();
+    archive
+        .by_name(&format!("{}{}", root, LEVEL_DAT))?
+        .read_to_end(&mut level_dat)?;
+    let mut info = parse_level_dat(&level_dat)?;
+
+    for name in archive.file_names() {
+        let Some(relative) = name
+            .replace('\\', "/")
+            .strip_prefix(&root)
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let mut parts = relative.split('/');
+        let dimension = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("dimensions"), Some(namespace), Some(dimension), Some(_))
+                if namespace != "minecraft" && !dimension.is_empty() =>
+            {
+                Some(format!("{}:{}", namespace, dimension))
+            }
+            (Some(folder), Some(_), _, _)
+                if folder
+                    .strip_prefix("DIM")
+                    .and_then(|n| n.parse::<i32>().ok())
+                    .is_some_and(|n| n != -1 && n != 1) =>
+            {
+                Some(folder.to_string())
+            }
+            _ => None,
+        };
+        if let Some(dimension) = dimension {
+            if !info.mod_dimensions.contains(&dimension) {
+                info.mod_dimensions.push(dimension);
+            }
+        }
+    }
+    info.mod_dimensions.sort();
+    Ok((archive, WorldZip { root, info }))
+}
+
+/// Extracts the entries below `root` into `target`.
+fn extract_world_zip<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    root: &str,
+    target: &Path,
+) -> Result<()> {
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(path) = entry.enclosed_name() else {
+            warn!(
+                "[WorldTransfer] Skipping unsafe zip entry '{}'",
+                entry.name()
+            );
+            continue;
+        };
+        let path = path.to_string_lossy().replace('\\', "/");
+        let Some(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        if relative.is_empty()
+            || SKIPPED_FILES.contains(&relative)
+            || (root.is_empty() && relative.starts_with("__MACOSX/"))
+        {
+            continue;
+        }
+        let out_path = target.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut BufWriter::new(File::create(&out_path)?))?;
+    }
+    Ok(())
+}
+
+/// Reads the world in a zip and checks it against the target profile without importing it.
+pub async fn inspect_world_zip(
+    zip_path: &Path,
+    target_profile_id: Uuid,
+) -> Result<WorldCompatibilityReport> {
+    let path = zip_path.to_path_buf();
+    let (_, world_zip) = tokio::task::spawn_blocking(move || open_world_zip(&path))
+        .await
+        .map_err(|e| AppError::Other(format!("Reading world zip failed: {}", e)))??;
+    compatibility_report(world_zip.info, target_profile_id).await
+}
+
+/// Imports the world in a zip into a profile. The world may be at the top level of the zip or
+/// in (nested) folders. Without `world_name` the name from level.dat is used.
+pub async fn import_world_from_zip(
+    profile_id: Uuid,
+    zip_path: &Path,
+    world_name: Option<String>,
+) -> Result<WorldImportResult> {
+    let state = State::get().await?;
+    let saves_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?
+        .join("saves");
+    tokio::fs::create_dir_all(&saves_path).await?;
+
+    let path = zip_path.to_path_buf();
+    let (archive, world_zip) = tokio::task::spawn_blocking(move || open_world_zip(&path))
+        .await
+        .map_err(|e| AppError::Other(format!("Reading world zip failed: {}", e)))??;
+
+    let display_name = world_name
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| world_zip.info.level_name.clone())
+        .or_else(|| {
+            world_zip
+                .root
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .filter(|n| !n.is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| {
+            zip_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "Imported World".to_string());
+    let folder_name =
+        world_utils::find_unique_world_folder_name(&saves_path, &display_name).await?;
+    let target_path = saves_path.join(&folder_name);
+    let temp_path = saves_path.join(format!(".{}.importing", folder_name));
+
+    let extract_result = {
+        let root = world_zip.root.clone();
+        let temp = temp_path.clone();
+        let target = target_path.clone();
+        let mut archive = archive;
+        tokio::task::spawn_blocking(move || {
+            if temp.exists() {
+                fs::remove_dir_all(&temp)?;
+            }
+            fs::create_dir_all(&temp)?;
+            extract_world_zip(&mut archive, &root, &temp)?;
+            fs::rename(&temp, &target)?;
+            Ok::<(), AppError>(())
+        })
+        .await
+        .map_err(|e| AppError::Other(format!("World import task failed: {}", e)))
+        .and_then(|r| r)
+    };
+    if let Err(e) = extract_result {
+        let _ = tokio::fs::remove_dir_all(&temp_path).await;
+        return Err(e);
+    }
+
+    if world_zip.info.level_name.as_deref() != Some(display_name.as_str()) {
+        if let Err(e) =
+            world_utils::modify_level_dat_name(&target_path.join(LEVEL_DAT), &display_name).await
+        {
+            let _ = tokio::fs::remove_dir_all(&target_path).await;
+            return Err(e);
+        }
+    }
+    info!(
+        "[WorldTransfer] Imported world '{}' from {:?} into profile {} as '{}'",
+        display_name, zip_path, profile_id, folder_name
+    );
+
+    let mut info = world_zip.info;
+    info.level_name = Some(display_name);
+    let compatibility = compatibility_report(info, profile_id).await?;
+    Ok(WorldImportResult {
+        folder_name,
+        compatibility,
+    })
+}
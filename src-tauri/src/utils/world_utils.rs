@@ -321,7 +321,10 @@ pub async fn copy_world_directory(
 }
 
 /// Reads, modifies the LevelName tag, and writes back a level.dat file.
-async fn modify_level_dat_name(level_dat_path: &Path, new_level_name: &str) -> Result<()> {
+pub(crate) async fn modify_level_dat_name(
+    level_dat_path: &Path,
+    new_level_name: &str,
+) -> Result<()> {
     // 1. Read the file
     let compressed_bytes = fs::read(level_dat_path).await.map_err(|e| {
         error!(
@@ -0,0 +1,154 @@
+// src-tauri/tests/cli_test.rs
+
+use clap::Parser;
+use noriskclient_launcher_v3_lib::cli::{find_profile, Cli, Command, ContentKind, ProfilesCommand};
+use noriskclient_launcher_v3_lib::error::AppError;
+use noriskclient_launcher_v3_lib::state::profile_state::Profile;
+use uuid::Uuid;
+
+//cargo test --package noriskclient-launcher-v3 --test cli_test
+
+fn profile(name: &str) -> Profile {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "path": name.to_lowercase(),
+        "game_version": "1.21.4",
+        "loader": "fabric",
+        "loader_version": null,
+        "last_played": null,
+        "description": null,
+        "norisk_information": null,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_parse_arguments() {
+    let cli = Cli::try_parse_from([
+        "norisk-cli",
+        "profiles",
+        "create",
+        "Test Server Bot",
+        "--game-version",
+        "1.21.4",
+        "--loader",
+        "fabric",
+    ])
+    .unwrap();
+    match cli.command {
+        Command::Profiles {
+            command:
+                ProfilesCommand::Create {
+                    name,
+                    game_version,
+                    loader,
+                    loader_version,
+                    pack,
+                },
+        } => {
+            assert_eq!(name, "Test Server Bot");
+            assert_eq!(game_version, "1.21.4");
+            assert_eq!(loader, "fabric");
+            assert_eq!(loader_version, None);
+            assert_eq!(pack, None);
+        }
+        _ => panic!("expected profiles create"),
+    }
+
+    let cli = Cli::try_parse_from(["norisk-cli", "install", "Bot", "sodium"]).unwrap();
+    match cli.command {
+        Command::Install {
+            project,
+            kind,
+            ignore_incompatibilities,
+            ..
+        } => {
+            assert_eq!(project, "sodium");
+            assert!(matches!(kind, ContentKind::Mod));
+            assert!(!ignore_incompatibilities);
+        }
+        _ => panic!("expected install"),
+    }
+
+    let cli = Cli::try_parse_from([
+        "norisk-cli",
+        "launch",
+        "Bot",
+        "--server",
+        "localhost:25565",
+        "--detach",
+    ])
+    .unwrap();
+    match cli.command {
+        Command::Launch {
+            server,
+            world,
+            detach,
+            ..
+        } => {
+            assert_eq!(server.as_deref(), Some("localhost:25565"));
+            assert_eq!(world, None);
+            assert!(detach);
+        }
+        _ => panic!("expected launch"),
+    }
+
+    // Quick Play can only target one of both
+    assert!(Cli::try_parse_from([
+        "norisk-cli",
+        "launch",
+        "Bot",
+        "--server",
+        "localhost",
+        "--world",
+        "New World",
+    ])
+    .is_err());
+    assert!(
+        Cli::try_parse_from(["norisk-cli", "install", "Bot", "x", "--kind", "plugin"]).is_err()
+    );
+    assert!(Cli::try_parse_from(["norisk-cli", "logs", "not-a-uuid"]).is_err());
+    assert!(Cli::try_parse_from(["norisk-cli", "profiles", "create", "Bot"]).is_err());
+}
+
+#[test]
+fn test_find_profile() {
+    let survival = profile("Survival");
+    let bot = profile("Test Server Bot");
+    let profiles = vec![
+        survival.clone(),
+        bot.clone(),
+        profile("Duplicate"),
+        profile("duplicate"),
+    ];
+
+    assert_eq!(
+        find_profile(profiles.clone(), &bot.id.to_string())
+            .unwrap()
+            .id,
+        bot.id
+    );
+    assert_eq!(
+        find_profile(profiles.clone(), "test server BOT")
+            .unwrap()
+            .id,
+        bot.id
+    );
+    assert_eq!(
+        find_profile(profiles.clone(), "survival").unwrap().id,
+        survival.id
+    );
+    assert!(matches!(
+        find_profile(profiles.clone(), "Duplicate"),
+        Err(AppError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        find_profile(profiles.clone(), "Creative"),
+        Err(AppError::NotFound(_))
+    ));
+    let unknown = Uuid::new_v4();
+    assert!(matches!(
+        find_profile(profiles, &unknown.to_string()),
+        Err(AppError::ProfileNotFound(id)) if id == unknown
+    ));
+}
@@ -0,0 +1,14 @@
+// src-tauri/tests/common/mod.rs
+// Helpers shared by the integration tests
+
+use fastnbt::Value;
+
+/// NBT compound from key/value pairs.
+pub fn compound(entries: Vec<(&str, Value)>) -> Value {
+    Value::Compound(
+        entries
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
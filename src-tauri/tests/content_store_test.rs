@@ -0,0 +1,63 @@
+// src-tauri/tests/content_store_test.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::state::content_store_state::{
+    ContentStoreManager, MaterializeMethod,
+};
+use noriskclient_launcher_v3_lib::utils::hash_utils::calculate_sha1_from_bytes;
+use std::fs;
+
+//cargo test --package noriskclient-launcher-v3 --test content_store_test
+
+#[tokio::test]
+async fn test_link_dedup_and_gc() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let store = ContentStoreManager::new(temp_dir.path().join("store"))?;
+
+    let cache_dir = temp_dir.path().join("mod_cache");
+    fs::create_dir_all(&cache_dir)?;
+    let jar = cache_dir.join("sodium.jar");
+    fs::write(&jar, b"not really a jar")?;
+
+    let profile_a = temp_dir.path().join("a/mods/sodium.jar");
+    let profile_b = temp_dir.path().join("b/mods/sodium.jar");
+    let methods = store
+        .link_files(&[
+            (jar.clone(), profile_a.clone()),
+            (jar.clone(), profile_b.clone()),
+        ])
+        .await?;
+    assert_eq!(methods, vec![MaterializeMethod::Hardlink; 2]);
+    assert_eq!(fs::read(&profile_a)?, b"not really a jar");
+
+    // One blob for both profiles, temp files of running ingests don't count
+    let blob = store.blob_path(&calculate_sha1_from_bytes(b"not really a jar"));
+    // The blob is the cache file itself, not a second copy
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        assert_eq!(fs::metadata(&jar)?.ino(), fs::metadata(&blob)?.ino());
+    }
+    fs::write(blob.with_extension("tmp-1234"), b"not really")?;
+    let stats = store.stats().await?;
+    assert_eq!(stats.blob_count, 1);
+    assert_eq!(stats.reference_count, 2);
+
+    // Still referenced by profile b
+    fs::remove_file(&profile_a)?;
+    let report = store.garbage_collect().await?;
+    assert_eq!(report.removed_blobs, 0);
+    assert_eq!(report.pruned_references, 1);
+
+    // No references left
+    store
+        .release_paths(std::slice::from_ref(&profile_b))
+        .await?;
+    let report = store.garbage_collect().await?;
+    assert_eq!(report.removed_blobs, 1);
+    assert_eq!(store.stats().await?.blob_count, 0);
+
+    // The profile file outlives its blob, hardlinks are independent directory entries
+    assert!(profile_b.exists());
+    Ok(())
+}
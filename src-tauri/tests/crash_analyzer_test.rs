@@ -0,0 +1,200 @@
+// src-tauri/tests/crash_analyzer_test.rs
+
+use noriskclient_launcher_v3_lib::minecraft::crash_analyzer::{
+    CrashAnalysisContext, CrashCategory, CrashSignature, SignatureDatabase, SuggestedFix,
+};
+use serde_json::json;
+
+//cargo test --package noriskclient-launcher-v3 --test crash_analyzer_test
+
+const OUT_OF_MEMORY_REPORT: &str = "---- Minecraft Crash Report ----
+// Why did you do that?
+
+Time: 2024-05-01 12:00:00
+Description: Unexpected error
+
+java.lang.RuntimeException: Chunk build failed
+\tat net.minecraft.class_761.method_22710(class_761.java:1450)
+\tat net.minecraft.class_757.method_3188(class_757.java:1276)
+Caused by: java.lang.OutOfMemoryError: Java heap space
+\tat java.base/java.util.Arrays.copyOf(Arrays.java:3537)
+\t... 12 more
+
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+---------------------------------------------------------------------------------------
+
+-- Head --
+Thread: Render thread
+Suspected Mods:
+\tSodium (sodium), Version: 0.5.8+mc1.20.1
+\t\tIssue tracker URL: https://github.com/CaffeineMC/sodium-fabric/issues
+Stacktrace:
+\tat net.minecraft.class_761.method_22710(class_761.java:1450)
+
+-- System Details --
+Details:
+\tMinecraft Version: 1.20.1
+\tJava Version: 17.0.8, Eclipse Adoptium
+";
+
+const INCOMPATIBLE_MODS_LOG: &str = "[12:00:00] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.7
+[12:00:01] [main/ERROR]: Incompatible mods found!
+net.fabricmc.loader.impl.FormattedException: Some of your mods are incompatible with the game or each other!
+\tat net.fabricmc.loader.impl.FabricLoaderImpl.load(FabricLoaderImpl.java:190)
+A potential solution has been determined, this may resolve your problem:
+\t - Install sodium, any version.
+More details:
+\t - Mod 'Iris' (iris) 1.6.11+1.20.1 requires any version of mod 'Sodium' (sodium), which is missing!
+\t - Mod 'OptiFabric' (optifabric) 1.13.24 is incompatible with any version of mod 'Indium' (indium), but a matching version is present: 1.0.27+mc1.20.1!
+\t - Mod 'Minecraft' (minecraft) 1.20.1 requires version 21 or later of 'OpenJDK 64-Bit Server VM' (java), but only the wrong version is present: 17!
+";
+
+#[test]
+fn test_crash_report_analysis() {
+    let database = SignatureDatabase::builtin().unwrap();
+    let context = CrashAnalysisContext {
+        max_memory_mb: Some(2048),
+        system_memory_mb: Some(16384),
+        ..Default::default()
+    };
+
+    // The log contains the same report again, findings must not be duplicated
+    let log = format!(
+        "[12:00:00] [Render thread/INFO]: Loading\n{}",
+        OUT_OF_MEMORY_REPORT
+    );
+    let analysis = database.analyze(Some(OUT_OF_MEMORY_REPORT), Some(&log), &context);
+
+    assert_eq!(analysis.description.as_deref(), Some("Unexpected error"));
+    let classes: Vec<&str> = analysis
+        .exceptions
+        .iter()
+        .map(|e| e.class_name.as_str())
+        .collect();
+    assert_eq!(
+        classes,
+        vec!["java.lang.RuntimeException", "java.lang.OutOfMemoryError"]
+    );
+    assert_eq!(analysis.exceptions[0].frames.len(), 2);
+    assert_eq!(analysis.running_java, Some(17));
+    assert_eq!(analysis.suspected_mods.len(), 1);
+    assert_eq!(analysis.suspected_mods[0].mod_id, "sodium");
+
+    // Out of memory is more specific than the suspected mod
+    assert_eq!(analysis.findings.len(), 2);
+    assert_eq!(analysis.findings[0].category, CrashCategory::OutOfMemory);
+    assert_eq!(
+        analysis.findings[0].fixes,
+        vec![SuggestedFix::RaiseMemory {
+            recommended_mb: 4096
+        }]
+    );
+    assert_eq!(analysis.findings[1].signature_id, "suspected_mod");
+    assert!(analysis.findings[1]
+        .fixes
+        .contains(&SuggestedFix::DisableMod {
+            mod_id: "sodium".to_string()
+        }));
+
+    // Without the memory settings the fix can't be filled in, the finding stays
+    let analysis = database.analyze(
+        Some(OUT_OF_MEMORY_REPORT),
+        None,
+        &CrashAnalysisContext::default(),
+    );
+    assert!(analysis.findings[0].fixes.is_empty());
+}
+
+#[test]
+fn test_log_analysis() {
+    let database = SignatureDatabase::builtin().unwrap();
+    let analysis = database.analyze(
+        None,
+        Some(INCOMPATIBLE_MODS_LOG),
+        &CrashAnalysisContext::default(),
+    );
+
+    assert_eq!(analysis.exceptions.len(), 1);
+    assert_eq!(
+        analysis.exceptions[0].class_name,
+        "net.fabricmc.loader.impl.FormattedException"
+    );
+    assert_eq!(analysis.required_java, Some(21));
+
+    let fixes_of = |id: &str| {
+        analysis
+            .findings
+            .iter()
+            .filter(|f| f.signature_id == id)
+            .flat_map(|f| f.fixes.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        fixes_of("java_too_old"),
+        vec![SuggestedFix::SwitchJava { major_version: 21 }]
+    );
+    assert_eq!(
+        fixes_of("fabric_missing_dependency"),
+        vec![SuggestedFix::InstallMod {
+            mod_id: "sodium".to_string()
+        }]
+    );
+    assert_eq!(
+        fixes_of("incompatible_mods"),
+        vec![
+            SuggestedFix::DisableMod {
+                mod_id: "optifabric".to_string()
+            },
+            SuggestedFix::DisableMod {
+                mod_id: "indium".to_string()
+            }
+        ]
+    );
+    // Minecraft and Java can't be updated like mods
+    assert!(fixes_of("fabric_wrong_dependency_version").is_empty());
+}
+
+#[test]
+fn test_custom_signatures() {
+    let signature: CrashSignature = serde_json::from_value(json!({
+        "id": "sodium_outdated_driver",
+        "title": "Outdated driver for Sodium",
+        "category": "graphics_driver",
+        "message": "Sodium needs driver {version} or newer.",
+        "patterns": ["Sodium requires driver (?P<version>[\\d.]+)"],
+        "fixes": [
+            { "type": "update_graphics_driver" },
+            { "type": "switch_java", "major_version": "{running_java}" }
+        ]
+    }))
+    .unwrap();
+    let database = SignatureDatabase::new(vec![signature.clone()]).unwrap();
+
+    let analysis = database.analyze(
+        None,
+        Some("[Render thread/ERROR]: Sodium requires driver 531.61 or newer"),
+        &CrashAnalysisContext {
+            running_java: Some(17),
+            ..Default::default()
+        },
+    );
+    assert_eq!(analysis.findings.len(), 1);
+    assert_eq!(
+        analysis.findings[0].message,
+        "Sodium needs driver 531.61 or newer."
+    );
+    assert_eq!(
+        analysis.findings[0].fixes,
+        vec![
+            SuggestedFix::UpdateGraphicsDriver,
+            SuggestedFix::SwitchJava { major_version: 17 }
+        ]
+    );
+
+    let broken = CrashSignature {
+        patterns: vec!["(unclosed".to_string()],
+        ..signature
+    };
+    assert!(SignatureDatabase::new(vec![broken]).is_err());
+}
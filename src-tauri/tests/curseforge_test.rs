@@ -0,0 +1,207 @@
+// src-tauri/tests/curseforge_test.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::integrations::curseforge::{
+    determine_loader_from_mod_loaders, process_curseforge_pack, resolve_curseforge_files,
+    CurseForgeApi, CurseForgeModLoader,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::{ModLoader, ModSource};
+use std::io::Write;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+//cargo test --package noriskclient-launcher-v3 --test curseforge_test
+
+const MANIFEST: &str = r#"{
+    "minecraft": {
+        "version": "1.20.1",
+        "modLoaders": [{ "id": "forge-47.2.0", "primary": true }]
+    },
+    "manifestType": "minecraftModpack",
+    "manifestVersion": 1,
+    "name": "Test Pack",
+    "version": "1.0.0",
+    "author": "NoRisk",
+    "files": [
+        { "projectID": 100, "fileID": 1000, "required": true },
+        { "projectID": 200, "fileID": 2000, "required": true },
+        { "projectID": 300, "fileID": 3000, "required": true },
+        { "projectID": 400, "fileID": 4000, "required": true },
+        { "projectID": 500, "fileID": 5000, "required": false }
+    ],
+    "overrides": "overrides"
+}"#;
+
+const FILES_RESPONSE: &str = r#"{ "data": [
+    { "id": 1000, "modId": 100, "displayName": "JEI 15.2", "fileName": "jei-1.20.1-15.2.jar",
+      "downloadUrl": "http://mock/jei.jar", "fileLength": 10,
+      "hashes": [{ "value": "0123456789abcdef0123456789abcdef01234567", "algo": 1 }, { "value": "d41d8cd98f00b204e9800998ecf8427e", "algo": 2 }] },
+    { "id": 2000, "modId": 200, "displayName": "Faithful", "fileName": "Faithful.zip",
+      "downloadUrl": "http://mock/faithful.zip", "fileLength": 10, "hashes": [] },
+    { "id": 3000, "modId": 300, "displayName": "Restricted", "fileName": "restricted.jar",
+      "downloadUrl": null, "fileLength": 10, "hashes": [] }
+] }"#;
+
+const PROJECTS_RESPONSE: &str = r#"{ "data": [
+    { "id": 100, "name": "Just Enough Items", "slug": "jei", "classId": 6 },
+    { "id": 200, "name": "Faithful 32x", "slug": "faithful", "classId": 12 },
+    { "id": 300, "name": "Restricted Mod", "slug": "restricted", "classId": 6 }
+] }"#;
+
+const API_KEY: &str = "test-key";
+
+/// Minimal HTTP server answering the two CurseForge endpoints used by the importer.
+/// Like the real API it rejects requests without a valid key.
+async fn start_mock_api() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 16 * 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let request_line = request.lines().next().unwrap_or_default();
+
+                let authorized = request
+                    .lines()
+                    .any(|line| line.eq_ignore_ascii_case(&format!("x-api-key: {}", API_KEY)));
+
+                let (status, body) = if !authorized {
+                    ("403 Forbidden", "")
+                } else if request_line.starts_with("POST /v1/mods/files") {
+                    ("200 OK", FILES_RESPONSE)
+                } else if request_line.starts_with("POST /v1/mods") {
+                    ("200 OK", PROJECTS_RESPONSE)
+                } else {
+                    ("200 OK", "{}")
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+fn write_test_pack(path: &std::path::Path) {
+    let file = std::fs::File::create(path).unwrap();
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file("manifest.json", options).unwrap();
+    zip.write_all(MANIFEST.as_bytes()).unwrap();
+    zip.start_file("overrides/config/jei.toml", options)
+        .unwrap();
+    zip.write_all(b"enabled = true").unwrap();
+    zip.finish().unwrap();
+}
+
+#[test]
+fn test_loader_mapping() -> Result<()> {
+    let loaders = vec![
+        CurseForgeModLoader {
+            id: "fabric-0.15.7".to_string(),
+            primary: false,
+        },
+        CurseForgeModLoader {
+            id: "neoforge-1.20.1-47.1.84".to_string(),
+            primary: true,
+        },
+    ];
+    let (loader, version) = determine_loader_from_mod_loaders(&loaders)?;
+    assert_eq!(loader, ModLoader::NeoForge);
+    assert_eq!(version.as_deref(), Some("47.1.84"));
+
+    let (loader, version) = determine_loader_from_mod_loaders(&[])?;
+    assert_eq!(loader, ModLoader::Vanilla);
+    assert!(version.is_none());
+
+    assert!(determine_loader_from_mod_loaders(&[CurseForgeModLoader {
+        id: "rift-1.0".to_string(),
+        primary: true,
+    }])
+    .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_curseforge_pack_resolution_offline() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let pack_path = temp_dir.path().join("test-pack.zip");
+    write_test_pack(&pack_path);
+
+    let (profile, manifest) = process_curseforge_pack(&pack_path).await?;
+    assert_eq!(profile.name, "Test Pack");
+    assert_eq!(profile.game_version, "1.20.1");
+    assert_eq!(profile.loader, ModLoader::Forge);
+    assert_eq!(profile.loader_version.as_deref(), Some("1.20.1-47.2.0"));
+    assert_eq!(manifest.files.len(), 5);
+
+    let api = CurseForgeApi::new(start_mock_api().await, Some(API_KEY.to_string()));
+    let resolved = resolve_curseforge_files(&api, &manifest).await?;
+
+    // JEI is a mod with a download URL
+    assert_eq!(resolved.mods.len(), 1);
+    match &resolved.mods[0].source {
+        ModSource::Url {
+            url,
+            file_name,
+            file_hash_sha1,
+        } => {
+            assert_eq!(url, "http://mock/jei.jar");
+            assert_eq!(file_name.as_deref(), Some("jei-1.20.1-15.2.jar"));
+            assert_eq!(
+                file_hash_sha1.as_deref(),
+                Some("0123456789abcdef0123456789abcdef01234567")
+            );
+        }
+        other => panic!("Unexpected mod source: {:?}", other),
+    }
+
+    // Faithful is a resource pack
+    assert_eq!(resolved.instance_files.len(), 1);
+    assert_eq!(
+        resolved.instance_files[0].relative_path,
+        "resourcepacks/Faithful.zip"
+    );
+    assert_eq!(resolved.instance_files[0].file_id, 2000);
+    assert!(resolved.instance_files[0].sha1.is_none());
+
+    // 3000 has no download URL, 4000 is unknown to the API, 5000 is optional and skipped
+    let mut unresolved: Vec<u32> = resolved.unresolved.iter().map(|f| f.file_id).collect();
+    unresolved.sort();
+    assert_eq!(unresolved, vec![3000, 4000]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_curseforge_files_unresolved_without_api_key() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let pack_path = temp_dir.path().join("test-pack.zip");
+    write_test_pack(&pack_path);
+    let (_, manifest) = process_curseforge_pack(&pack_path).await?;
+    let base_url = start_mock_api().await;
+
+    // A missing or rejected key reports every required file instead of failing the import
+    for api_key in [None, Some("wrong-key".to_string())] {
+        let api = CurseForgeApi::new(base_url.clone(), api_key);
+        let resolved = resolve_curseforge_files(&api, &manifest).await?;
+        assert!(resolved.mods.is_empty());
+        assert!(resolved.instance_files.is_empty());
+        let mut unresolved: Vec<u32> = resolved.unresolved.iter().map(|f| f.file_id).collect();
+        unresolved.sort();
+        assert_eq!(unresolved, vec![1000, 2000, 3000, 4000]);
+    }
+    Ok(())
+}
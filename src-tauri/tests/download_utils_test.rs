@@ -0,0 +1,142 @@
+// src-tauri/tests/download_utils_test.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::utils::download_utils::{DownloadOutcome, VerifiedDownload};
+use noriskclient_launcher_v3_lib::utils::hash_utils::calculate_sha1_from_bytes;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+//cargo test --package noriskclient-launcher-v3 --test download_utils_test
+
+fn test_body() -> Vec<u8> {
+    (0..64 * 1024).map(|i| (i % 251) as u8).collect()
+}
+
+/// Serves `body` on every path except `/missing`. The first response is cut off after half
+/// of the body, later requests honour `Range: bytes=N-` with a 206.
+async fn start_flaky_server(body: Vec<u8>, requests: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+            let body = body.clone();
+            let request_number = requests.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 16 * 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+
+                if request.starts_with("get /missing") {
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                        .await;
+                    return;
+                }
+
+                let range_start = request
+                    .lines()
+                    .find_map(|l| l.strip_prefix("range: bytes="))
+                    .and_then(|r| r.trim_end_matches('-').parse::<usize>().ok());
+
+                match range_start {
+                    Some(start) => {
+                        let head = format!(
+                            "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                            body.len() - start,
+                            start,
+                            body.len() - 1,
+                            body.len()
+                        );
+                        let _ = socket.write_all(head.as_bytes()).await;
+                        let _ = socket.write_all(&body[start..]).await;
+                    }
+                    None => {
+                        let head = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        );
+                        let _ = socket.write_all(head.as_bytes()).await;
+                        if request_number == 0 {
+                            // Simulate a dropped connection
+                            let _ = socket.write_all(&body[..body.len() / 2]).await;
+                            let _ = socket.shutdown().await;
+                            return;
+                        }
+                        let _ = socket.write_all(&body).await;
+                    }
+                }
+            });
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_resume_after_dropped_connection() -> Result<()> {
+    let body = test_body();
+    let requests = Arc::new(AtomicUsize::new(0));
+    let base_url = start_flaky_server(body.clone(), requests.clone()).await;
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("libs/test.jar");
+
+    let download = VerifiedDownload::new(format!("{}/test.jar", base_url), &target)
+        .sha1(Some(&calculate_sha1_from_bytes(&body)))
+        .size(Some(body.len() as u64))
+        .initial_backoff(Duration::from_millis(10));
+
+    let outcome = download.download().await?;
+    assert_eq!(
+        outcome,
+        DownloadOutcome::Downloaded {
+            bytes: body.len() as u64,
+            resumed: true
+        }
+    );
+    assert_eq!(std::fs::read(&target)?, body);
+    assert!(!temp_dir.path().join("libs/test.jar.part").exists());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    // Second run only verifies the existing file
+    assert_eq!(download.download().await?, DownloadOutcome::AlreadyPresent);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hash_mismatch_and_missing_file() -> Result<()> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let base_url = start_flaky_server(test_body(), requests.clone()).await;
+    let temp_dir = tempfile::tempdir()?;
+
+    let target = temp_dir.path().join("broken.jar");
+    let result = VerifiedDownload::new(format!("{}/broken.jar", base_url), &target)
+        .sha1(Some("0000000000000000000000000000000000000000"))
+        .max_retries(2)
+        .initial_backoff(Duration::from_millis(10))
+        .download()
+        .await;
+    assert!(result.is_err());
+    assert!(!target.exists());
+    assert!(!temp_dir.path().join("broken.jar.part").exists());
+
+    // 404 is not retried
+    let before = requests.load(Ordering::SeqCst);
+    let result = VerifiedDownload::new(
+        format!("{}/missing", base_url),
+        temp_dir.path().join("missing.jar"),
+    )
+    .initial_backoff(Duration::from_millis(10))
+    .download()
+    .await;
+    assert!(result.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), before + 1);
+    Ok(())
+}
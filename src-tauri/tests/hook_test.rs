@@ -0,0 +1,102 @@
+// src-tauri/tests/hook_test.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::state::config_state::Hooks;
+use noriskclient_launcher_v3_lib::state::profile_state::ProfileHooks;
+use noriskclient_launcher_v3_lib::utils::hook_utils::{
+    run_pre_launch_hook, split_command_line, HookContext, ResolvedHooks,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+//cargo test --package noriskclient-launcher-v3 --test hook_test
+
+#[test]
+fn test_profile_hooks_override_global() {
+    let global = Hooks {
+        pre_launch: Some("backup.sh".to_string()),
+        wrapper: Some("gamemoderun".to_string()),
+        post_exit: Some("notify-send done".to_string()),
+        pre_launch_timeout_secs: Some(30),
+        abort_on_pre_launch_failure: true,
+    };
+    let profile = ProfileHooks {
+        wrapper: Some("mangohud --dlsym".to_string()),
+        post_exit: Some(String::new()),
+        abort_on_pre_launch_failure: Some(false),
+        ..Default::default()
+    };
+
+    let resolved = ResolvedHooks::resolve(&global, &profile);
+    assert_eq!(resolved.pre_launch.as_deref(), Some("backup.sh"));
+    assert_eq!(resolved.wrapper.as_deref(), Some("mangohud --dlsym"));
+    assert_eq!(resolved.post_exit, None);
+    assert_eq!(resolved.pre_launch_timeout, Some(Duration::from_secs(30)));
+    assert!(!resolved.abort_on_pre_launch_failure);
+}
+
+#[test]
+fn test_placeholders_and_splitting() {
+    let profile_id = Uuid::new_v4();
+    let context = HookContext::new(PathBuf::from("/games/My Pack"), profile_id, "My Pack")
+        .with_java_path(PathBuf::from("/java/bin/java"));
+
+    let parts = split_command_line(r#""/opt/my scripts/backup.sh" '{instance_dir}' --pid {pid}"#);
+    assert_eq!(
+        parts,
+        vec![
+            "/opt/my scripts/backup.sh",
+            "{instance_dir}",
+            "--pid",
+            "{pid}"
+        ]
+    );
+    assert_eq!(context.substitute(&parts[1]), "/games/My Pack");
+    // Unknown until the game runs
+    assert_eq!(context.substitute(&parts[3]), "{pid}");
+    assert_eq!(
+        context
+            .clone()
+            .with_pid(4242)
+            .substitute("{pid} {java_path} {profile_id}"),
+        format!("4242 /java/bin/java {}", profile_id)
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_pre_launch_hook_exit_code_env_and_timeout() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let context = HookContext::new(temp_dir.path().to_path_buf(), Uuid::new_v4(), "Test");
+    let mut env_vars = HashMap::new();
+    env_vars.insert("NRC_TEST_VALUE".to_string(), "42".to_string());
+
+    run_pre_launch_hook(
+        r#"sh -c 'test "$NRC_TEST_VALUE" = 42 && touch "$1/ran"' hook {instance_dir}"#,
+        &context,
+        &env_vars,
+        None,
+    )
+    .await?;
+    assert!(temp_dir.path().join("ran").exists());
+
+    assert!(
+        run_pre_launch_hook("sh -c 'exit 3'", &context, &env_vars, None)
+            .await
+            .is_err()
+    );
+
+    let started = std::time::Instant::now();
+    let timed_out = run_pre_launch_hook(
+        "sleep 10",
+        &context,
+        &env_vars,
+        Some(Duration::from_millis(200)),
+    )
+    .await;
+    assert!(timed_out.is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+    Ok(())
+}
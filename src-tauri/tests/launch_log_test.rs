@@ -0,0 +1,145 @@
+// src-tauri/tests/launch_log_test.rs
+
+use chrono::{Duration, Local, TimeZone, Utc};
+use noriskclient_launcher_v3_lib::state::config_state::LaunchLogConfig;
+use noriskclient_launcher_v3_lib::utils::launch_log_utils::{
+    list_launch_logs, prune_launch_logs, read_launch_log, LaunchLogWriter, Log4jLineParser,
+};
+
+//cargo test --package noriskclient-launcher-v3 --test launch_log_test
+
+#[tokio::test]
+async fn test_launch_log_rotation() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = LaunchLogConfig {
+        max_file_size_mb: 1,
+        max_files_per_launch: 3,
+        ..Default::default()
+    };
+    let started_at = Local.with_ymd_and_hms(2025, 1, 31, 18, 4, 12).unwrap();
+    let mut writer = LaunchLogWriter::create(dir.path(), started_at, &config)
+        .await
+        .unwrap();
+    assert_eq!(writer.launch_id(), "2025-01-31_18-04-12");
+    writer
+        .write_line("Error: Could not find or load main class net.minecraft.client.main.Main")
+        .await
+        .unwrap();
+    // About 4 MB: four files, the second one is deleted again
+    let filler = "x".repeat(1023);
+    for i in 0..4000 {
+        writer
+            .write_line(&format!("{:04}{}", i, &filler[4..]))
+            .await
+            .unwrap();
+    }
+    writer.write_line("Stopping!").await.unwrap();
+    writer.flush().await.unwrap();
+
+    let logs = list_launch_logs(dir.path()).await.unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].id, "2025-01-31_18-04-12");
+    assert_eq!(logs[0].files, 3);
+
+    let content = read_launch_log(dir.path(), "2025-01-31_18-04-12")
+        .await
+        .unwrap();
+    // The startup output is always kept
+    assert!(content.starts_with("Error: Could not find or load main class"));
+    assert!(content.contains("[...]\n"));
+    assert!(content.ends_with("Stopping!\n"));
+    assert!(!content.contains("\n1500x"));
+    assert!(read_launch_log(dir.path(), "../secrets").await.is_err());
+
+    // A second launch in the same second gets its own log
+    let second = LaunchLogWriter::create(dir.path(), started_at, &config)
+        .await
+        .unwrap();
+    assert_eq!(second.launch_id(), "2025-01-31_18-04-12_2");
+}
+
+#[tokio::test]
+async fn test_prune_launch_logs() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = LaunchLogConfig {
+        max_launches: 2,
+        retention_days: 7,
+        ..Default::default()
+    };
+    for hour in 10..14 {
+        let started_at = Local.with_ymd_and_hms(2025, 3, 1, hour, 0, 0).unwrap();
+        let mut writer = LaunchLogWriter::create(dir.path(), started_at, &config)
+            .await
+            .unwrap();
+        writer.write_line("[main/INFO]: Hello").await.unwrap();
+        writer.flush().await.unwrap();
+    }
+
+    // Only the newest launches are kept
+    assert_eq!(
+        prune_launch_logs(dir.path(), &config, Utc::now())
+            .await
+            .unwrap(),
+        2
+    );
+    let ids: Vec<String> = list_launch_logs(dir.path())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|log| log.id)
+        .collect();
+    assert_eq!(ids, vec!["2025-03-01_13-00-00", "2025-03-01_12-00-00"]);
+
+    // Everything is older than the retention a few weeks later
+    assert_eq!(
+        prune_launch_logs(dir.path(), &config, Utc::now() + Duration::days(8))
+            .await
+            .unwrap(),
+        2
+    );
+    assert!(list_launch_logs(dir.path()).await.unwrap().is_empty());
+}
+
+#[test]
+fn test_log4j_events_to_lines() {
+    let mut parser = Log4jLineParser::default();
+    assert_eq!(
+        parser.push_line("Error: Could not find or load main class"),
+        vec!["Error: Could not find or load main class"]
+    );
+
+    let event = [
+        r#"<log4j:Event logger="net.minecraft.client.Minecraft" timestamp="1700000000000" level="WARN" thread="Render &amp; thread">"#,
+        r#"  <log4j:Message><![CDATA[Missing sound ]]]]><![CDATA[> for event"#,
+        r#"second line]]></log4j:Message>"#,
+        r#"  <log4j:Throwable><![CDATA[java.lang.IllegalStateException: boom"#,
+        r#"	at a.b.C.d(C.java:1)"#,
+        r#"]]></log4j:Throwable>"#,
+    ];
+    for line in event {
+        assert!(parser.push_line(line).is_empty());
+    }
+    let time = Local
+        .timestamp_millis_opt(1_700_000_000_000)
+        .unwrap()
+        .format("%H:%M:%S");
+    assert_eq!(
+        parser.push_line("</log4j:Event>"),
+        vec![
+            format!(
+                "[{}] [Render & thread/WARN]: Missing sound ]]> for event",
+                time
+            ),
+            "second line".to_string(),
+            "java.lang.IllegalStateException: boom".to_string(),
+            "\tat a.b.C.d(C.java:1)".to_string(),
+        ]
+    );
+
+    // The rest of an event that never ended is kept
+    assert!(parser
+        .push_line(r#"<log4j:Event logger="x" timestamp="1" level="INFO" thread="main">"#)
+        .is_empty());
+    assert_eq!(parser.finish().len(), 1);
+    assert!(parser.finish().is_empty());
+}
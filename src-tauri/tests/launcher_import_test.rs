@@ -0,0 +1,91 @@
+// src-tauri/tests/launcher_import_test.rs
+
+use noriskclient_launcher_v3_lib::integrations::launcher_import::{
+    parse_instance_cfg, parse_memory_value, parse_mmc_pack, parse_vanilla_version_id,
+    split_memory_from_jvm_args,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+
+//cargo test --package noriskclient-launcher-v3 --test launcher_import_test
+
+#[test]
+fn test_parse_prism_instance() {
+    let cfg = parse_instance_cfg(
+        "[General]\nInstanceType=OneSix\nname=\"Fabric Survival\"\nOverrideMemory=true\nMaxMemAlloc=6144\nMinMemAlloc=1024\nJvmArgs=-XX:+UseG1GC\n",
+    );
+    assert_eq!(cfg.get("name").map(String::as_str), Some("Fabric Survival"));
+    assert_eq!(cfg.get("MaxMemAlloc").map(String::as_str), Some("6144"));
+    assert_eq!(cfg.get("JvmArgs").map(String::as_str), Some("-XX:+UseG1GC"));
+
+    let info = parse_mmc_pack(
+        r#"{ "components": [
+            { "uid": "org.lwjgl3", "version": "3.3.1" },
+            { "uid": "net.minecraft", "version": "1.20.1" },
+            { "uid": "net.fabricmc.intermediary", "version": "1.20.1" },
+            { "uid": "net.fabricmc.fabric-loader", "version": "0.15.7" }
+        ], "formatVersion": 1 }"#,
+    )
+    .unwrap();
+    assert_eq!(info.game_version, "1.20.1");
+    assert_eq!(info.loader, ModLoader::Fabric);
+    assert_eq!(info.loader_version.as_deref(), Some("0.15.7"));
+
+    let forge = parse_mmc_pack(
+        r#"{ "components": [
+            { "uid": "net.minecraft", "version": "1.20.1" },
+            { "uid": "net.minecraftforge", "version": "47.2.0" }
+        ] }"#,
+    )
+    .unwrap();
+    assert_eq!(forge.loader, ModLoader::Forge);
+    assert_eq!(forge.loader_version.as_deref(), Some("1.20.1-47.2.0"));
+
+    assert!(parse_mmc_pack(r#"{ "components": [] }"#).is_err());
+}
+
+#[test]
+fn test_parse_vanilla_version_ids() {
+    let vanilla = parse_vanilla_version_id("1.21.1").unwrap();
+    assert_eq!(vanilla.loader, ModLoader::Vanilla);
+    assert_eq!(vanilla.game_version, "1.21.1");
+
+    let fabric = parse_vanilla_version_id("fabric-loader-0.16.5-1.21.1").unwrap();
+    assert_eq!(fabric.loader, ModLoader::Fabric);
+    assert_eq!(fabric.game_version, "1.21.1");
+    assert_eq!(fabric.loader_version.as_deref(), Some("0.16.5"));
+
+    let forge = parse_vanilla_version_id("1.20.1-forge-47.2.0").unwrap();
+    assert_eq!(forge.loader, ModLoader::Forge);
+    assert_eq!(forge.loader_version.as_deref(), Some("1.20.1-47.2.0"));
+
+    let old_forge = parse_vanilla_version_id("1.12.2-forge1.12.2-14.23.5.2859").unwrap();
+    assert_eq!(old_forge.game_version, "1.12.2");
+    assert_eq!(
+        old_forge.loader_version.as_deref(),
+        Some("1.12.2-14.23.5.2859")
+    );
+
+    let neoforge = parse_vanilla_version_id("neoforge-20.4.80").unwrap();
+    assert_eq!(neoforge.loader, ModLoader::NeoForge);
+    assert_eq!(neoforge.game_version, "1.20.4");
+
+    assert!(parse_vanilla_version_id("latest-release").is_none());
+}
+
+#[test]
+fn test_split_memory_from_jvm_args() {
+    let (memory, rest) =
+        split_memory_from_jvm_args("-Xmx4G -XX:+UnlockExperimentalVMOptions -Xms512M");
+    let memory = memory.unwrap();
+    assert_eq!(memory.max, 4096);
+    assert_eq!(memory.min, 512);
+    assert_eq!(rest.as_deref(), Some("-XX:+UnlockExperimentalVMOptions"));
+
+    let (memory, rest) = split_memory_from_jvm_args("-XX:+UseG1GC");
+    assert!(memory.is_none());
+    assert_eq!(rest.as_deref(), Some("-XX:+UseG1GC"));
+
+    // Values that don't fit into megabytes as u32 are rejected instead of overflowing
+    assert_eq!(parse_memory_value("4194303G"), Some(4294966272));
+    assert_eq!(parse_memory_value("4194304G"), None);
+}
@@ -0,0 +1,189 @@
+// src-tauri/tests/level_dat_test.rs
+
+mod common;
+
+use common::compound;
+use fastnbt::{IntArray, Value};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use noriskclient_launcher_v3_lib::utils::level_dat_utils::{
+    apply_level_dat_edit, parse_level_dat_details, LevelDatEdit, SpawnPoint,
+};
+use std::collections::HashMap;
+use std::io::Write;
+
+//cargo test --package noriskclient-launcher-v3 --test level_dat_test
+
+fn gzip(root: &Value) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&fastnbt::to_bytes(root).unwrap())
+        .unwrap();
+    encoder.finish().unwrap()
+}
+
+/// A 1.20 level.dat: string game rules, spawn as SpawnX/Y/Z
+fn level_dat_1_20() -> Vec<u8> {
+    gzip(&compound(vec![(
+        "Data",
+        compound(vec![
+            ("LevelName", Value::String("Survival".to_string())),
+            ("DataVersion", Value::Int(3465)),
+            (
+                "Version",
+                compound(vec![("Name", Value::String("1.20.1".to_string()))]),
+            ),
+            ("GameType", Value::Int(0)),
+            ("Difficulty", Value::Byte(2)),
+            ("hardcore", Value::Byte(0)),
+            ("allowCommands", Value::Byte(0)),
+            ("SpawnX", Value::Int(16)),
+            ("SpawnY", Value::Int(70)),
+            ("SpawnZ", Value::Int(-32)),
+            (
+                "WorldGenSettings",
+                compound(vec![
+                    ("seed", Value::Long(-4172144997902289642)),
+                    (
+                        "dimensions",
+                        compound(vec![
+                            ("minecraft:overworld", compound(vec![])),
+                            ("minecraft:the_nether", compound(vec![])),
+                        ]),
+                    ),
+                ]),
+            ),
+            (
+                "GameRules",
+                compound(vec![
+                    ("keepInventory", Value::String("false".to_string())),
+                    ("randomTickSpeed", Value::String("3".to_string())),
+                ]),
+            ),
+            (
+                "DataPacks",
+                compound(vec![
+                    (
+                        "Enabled",
+                        Value::List(vec![
+                            Value::String("vanilla".to_string()),
+                            Value::String("file/terralith.zip".to_string()),
+                        ]),
+                    ),
+                    ("Disabled", Value::List(vec![])),
+                ]),
+            ),
+            ("Player", compound(vec![("playerGameType", Value::Int(0))])),
+        ]),
+    )]))
+}
+
+#[test]
+fn test_parse_level_dat_details() {
+    let details = parse_level_dat_details(&level_dat_1_20()).unwrap();
+    assert_eq!(details.level_name.as_deref(), Some("Survival"));
+    assert_eq!(details.data_version, Some(3465));
+    assert_eq!(details.version_name.as_deref(), Some("1.20.1"));
+    assert_eq!(details.seed, Some(-4172144997902289642));
+    assert_eq!(
+        details.spawn,
+        Some(SpawnPoint {
+            x: 16,
+            y: 70,
+            z: -32
+        })
+    );
+    assert_eq!(details.difficulty, Some(2));
+    assert_eq!(details.allow_commands, Some(false));
+    assert_eq!(details.game_rules["keepInventory"], "false");
+    assert_eq!(
+        details.enabled_datapacks,
+        vec!["vanilla", "file/terralith.zip"]
+    );
+    assert_eq!(
+        details.dimensions,
+        vec!["minecraft:overworld", "minecraft:the_nether"]
+    );
+
+    // Newer worlds: typed game rules, spawn as an int array, seed outside WorldGenSettings
+    let newer = gzip(&compound(vec![(
+        "Data",
+        compound(vec![
+            ("RandomSeed", Value::Long(42)),
+            (
+                "spawn",
+                compound(vec![(
+                    "pos",
+                    Value::IntArray(IntArray::new(vec![1, 64, 2])),
+                )]),
+            ),
+            (
+                "game_rules",
+                compound(vec![
+                    ("minecraft:keep_inventory", Value::Byte(1)),
+                    ("minecraft:random_tick_speed", Value::Int(3)),
+                ]),
+            ),
+        ]),
+    )]));
+    let details = parse_level_dat_details(&newer).unwrap();
+    assert_eq!(details.seed, Some(42));
+    assert_eq!(details.spawn, Some(SpawnPoint { x: 1, y: 64, z: 2 }));
+    assert_eq!(details.game_rules["minecraft:keep_inventory"], "true");
+    assert_eq!(details.game_rules["minecraft:random_tick_speed"], "3");
+}
+
+#[test]
+fn test_edit_level_dat() {
+    let edit = LevelDatEdit {
+        level_name: Some("  Creative Copy ".to_string()),
+        difficulty: Some(0),
+        game_type: Some(1),
+        allow_commands: Some(true),
+        game_rules: HashMap::from([
+            ("keepInventory".to_string(), "true".to_string()),
+            ("randomTickSpeed".to_string(), "10".to_string()),
+        ]),
+    };
+    let edited = apply_level_dat_edit(&level_dat_1_20(), &edit).unwrap();
+    let details = parse_level_dat_details(&edited).unwrap();
+    assert_eq!(details.level_name.as_deref(), Some("Creative Copy"));
+    assert_eq!(details.difficulty, Some(0));
+    assert_eq!(details.game_type, Some(1));
+    assert_eq!(details.allow_commands, Some(true));
+    assert_eq!(details.game_rules["keepInventory"], "true");
+    assert_eq!(details.game_rules["randomTickSpeed"], "10");
+    // Everything else is untouched
+    assert_eq!(details.seed, Some(-4172144997902289642));
+    assert_eq!(details.enabled_datapacks.len(), 2);
+
+    let invalid_edits = [
+        LevelDatEdit {
+            difficulty: Some(4),
+            ..Default::default()
+        },
+        LevelDatEdit {
+            level_name: Some(" ".to_string()),
+            ..Default::default()
+        },
+        LevelDatEdit {
+            game_rules: HashMap::from([("keepInventory".to_string(), "3".to_string())]),
+            ..Default::default()
+        },
+        LevelDatEdit {
+            game_rules: HashMap::from([("randomTickSpeed".to_string(), "fast".to_string())]),
+            ..Default::default()
+        },
+        LevelDatEdit {
+            game_rules: HashMap::from([("notARule".to_string(), "true".to_string())]),
+            ..Default::default()
+        },
+    ];
+    for edit in invalid_edits {
+        assert!(
+            apply_level_dat_edit(&level_dat_1_20(), &edit).is_err(),
+            "{:?}",
+            edit
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// src-tauri/tests/mirror_test.rs
+
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::state::config_state::{MirrorConfig, MirrorRule};
+use noriskclient_launcher_v3_lib::utils::download_utils::{DownloadOutcome, VerifiedDownload};
+use noriskclient_launcher_v3_lib::utils::hash_utils::calculate_sha1_from_bytes;
+use noriskclient_launcher_v3_lib::utils::mirror_utils::{
+    apply_mirror_config, candidate_urls, fetch_bytes, send_with_fallback,
+};
+use reqwest::Method;
+use std::time::Duration;
+
+//cargo test --package noriskclient-launcher-v3 --test mirror_test
+
+// The mirror configuration is global, so everything runs in one test.
+#[tokio::test]
+async fn test_mirror_rewriting_and_local_mirror() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mirror_root = temp_dir.path().join("mirror");
+    let mirror_url = url::Url::from_directory_path(&mirror_root)
+        .unwrap()
+        .to_string();
+
+    apply_mirror_config(&MirrorConfig {
+        rules: vec![
+            MirrorRule {
+                upstream: "https://libraries.minecraft.net/".to_string(),
+                mirrors: vec![
+                    "http://127.0.0.1:9/libraries".to_string(),
+                    format!("{}libraries/", mirror_url),
+                ],
+            },
+            MirrorRule {
+                upstream: "https://libraries.minecraft.net/org/lwjgl/".to_string(),
+                mirrors: vec!["http://lwjgl-cache.local/".to_string()],
+            },
+            MirrorRule {
+                upstream: "https://piston-meta.mojang.com/".to_string(),
+                mirrors: vec![format!("{}meta/", mirror_url)],
+            },
+        ],
+        fallback_to_upstream: false,
+    });
+
+    // Mirrors in order, no upstream fallback
+    let candidates = candidate_urls("https://libraries.minecraft.net/com/mojang/brigadier.jar");
+    assert_eq!(
+        candidates,
+        vec![
+            "http://127.0.0.1:9/libraries/com/mojang/brigadier.jar".to_string(),
+            format!("{}libraries/com/mojang/brigadier.jar", mirror_url),
+        ]
+    );
+
+    // The most specific rule wins
+    assert_eq!(
+        candidate_urls("https://libraries.minecraft.net/org/lwjgl/lwjgl.jar"),
+        vec!["http://lwjgl-cache.local/lwjgl.jar".to_string()]
+    );
+
+    // Unrelated URLs are untouched
+    assert_eq!(
+        candidate_urls("https://api.modrinth.com/v2"),
+        vec!["https://api.modrinth.com/v2".to_string()]
+    );
+
+    // Download served from the file:// mirror after the unreachable HTTP mirror failed
+    let body = b"brigadier".to_vec();
+    let mirrored = mirror_root.join("libraries/com/mojang/brigadier.jar");
+    std::fs::create_dir_all(mirrored.parent().unwrap())?;
+    std::fs::write(&mirrored, &body)?;
+
+    let target = temp_dir.path().join("libs/brigadier.jar");
+    let outcome = VerifiedDownload::new(
+        "https://libraries.minecraft.net/com/mojang/brigadier.jar",
+        &target,
+    )
+    .sha1(Some(&calculate_sha1_from_bytes(&body)))
+    .max_retries(0)
+    .initial_backoff(Duration::from_millis(10))
+    .download()
+    .await?;
+    assert!(matches!(outcome, DownloadOutcome::Downloaded { .. }));
+    assert_eq!(std::fs::read(&target)?, body);
+
+    // Metadata from the local mirror
+    std::fs::create_dir_all(mirror_root.join("meta/v1"))?;
+    std::fs::write(mirror_root.join("meta/v1/x.json"), br#"{"id":"1.21"}"#)?;
+    let bytes = fetch_bytes("https://piston-meta.mojang.com/v1/x.json").await?;
+    let meta: serde_json::Value = serde_json::from_slice(&bytes)?;
+    assert_eq!(meta["id"], "1.21");
+
+    // API requests fall back the same way, file:// mirrors only answer GET requests
+    let response = send_with_fallback(
+        Method::GET,
+        "https://libraries.minecraft.net/com/mojang/brigadier.jar",
+        |builder| builder.header("User-Agent", "mirror-test"),
+    )
+    .await?;
+    assert!(response.status().is_success());
+    assert_eq!(response.bytes().await.unwrap().to_vec(), body);
+    assert!(send_with_fallback(
+        Method::POST,
+        "https://libraries.minecraft.net/com/mojang/brigadier.jar",
+        |builder| builder.body("{}"),
+    )
+    .await
+    .is_err());
+
+    apply_mirror_config(&MirrorConfig::default());
+    Ok(())
+}
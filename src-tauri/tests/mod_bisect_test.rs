@@ -0,0 +1,181 @@
+// src-tauri/tests/mod_bisect_test.rs
+
+use async_trait::async_trait;
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::minecraft::mod_bisect::{
+    bisect, classify_run, BisectCandidate, BisectModRef, BisectRunner, BisectStatus,
+    RunObservation, RunOutcome,
+};
+
+//cargo test --package noriskclient-launcher-v3 --test mod_bisect_test
+
+fn mod_ref(name: &str) -> BisectModRef {
+    BisectModRef::Custom {
+        filename: format!("{}.jar", name),
+    }
+}
+
+fn candidate(name: &str, requires: &[&str]) -> BisectCandidate {
+    BisectCandidate {
+        mod_ref: mod_ref(name),
+        name: name.to_string(),
+        requires: requires.iter().map(|r| mod_ref(r)).collect(),
+    }
+}
+
+/// Crashes when all mods of one of the combinations are enabled.
+struct SimulatedRunner {
+    crashing_combinations: Vec<Vec<BisectModRef>>,
+    /// Every enabled set must contain the requirements of its mods
+    candidates: Vec<BisectCandidate>,
+    runs: usize,
+}
+
+#[async_trait]
+impl BisectRunner for SimulatedRunner {
+    async fn run(&mut self, enabled: &[BisectModRef]) -> Result<RunOutcome> {
+        self.runs += 1;
+        for candidate in self
+            .candidates
+            .iter()
+            .filter(|c| enabled.contains(&c.mod_ref))
+        {
+            for required in &candidate.requires {
+                assert!(
+                    enabled.contains(required),
+                    "{} enabled without {:?}",
+                    candidate.name,
+                    required
+                );
+            }
+        }
+        let crashed = self
+            .crashing_combinations
+            .iter()
+            .any(|combination| combination.iter().all(|m| enabled.contains(m)));
+        Ok(if crashed {
+            RunOutcome::Crashed
+        } else {
+            RunOutcome::Stable
+        })
+    }
+}
+
+fn pack() -> Vec<BisectCandidate> {
+    let mut candidates: Vec<BisectCandidate> = (0..40)
+        .map(|i| candidate(&format!("filler-{}", i), &[]))
+        .collect();
+    candidates.insert(7, candidate("fabric-api", &[]));
+    candidates.insert(13, candidate("sodium", &["fabric-api"]));
+    candidates.insert(21, candidate("iris", &["sodium", "fabric-api"]));
+    candidates.insert(30, candidate("optifabric", &[]));
+    candidates
+}
+
+fn names(candidates: &[BisectCandidate]) -> Vec<&str> {
+    candidates.iter().map(|c| c.name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_bisect_finds_single_mod_and_interactions() -> Result<()> {
+    let candidates = pack();
+
+    // A single mod, tested together with its dependencies
+    let mut runner = SimulatedRunner {
+        crashing_combinations: vec![vec![mod_ref("iris")]],
+        candidates: candidates.clone(),
+        runs: 0,
+    };
+    let report = bisect(&candidates, &mut runner).await?;
+    assert_eq!(report.status, BisectStatus::Found);
+    assert_eq!(names(&report.culprits), vec!["iris"]);
+    assert_eq!(names(&report.dependencies), vec!["fabric-api", "sodium"]);
+    assert_eq!(report.runs.len(), runner.runs);
+    assert!(runner.runs <= 16, "{} runs", runner.runs);
+
+    // The crash comes from a library, not from the mod that pulled it in
+    let mut runner = SimulatedRunner {
+        crashing_combinations: vec![vec![mod_ref("fabric-api")]],
+        candidates: candidates.clone(),
+        runs: 0,
+    };
+    let report = bisect(&candidates, &mut runner).await?;
+    assert_eq!(names(&report.culprits), vec!["fabric-api"]);
+
+    // Two mods that only crash together, one in each half
+    let mut runner = SimulatedRunner {
+        crashing_combinations: vec![vec![mod_ref("filler-2"), mod_ref("optifabric")]],
+        candidates: candidates.clone(),
+        runs: 0,
+    };
+    let report = bisect(&candidates, &mut runner).await?;
+    let mut culprits = names(&report.culprits);
+    culprits.sort();
+    assert_eq!(culprits, vec!["filler-2", "optifabric"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bisect_not_caused_by_mods() -> Result<()> {
+    let candidates = pack();
+
+    let mut runner = SimulatedRunner {
+        crashing_combinations: vec![],
+        candidates: candidates.clone(),
+        runs: 0,
+    };
+    let report = bisect(&candidates, &mut runner).await?;
+    assert_eq!(report.status, BisectStatus::NotReproducible);
+    assert_eq!(runner.runs, 1);
+
+    let mut runner = SimulatedRunner {
+        crashing_combinations: vec![vec![]],
+        candidates: candidates.clone(),
+        runs: 0,
+    };
+    let report = bisect(&candidates, &mut runner).await?;
+    assert_eq!(report.status, BisectStatus::CrashesWithoutMods);
+    assert!(report.culprits.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_classify_run() {
+    let observation = |exit: Option<Option<i32>>, crash_report: bool, log: &str| RunObservation {
+        exit,
+        crash_report,
+        log: log.to_string(),
+    };
+    let title_screen =
+        "[Render thread/INFO]: Loading...\n[Sound Library Loader/INFO]: Sound engine started\n";
+
+    assert_eq!(
+        classify_run(&observation(None, false, title_screen)),
+        RunOutcome::Stable
+    );
+    assert_eq!(
+        classify_run(&observation(None, false, "[main/INFO]: Loading 150 mods")),
+        RunOutcome::Inconclusive
+    );
+    // Closed by the user before the title screen
+    assert_eq!(
+        classify_run(&observation(Some(Some(0)), false, "")),
+        RunOutcome::Inconclusive
+    );
+    assert_eq!(
+        classify_run(&observation(Some(Some(-1)), false, "")),
+        RunOutcome::Crashed
+    );
+    assert_eq!(
+        classify_run(&observation(None, true, title_screen)),
+        RunOutcome::Crashed
+    );
+    let mixin_failure = format!(
+        "{}[Render thread/ERROR]: Mixin apply failed sodium.mixins.json:core.MixinWindow",
+        title_screen
+    );
+    assert_eq!(
+        classify_run(&observation(None, false, &mixin_failure)),
+        RunOutcome::Crashed
+    );
+}
@@ -0,0 +1,157 @@
+// src-tauri/tests/mod_checker_test.rs
+
+use noriskclient_launcher_v3_lib::minecraft::mod_checker::{
+    check_mods, ModCheckContext, ModFile, ModIssueKind, ModIssueSeverity,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+use noriskclient_launcher_v3_lib::utils::mod_metadata::{
+    DependencyKind, JarMetadata, ModDependency, ModEnvironment, ModMetadata, ModMetadataFormat,
+    NestedJarMetadata,
+};
+use noriskclient_launcher_v3_lib::utils::version_range::{matches, RangeSyntax};
+
+//cargo test --package noriskclient-launcher-v3 --test mod_checker_test
+
+fn fabric_mod(
+    id: &str,
+    version: &str,
+    dependencies: &[(&str, &str, DependencyKind)],
+) -> ModMetadata {
+    ModMetadata {
+        format: ModMetadataFormat::Fabric,
+        mod_id: id.to_string(),
+        name: None,
+        version: Some(version.to_string()),
+        description: None,
+        authors: Vec::new(),
+        environment: ModEnvironment::Both,
+        icon: None,
+        icon_data: None,
+        dependencies: dependencies
+            .iter()
+            .map(|(mod_id, range, kind)| ModDependency {
+                mod_id: mod_id.to_string(),
+                version_range: Some(range.to_string()),
+                kind: *kind,
+                environment: ModEnvironment::Both,
+            })
+            .collect(),
+        provides: Vec::new(),
+    }
+}
+
+fn jar(file_name: &str, mods: Vec<ModMetadata>) -> ModFile {
+    ModFile {
+        file_name: file_name.to_string(),
+        metadata: Some(JarMetadata {
+            mods,
+            nested: Vec::new(),
+        }),
+    }
+}
+
+#[test]
+fn test_version_ranges() {
+    let fabric = RangeSyntax::Fabric;
+    assert_eq!(matches(">=0.15 <0.16", "0.15.11", fabric), Some(true));
+    assert_eq!(matches("~1.20", "1.20.1", fabric), Some(true));
+    assert_eq!(matches("~1.20.1", "1.21", fabric), Some(false));
+    assert_eq!(matches("1.20.x", "1.20.4", fabric), Some(true));
+    assert_eq!(matches("1.19 || 1.20.1", "1.20.1", fabric), Some(true));
+    assert_eq!(matches(">=1.21", "1.21-pre1", fabric), Some(false));
+    assert_eq!(matches(">=1.20", "24w10a", fabric), None);
+
+    let maven = RangeSyntax::Maven;
+    assert_eq!(matches("[47,)", "47.2.0", maven), Some(true));
+    assert_eq!(matches("[1.20,1.21)", "1.21", maven), Some(false));
+    assert_eq!(matches("[20.4,)", "20.4.80-beta", maven), Some(true));
+    assert_eq!(matches("[1.0],[2.0,)", "1.0.0", maven), Some(true));
+    assert_eq!(matches("1.0", "0.1", maven), Some(true));
+}
+
+#[test]
+fn test_dependencies_and_conflicts() {
+    let context = ModCheckContext {
+        minecraft_version: "1.20.1".to_string(),
+        loader: ModLoader::Fabric,
+        loader_version: Some("0.14.21".to_string()),
+        java_major_version: Some(17),
+    };
+
+    // Bundled library satisfies the dependency
+    let mut sodium = jar(
+        "sodium.jar",
+        vec![fabric_mod(
+            "sodium",
+            "0.5.8",
+            &[
+                ("minecraft", "~1.20", DependencyKind::Required),
+                ("fabricloader", ">=0.15", DependencyKind::Required),
+                ("fabric-rendering-v1", "*", DependencyKind::Required),
+                ("optifabric", "*", DependencyKind::Incompatible),
+            ],
+        )],
+    );
+    sodium
+        .metadata
+        .as_mut()
+        .unwrap()
+        .nested
+        .push(NestedJarMetadata {
+            path: "META-INF/jars/rendering.jar".to_string(),
+            metadata: JarMetadata {
+                mods: vec![fabric_mod("fabric-rendering-v1", "3.0.8", &[])],
+                nested: Vec::new(),
+            },
+        });
+    let mods = vec![
+        sodium,
+        jar(
+            "iris.jar",
+            vec![fabric_mod(
+                "iris",
+                "1.6",
+                &[
+                    ("sodium", "0.5.x", DependencyKind::Required),
+                    ("lithium", "*", DependencyKind::Required),
+                ],
+            )],
+        ),
+        jar(
+            "optifabric.jar",
+            vec![fabric_mod("optifabric", "1.14", &[])],
+        ),
+        jar("iris-copy.jar", vec![fabric_mod("iris", "1.5", &[])]),
+        jar(
+            "jei.jar",
+            vec![ModMetadata {
+                format: ModMetadataFormat::Forge,
+                ..fabric_mod("jei", "15.2", &[])
+            }],
+        ),
+    ];
+
+    let report = check_mods(&mods, &context);
+    let kinds: Vec<(ModIssueKind, Option<&str>)> = report
+        .issues
+        .iter()
+        .map(|i| (i.kind, i.related_mod_id.as_deref().or(i.mod_id.as_deref())))
+        .collect();
+    assert!(kinds.contains(&(
+        ModIssueKind::DependencyVersionMismatch,
+        Some("fabricloader")
+    )));
+    assert!(kinds.contains(&(ModIssueKind::Incompatible, Some("optifabric"))));
+    assert!(kinds.contains(&(ModIssueKind::MissingDependency, Some("lithium"))));
+    assert!(kinds.contains(&(ModIssueKind::DuplicateMod, Some("iris"))));
+    assert!(kinds.contains(&(ModIssueKind::WrongLoader, Some("jei"))));
+    assert!(!kinds
+        .iter()
+        .any(|(_, id)| *id == Some("fabric-rendering-v1") || *id == Some("minecraft")));
+    assert_eq!(report.error_count(), 4);
+    assert!(report
+        .issues
+        .iter()
+        .any(|i| i.kind == ModIssueKind::WrongLoader && i.severity == ModIssueSeverity::Warning));
+    assert!(report.into_result().is_err());
+}
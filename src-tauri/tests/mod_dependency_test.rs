@@ -0,0 +1,277 @@
+// src-tauri/tests/mod_dependency_test.rs
+
+use noriskclient_launcher_v3_lib::error::AppError;
+use noriskclient_launcher_v3_lib::integrations::modrinth::{
+    ModrinthDependency, ModrinthDependencyType, ModrinthVersion,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::{
+    apply_modrinth_install, apply_modrinth_mod_update, check_modrinth_conflicts,
+    find_orphaned_dependencies, Mod, ModSource, ModrinthInstallCandidate, Profile,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+//cargo test --package noriskclient-launcher-v3 --test mod_dependency_test
+
+fn test_mod(name: &str, installed_as_dependency: bool, dependencies: Vec<Uuid>) -> Mod {
+    Mod {
+        id: Uuid::new_v4(),
+        source: ModSource::Local {
+            file_name: format!("{}.jar", name),
+        },
+        enabled: true,
+        display_name: Some(name.to_string()),
+        version: None,
+        game_versions: None,
+        file_name_override: None,
+        associated_loader: None,
+        installed_as_dependency,
+        dependencies,
+    }
+}
+
+#[test]
+fn test_orphaned_dependencies() {
+    // sodium-extra -> sodium -> fabric-api, iris -> sodium, and a cycle library-a <-> library-b
+    let fabric_api = test_mod("fabric-api", true, vec![]);
+    let sodium = test_mod("sodium", true, vec![fabric_api.id]);
+    let library_b = test_mod("library-b", true, vec![]);
+    let library_a = test_mod("library-a", true, vec![library_b.id]);
+    let sodium_extra = test_mod("sodium-extra", false, vec![sodium.id, library_a.id]);
+    let iris = test_mod("iris", false, vec![sodium.id]);
+    let mut library_b = library_b;
+    library_b.dependencies.push(library_a.id);
+
+    let mods = vec![
+        fabric_api.clone(),
+        sodium.clone(),
+        library_a.clone(),
+        library_b.clone(),
+        sodium_extra.clone(),
+        iris.clone(),
+    ];
+
+    // Sodium is still needed by Iris, the cyclic libraries are not
+    let mut orphaned = find_orphaned_dependencies(&mods, &[sodium_extra.id]);
+    orphaned.sort();
+    let mut expected = vec![library_a.id, library_b.id];
+    expected.sort();
+    assert_eq!(orphaned, expected);
+
+    // Removing both leaves the whole chain unused
+    let orphaned = find_orphaned_dependencies(&mods, &[sodium_extra.id, iris.id]);
+    assert_eq!(orphaned.len(), 4);
+    assert!(orphaned.contains(&fabric_api.id));
+
+    // Explicitly installed mods are never orphaned
+    let mut mods = mods;
+    mods[0].installed_as_dependency = false;
+    let orphaned = find_orphaned_dependencies(&mods, &[sodium_extra.id, iris.id]);
+    assert!(!orphaned.contains(&fabric_api.id));
+    assert!(orphaned.contains(&sodium.id));
+}
+
+#[test]
+fn test_incompatible_dependency_matching() {
+    let incompatible = |project_id: Option<&str>, version_id: Option<&str>| ModrinthDependency {
+        version_id: version_id.map(str::to_string),
+        project_id: project_id.map(str::to_string),
+        file_name: None,
+        dependency_type: ModrinthDependencyType::Incompatible,
+    };
+
+    let whole_project = incompatible(Some("AANobbMI"), None);
+    assert!(whole_project.excludes("AANobbMI", "any-version"));
+    assert!(!whole_project.excludes("P7dR8mSH", "any-version"));
+
+    // A version_id only excludes that exact version
+    let single_version = incompatible(Some("AANobbMI"), Some("tFw0iWAk"));
+    assert!(single_version.excludes("AANobbMI", "tFw0iWAk"));
+    assert!(!single_version.excludes("AANobbMI", "newer"));
+
+    let required = ModrinthDependency {
+        dependency_type: ModrinthDependencyType::Required,
+        ..whole_project
+    };
+    assert!(!required.excludes("AANobbMI", "any-version"));
+}
+
+/// Version with one primary file, `dependencies` as (type, project id)
+fn modrinth_version(
+    project_id: &str,
+    version_id: &str,
+    dependencies: &[(&str, &str)],
+) -> ModrinthVersion {
+    serde_json::from_value(serde_json::json!({
+        "id": version_id,
+        "project_id": project_id,
+        "author_id": null,
+        "featured": false,
+        "name": project_id,
+        "version_number": version_id,
+        "changelog": null,
+        "dependencies": dependencies
+            .iter()
+            .map(|(dependency_type, project)| serde_json::json!({
+                "version_id": null,
+                "project_id": project,
+                "file_name": null,
+                "dependency_type": dependency_type,
+            }))
+            .collect::<Vec<_>>(),
+        "game_versions": ["1.21.4"],
+        "version_type": "release",
+        "loaders": ["fabric"],
+        "files": [{
+            "hashes": {"sha512": null, "sha1": format!("{}-sha1", version_id)},
+            "url": format!("https://cdn.modrinth.com/{}.jar", version_id),
+            "filename": format!("{}.jar", version_id),
+            "primary": true,
+            "size": 1,
+            "file_type": null,
+        }],
+        "date_published": "2025-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+fn installed_mod(version: &ModrinthVersion, installed_as_dependency: bool) -> Mod {
+    let candidate = ModrinthInstallCandidate::from_version(version).unwrap();
+    Mod {
+        source: ModSource::Modrinth {
+            project_id: candidate.project_id,
+            version_id: candidate.version_id,
+            file_name: candidate.file_name,
+            download_url: candidate.download_url,
+            file_hash_sha1: candidate.file_hash_sha1,
+        },
+        ..test_mod(&version.project_id, installed_as_dependency, vec![])
+    }
+}
+
+fn profile_with(mods: Vec<Mod>) -> Profile {
+    let mut profile: Profile = serde_json::from_value(serde_json::json!({
+        "name": "Dependencies",
+        "path": "dependencies",
+        "game_version": "1.21.4",
+        "loader": "fabric",
+        "loader_version": null,
+        "last_played": null,
+        "description": null,
+        "norisk_information": null,
+    }))
+    .unwrap();
+    profile.mods = mods;
+    profile
+}
+
+fn mods_json(profile: &Profile) -> serde_json::Value {
+    serde_json::to_value(&profile.mods).unwrap()
+}
+
+#[test]
+fn test_rejected_install_leaves_profile_unchanged() {
+    // Installed sodium declares optifabric incompatible
+    let sodium = modrinth_version("sodium", "sodium-1", &[("incompatible", "optifabric")]);
+    let installed_versions = HashMap::from([("sodium-1-sha1".to_string(), sodium.clone())]);
+    let mut profile = profile_with(vec![installed_mod(&sodium, false)]);
+    let before = mods_json(&profile);
+
+    // The picked mod itself is fine, its dependency is not
+    let picked = modrinth_version("shaders", "shaders-1", &[("required", "optifabric")]);
+    let optifabric = modrinth_version("optifabric", "optifabric-1", &[]);
+    let mut dependency = ModrinthInstallCandidate::from_version(&optifabric).unwrap();
+    dependency.required_by.push(0);
+    let candidates = vec![
+        ModrinthInstallCandidate::from_version(&picked).unwrap(),
+        dependency,
+    ];
+    match check_modrinth_conflicts(&profile, &candidates, &installed_versions) {
+        Err(AppError::ModIncompatible {
+            mod_name,
+            conflicts,
+            ..
+        }) => {
+            assert_eq!(mod_name, "optifabric");
+            assert_eq!(conflicts, "sodium");
+        }
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+    assert_eq!(mods_json(&profile), before);
+
+    // Two versions of the same install that exclude each other
+    let breaks_picked = modrinth_version("old-lib", "old-lib-1", &[("incompatible", "iris")]);
+    let iris = modrinth_version("iris", "iris-1", &[("required", "old-lib")]);
+    let mut dependency = ModrinthInstallCandidate::from_version(&breaks_picked).unwrap();
+    dependency.required_by.push(0);
+    let candidates = vec![
+        ModrinthInstallCandidate::from_version(&iris).unwrap(),
+        dependency,
+    ];
+    assert!(check_modrinth_conflicts(&profile, &candidates, &installed_versions).is_err());
+
+    // A compatible tree is added in one go and linked
+    let fabric_api = modrinth_version("fabric-api", "fabric-api-1", &[]);
+    let mut dependency = ModrinthInstallCandidate::from_version(&fabric_api).unwrap();
+    dependency.required_by.push(0);
+    let candidates = vec![
+        ModrinthInstallCandidate::from_version(&iris).unwrap(),
+        dependency,
+    ];
+    check_modrinth_conflicts(&profile, &candidates, &installed_versions).unwrap();
+    assert!(apply_modrinth_install(&mut profile, &candidates, None));
+    assert_eq!(profile.mods.len(), 3);
+    let iris_mod = &profile.mods[1];
+    let fabric_api_mod = &profile.mods[2];
+    assert!(!iris_mod.installed_as_dependency);
+    assert!(fabric_api_mod.installed_as_dependency);
+    assert_eq!(iris_mod.dependencies, vec![fabric_api_mod.id]);
+
+    // Installing the same tree again changes nothing
+    assert!(!apply_modrinth_install(&mut profile, &candidates, None));
+}
+
+#[test]
+fn test_update_to_incompatible_version_is_rejected() {
+    let fabric_api = modrinth_version("fabric-api", "fabric-api-1", &[]);
+    let iris = modrinth_version("iris", "iris-1", &[]);
+    let sodium = modrinth_version("sodium", "sodium-1", &[]);
+    let mut profile = profile_with(vec![
+        installed_mod(&fabric_api, false),
+        installed_mod(&iris, false),
+        installed_mod(&sodium, false),
+    ]);
+    let sodium_id = profile.mods[2].id;
+    let before = mods_json(&profile);
+
+    // The new version excludes a mod in the profile
+    let breaking = modrinth_version("sodium", "sodium-2", &[("incompatible", "iris")]);
+    let result = apply_modrinth_mod_update(&mut profile, sodium_id, &breaking, &HashMap::new());
+    assert!(matches!(result, Err(AppError::ModIncompatible { .. })));
+    assert_eq!(mods_json(&profile), before);
+
+    // A mod in the profile excludes the new version
+    let iris_breaks_sodium = modrinth_version("iris", "iris-1", &[("incompatible", "sodium")]);
+    let installed_versions = HashMap::from([("iris-1-sha1".to_string(), iris_breaks_sodium)]);
+    let compatible = modrinth_version(
+        "sodium",
+        "sodium-3",
+        &[("required", "fabric-api"), ("required", "lithium")],
+    );
+    let result =
+        apply_modrinth_mod_update(&mut profile, sodium_id, &compatible, &installed_versions);
+    assert!(matches!(result, Err(AppError::ModIncompatible { .. })));
+    assert_eq!(mods_json(&profile), before);
+
+    // Dependencies already in the profile are linked, the others are returned
+    let missing =
+        apply_modrinth_mod_update(&mut profile, sodium_id, &compatible, &HashMap::new()).unwrap();
+    assert_eq!(missing, vec![("lithium".to_string(), None)]);
+    let updated = &profile.mods[2];
+    assert_eq!(updated.version.as_deref(), Some("sodium-3"));
+    assert!(matches!(
+        &updated.source,
+        ModSource::Modrinth { version_id, .. } if version_id == "sodium-3"
+    ));
+    assert_eq!(updated.dependencies, vec![profile.mods[0].id]);
+}
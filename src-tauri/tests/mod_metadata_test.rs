@@ -0,0 +1,170 @@
+// src-tauri/tests/mod_metadata_test.rs
+
+use noriskclient_launcher_v3_lib::error::{AppError, Result};
+use noriskclient_launcher_v3_lib::state::profile_state::{fill_mod_from_metadata, Mod, ModLoader};
+use noriskclient_launcher_v3_lib::utils::mod_metadata::{
+    read_jar_metadata, read_jar_metadata_from_bytes, DependencyKind, ModEnvironment,
+    ModMetadataFormat,
+};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+//cargo test --package noriskclient-launcher-v3 --test mod_metadata_test
+
+fn build_jar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        writer
+            .start_file(*name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_fabric_with_nested_jar() -> Result<()> {
+    let nested = build_jar(&[(
+        "fabric.mod.json",
+        br#"{"schemaVersion":1,"id":"fabric-api-base","version":"0.4.31"}"#,
+    )]);
+    // Raw line break in the description, which loaders tolerate
+    let fabric_json =
+        "{\"schemaVersion\":1,\"id\":\"sodium\",\"version\":\"0.5.8\",\"name\":\"Sodium\",
+        \"description\":\"Fast\nrendering\",\"authors\":[\"JellySquid\",{\"name\":\"IMS\"}],
+        \"environment\":\"client\",\"icon\":{\"16\":\"small.png\",\"128\":\"big.png\"},
+        \"depends\":{\"fabricloader\":\">=0.12\",\"minecraft\":[\"1.20\",\"1.20.1\"]},
+        \"breaks\":{\"optifabric\":\"*\"},\"jars\":[{\"file\":\"META-INF/jars/base.jar\"}]}";
+    let jar = build_jar(&[
+        ("fabric.mod.json", fabric_json.as_bytes()),
+        ("big.png", b"png"),
+        ("META-INF/jars/base.jar", &nested),
+    ]);
+
+    let metadata = read_jar_metadata_from_bytes(&jar, true)?;
+    let sodium = metadata.primary_for_loader(&ModLoader::Quilt).unwrap();
+    assert_eq!(sodium.format, ModMetadataFormat::Fabric);
+    assert_eq!(sodium.display_name(), "Sodium");
+    assert_eq!(sodium.authors, vec!["JellySquid", "IMS"]);
+    assert_eq!(sodium.environment, ModEnvironment::Client);
+    assert_eq!(sodium.icon.as_deref(), Some("big.png"));
+    assert_eq!(sodium.icon_data.as_deref(), Some("cG5n"));
+
+    let minecraft = sodium
+        .dependencies
+        .iter()
+        .find(|d| d.mod_id == "minecraft")
+        .unwrap();
+    assert_eq!(minecraft.version_range.as_deref(), Some("1.20 || 1.20.1"));
+    let optifabric = sodium
+        .dependencies
+        .iter()
+        .find(|d| d.mod_id == "optifabric")
+        .unwrap();
+    assert_eq!(optifabric.kind, DependencyKind::Incompatible);
+    assert_eq!(optifabric.version_range, None);
+
+    assert_eq!(metadata.nested.len(), 1);
+    assert_eq!(metadata.nested[0].path, "META-INF/jars/base.jar");
+    assert_eq!(metadata.all_mods()[1].mod_id, "fabric-api-base");
+    Ok(())
+}
+
+#[test]
+fn test_neoforge_mods_toml_and_mcmod_info() -> Result<()> {
+    let mods_toml = br#"
+modLoader="javafml"
+loaderVersion="[1,)"
+license="MIT"
+authors="Someone"
+[[mods]]
+modId="examplemod"
+version="${file.jarVersion}"
+displayName="Example Mod"
+logoFile="logo.png"
+[[dependencies.examplemod]]
+modId="neoforge"
+type="required"
+versionRange="[20.4,)"
+side="BOTH"
+[[dependencies.examplemod]]
+modId="jei"
+type="optional"
+side="CLIENT"
+"#;
+    let jar = build_jar(&[
+        ("META-INF/mods.toml", mods_toml),
+        (
+            "META-INF/MANIFEST.MF",
+            b"Manifest-Version: 1.0\r\nImplementation-Version: 1.2.3\r\n",
+        ),
+    ]);
+    let metadata = read_jar_metadata_from_bytes(&jar, false)?;
+    let example = metadata.primary_for_loader(&ModLoader::NeoForge).unwrap();
+    assert_eq!(example.format, ModMetadataFormat::NeoForge);
+    assert_eq!(example.version.as_deref(), Some("1.2.3"));
+    assert_eq!(example.authors, vec!["Someone"]);
+    assert_eq!(example.environment, ModEnvironment::Unknown);
+    assert_eq!(example.dependencies[0].kind, DependencyKind::Required);
+    assert_eq!(example.dependencies[1].kind, DependencyKind::Optional);
+    assert_eq!(example.dependencies[1].environment, ModEnvironment::Client);
+
+    let mcmod_info = br#"{"modListVersion":2,"modList":[{"modid":"oldmod","name":"Old Mod",
+        "version":"${version}","authorList":["Notch"],"requiredMods":["Forge@[10.13,)"],
+        "dependencies":["Forge","codechickencore"]}]}"#;
+    let jar = build_jar(&[("mcmod.info", mcmod_info)]);
+    let metadata = read_jar_metadata_from_bytes(&jar, false)?;
+    let old_mod = &metadata.mods[0];
+    assert_eq!(old_mod.format, ModMetadataFormat::LegacyForge);
+    assert_eq!(old_mod.version, None);
+    assert_eq!(old_mod.dependencies.len(), 2);
+    assert_eq!(
+        old_mod.dependencies[0].version_range.as_deref(),
+        Some("[10.13,)")
+    );
+    assert_eq!(old_mod.dependencies[1].kind, DependencyKind::Optional);
+
+    // Jars without metadata are not an error
+    let plain = build_jar(&[("com/example/Library.class", b"")]);
+    assert!(read_jar_metadata_from_bytes(&plain, false)?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fill_mod_from_jar_file() -> Result<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let jar_path = dir.path().join("custom-1.0.jar");
+    std::fs::write(
+        &jar_path,
+        build_jar(&[(
+            "fabric.mod.json",
+            br#"{"schemaVersion":1,"id":"custom","version":"1.0.2","name":"Custom Mod"}"#,
+        )]),
+    )?;
+    let metadata = read_jar_metadata(&jar_path, false).await?;
+    let custom = metadata.primary_for_loader(&ModLoader::Fabric).unwrap();
+
+    let mut url_mod: Mod = serde_json::from_value(serde_json::json!({
+        "source": {"type": "url", "url": "https://example.com/custom-1.0.jar", "file_name": "custom-1.0.jar"},
+        "enabled": true,
+        "display_name": null,
+        "version": null,
+        "game_versions": null,
+        "file_name_override": null,
+        "associated_loader": null,
+    }))?;
+    assert!(fill_mod_from_metadata(&mut url_mod, custom));
+    assert_eq!(url_mod.display_name.as_deref(), Some("Custom Mod"));
+    assert_eq!(url_mod.version.as_deref(), Some("1.0.2"));
+    // Names that are already set are kept
+    url_mod.display_name = Some("Renamed".to_string());
+    assert!(!fill_mod_from_metadata(&mut url_mod, custom));
+    assert_eq!(url_mod.display_name.as_deref(), Some("Renamed"));
+
+    assert!(matches!(
+        read_jar_metadata(&dir.path().join("missing.jar"), false).await,
+        Err(AppError::FileNotFound(_))
+    ));
+    Ok(())
+}
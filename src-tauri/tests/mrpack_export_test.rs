@@ -0,0 +1,90 @@
+// src-tauri/tests/mrpack_export_test.rs
+
+use noriskclient_launcher_v3_lib::integrations::mrpack::{
+    hash_mrpack_file, process_mrpack, write_mrpack, ModrinthIndex, ModrinthIndexFile,
+    FABRIC_LOADER_DEPENDENCY, MINECRAFT_DEPENDENCY,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+use noriskclient_launcher_v3_lib::utils::hash_utils::calculate_sha1_from_bytes;
+use std::collections::HashMap;
+use std::io::Read;
+
+//cargo test --package noriskclient-launcher-v3 --test mrpack_export_test
+
+#[tokio::test]
+async fn test_mrpack_export_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let jar_bytes = b"PK\x03\x04 not really a jar".to_vec();
+    let jar_path = dir.path().join("sodium-0.5.8.jar");
+    std::fs::write(&jar_path, &jar_bytes).unwrap();
+    let config_path = dir.path().join("options.txt");
+    std::fs::write(&config_path, "renderDistance:12\n").unwrap();
+
+    // The index describes the file on disk
+    let (hashes, file_size) = hash_mrpack_file(&jar_path).await.unwrap();
+    assert_eq!(hashes["sha1"], calculate_sha1_from_bytes(&jar_bytes));
+    assert_eq!(hashes["sha512"].len(), 128);
+    assert_eq!(file_size, jar_bytes.len() as u64);
+
+    let mut dependencies = HashMap::new();
+    dependencies.insert(MINECRAFT_DEPENDENCY.to_string(), "1.20.1".to_string());
+    dependencies.insert(FABRIC_LOADER_DEPENDENCY.to_string(), "0.15.11".to_string());
+    let manifest = ModrinthIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: "1.0.0".to_string(),
+        name: "Round Trip".to_string(),
+        summary: Some("Exported in a test".to_string()),
+        files: vec![ModrinthIndexFile {
+            path: "mods/sodium-0.5.8.jar".to_string(),
+            hashes: hashes.clone(),
+            env: None,
+            downloads: vec!["https://cdn.modrinth.com/data/AANobbMI/sodium.jar".to_string()],
+            file_size,
+        }],
+        dependencies,
+    };
+    let pack_path = dir.path().join("export").join("Round Trip.mrpack");
+    std::fs::create_dir_all(pack_path.parent().unwrap()).unwrap();
+    write_mrpack(
+        &pack_path,
+        &manifest,
+        vec![
+            (config_path.clone(), "options.txt".to_string()),
+            // Duplicates are only written once
+            (config_path, "options.txt".to_string()),
+            (jar_path, "mods/extra.jar.disabled".to_string()),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let (profile, imported) = process_mrpack(pack_path.clone()).await.unwrap();
+    assert_eq!(profile.name, "Round Trip");
+    assert_eq!(profile.game_version, "1.20.1");
+    assert_eq!(profile.loader, ModLoader::Fabric);
+    assert_eq!(profile.loader_version.as_deref(), Some("0.15.11"));
+    assert_eq!(imported.files.len(), 1);
+    assert_eq!(imported.files[0].path, "mods/sodium-0.5.8.jar");
+    assert_eq!(imported.files[0].hashes, hashes);
+    assert_eq!(imported.files[0].file_size, file_size);
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&pack_path).unwrap()).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "modrinth.index.json",
+            "overrides/mods/extra.jar.disabled",
+            "overrides/options.txt"
+        ]
+    );
+    let mut options = String::new();
+    archive
+        .by_name("overrides/options.txt")
+        .unwrap()
+        .read_to_string(&mut options)
+        .unwrap();
+    assert_eq!(options, "renderDistance:12\n");
+}
@@ -0,0 +1,76 @@
+// src-tauri/tests/offline_test.rs
+
+use noriskclient_launcher_v3_lib::error::{AppError, Result};
+use noriskclient_launcher_v3_lib::utils::offline_utils::{fetch_json_cached, set_offline_mode};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+//cargo test --package noriskclient-launcher-v3 --test offline_test
+
+/// Answers every request with `body` as JSON.
+async fn start_json_server(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+// The offline flag is global, so everything runs in one test.
+#[tokio::test]
+async fn test_metadata_cache_and_offline_mode() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let cache_path = temp_dir.path().join("metadata_cache/version_manifest.json");
+    let unreachable = "http://127.0.0.1:9/version_manifest.json";
+
+    // Nothing cached and no network
+    assert!(
+        fetch_json_cached::<serde_json::Value>(unreachable, &cache_path)
+            .await
+            .is_err()
+    );
+
+    // Online fetch persists the response
+    let base_url = start_json_server(r#"{"latest":"1.21"}"#).await;
+    let url = format!("{}/version_manifest.json", base_url);
+    let online: serde_json::Value = fetch_json_cached(&url, &cache_path).await?;
+    assert_eq!(online["latest"], "1.21");
+    assert!(cache_path.exists());
+
+    // Network failure falls back to the cached copy
+    let fallback: serde_json::Value = fetch_json_cached(unreachable, &cache_path).await?;
+    assert_eq!(fallback, online);
+
+    // A broken response neither fails nor replaces the cached copy
+    let broken_base_url = start_json_server("<html>").await;
+    let broken_url = format!("{}/version_manifest.json", broken_base_url);
+    let fallback: serde_json::Value = fetch_json_cached(&broken_url, &cache_path).await?;
+    assert_eq!(fallback, online);
+    assert_eq!(std::fs::read(&cache_path)?, br#"{"latest":"1.21"}"#);
+
+    // Offline mode never touches the network
+    set_offline_mode(true);
+    let offline: serde_json::Value = fetch_json_cached(&url, &cache_path).await?;
+    assert_eq!(offline, online);
+
+    let missing = temp_dir.path().join("metadata_cache/piston_meta/1.21.json");
+    let result = fetch_json_cached::<serde_json::Value>(&url, &missing).await;
+    set_offline_mode(false);
+    assert!(matches!(result, Err(AppError::OfflineUnavailable(_))));
+
+    Ok(())
+}
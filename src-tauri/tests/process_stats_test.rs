@@ -0,0 +1,82 @@
+// src-tauri/tests/process_stats_test.rs
+
+use chrono::{Duration, Utc};
+use noriskclient_launcher_v3_lib::utils::process_stats_utils::{
+    parse_max_heap_mb, ProcessSample, ProcessStatsWindow, ResourceWarning,
+};
+
+//cargo test --package noriskclient-launcher-v3 --test process_stats_test
+
+const MB: u64 = 1024 * 1024;
+const GB: u64 = 1024 * MB;
+
+fn sample(
+    seconds: i64,
+    memory_mb: u64,
+    cpu: f32,
+    available_gb: u64,
+    swap_mb: u64,
+) -> ProcessSample {
+    ProcessSample {
+        timestamp: Utc::now() + Duration::seconds(seconds),
+        memory_bytes: memory_mb * MB,
+        virtual_memory_bytes: 2 * memory_mb * MB,
+        cpu_percent: cpu,
+        thread_count: Some(40 + seconds as u32),
+        disk_read_bytes: MB,
+        disk_written_bytes: 0,
+        system_available_memory_bytes: available_gb * GB,
+        system_used_swap_bytes: swap_mb * MB,
+    }
+}
+
+#[test]
+fn test_parse_max_heap() {
+    assert_eq!(
+        parse_max_heap_mb(["-Xms1G", "-Xmx4G", "-jar", "client.jar"]),
+        Some(4096)
+    );
+    // The JVM uses the last -Xmx
+    assert_eq!(parse_max_heap_mb(["-Xmx2048M", "-Xmx3072m"]), Some(3072));
+    assert_eq!(parse_max_heap_mb(["-Xms512M", "--version"]), None);
+}
+
+#[test]
+fn test_stats_window() {
+    let mut window = ProcessStatsWindow::new(3, Some(7168), 8 * GB);
+    window.push(sample(0, 1000, 50.0, 4, 100));
+    window.push(sample(5, 3000, 150.0, 2, 100));
+    window.push(sample(10, 2000, 100.0, 2, 100));
+    window.push(sample(15, 2000, 100.0, 2, 100));
+
+    // Only the last samples are kept, the totals cover the whole run
+    let stats = window.stats();
+    assert_eq!(stats.samples.len(), 3);
+    assert_eq!(stats.samples[0].memory_bytes, 3000 * MB);
+    let summary = stats.summary;
+    assert_eq!(summary.sample_count, 4);
+    assert_eq!(summary.avg_memory_bytes, 2000 * MB);
+    assert_eq!(summary.peak_memory_bytes, 3000 * MB);
+    assert_eq!(summary.avg_cpu_percent, 100.0);
+    assert_eq!(summary.peak_cpu_percent, 150.0);
+    assert_eq!(summary.peak_thread_count, Some(55));
+    assert_eq!(summary.disk_read_bytes, 4 * MB);
+    // 7 GB heap on an 8 GB system, but no swapping yet
+    assert_eq!(
+        summary.warnings,
+        vec![ResourceWarning::HeapTooLarge {
+            max_heap_mb: 7168,
+            total_memory_mb: 8192
+        }]
+    );
+
+    // Memory runs out and the system starts swapping
+    window.push(sample(20, 2500, 100.0, 0, 900));
+    assert!(window
+        .summary()
+        .warnings
+        .contains(&ResourceWarning::Swapping {
+            swap_growth_bytes: 800 * MB,
+            min_available_memory_bytes: 0
+        }));
+}
@@ -0,0 +1,134 @@
+// src-tauri/tests/profile_verifier_test.rs
+
+use noriskclient_launcher_v3_lib::minecraft::downloads::mod_resolver::TargetMod;
+use noriskclient_launcher_v3_lib::minecraft::profile_verifier::{
+    find_broken_cached_mods, repair_profile_mods, VerifyProblem,
+};
+use noriskclient_launcher_v3_lib::state::content_store_state::ContentStoreManager;
+use noriskclient_launcher_v3_lib::utils::hash_utils::calculate_sha1_from_bytes;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+//cargo test --package noriskclient-launcher-v3 --test profile_verifier_test
+
+fn jar_bytes(content: &str) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("fabric.mod.json", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(content.as_bytes()).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+fn target(cache_dir: &Path, filename: &str) -> TargetMod {
+    TargetMod {
+        mod_id: format!("modrinth:{}", filename),
+        filename: filename.to_string(),
+        cache_path: cache_dir.join(filename),
+    }
+}
+
+#[tokio::test]
+async fn test_find_broken_cached_mods() {
+    let dir = tempfile::tempdir().unwrap();
+    let sodium = target(dir.path(), "sodium.jar");
+    let lithium = target(dir.path(), "lithium.jar");
+    let custom = target(dir.path(), "custom.jar");
+    let broken_custom = target(dir.path(), "broken-custom.jar");
+    let missing = target(dir.path(), "missing.jar");
+
+    let sodium_bytes = jar_bytes("sodium");
+    fs::write(&sodium.cache_path, &sodium_bytes).unwrap();
+    // Right size, wrong content
+    let mut lithium_bytes = jar_bytes("lithium");
+    fs::write(&lithium.cache_path, &lithium_bytes).unwrap();
+    let lithium_sha1 = calculate_sha1_from_bytes(&lithium_bytes);
+    lithium_bytes[0] ^= 0xff;
+    fs::write(&lithium.cache_path, &lithium_bytes).unwrap();
+    // Without a known hash only a readable jar counts
+    fs::write(&custom.cache_path, jar_bytes("custom")).unwrap();
+    fs::write(&broken_custom.cache_path, b"truncated").unwrap();
+
+    let known_hashes = HashMap::from([
+        (
+            "sodium.jar".to_string(),
+            calculate_sha1_from_bytes(&sodium_bytes),
+        ),
+        ("lithium.jar".to_string(), lithium_sha1.clone()),
+    ]);
+    let targets = vec![
+        sodium,
+        lithium.clone(),
+        custom,
+        broken_custom.clone(),
+        missing.clone(),
+    ];
+
+    let broken = find_broken_cached_mods(&targets, &known_hashes).await;
+    assert_eq!(
+        broken,
+        vec![
+            (
+                lithium.cache_path,
+                Some(lithium_sha1),
+                VerifyProblem::Corrupt
+            ),
+            (broken_custom.cache_path, None, VerifyProblem::Corrupt),
+            (missing.cache_path, None, VerifyProblem::Missing),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_repair_profile_mods() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ContentStoreManager::new(dir.path().join("store")).unwrap();
+    let cache_dir = dir.path().join("mod_cache");
+    let mods_dir = dir.path().join("profile").join("mods");
+    fs::create_dir_all(&cache_dir).unwrap();
+
+    let sodium = target(&cache_dir, "sodium.jar");
+    let iris = target(&cache_dir, "iris.jar");
+    let not_linked = target(&cache_dir, "lithium.jar");
+    let sodium_bytes = jar_bytes("sodium");
+    let iris_bytes = jar_bytes("iris");
+    fs::write(&sodium.cache_path, &sodium_bytes).unwrap();
+    fs::write(&iris.cache_path, &iris_bytes).unwrap();
+    fs::write(&not_linked.cache_path, jar_bytes("lithium")).unwrap();
+    store
+        .link_files(&[
+            (sodium.cache_path.clone(), mods_dir.join("sodium.jar")),
+            (iris.cache_path.clone(), mods_dir.join("iris.jar")),
+        ])
+        .await
+        .unwrap();
+
+    // Written in place through the hardlink, the cache file is then re-downloaded by the
+    // cache check while the blob and the profile file stay broken
+    fs::write(mods_dir.join("sodium.jar"), b"corrupt").unwrap();
+    fs::remove_file(&sodium.cache_path).unwrap();
+    fs::write(&sodium.cache_path, &sodium_bytes).unwrap();
+
+    let known_hashes = HashMap::from([(
+        "sodium.jar".to_string(),
+        calculate_sha1_from_bytes(&sodium_bytes),
+    )]);
+    let targets = vec![sodium, iris, not_linked];
+    let (checked, repairs) = repair_profile_mods(&store, &targets, &mods_dir, &known_hashes).await;
+
+    // The mod that is not linked yet is left to the next launch
+    assert_eq!(checked, 2);
+    assert_eq!(
+        repairs,
+        vec![(mods_dir.join("sodium.jar"), VerifyProblem::Corrupt, None)]
+    );
+    assert_eq!(fs::read(mods_dir.join("sodium.jar")).unwrap(), sodium_bytes);
+    assert_eq!(fs::read(mods_dir.join("iris.jar")).unwrap(), iris_bytes);
+    let blob = store.blob_path(&calculate_sha1_from_bytes(&sodium_bytes));
+    assert_eq!(fs::read(blob).unwrap(), sodium_bytes);
+
+    // Nothing left to repair
+    let (_, repairs) = repair_profile_mods(&store, &targets, &mods_dir, &known_hashes).await;
+    assert!(repairs.is_empty());
+}
@@ -0,0 +1,62 @@
+// src-tauri/tests/server_monitor_test.rs
+
+use chrono::{Duration, Utc};
+use noriskclient_launcher_v3_lib::state::server_monitor_state::{ServerHistory, ServerSample};
+use noriskclient_launcher_v3_lib::utils::server_ping::parse_legacy_response;
+
+//cargo test --package noriskclient-launcher-v3 --test server_monitor_test
+
+fn sample(minutes_ago: i64, online: bool) -> ServerSample {
+    ServerSample {
+        timestamp: Utc::now() - Duration::minutes(minutes_ago),
+        online,
+        latency_ms: online.then_some(40),
+        players_online: online.then_some(3),
+        players_max: online.then_some(20),
+        error: (!online).then(|| "Connection refused".to_string()),
+    }
+}
+
+#[test]
+fn test_parse_legacy_response() {
+    // 1.4 - 1.6
+    let status =
+        parse_legacy_response("\u{a7}1\u{0}78\u{0}1.6.4\u{0}A Minecraft Server\u{0}5\u{0}20")
+            .unwrap();
+    assert!(status.legacy);
+    let version = status.version.unwrap();
+    assert_eq!((version.name.as_str(), version.protocol), ("1.6.4", 78));
+    let players = status.players.unwrap();
+    assert_eq!((players.online, players.max), (5, 20));
+    assert_eq!(status.description.unwrap().get(), "\"A Minecraft Server\"");
+
+    // Beta 1.8 - 1.3, the MOTD may contain § itself
+    let status = parse_legacy_response("\u{a7}aGreen MOTD\u{a7}2\u{a7}10").unwrap();
+    assert!(status.version.is_none());
+    let players = status.players.unwrap();
+    assert_eq!((players.online, players.max), (2, 10));
+    assert_eq!(status.description.unwrap().get(), "\"\u{a7}aGreen MOTD\"");
+
+    assert!(parse_legacy_response("\u{a7}1\u{0}78\u{0}1.6.4").is_err());
+    assert!(parse_legacy_response("Kicked for spamming").is_err());
+}
+
+#[test]
+fn test_server_history() {
+    let mut history = ServerHistory::default();
+    // The first known state is not a change
+    assert_eq!(history.record(sample(10, true)), None);
+    assert_eq!(history.online, Some(true));
+
+    // A single failed poll does not take the server down, the second one does
+    assert_eq!(history.record(sample(9, false)), None);
+    assert_eq!(history.online, Some(true));
+    assert_eq!(history.record(sample(8, false)), Some(false));
+    assert_eq!(history.record(sample(7, false)), None);
+    assert_eq!(history.record(sample(6, true)), Some(true));
+    assert_eq!(history.uptime(), Some(0.4));
+
+    history.prune(Utc::now() - Duration::minutes(8) - Duration::seconds(30));
+    assert_eq!(history.samples.len(), 3);
+    assert_eq!(history.online, Some(true));
+}
@@ -0,0 +1,110 @@
+// src-tauri/tests/server_profile_test.rs
+
+use noriskclient_launcher_v3_lib::minecraft::server_installer::{
+    find_forge_launch_target, ServerLaunchTarget,
+};
+use noriskclient_launcher_v3_lib::state::profile_state::ModLoader;
+use noriskclient_launcher_v3_lib::utils::server_properties_utils::{
+    accept_eula, is_eula_accepted, read_server_properties, write_server_properties,
+    ServerProperties,
+};
+use std::path::{Path, PathBuf};
+
+//cargo test --package noriskclient-launcher-v3 --test server_profile_test
+
+#[tokio::test]
+async fn test_server_properties_round_trip_and_eula() {
+    let content = "#Minecraft server properties\n#Sat Jan 04 12:00:00 CET 2025\nmotd=\\u00A7aNoRisk \\: Test\nserver-port=25570\n\nmax-players=20\n";
+    let mut properties = ServerProperties::parse(content);
+    assert_eq!(properties.get("motd"), Some("§aNoRisk : Test"));
+    assert_eq!(properties.server_port(), 25570);
+    // Unchanged files are written back as they were read
+    assert_eq!(properties.to_string(), content.replace("\\:", ":"));
+
+    properties.set("max-players", "5");
+    properties.set("white-list", "true");
+    let written = properties.to_string();
+    assert!(written.starts_with("#Minecraft server properties\n#Sat Jan 04"));
+    assert!(written.contains("motd=\\u00A7aNoRisk : Test\nserver-port=25570\n\nmax-players=5\n"));
+    assert!(written.ends_with("white-list=true\n"));
+    assert_eq!(ServerProperties::parse(&written).entries().len(), 4);
+    assert_eq!(
+        ServerProperties::parse("server-port=abc").server_port(),
+        25565
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    assert!(read_server_properties(dir.path())
+        .await
+        .unwrap()
+        .entries()
+        .is_empty());
+    write_server_properties(dir.path(), &properties)
+        .await
+        .unwrap();
+    let read = read_server_properties(dir.path()).await.unwrap();
+    assert_eq!(read.get("white-list"), Some("true"));
+
+    assert!(!is_eula_accepted(dir.path()).await.unwrap());
+    std::fs::write(dir.path().join("eula.txt"), "eula=false\n").unwrap();
+    assert!(!is_eula_accepted(dir.path()).await.unwrap());
+    accept_eula(dir.path()).await.unwrap();
+    assert!(is_eula_accepted(dir.path()).await.unwrap());
+}
+
+fn touch(path: &Path) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, b"").unwrap();
+}
+
+#[tokio::test]
+async fn test_find_forge_launch_target() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        find_forge_launch_target(dir.path(), ModLoader::Forge, "1.16.5-36.2.39")
+            .await
+            .unwrap(),
+        None
+    );
+
+    // Before 1.17 the installer leaves a server jar next to itself
+    touch(&dir.path().join("forge-1.16.5-36.2.39-installer.jar"));
+    touch(&dir.path().join("forge-1.16.5-36.2.39.jar"));
+    let target = find_forge_launch_target(dir.path(), ModLoader::Forge, "1.16.5-36.2.39")
+        .await
+        .unwrap();
+    assert_eq!(
+        target,
+        Some(ServerLaunchTarget::Jar(PathBuf::from(
+            "forge-1.16.5-36.2.39.jar"
+        )))
+    );
+    assert_eq!(
+        target.unwrap().java_args(),
+        vec!["-jar".to_string(), "forge-1.16.5-36.2.39.jar".to_string()]
+    );
+
+    // Newer versions start through an args file in the libraries
+    let args_file =
+        PathBuf::from("libraries/net/neoforged/neoforge/21.1.77").join(if cfg!(windows) {
+            "win_args.txt"
+        } else {
+            "unix_args.txt"
+        });
+    touch(&dir.path().join(&args_file));
+    let target = find_forge_launch_target(dir.path(), ModLoader::NeoForge, "21.1.77")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(target, ServerLaunchTarget::ArgsFile(args_file.clone()));
+    assert_eq!(
+        target.java_args(),
+        vec![format!("@{}", args_file.to_string_lossy())]
+    );
+    assert_eq!(
+        find_forge_launch_target(dir.path(), ModLoader::Fabric, "21.1.77")
+            .await
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,155 @@
+// src-tauri/tests/servers_dat_test.rs
+
+use fastnbt::Value;
+use noriskclient_launcher_v3_lib::state::favorite_servers_state::FavoriteServer;
+use noriskclient_launcher_v3_lib::utils::servers_dat_utils::{
+    add_server, apply_favorites, edit_server, move_server, parse_servers_dat, remove_server,
+    server_list, write_servers_dat, FavoriteSyncChanges, ServerEntryEdit,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+//cargo test --package noriskclient-launcher-v3 --test servers_dat_test
+
+fn entry(name: &str, ip: &str, extra: Vec<(&str, Value)>) -> Value {
+    let mut entry: HashMap<String, Value> =
+        extra.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+    entry.insert("name".to_string(), Value::String(name.to_string()));
+    entry.insert("ip".to_string(), Value::String(ip.to_string()));
+    Value::Compound(entry)
+}
+
+fn servers_dat(entries: Vec<Value>) -> Vec<u8> {
+    fastnbt::to_bytes(&Value::Compound(HashMap::from([(
+        "servers".to_string(),
+        Value::List(entries),
+    )])))
+    .unwrap()
+}
+
+fn addresses(root: &Value) -> Vec<String> {
+    server_list(root)
+        .into_iter()
+        .map(|s| s.address.unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn test_edit_server_list() {
+    let bytes = servers_dat(vec![
+        entry(
+            "Hypixel",
+            "mc.hypixel.net",
+            vec![
+                ("icon", Value::String("iVBORw0KGgo=".to_string())),
+                ("hidden", Value::Byte(0)),
+                ("someModTag", Value::Int(7)),
+            ],
+        ),
+        entry("Local", "localhost:25566", vec![]),
+    ]);
+    let mut root = parse_servers_dat(&bytes).unwrap();
+    assert_eq!(addresses(&root), vec!["mc.hypixel.net", "localhost:25566"]);
+
+    assert_eq!(
+        add_server(&mut root, " Friends ", "friends.example.org", Some(0)).unwrap(),
+        0
+    );
+    assert!(add_server(&mut root, "Broken", "example.org:notaport", None).is_err());
+    edit_server(
+        &mut root,
+        1,
+        &ServerEntryEdit {
+            name: Some("Hypixel Network".to_string()),
+            accepts_textures: Some(1),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    move_server(&mut root, 2, 0).unwrap();
+    remove_server(&mut root, 1).unwrap();
+    assert!(remove_server(&mut root, 5).is_err());
+
+    // Written back as uncompressed NBT with icons and unknown tags intact
+    let reparsed = parse_servers_dat(&write_servers_dat(&root).unwrap()).unwrap();
+    assert_eq!(reparsed, root);
+    let servers = server_list(&reparsed);
+    assert_eq!(
+        addresses(&reparsed),
+        vec!["localhost:25566", "mc.hypixel.net"]
+    );
+    assert_eq!(servers[1].name.as_deref(), Some("Hypixel Network"));
+    assert_eq!(servers[1].icon_base64.as_deref(), Some("iVBORw0KGgo="));
+    assert_eq!(servers[1].accepts_textures, Some(1));
+    let Value::Compound(root) = &reparsed else {
+        unreachable!()
+    };
+    let Some(Value::List(entries)) = root.get("servers") else {
+        unreachable!()
+    };
+    let Value::Compound(hypixel) = &entries[1] else {
+        unreachable!()
+    };
+    assert_eq!(hypixel.get("someModTag"), Some(&Value::Int(7)));
+
+    // A missing or empty servers.dat is an empty list
+    assert!(server_list(&parse_servers_dat(&[]).unwrap()).is_empty());
+}
+
+#[test]
+fn test_apply_favorites() {
+    let community = FavoriteServer {
+        id: Uuid::new_v4(),
+        name: "Community".to_string(),
+        address: "play.community.net".to_string(),
+        previous_addresses: vec!["old.community.net".to_string()],
+        icon_base64: None,
+        auto_sync: true,
+        added_at: chrono::Utc::now(),
+    };
+    let events = FavoriteServer {
+        id: Uuid::new_v4(),
+        name: "Events".to_string(),
+        address: "events.community.net".to_string(),
+        previous_addresses: Vec::new(),
+        icon_base64: None,
+        auto_sync: false,
+        added_at: chrono::Utc::now(),
+    };
+    let favorites = [community, events];
+
+    // The old address is updated in place, the missing favorite is added on top
+    let mut root = parse_servers_dat(&servers_dat(vec![
+        entry("Survival", "survival.example.org", vec![]),
+        entry(
+            "Our server",
+            "Old.Community.net:25565",
+            vec![("icon", Value::String("aWNvbg==".to_string()))],
+        ),
+    ]))
+    .unwrap();
+    assert_eq!(
+        apply_favorites(&mut root, &favorites).unwrap(),
+        FavoriteSyncChanges {
+            added: 1,
+            updated: 1
+        }
+    );
+    let servers = server_list(&root);
+    assert_eq!(
+        addresses(&root),
+        vec![
+            "events.community.net",
+            "survival.example.org",
+            "play.community.net"
+        ]
+    );
+    assert_eq!(servers[2].name.as_deref(), Some("Community"));
+    assert_eq!(servers[2].icon_base64.as_deref(), Some("aWNvbg=="));
+
+    // Syncing again changes nothing
+    assert_eq!(
+        apply_favorites(&mut root, &favorites).unwrap(),
+        FavoriteSyncChanges::default()
+    );
+}
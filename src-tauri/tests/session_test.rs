@@ -0,0 +1,130 @@
+// src-tauri/tests/session_test.rs
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use noriskclient_launcher_v3_lib::state::session_state::{
+    aggregate_sessions, GameSession, SessionFilter,
+};
+use uuid::Uuid;
+
+//cargo test --package noriskclient-launcher-v3 --test session_test
+
+fn session(
+    profile_id: Uuid,
+    account: &str,
+    pack_version: &str,
+    started_at: DateTime<Utc>,
+    minutes: Option<i64>,
+    crashed: bool,
+) -> GameSession {
+    let mut session = GameSession {
+        id: Uuid::new_v4(),
+        profile_id,
+        profile_name: Some(format!("Profile {}", &profile_id.to_string()[..4])),
+        account_uuid: Some(account.to_string()),
+        account_name: Some(format!("{}_name", account)),
+        minecraft_version: Some("1.21.4".to_string()),
+        modloader: Some("fabric".to_string()),
+        modloader_version: None,
+        norisk_pack: Some("norisk-prod".to_string()),
+        norisk_pack_version: Some(pack_version.to_string()),
+        started_at,
+        ended_at: None,
+        duration_secs: None,
+        exit_code: None,
+        crashed: false,
+        exit_unknown: false,
+    };
+    if let Some(minutes) = minutes {
+        let exit_code = if crashed { Some(1) } else { Some(0) };
+        session.finish(started_at + Duration::minutes(minutes), exit_code, crashed);
+    }
+    session
+}
+
+#[test]
+fn test_playtime_by_profile_account_and_week() {
+    let profile_a = Uuid::new_v4();
+    let profile_b = Uuid::new_v4();
+    // Wednesdays at noon, so the week does not depend on the local time zone
+    let week_3 = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+    let week_4 = Utc.with_ymd_and_hms(2025, 1, 22, 12, 0, 0).unwrap();
+    let sessions = vec![
+        session(profile_a, "acc1", "v1", week_3, Some(30), false),
+        session(profile_b, "acc2", "v1", week_3, Some(90), false),
+        session(profile_a, "acc2", "v1", week_4, Some(10), false),
+        // Still running, counts up to now
+        session(profile_a, "acc1", "v1", week_4, None, false),
+    ];
+    let now = week_4 + Duration::minutes(20);
+
+    let stats = aggregate_sessions(&sessions, now);
+    assert_eq!(stats.sessions, 4);
+    assert_eq!(stats.total_secs, (30 + 90 + 10 + 20) * 60);
+
+    assert_eq!(stats.by_profile.len(), 2);
+    assert_eq!(stats.by_profile[0].key, profile_b.to_string());
+    assert_eq!(stats.by_profile[0].total_secs, 90 * 60);
+    assert_eq!(stats.by_profile[1].sessions, 3);
+    assert_eq!(stats.by_profile[1].total_secs, 60 * 60);
+
+    let acc2 = stats.by_account.iter().find(|e| e.key == "acc2").unwrap();
+    assert_eq!(acc2.total_secs, 100 * 60);
+    assert_eq!(acc2.name.as_deref(), Some("acc2_name"));
+
+    let weeks: Vec<(&str, u64)> = stats
+        .by_week
+        .iter()
+        .map(|e| (e.key.as_str(), e.total_secs))
+        .collect();
+    assert_eq!(weeks, vec![("2025-W03", 120 * 60), ("2025-W04", 30 * 60)]);
+
+    // An exit the launcher did not see adds no playtime
+    let mut abandoned = session(profile_b, "acc2", "v1", week_4, None, false);
+    abandoned.exit_unknown = true;
+    assert_eq!(abandoned.played_secs(now), 0);
+}
+
+#[test]
+fn test_crash_rate_per_pack_version_and_filter() {
+    let profile = Uuid::new_v4();
+    let start = Utc.with_ymd_and_hms(2025, 2, 5, 12, 0, 0).unwrap();
+    let mut unknown_exit = session(profile, "acc1", "v2", start, Some(5), false);
+    unknown_exit.exit_unknown = true;
+    let sessions = vec![
+        session(profile, "acc1", "v1", start, Some(60), false),
+        session(profile, "acc1", "v1", start, Some(60), false),
+        session(profile, "acc1", "v2", start, Some(1), true),
+        session(profile, "acc1", "v2", start, Some(30), false),
+        session(profile, "acc1", "v2", start, None, false),
+        unknown_exit,
+    ];
+
+    let stats = aggregate_sessions(&sessions, start + Duration::hours(1));
+    assert_eq!(stats.by_pack_version.len(), 2);
+    let v1 = &stats.by_pack_version[0];
+    assert_eq!(v1.norisk_pack_version.as_deref(), Some("v1"));
+    assert_eq!((v1.sessions, v1.crashes), (2, 0));
+    assert_eq!(v1.crash_rate, 0.0);
+    // Running sessions and unknown exits are left out of the crash rate
+    let v2 = &stats.by_pack_version[1];
+    assert_eq!((v2.sessions, v2.crashes), (2, 1));
+    assert_eq!(v2.crash_rate, 0.5);
+
+    let filter = SessionFilter {
+        profile_id: Some(profile),
+        since: Some(start),
+        until: Some(start + Duration::days(1)),
+        ..Default::default()
+    };
+    assert!(filter.matches(&sessions[0]));
+    let other_account = SessionFilter {
+        account_uuid: Some("acc2".to_string()),
+        ..Default::default()
+    };
+    assert!(!other_account.matches(&sessions[0]));
+    let later = SessionFilter {
+        since: Some(start + Duration::seconds(1)),
+        ..Default::default()
+    };
+    assert!(!later.matches(&sessions[0]));
+}
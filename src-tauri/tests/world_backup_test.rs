@@ -0,0 +1,183 @@
+// src-tauri/tests/world_backup_test.rs
+
+use chrono::{Duration, TimeZone, Utc};
+use noriskclient_launcher_v3_lib::error::Result;
+use noriskclient_launcher_v3_lib::utils::world_backup_utils::{
+    create_backup, delete_backup, list_backups, remove_unused_chunks, restore_backup,
+    select_expired_backups, BackupFormat, BackupRetention, BackupTrigger, WorldBackup,
+};
+use std::fs;
+use std::path::Path;
+
+//cargo test --package noriskclient-launcher-v3 --test world_backup_test
+
+fn write_world(world: &Path) {
+    fs::create_dir_all(world.join("region")).unwrap();
+    fs::create_dir_all(world.join("datapacks")).unwrap();
+    fs::write(world.join("level.dat"), b"level data").unwrap();
+    fs::write(world.join("session.lock"), b"").unwrap();
+    // Larger than a chunk, with a repeating pattern
+    let region: Vec<u8> = (0..300_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(world.join("region").join("r.0.0.mca"), region).unwrap();
+}
+
+fn assert_same_world(original: &Path, restored: &Path) {
+    for file in ["level.dat", "region/r.0.0.mca"] {
+        assert_eq!(
+            fs::read(original.join(file)).unwrap(),
+            fs::read(restored.join(file)).unwrap(),
+            "{} differs",
+            file
+        );
+    }
+    assert!(restored.join("datapacks").is_dir());
+    assert!(!restored.join("session.lock").exists());
+}
+
+#[test]
+fn test_archive_backup_roundtrip() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let world = dir.path().join("saves").join("My World");
+    let backups = dir.path().join("backups");
+    write_world(&world);
+
+    let backup = create_backup(
+        &world,
+        &backups,
+        BackupFormat::Archive,
+        BackupTrigger::Manual,
+        false,
+    )?;
+    assert_eq!(backup.world_folder, "My World");
+    assert_eq!(backup.file_count, 2);
+    assert!(backup.stored_size_bytes < backup.world_size_bytes);
+
+    let listed = list_backups(&backups)?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, backup.id);
+
+    let restored = dir.path().join("saves").join("Restored");
+    restore_backup(&backups, &backup.id, &restored)?;
+    assert_same_world(&world, &restored);
+    // Never restores over an existing world
+    assert!(restore_backup(&backups, &backup.id, &restored).is_err());
+    assert!(restore_backup(&backups, "../escape", &dir.path().join("x")).is_err());
+
+    delete_backup(&backups, &backup.id)?;
+    assert!(list_backups(&backups)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_incremental_backups_share_chunks() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let world = dir.path().join("world");
+    let backups = dir.path().join("backups");
+    write_world(&world);
+
+    let first = create_backup(
+        &world,
+        &backups,
+        BackupFormat::Incremental,
+        BackupTrigger::GameExit,
+        false,
+    )?;
+    let second = create_backup(
+        &world,
+        &backups,
+        BackupFormat::Incremental,
+        BackupTrigger::GameExit,
+        false,
+    )?;
+    // Only the manifest of the unchanged world is new
+    assert!(
+        second.stored_size_bytes < 2048,
+        "{} bytes stored",
+        second.stored_size_bytes
+    );
+
+    fs::write(world.join("level.dat"), b"changed level data").unwrap();
+    let third = create_backup(
+        &world,
+        &backups,
+        BackupFormat::Incremental,
+        BackupTrigger::GameExit,
+        false,
+    )?;
+
+    // Deleting a backup keeps the chunks the others still use
+    delete_backup(&backups, &first.id)?;
+    let restored = dir.path().join("restored");
+    restore_backup(&backups, &second.id, &restored)?;
+    assert_eq!(fs::read(restored.join("level.dat"))?, b"level data");
+    let restored_third = dir.path().join("restored_third");
+    restore_backup(&backups, &third.id, &restored_third)?;
+    assert_same_world(&world, &restored_third);
+
+    delete_backup(&backups, &second.id)?;
+    delete_backup(&backups, &third.id)?;
+    assert_eq!(remove_unused_chunks(&backups)?, 0);
+    let chunk_files = fs::read_dir(backups.join("chunks"))?
+        .flat_map(|shard| fs::read_dir(shard.unwrap().path()).unwrap())
+        .count();
+    assert_eq!(chunk_files, 0);
+    Ok(())
+}
+
+#[test]
+fn test_retention_selection() {
+    let start = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+    let backup = |id: &str, minutes: i64, trigger: BackupTrigger| WorldBackup {
+        id: id.to_string(),
+        world_folder: "world".to_string(),
+        created_at: start + Duration::minutes(minutes),
+        trigger,
+        format: BackupFormat::Archive,
+        file_count: 1,
+        world_size_bytes: 1,
+        stored_size_bytes: 1,
+        taken_while_running: false,
+    };
+    // Three backups around noon for 30 days, plus a manual one at the start. Days are local,
+    // noon UTC keeps the backups of a day on the same local day in every timezone.
+    let mut backups = vec![backup("manual", 0, BackupTrigger::Manual)];
+    for day in 0..30 {
+        for slot in 0..3 {
+            backups.push(backup(
+                &format!("d{:02}-{}", day, slot),
+                day * 24 * 60 + slot * 20,
+                BackupTrigger::Scheduled,
+            ));
+        }
+    }
+
+    let retention = BackupRetention {
+        keep_last: 2,
+        keep_daily: 3,
+        keep_weekly: 0,
+    };
+    let expired = select_expired_backups(&backups, &retention);
+    let kept: Vec<&str> = backups
+        .iter()
+        .map(|b| b.id.as_str())
+        .filter(|id| !expired.iter().any(|e| e == id))
+        .collect();
+    assert_eq!(kept, vec!["manual", "d27-2", "d28-2", "d29-1", "d29-2"]);
+
+    // Weekly keeps the newest of each week, nothing at all still keeps the newest backup
+    let weekly = BackupRetention {
+        keep_last: 0,
+        keep_daily: 0,
+        keep_weekly: 2,
+    };
+    let expired = select_expired_backups(&backups, &weekly);
+    assert_eq!(expired.len(), 90 - 2);
+    let nothing = BackupRetention {
+        keep_last: 0,
+        keep_daily: 0,
+        keep_weekly: 0,
+    };
+    let expired = select_expired_backups(&backups, &nothing);
+    assert!(!expired.contains(&"d29-2".to_string()));
+    assert_eq!(expired.len(), 89);
+}
@@ -0,0 +1,214 @@
+// src-tauri/tests/world_map_test.rs
+
+mod common;
+
+use common::compound;
+use fastnbt::{LongArray, Value};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use noriskclient_launcher_v3_lib::utils::world_map_utils::{
+    block_color, parse_region_file_name, render_region, update_map_tiles, WorldMapTile,
+};
+use std::io::Write;
+use std::path::Path;
+
+//cargo test --package noriskclient-launcher-v3 --test world_map_test
+
+/// Packs values the way 1.16+ does: no value spans two longs.
+fn pack(values: &[u64], bits: usize) -> LongArray {
+    let per_long = 64 / bits;
+    let longs = values
+        .chunks(per_long)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u64, |long, (i, v)| long | (v << (i * bits))) as i64
+        })
+        .collect();
+    LongArray::new(longs)
+}
+
+/// A 1.18+ chunk: stone at y=64, above it grass in the east half and water in the west half.
+fn chunk(status: &str) -> Value {
+    let palette = ["air", "grass_block", "stone", "water"]
+        .iter()
+        .map(|name| compound(vec![("Name", Value::String(format!("minecraft:{}", name)))]))
+        .collect();
+    let mut blocks = vec![0u64; 4096];
+    for z in 0..16 {
+        for x in 0..16 {
+            blocks[z * 16 + x] = 2;
+            blocks[256 + z * 16 + x] = if x < 8 { 3 } else { 1 };
+        }
+    }
+    // Heightmap values count from the bottom of the world (-64) plus one
+    let heightmap = vec![65 + 64 + 1; 256];
+    compound(vec![
+        ("DataVersion", Value::Int(3955)),
+        ("yPos", Value::Int(-4)),
+        ("Status", Value::String(status.to_string())),
+        (
+            "sections",
+            Value::List(vec![compound(vec![
+                ("Y", Value::Byte(4)),
+                (
+                    "block_states",
+                    compound(vec![
+                        ("palette", Value::List(palette)),
+                        ("data", Value::LongArray(pack(&blocks, 4))),
+                    ]),
+                ),
+            ])]),
+        ),
+        (
+            "Heightmaps",
+            compound(vec![(
+                "WORLD_SURFACE",
+                Value::LongArray(pack(&heightmap, 9)),
+            )]),
+        ),
+    ])
+}
+
+/// Writes a region file with the given chunks (index in region, chunk NBT).
+fn write_region(path: &Path, chunks: &[(usize, Value)]) {
+    let mut header = vec![0u8; 8192];
+    let mut body = Vec::new();
+    for (index, chunk) in chunks {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&fastnbt::to_bytes(chunk).unwrap())
+            .unwrap();
+        let compressed = encoder.finish().unwrap();
+        let sector = 2 + body.len() / 4096;
+        let mut record = ((compressed.len() + 1) as u32).to_be_bytes().to_vec();
+        record.push(2);
+        record.extend(compressed);
+        record.resize(record.len().div_ceil(4096) * 4096, 0);
+        header[index * 4..index * 4 + 4].copy_from_slice(&[
+            (sector >> 16) as u8,
+            (sector >> 8) as u8,
+            sector as u8,
+            (record.len() / 4096) as u8,
+        ]);
+        body.extend(record);
+    }
+    header.extend(body);
+    std::fs::write(path, header).unwrap();
+}
+
+#[test]
+fn test_render_region() {
+    assert_eq!(parse_region_file_name("r.-1.2.mca"), Some((-1, 2)));
+    assert_eq!(parse_region_file_name("r.0.0.mcr"), None);
+    assert_eq!(block_color("minecraft:cave_air"), None);
+    assert_eq!(block_color("minecraft:wall_torch"), None);
+    assert_eq!(
+        block_color("minecraft:oak_stairs"),
+        block_color("minecraft:oak_planks")
+    );
+    assert_eq!(
+        block_color("minecraft:light_blue_wool"),
+        block_color("minecraft:light_blue_concrete")
+    );
+    assert_ne!(
+        block_color("minecraft:light_blue_wool"),
+        block_color("minecraft:blue_wool")
+    );
+    assert!(block_color("somemod:magic_leaves").is_some());
+
+    let dir = tempfile::tempdir().unwrap();
+    let region = dir.path().join("r.0.0.mca");
+    // Chunk 0,0 is finished, chunk 1,0 is still being generated
+    write_region(
+        &region,
+        &[
+            (0, chunk("minecraft:full")),
+            (1, chunk("minecraft:features")),
+        ],
+    );
+    let tile = render_region(&region, false).unwrap().unwrap();
+    assert_eq!(tile.dimensions(), (512, 512));
+    assert_eq!(
+        tile.get_pixel(12, 5).0[..3],
+        block_color("minecraft:grass_block").unwrap()
+    );
+    let water = tile.get_pixel(3, 5).0;
+    assert_eq!(water[3], 255);
+    assert!(water[2] > water[0] + 40, "{:?}", water);
+    assert_eq!(tile.get_pixel(20, 5).0[3], 0);
+    assert_eq!(tile.get_pixel(300, 300).0[3], 0);
+
+    // Nothing renderable at all
+    write_region(&region, &[(0, chunk("minecraft:features"))]);
+    assert!(render_region(&region, false).unwrap().is_none());
+}
+
+#[test]
+fn test_map_tile_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let region_dir = dir.path().join("region");
+    let cache_dir = dir.path().join("cache");
+    std::fs::create_dir_all(&region_dir).unwrap();
+    write_region(&region_dir.join("r.0.0.mca"), &[(0, chunk("full"))]);
+    let tile_keys = |tiles: &[WorldMapTile]| {
+        let mut keys: Vec<(u8, i32, i32)> = tiles.iter().map(|t| (t.zoom, t.x, t.z)).collect();
+        keys.sort();
+        keys
+    };
+    let no_progress = |_: usize, _: usize| {};
+
+    let update = update_map_tiles(&region_dir, &cache_dir, false, &no_progress).unwrap();
+    assert_eq!((update.rendered_regions, update.cached_regions), (1, 0));
+    assert_eq!(
+        tile_keys(&update.tiles),
+        vec![(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0)]
+    );
+    assert!(update.tiles.iter().all(|t| t.path.is_file()));
+
+    // Nothing changed: everything comes from the cache
+    let update = update_map_tiles(&region_dir, &cache_dir, false, &no_progress).unwrap();
+    assert_eq!((update.rendered_regions, update.cached_regions), (0, 1));
+
+    // A new region only renders that region and the tiles above it
+    write_region(&region_dir.join("r.-1.0.mca"), &[(0, chunk("full"))]);
+    let update = update_map_tiles(&region_dir, &cache_dir, false, &no_progress).unwrap();
+    assert_eq!((update.rendered_regions, update.cached_regions), (1, 1));
+    assert_eq!(
+        tile_keys(&update.tiles),
+        vec![
+            (0, -1, 0),
+            (0, 0, 0),
+            (1, -1, 0),
+            (1, 0, 0),
+            (2, -1, 0),
+            (2, 0, 0),
+            (3, -1, 0),
+            (3, 0, 0)
+        ]
+    );
+
+    // A changed region is rendered again
+    write_region(
+        &region_dir.join("r.0.0.mca"),
+        &[(0, chunk("full")), (1, chunk("full"))],
+    );
+    let update = update_map_tiles(&region_dir, &cache_dir, false, &no_progress).unwrap();
+    assert_eq!((update.rendered_regions, update.cached_regions), (1, 1));
+    let tile = update
+        .tiles
+        .iter()
+        .find(|t| (t.zoom, t.x, t.z) == (0, 0, 0))
+        .map(|t| image::open(&t.path).unwrap().into_rgba8())
+        .unwrap();
+    assert_eq!(tile.get_pixel(20, 5).0[3], 255);
+
+    // Removed regions lose their tiles
+    std::fs::remove_file(region_dir.join("r.-1.0.mca")).unwrap();
+    let update = update_map_tiles(&region_dir, &cache_dir, false, &no_progress).unwrap();
+    assert_eq!(
+        tile_keys(&update.tiles),
+        vec![(0, 0, 0), (1, 0, 0), (2, 0, 0), (3, 0, 0)]
+    );
+}
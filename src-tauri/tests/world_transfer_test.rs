@@ -0,0 +1,141 @@
+// src-tauri/tests/world_transfer_test.rs
+
+use fastnbt::Value;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use noriskclient_launcher_v3_lib::utils::world_transfer_utils::{
+    check_compatibility, find_world_root, parse_level_dat, release_data_version, write_world_zip,
+    WorldCompatibilityWarning,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+
+//cargo test --package noriskclient-launcher-v3 --test world_transfer_test
+
+fn level_dat(data_version: i32, dimensions: &[&str]) -> Vec<u8> {
+    let dimensions: HashMap<String, Value> = dimensions
+        .iter()
+        .map(|id| (id.to_string(), Value::Compound(HashMap::new())))
+        .collect();
+    let data = HashMap::from([
+        (
+            "LevelName".to_string(),
+            Value::String("Survival".to_string()),
+        ),
+        ("DataVersion".to_string(), Value::Int(data_version)),
+        (
+            "Version".to_string(),
+            Value::Compound(HashMap::from([(
+                "Name".to_string(),
+                Value::String("1.21.1".to_string()),
+            )])),
+        ),
+        (
+            "WorldGenSettings".to_string(),
+            Value::Compound(HashMap::from([(
+                "dimensions".to_string(),
+                Value::Compound(dimensions),
+            )])),
+        ),
+    ]);
+    let root = Value::Compound(HashMap::from([("Data".to_string(), Value::Compound(data))]));
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&fastnbt::to_bytes(&root).unwrap())
+        .unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_level_dat_and_compatibility() {
+    let info = parse_level_dat(&level_dat(
+        3955,
+        &[
+            "minecraft:overworld",
+            "minecraft:the_nether",
+            "twilightforest:twilight_forest",
+        ],
+    ))
+    .unwrap();
+    assert_eq!(info.level_name.as_deref(), Some("Survival"));
+    assert_eq!(info.data_version, Some(3955));
+    assert_eq!(info.version_name.as_deref(), Some("1.21.1"));
+    assert_eq!(info.mod_dimensions, vec!["twilightforest:twilight_forest"]);
+
+    // Same version, mod installed
+    let mods: HashSet<String> = ["twilightforest".to_string()].into();
+    assert!(
+        check_compatibility(&info, "1.21.1", release_data_version("1.21.1"), Some(&mods))
+            .is_empty()
+    );
+
+    // Older target without the mod
+    let warnings = check_compatibility(
+        &info,
+        "1.20.1",
+        release_data_version("1.20.1"),
+        Some(&HashSet::new()),
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            WorldCompatibilityWarning::Downgrade {
+                world_data_version: 3955,
+                world_version_name: Some("1.21.1".to_string()),
+                target_version: "1.20.1".to_string(),
+                target_data_version: 3465,
+            },
+            WorldCompatibilityWarning::MissingModDimensions {
+                dimensions: vec!["twilightforest:twilight_forest".to_string()],
+                mod_ids: vec!["twilightforest".to_string()],
+            },
+        ]
+    );
+
+    // Unknown target version or mods: nothing to warn about
+    assert!(check_compatibility(&info, "24w14a", None, None).is_empty());
+}
+
+#[test]
+fn test_world_zip_layout() {
+    assert_eq!(
+        find_world_root(["level.dat", "region/r.0.0.mca"]).as_deref(),
+        Some("")
+    );
+    // Nested top folders, a backup copy of level.dat deeper down and macOS metadata
+    assert_eq!(
+        find_world_root([
+            "__MACOSX/level.dat",
+            "Downloads/",
+            "Downloads/My World/level.dat",
+            "Downloads/My World/region/r.0.0.mca",
+            "Downloads/My World/backup/old/level.dat",
+        ])
+        .as_deref(),
+        Some("Downloads/My World/")
+    );
+    assert_eq!(find_world_root(["readme.txt", "mods/sodium.jar"]), None);
+
+    let dir = tempfile::tempdir().unwrap();
+    let world = dir.path().join("My World");
+    std::fs::create_dir_all(world.join("region")).unwrap();
+    std::fs::write(world.join("level.dat"), level_dat(3955, &[])).unwrap();
+    std::fs::write(world.join("session.lock"), b"").unwrap();
+    std::fs::write(world.join("region").join("r.0.0.mca"), b"chunks").unwrap();
+
+    let mut buffer = Cursor::new(Vec::new());
+    write_world_zip(&world, "My World", &mut buffer).unwrap();
+    let archive = zip::ZipArchive::new(Cursor::new(buffer.into_inner())).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "My World/",
+            "My World/level.dat",
+            "My World/region/",
+            "My World/region/r.0.0.mca"
+        ]
+    );
+    assert_eq!(find_world_root(names).as_deref(), Some("My World/"));
+}
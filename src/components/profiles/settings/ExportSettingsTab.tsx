@@ -1,8 +1,9 @@
 "use client";
 
 import React, { useEffect, useRef, useState } from "react";
-import type { Profile } from "../../../types/profile"; // Adjust path as needed
+import type { ExportFormat, Profile } from "../../../types/profile"; // Adjust path as needed
 import { Input } from "../../ui/Input";
+import { Select } from "../../ui/Select";
 import { Checkbox } from "../../ui/Checkbox";
 import { Button } from "../../ui/buttons/Button";
 import { Card } from "../../ui/Card";
@@ -43,6 +44,7 @@ export function ExportSettingsTab({
   const selectedExportPathsRef = useRef<Set<string>>(selectedExportPaths);
 
   const [exportOpenFolder, setExportOpenFolder] = useState(true);
+  const [exportFormat, setExportFormat] = useState<ExportFormat>("noriskpack");
 
   const [directoryStructure, setDirectoryStructure] = useState<FileNode | null>(
     null,
@@ -133,6 +135,7 @@ export function ExportSettingsTab({
           ? Array.from(currentPathsForExport) // Use the value from the ref
           : undefined,
       open_folder: exportOpenFolder,
+      format: exportFormat,
     });
 
     toast.promise(exportPromise, {
@@ -188,6 +191,7 @@ export function ExportSettingsTab({
     isLoadingDirectory,
     exportOpenFolder,
     setExportOpenFolder,
+    exportFormat,
   ]);
 
   return (
@@ -228,7 +232,31 @@ export function ExportSettingsTab({
             variant="flat"
           />
           <p className="mt-1 text-xs text-white/50 font-minecraft-ten tracking-wide">
-            The .noriskpack extension will be added automatically.
+            The .{exportFormat} extension will be added automatically.
+          </p>
+        </div>
+
+        {/* Format section */}
+        <div className="space-y-1">
+          <label
+            className="block text-2xl text-white font-minecraft mb-2 lowercase"
+          >
+            Export Format
+          </label>
+          <Select
+            value={exportFormat}
+            onChange={(value) => setExportFormat(value as ExportFormat)}
+            options={[
+              { value: "noriskpack", label: "NoRisk Pack (.noriskpack)" },
+              { value: "mrpack", label: "Modrinth Pack (.mrpack)" },
+            ]}
+            disabled={isExporting}
+            variant="flat"
+          />
+          <p className="mt-1 text-xs text-white/50 font-minecraft-ten tracking-wide">
+            {exportFormat === "mrpack"
+              ? "Mods are referenced by their download URL, mods without one are skipped."
+              : "Can be imported by the NoRisk launcher only."}
           </p>
         </div>
 
@@ -146,6 +146,8 @@ export function SettingsTab() {
           pre_launch: null,
           wrapper: null,
           post_exit: null,
+          pre_launch_timeout_secs: null,
+          abort_on_pre_launch_failure: true,
         },
       };
       setConfig(configWithHooks);
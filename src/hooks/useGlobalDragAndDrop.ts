@@ -65,8 +65,12 @@ export function useGlobalDragAndDrop() {
               console.log(`[DragDrop Hook ${instanceId}] Cleared pathKey from cache: ${pathKey}`);
             }, PROCESS_COOLDOWN_MS);
 
+            // A .zip dropped onto a content area is a resource/shader/data pack, anywhere else a CurseForge modpack
+            const dropTarget = useAppDragDropStore.getState();
+            const isContentDropActive = !!(dropTarget.activeDropProfileId && dropTarget.activeDropContentType);
             const profilePackPath = droppedPaths.find(path => 
-              path.toLowerCase().endsWith('.noriskpack') || path.toLowerCase().endsWith('.mrpack')
+              path.toLowerCase().endsWith('.noriskpack') || path.toLowerCase().endsWith('.mrpack') ||
+              (!isContentDropActive && path.toLowerCase().endsWith('.zip'))
             );
 
             if (profilePackPath) {
@@ -161,7 +165,7 @@ export function useGlobalDragAndDrop() {
                 toast(`No files matching expected types (${expectedExtensions.join(', ')}) for ${itemTypeName} were dropped.`);
               }
             } else {
-              toast('Drop files onto an active profile content area to import them, or drop a .noriskpack/.mrpack file anywhere to import a profile.');
+              toast('Drop files onto an active profile content area to import them, or drop a .noriskpack/.mrpack/CurseForge .zip file anywhere to import a profile.');
             }
           } else if (payload.type === 'cancel') {
             console.log(`[DragDrop Hook ${instanceId}] File drop cancelled at ${eventTimestamp}`);
@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 // Import the actual type with corrected path
-import type { ProcessMetadata, CrashlogDto } from "../types/processState";
+import type { ProcessMetadata, CrashlogDto, ProcessStats, ProcessStatsRecord, LaunchLogInfo, GameSession, SessionFilter, SessionAggregates } from "../types/processState";
+import type { CrashAnalysis } from "../types/crashAnalysis";
 
 export async function isMinecraftRunning(profileId: string): Promise<boolean> {
   try {
@@ -108,3 +109,63 @@ export async function submitCrashLog(payload: CrashlogDto): Promise<void> {
     throw error; // Re-throw or handle as needed
   }
 }
+
+/**
+ * Analyzes the last run of a profile for known crash causes.
+ */
+export async function analyzeProfileCrash(profileId: string): Promise<CrashAnalysis> {
+  return invoke<CrashAnalysis>("analyze_profile_crash", { profileId });
+}
+
+/**
+ * Analyzes a crash report and/or log, e.g. from a file the user opened.
+ */
+export async function analyzeCrashLog(
+  crashReport: string | null,
+  log: string | null,
+  profileId?: string,
+): Promise<CrashAnalysis> {
+  return invoke<CrashAnalysis>("analyze_crash_log", { crashReport, log, profileId });
+}
+
+/**
+ * Fetches recent memory/CPU/thread/disk samples of a running process. Null until the first sample was taken.
+ */
+export async function getProcessStats(processId: string): Promise<ProcessStats | null> {
+  return invoke<ProcessStats | null>("get_process_stats", { processId });
+}
+
+/**
+ * Fetches resource summaries of finished processes (of one profile if given), newest first.
+ */
+export async function getProcessStatsHistory(profileId?: string): Promise<ProcessStatsRecord[]> {
+  return invoke<ProcessStatsRecord[]>("get_process_stats_history", { profileId });
+}
+
+/**
+ * Lists the captured stdout/stderr logs of past launches of a profile, newest first.
+ */
+export async function getLaunchLogs(profileId: string): Promise<LaunchLogInfo[]> {
+  return invoke<LaunchLogInfo[]>("get_launch_logs", { profileId });
+}
+
+/**
+ * Reads a launch log with all of its files.
+ */
+export async function readLaunchLog(profileId: string, launchId: string): Promise<string> {
+  return invoke<string>("read_launch_log", { profileId, launchId });
+}
+
+/**
+ * Fetches recorded game sessions matching the filter, newest first.
+ */
+export async function getGameSessions(filter?: SessionFilter): Promise<GameSession[]> {
+  return invoke<GameSession[]>("get_game_sessions", { filter });
+}
+
+/**
+ * Fetches playtime per profile, account and week and crash rates per pack version.
+ */
+export async function getSessionStats(filter?: SessionFilter): Promise<SessionAggregates> {
+  return invoke<SessionAggregates>("get_session_stats", { filter });
+}
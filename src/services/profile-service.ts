@@ -6,6 +6,7 @@ import type {
   CreateProfileParams,
   CustomModInfo,
   ExportProfileParams,
+  Mod,
   Profile,
   UpdateProfileParams,
   AllProfilesAndLastPlayed,
@@ -15,6 +16,7 @@ import type {
   LocalContentItem,
   ImageSource,
   UploadProfileIconPayload,
+  BisectReport,
 } from "../types/profile";
 import type {
   DataPackInfo,
@@ -96,11 +98,33 @@ export async function setProfileModEnabled(
   return invoke<void>("set_profile_mod_enabled", { profileId, modId, enabled });
 }
 
+/** Returns the dependencies that were removed along with the mod. */
 export async function deleteModFromProfile(
   profileId: string,
   modId: string,
-): Promise<void> {
-  return invoke<void>("delete_mod_from_profile", { profileId, modId });
+  removeOrphanedDependencies?: boolean,
+): Promise<Mod[]> {
+  return invoke<Mod[]>("delete_mod_from_profile", {
+    profileId,
+    modId,
+    removeOrphanedDependencies,
+  });
+}
+
+export async function getOrphanedModDependencies(
+  profileId: string,
+  modId: string,
+): Promise<Mod[]> {
+  return invoke<Mod[]>("get_orphaned_mod_dependencies", { profileId, modId });
+}
+
+/** Launches the profile repeatedly with parts of its mods disabled to find the crashing ones. */
+export async function bisectProfileMods(profileId: string): Promise<BisectReport> {
+  return invoke<BisectReport>("bisect_profile_mods", { profileId });
+}
+
+export async function cancelModBisect(profileId: string): Promise<void> {
+  return invoke<void>("cancel_mod_bisect", { profileId });
 }
 
 export async function addModrinthModToProfile(
@@ -114,6 +138,7 @@ export async function addModrinthModToProfile(
   versionNumber?: string,
   loaders?: string[],
   gameVersions?: string[],
+  ignoreIncompatibilities?: boolean,
 ): Promise<void> {
   return invoke<void>("add_modrinth_mod_to_profile", {
     profileId,
@@ -126,6 +151,7 @@ export async function addModrinthModToProfile(
     versionNumber,
     loaders,
     gameVersions,
+    ignoreIncompatibilities,
   });
 }
 
@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * Creates a server profile with the version and loader of a client profile, linked to it.
+ * @returns The id of the new server profile.
+ */
+export const createServerProfileForClient = (
+  clientProfileId: string,
+  name: string | null = null,
+): Promise<string> => {
+  return invoke('create_server_profile_for_client', { clientProfileId, name });
+};
+
+/**
+ * Whether the Minecraft EULA was accepted for a server profile. Servers don't start without it.
+ */
+export const isServerEulaAccepted = (profileId: string): Promise<boolean> => {
+  return invoke('is_server_eula_accepted', { profileId });
+};
+
+/**
+ * Accepts the Minecraft EULA (https://aka.ms/MinecraftEULA) for a server profile.
+ * Only call this after the user agreed to it.
+ */
+export const acceptServerEula = (profileId: string): Promise<void> => {
+  return invoke('accept_server_eula', { profileId });
+};
+
+/**
+ * All entries of the server's server.properties, empty before its first start.
+ */
+export const getServerProperties = (profileId: string): Promise<Record<string, string>> => {
+  return invoke('get_server_properties', { profileId });
+};
+
+/**
+ * Changes the given server.properties keys, the rest of the file is kept.
+ */
+export const setServerProperties = (profileId: string, properties: Record<string, string>): Promise<void> => {
+  return invoke('set_server_properties', { profileId, properties });
+};
+
+/**
+ * Sends a command to the console of a running server process.
+ */
+export const sendServerCommand = (processId: string, command: string): Promise<void> => {
+  return invoke('send_server_command', { processId, command });
+};
+
+/**
+ * Launches a client profile (the linked one if none is given) directly into a server profile.
+ */
+export const launchClientIntoServer = (
+  serverProfileId: string,
+  clientProfileId: string | null = null,
+): Promise<void> => {
+  return invoke('launch_client_into_server', { serverProfileId, clientProfileId });
+};
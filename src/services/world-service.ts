@@ -4,7 +4,21 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
     WorldInfo,
     ServerInfo,
-    ServerPingInfo
+    ServerPingInfo,
+    ServerEntryEdit,
+    FavoriteServer,
+    FavoriteServerUpdate,
+    ServerSyncResult,
+    MonitoredServer,
+    MonitoredServerStatus,
+    ServerMonitorConfig,
+    ServerSample,
+    WorldBackup,
+    WorldDetails,
+    LevelDatEdit,
+    WorldMap,
+    WorldCompatibilityReport,
+    WorldImportResult
 } from '../types/minecraft';
 import type {
     CopyWorldParams // Assuming this is defined in profile types
@@ -18,6 +32,131 @@ export const getServersForProfile = (profileId: string): Promise<ServerInfo[]> =
   return invoke('get_servers_for_profile', { profileId });
 };
 
+/**
+ * Adds a server to the multiplayer list of a profile (at the end unless an index is given).
+ * @returns A promise that resolves with the updated server list.
+ */
+export const addProfileServer = (
+  profileId: string,
+  name: string,
+  address: string,
+  index: number | null = null,
+): Promise<ServerInfo[]> => {
+  console.debug(`[WorldService] Adding server ${address} to profile: ${profileId}`);
+  return invoke('add_profile_server', { profileId, name, address, index });
+};
+
+/**
+ * Edits the server at the given position of a profile's server list.
+ */
+export const editProfileServer = (profileId: string, index: number, edit: ServerEntryEdit): Promise<ServerInfo[]> => {
+  return invoke('edit_profile_server', { profileId, index, edit });
+};
+
+/**
+ * Removes the server at the given position of a profile's server list.
+ */
+export const removeProfileServer = (profileId: string, index: number): Promise<ServerInfo[]> => {
+  console.debug(`[WorldService] Removing server #${index} from profile: ${profileId}`);
+  return invoke('remove_profile_server', { profileId, index });
+};
+
+/**
+ * Moves a server within a profile's server list.
+ */
+export const moveProfileServer = (profileId: string, fromIndex: number, toIndex: number): Promise<ServerInfo[]> => {
+  return invoke('move_profile_server', { profileId, fromIndex, toIndex });
+};
+
+/**
+ * Fetches the launcher-wide favorite servers.
+ */
+export const getFavoriteServers = (): Promise<FavoriteServer[]> => {
+  return invoke('get_favorite_servers');
+};
+
+export const addFavoriteServer = (
+  name: string,
+  address: string,
+  iconBase64: string | null = null,
+  autoSync = false,
+): Promise<FavoriteServer> => {
+  return invoke('add_favorite_server', { name, address, iconBase64, autoSync });
+};
+
+/**
+ * Updates a favorite server. A changed address is fixed in all profiles on the next sync.
+ */
+export const updateFavoriteServer = (id: string, update: FavoriteServerUpdate): Promise<FavoriteServer> => {
+  return invoke('update_favorite_server', { id, update });
+};
+
+export const removeFavoriteServer = (id: string): Promise<boolean> => {
+  return invoke('remove_favorite_server', { id });
+};
+
+/**
+ * Pushes favorite servers into the server lists of profiles.
+ * @param favoriteIds The favorites to push, all if null.
+ * @param profileIds The target profiles, all if null.
+ */
+export const syncFavoriteServers = (
+  favoriteIds: string[] | null = null,
+  profileIds: string[] | null = null,
+): Promise<ServerSyncResult[]> => {
+  console.debug(`[WorldService] Syncing favorite servers into profiles`);
+  return invoke('sync_favorite_servers', { favoriteIds, profileIds });
+};
+
+/**
+ * Fetches the monitored servers with their last known state.
+ * Up/down changes arrive as `server_status_changed` events.
+ */
+export const getMonitoredServers = (): Promise<MonitoredServerStatus[]> => {
+  return invoke('get_monitored_servers');
+};
+
+export const getServerMonitorConfig = (): Promise<ServerMonitorConfig> => {
+  return invoke('get_server_monitor_config');
+};
+
+export const addMonitoredServer = (name: string, address: string): Promise<MonitoredServer> => {
+  return invoke('add_monitored_server', { name, address });
+};
+
+export const setMonitoredServerEnabled = (id: string, enabled: boolean): Promise<void> => {
+  return invoke('set_monitored_server_enabled', { id, enabled });
+};
+
+export const removeMonitoredServer = (id: string): Promise<boolean> => {
+  return invoke('remove_monitored_server', { id });
+};
+
+/**
+ * Sets the poll interval (at least 10 seconds) and optionally how many hours of history are kept.
+ */
+export const setServerMonitorSchedule = (
+  intervalSecs: number,
+  historyHours: number | null = null,
+): Promise<void> => {
+  return invoke('set_server_monitor_schedule', { intervalSecs, historyHours });
+};
+
+/**
+ * Fetches the latency/player count samples of a monitored server, optionally only those after `since` (ISO timestamp).
+ */
+export const getServerHistory = (id: string, since: string | null = null): Promise<ServerSample[]> => {
+  return invoke('get_server_history', { id, since });
+};
+
+/**
+ * Polls all monitored servers right away.
+ */
+export const pollMonitoredServers = (): Promise<MonitoredServerStatus[]> => {
+  console.debug(`[WorldService] Polling monitored servers`);
+  return invoke('poll_monitored_servers');
+};
+
 /**
  * Fetches the list of worlds associated with a specific profile.
  */
@@ -87,4 +226,113 @@ export const getDifficultyString = (difficulty: number | null | undefined): stri
     case 3: return 'Hard';
     default: return `Unknown (${difficulty})`;
   }
-}; 
\ No newline at end of file
+}; 
+
+/**
+ * Reads all details of a world (seed, spawn, game rules, datapacks, size, players, dimensions).
+ */
+export const getWorldDetails = (profileId: string, worldFolder: string): Promise<WorldDetails> => {
+    return invoke('get_world_details', { profileId, worldFolder });
+};
+
+/**
+ * Edits name, difficulty, game type, commands or game rules of a world that isn't open.
+ * The previous level.dat is kept as a backup next to it.
+ */
+export const editWorldSettings = (profileId: string, worldFolder: string, edit: LevelDatEdit): Promise<WorldDetails> => {
+    console.debug(`[WorldService] Editing world: ${worldFolder} in profile ${profileId}`);
+    return invoke('edit_world_settings', { profileId, worldFolder, edit });
+};
+
+/**
+ * Renders the top-down map of a world dimension (default: overworld). Only regions changed since
+ * the last call are rendered again, progress is reported via `rendering_world_map` events.
+ */
+export const renderWorldMap = (
+    profileId: string,
+    worldFolder: string,
+    dimension: string | null = null,
+): Promise<WorldMap> => {
+    console.debug(`[WorldService] Rendering map of world: ${worldFolder} (${dimension ?? 'overworld'}) in profile ${profileId}`);
+    return invoke('render_world_map', { profileId, worldFolder, dimension });
+};
+
+/**
+ * Removes the cached map tiles of a world.
+ */
+export const clearWorldMapCache = (profileId: string, worldFolder: string): Promise<void> => {
+    return invoke('clear_world_map_cache', { profileId, worldFolder });
+};
+
+/**
+ * Checks a world against another profile before copying it there (downgrades, missing mod dimensions).
+ */
+export const checkWorldTransfer = (
+    sourceProfileId: string,
+    sourceWorldFolder: string,
+    targetProfileId: string,
+): Promise<WorldCompatibilityReport> => {
+    return invoke('check_world_transfer', { sourceProfileId, sourceWorldFolder, targetProfileId });
+};
+
+/**
+ * Exports a world as a zip. Without an output path the zip is written to the launcher's exports folder.
+ * @returns A promise that resolves with the path of the zip.
+ */
+export const exportWorld = (profileId: string, worldFolder: string, outputPath: string | null = null): Promise<string> => {
+    console.debug(`[WorldService] Exporting world: ${worldFolder} from profile ${profileId}`);
+    return invoke('export_world', { profileId, worldFolder, outputPath });
+};
+
+/**
+ * Reads the world in a zip and checks it against a profile without importing it.
+ */
+export const inspectWorldZip = (filePath: string, targetProfileId: string): Promise<WorldCompatibilityReport> => {
+    return invoke('inspect_world_zip', { filePath, targetProfileId });
+};
+
+/**
+ * Imports a world from a zip into a profile. Without a name the name stored in the world is used.
+ */
+export const importWorldZip = (profileId: string, filePath: string, worldName: string | null = null): Promise<WorldImportResult> => {
+    console.debug(`[WorldService] Importing world from ${filePath} into profile ${profileId}`);
+    return invoke('import_world_zip', { profileId, filePath, worldName });
+};
+
+/**
+ * Creates a manual backup of a world.
+ */
+export const createWorldBackup = (profileId: string, worldFolder: string): Promise<WorldBackup> => {
+    console.debug(`[WorldService] Backing up world: ${worldFolder} in profile ${profileId}`);
+    return invoke('create_world_backup', { profileId, worldFolder });
+};
+
+/**
+ * Lists the backups of a world, newest first.
+ */
+export const listWorldBackups = (profileId: string, worldFolder: string): Promise<WorldBackup[]> => {
+    return invoke('list_world_backups', { profileId, worldFolder });
+};
+
+/**
+ * Deletes a backup of a world.
+ */
+export const deleteWorldBackup = (profileId: string, worldFolder: string, backupId: string): Promise<void> => {
+    console.debug(`[WorldService] Deleting backup ${backupId} of world: ${worldFolder} in profile ${profileId}`);
+    return invoke('delete_world_backup', { profileId, worldFolder, backupId });
+};
+
+/**
+ * Restores a backup. Without a new name the world is replaced (its current state is backed up first),
+ * otherwise the backup is restored as a new world.
+ * @returns A promise that resolves with the folder name of the restored world.
+ */
+export const restoreWorldBackup = (
+    profileId: string,
+    worldFolder: string,
+    backupId: string,
+    newWorldName: string | null = null,
+): Promise<string> => {
+    console.debug(`[WorldService] Restoring backup ${backupId} of world: ${worldFolder} in profile ${profileId}`);
+    return invoke('restore_world_backup', { profileId, worldFolder, backupId, newWorldName });
+};
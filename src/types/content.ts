@@ -59,6 +59,7 @@ export interface InstallContentPayload {
   content_type: ContentType; // Using the ContentType enum
   loaders?: string[];
   game_versions?: string[];
+  ignore_incompatibilities?: boolean; // install despite Modrinth incompatibilities
 }
 
 /**
@@ -0,0 +1,61 @@
+// Corresponding Rust file: src-tauri/src/minecraft/crash_analyzer.rs
+
+export type CrashCategory =
+  | "out_of_memory"
+  | "java_version"
+  | "missing_dependency"
+  | "incompatible_mods"
+  | "mixin_failure"
+  | "graphics_driver"
+  | "corrupted_files"
+  | "mod_error"
+  | "other";
+
+export type SuggestedFix =
+  | { type: "raise_memory"; recommended_mb: number }
+  | { type: "switch_java"; major_version: number }
+  | { type: "disable_mod"; mod_id: string }
+  | { type: "install_mod"; mod_id: string }
+  | { type: "update_mod"; mod_id: string }
+  | { type: "update_graphics_driver" }
+  | { type: "verify_profile" };
+
+export interface CrashFinding {
+  signature_id: string;
+  title: string;
+  category: CrashCategory;
+  message: string;
+  evidence: string;
+  fixes: SuggestedFix[];
+}
+
+export interface ExceptionInfo {
+  class_name: string;
+  message: string | null;
+  frames: string[];
+}
+
+export interface SuspectedMod {
+  name: string;
+  mod_id: string;
+  version: string;
+}
+
+export interface MixinFailure {
+  mod_id: string | null;
+  config: string;
+  mixin: string;
+  target: string;
+}
+
+export interface CrashAnalysis {
+  description: string | null;
+  /** Outermost exception first, the root cause is the last one */
+  exceptions: ExceptionInfo[];
+  suspected_mods: SuspectedMod[];
+  mixin_failures: MixinFailure[];
+  running_java: number | null;
+  required_java: number | null;
+  /** Most relevant first */
+  findings: CrashFinding[];
+}
@@ -1,4 +1,5 @@
 import { ProcessMetadata } from "./processState";
+import { CrashAnalysis } from "./crashAnalysis";
 
 export enum EventType {
   InstallingJava = "installing_java",
@@ -27,6 +28,12 @@ export enum EventType {
   Error = "error",
   LaunchSuccessful = "launch_successful",
   CrashReportContentAvailable = "crash_report_content_available",
+  VerifyingProfile = "verifying_profile",
+  BisectingMods = "bisecting_mods",
+  BackingUpWorld = "backing_up_world",
+  RestoringWorld = "restoring_world",
+  RenderingWorldMap = "rendering_world_map",
+  ServerStatusChanged = "server_status_changed",
 }
 
 export interface EventPayload {
@@ -45,6 +52,7 @@ export interface MinecraftProcessExitedPayload {
   success: boolean;
   process_metadata: ProcessMetadata | null;
   crash_report_content?: string;
+  crash_analysis: CrashAnalysis | null;
 }
 
 export interface CrashReportContentAvailablePayload {
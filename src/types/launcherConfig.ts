@@ -5,8 +5,29 @@ export interface Hooks {
   pre_launch: string | null; // Option<String>
   wrapper: string | null; // Option<String>
   post_exit: string | null; // Option<String>
+  pre_launch_timeout_secs: number | null; // Option<u64>
+  abort_on_pre_launch_failure: boolean;
 }
 
+export interface MirrorRule {
+  upstream: string; // URL prefix that gets replaced
+  mirrors: string[]; // Vec<String>, tried in order (http(s):// or file://)
+}
+
+export interface MirrorConfig {
+  rules: MirrorRule[];
+  fallback_to_upstream: boolean;
+}
+
+export interface LaunchLogConfig {
+  max_file_size_mb: number; // A launch log continues in a new file after this size
+  max_files_per_launch: number; // The first file (startup output) is always kept
+  retention_days: number;
+  max_launches: number; // Kept per profile
+}
+
+export type JavaDistribution = "temurin" | "graalvm" | "zulu";
+
 export interface LauncherConfig {
   version: number; // u32
   is_experimental: boolean;
@@ -19,4 +40,10 @@ export interface LauncherConfig {
   concurrent_io_limit: number; // usize
   hooks: Hooks;
   hide_on_process_start: boolean;
+  mirrors: MirrorConfig;
+  offline_mode: boolean; // Launch from cached metadata without network requests
+  block_launch_on_mod_issues: boolean; // Refuse to launch when the mod check finds errors
+  launch_logs: LaunchLogConfig; // stdout/stderr captured per launch
+  java_distribution: JavaDistribution; // Downloaded for profiles without a custom Java path
+  curseforge_api_key?: string | null; // Needed to resolve the files of CurseForge modpacks
 } 
\ No newline at end of file
@@ -94,6 +94,107 @@ export interface WorldInfo {
   version_name?: string | null; // Rust Option<String> -> string | null
 }
 
+export interface WorldDetails {
+  folder_name: string;
+  level_name: string | null;
+  data_version: number | null;
+  version_name: string | null;
+  seed: number | null; // i64, may lose precision above 2^53
+  spawn: { x: number; y: number; z: number } | null;
+  game_type: number | null; // 0: Survival, 1: Creative, 2: Adventure, 3: Spectator
+  difficulty: number | null; // 0: Peaceful, 1: Easy, 2: Normal, 3: Hard
+  difficulty_locked: boolean | null;
+  hardcore: boolean | null;
+  allow_commands: boolean | null;
+  last_played: number | null;
+  game_rules: Record<string, string>; // Values as shown by /gamerule, e.g. "true" or "3"
+  enabled_datapacks: string[];
+  disabled_datapacks: string[];
+  dimensions: string[];
+  size_bytes: number;
+  player_count: number;
+  locked: boolean;
+}
+
+/** Unset fields stay as they are. Only game rules the world already has can be changed. */
+export interface LevelDatEdit {
+  level_name?: string | null;
+  difficulty?: number | null;
+  game_type?: number | null;
+  allow_commands?: boolean | null;
+  game_rules?: Record<string, string>;
+}
+
+export interface WorldMapTile {
+  zoom: number; // At zoom n a tile covers 512 * 2^n blocks per side
+  x: number;
+  z: number;
+  path: string; // Absolute path of the PNG, use convertFileSrc to display it
+}
+
+export interface WorldMap {
+  world_folder: string;
+  dimension: string; // e.g. "minecraft:overworld"
+  tile_size: number;
+  max_zoom: number;
+  spawn: { x: number; y: number; z: number } | null;
+  tiles: WorldMapTile[];
+  rendered_regions: number;
+  cached_regions: number;
+}
+
+export interface WorldLevelInfo {
+  level_name: string | null;
+  data_version: number | null; // Missing in worlds from before 1.9
+  version_name: string | null;
+  mod_dimensions: string[]; // e.g. "twilightforest:twilight_forest" or old Forge "DIM7"
+}
+
+export type WorldCompatibilityWarning =
+  | {
+      type: "downgrade";
+      world_data_version: number;
+      world_version_name: string | null;
+      target_version: string;
+      target_data_version: number;
+    }
+  | { type: "missing_mod_dimensions"; dimensions: string[]; mod_ids: string[] };
+
+export interface WorldCompatibilityReport {
+  world: WorldLevelInfo;
+  target_profile_id: string;
+  target_version: string;
+  target_data_version: number | null; // null if the version isn't known
+  warnings: WorldCompatibilityWarning[];
+}
+
+export interface WorldImportResult {
+  folder_name: string;
+  compatibility: WorldCompatibilityReport;
+}
+
+export type BackupFormat = "archive" | "incremental";
+export type BackupTrigger = "manual" | "game_exit" | "scheduled" | "before_restore";
+
+/** Which automatic backups are kept, manual backups are never pruned */
+export interface BackupRetention {
+  keep_last: number;
+  keep_daily: number;
+  keep_weekly: number;
+}
+
+export interface WorldBackup {
+  id: string;
+  world_folder: string;
+  created_at: string; // DateTime<Utc> as ISO string
+  trigger: BackupTrigger;
+  format: BackupFormat;
+  file_count: number;
+  world_size_bytes: number;
+  stored_size_bytes: number; // Disk space the backup added
+  taken_while_running: boolean;
+}
+
 export interface ServerInfo {
     name: string | null;
     address: string | null; // Matches Rust struct
@@ -102,6 +203,38 @@ export interface ServerInfo {
     previews_chat: number | null; // Seems to be boolean 0/1 (Rust u8)
 }
 
+export interface ServerEntryEdit {
+    name?: string | null;
+    address?: string | null;
+    icon_base64?: string | null; // Empty string removes the icon
+    accepts_textures?: number | null; // 0=prompt, 1=enabled, 2=disabled
+}
+
+export interface FavoriteServer {
+    id: string;
+    name: string;
+    address: string;
+    previous_addresses: string[]; // Old addresses, fixed in profiles on sync
+    icon_base64: string | null;
+    auto_sync: boolean; // Pushed into every profile before launch
+    added_at: string;
+}
+
+export interface FavoriteServerUpdate {
+    name?: string | null;
+    address?: string | null;
+    icon_base64?: string | null; // Empty string removes the icon
+    auto_sync?: boolean | null;
+}
+
+export interface ServerSyncResult {
+    profile_id: string;
+    profile_name: string;
+    added: number;
+    updated: number;
+    error: string | null;
+}
+
 export interface ServerPingInfo {
     description: string | null;
     description_json: object | null; // Representing serde_json::Value
@@ -111,5 +244,34 @@ export interface ServerPingInfo {
     players_max: number | null;
     favicon_base64: string | null;
     latency_ms: number | null;
+    legacy: boolean; // Only answered the pre-1.7 server list ping
     error: string | null; // Optional error message
+}
+
+export interface MonitoredServer {
+    id: string;
+    name: string;
+    address: string;
+    enabled: boolean;
+}
+
+export interface ServerSample {
+    timestamp: string;
+    online: boolean;
+    latency_ms: number | null;
+    players_online: number | null;
+    players_max: number | null;
+    error?: string;
+}
+
+export interface MonitoredServerStatus extends MonitoredServer {
+    online: boolean | null; // null until the server was polled
+    last_sample: ServerSample | null;
+    uptime: number | null; // 0.0 - 1.0 over the kept history
+}
+
+export interface ServerMonitorConfig {
+    servers: MonitoredServer[];
+    interval_secs: number;
+    history_hours: number;
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 // This file is auto-generated from the Rust sources. Do not edit manually.
 // Corresponding Rust file: src-tauri/src/state/process_state.rs
 
+import type { ProfileKind } from "./profile";
+
 /**
  * Represents the state of a managed process.
  * Corresponds to the Rust enum `ProcessState`.
@@ -31,6 +33,9 @@ export interface ProcessMetadata {
   norisk_pack?: string | null;
   profile_name?: string | null;
   profile_image_url?: string | null;
+  max_heap_mb?: number | null; // -Xmx the game was started with
+  launch_log_id?: string | null; // Launch log with the captured stdout/stderr
+  kind?: ProfileKind; // "server" for dedicated server processes
 }
 
 /**
@@ -40,4 +45,137 @@ export interface ProcessMetadata {
 export interface CrashlogDto {
   mcLogsUrl: string;
   metadata: ProcessMetadata | null;
-}
\ No newline at end of file
+}
+
+/**
+ * One resource measurement of a game process.
+ * Corresponds to the Rust struct `ProcessSample`.
+ */
+export interface ProcessSample {
+  timestamp: string;
+  memory_bytes: number; // Resident set size
+  virtual_memory_bytes: number;
+  cpu_percent: number; // 100 = one fully used core
+  thread_count: number | null; // Only available on Linux
+  disk_read_bytes: number; // Since the previous sample
+  disk_written_bytes: number;
+  system_available_memory_bytes: number;
+  system_used_swap_bytes: number;
+}
+
+export type ResourceWarning =
+  | { type: 'swapping'; swap_growth_bytes: number; min_available_memory_bytes: number }
+  | { type: 'heap_too_large'; max_heap_mb: number; total_memory_mb: number };
+
+/**
+ * Totals over a whole run of a process.
+ * Corresponds to the Rust struct `ProcessStatsSummary`.
+ */
+export interface ProcessStatsSummary {
+  first_sample_at: string | null;
+  last_sample_at: string | null;
+  sample_count: number;
+  avg_memory_bytes: number;
+  peak_memory_bytes: number;
+  avg_cpu_percent: number;
+  peak_cpu_percent: number;
+  peak_thread_count: number | null;
+  disk_read_bytes: number;
+  disk_written_bytes: number;
+  max_heap_mb: number | null;
+  total_memory_bytes: number;
+  warnings: ResourceWarning[];
+}
+
+export interface ProcessStats {
+  samples: ProcessSample[]; // Last 10 minutes
+  summary: ProcessStatsSummary;
+}
+
+/**
+ * Stats summary of a finished process.
+ * Corresponds to the Rust struct `ProcessStatsRecord`.
+ */
+export interface ProcessStatsRecord extends ProcessStatsSummary {
+  process_id: string;
+  profile_id: string;
+  profile_name: string | null;
+  minecraft_version: string | null;
+  started_at: string;
+  ended_at: string;
+  exit_code: number | null;
+}
+
+/**
+ * Captured stdout/stderr of one launch, possibly split over several files.
+ * Corresponds to the Rust struct `LaunchLogInfo`.
+ */
+export interface LaunchLogInfo {
+  id: string; // Start time, e.g. "2025-01-31_18-04-12"
+  size_bytes: number;
+  files: number;
+  modified: string;
+}
+
+/**
+ * One game run, from start to exit.
+ * Corresponds to the Rust struct `GameSession`.
+ */
+export interface GameSession {
+  id: string; // Same as the process id
+  profile_id: string;
+  profile_name: string | null;
+  account_uuid: string | null;
+  account_name: string | null;
+  minecraft_version: string | null;
+  modloader: string | null;
+  modloader_version: string | null;
+  norisk_pack: string | null;
+  norisk_pack_version: string | null; // Fingerprint of the pack's mods at launch
+  started_at: string;
+  ended_at: string | null; // Null while running or if the exit was missed
+  duration_secs: number | null;
+  exit_code: number | null;
+  crashed: boolean;
+  exit_unknown: boolean; // The launcher was closed when the game exited
+}
+
+/**
+ * Restricts session queries. Unset fields match everything.
+ */
+export interface SessionFilter {
+  profile_id?: string;
+  account_uuid?: string;
+  norisk_pack?: string;
+  since?: string;
+  until?: string;
+}
+
+export interface PlaytimeEntry {
+  key: string; // Profile id, account uuid or ISO week ("2025-W05")
+  name: string | null;
+  sessions: number;
+  total_secs: number;
+}
+
+export interface PackVersionStats {
+  norisk_pack: string;
+  norisk_pack_version: string | null;
+  minecraft_version: string | null;
+  sessions: number; // Sessions with an observed exit
+  crashes: number;
+  crash_rate: number; // 0.0 - 1.0
+  total_secs: number;
+}
+
+/**
+ * Corresponds to the Rust struct `SessionAggregates`.
+ */
+export interface SessionAggregates {
+  sessions: number;
+  total_secs: number;
+  by_profile: PlaytimeEntry[];
+  by_account: PlaytimeEntry[];
+  by_week: PlaytimeEntry[]; // Oldest first
+  by_pack_version: PackVersionStats[];
+}
@@ -1,4 +1,5 @@
 import { ContentType } from "./content";
+import type { BackupFormat, BackupRetention } from "./minecraft";
 
 export type ModLoader = "vanilla" | "forge" | "fabric" | "quilt" | "neoforge";
 export type ProfileState =
@@ -67,6 +68,24 @@ export interface ProfileSettings {
   fullscreen: boolean;
   extra_game_args: string[];           // Vec<String> -> string[] (Renamed from extra_args)
   custom_jvm_args: string | null;   // Option<String> -> string | null (New)
+  hooks?: ProfileHooks;                // Overrides the global hooks, "" disables one
+  env_vars?: Record<string, string>;   // HashMap<String, String>
+  world_backups?: WorldBackupSettings;
+}
+
+export interface WorldBackupSettings {
+  backup_on_exit: boolean;
+  interval_minutes: number | null; // Backups while the game runs, null disables them
+  format: BackupFormat;
+  retention: BackupRetention;
+}
+
+export interface ProfileHooks {
+  pre_launch: string | null;
+  wrapper: string | null;
+  post_exit: string | null;
+  pre_launch_timeout_secs: number | null; // Option<u64>
+  abort_on_pre_launch_failure: boolean | null; // Option<bool>, falls back to the global setting
 }
 
 interface ModSourceBase {
@@ -82,6 +101,7 @@ export interface ModSourceUrl extends ModSourceBase {
   type: "url";
   url: string;
   file_name: string | null;
+  file_hash_sha1?: string | null;
 }
 
 export interface ModSourceMaven extends ModSourceBase {
@@ -120,6 +140,8 @@ export interface Mod {
   game_versions: string[] | null;
   file_name_override: string | null;
   associated_loader: ModLoader | null;
+  installed_as_dependency: boolean; // pulled in by another mod, not chosen by the user
+  dependencies: string[]; // ids of mods in this profile that this mod requires
 }
 
 export interface NoriskModIdentifier {
@@ -138,6 +160,62 @@ export interface CustomModInfo {
   filename: string;
   is_enabled: boolean;
   path: string;
+  metadata: ModMetadata | null;
+}
+
+export type ModMetadataFormat = "fabric" | "quilt" | "forge" | "neo_forge" | "legacy_forge";
+export type ModEnvironment = "client" | "server" | "both" | "unknown";
+export type DependencyKind = "required" | "optional" | "incompatible" | "discouraged";
+
+export interface ModDependency {
+  mod_id: string;
+  version_range: string | null;
+  kind: DependencyKind;
+  environment: ModEnvironment;
+}
+
+// Read from the metadata files embedded in a mod jar
+export interface ModMetadata {
+  format: ModMetadataFormat;
+  mod_id: string;
+  name: string | null;
+  version: string | null;
+  description: string | null;
+  authors: string[];
+  environment: ModEnvironment;
+  icon: string | null; // Path inside the jar
+  icon_data: string | null; // Base64, only from get_mod_metadata_for_archives
+  dependencies: ModDependency[];
+  provides: string[];
+}
+
+export interface JarMetadata {
+  mods: ModMetadata[];
+  nested: NestedJarMetadata[];
+}
+
+export interface NestedJarMetadata {
+  path: string;
+  metadata: JarMetadata;
+}
+
+export type BisectModRef =
+  | { type: "profile"; mod_id: string }
+  | { type: "custom"; filename: string };
+
+export interface BisectCandidate {
+  mod_ref: BisectModRef;
+  name: string;
+  requires: BisectModRef[];
+}
+
+export type BisectStatus = "found" | "not_reproducible" | "crashes_without_mods";
+
+export interface BisectReport {
+  status: BisectStatus;
+  culprits: BisectCandidate[];
+  dependencies: BisectCandidate[];
+  runs: { enabled_mods: number; outcome: "crashed" | "stable" | "inconclusive" }[];
 }
 
 export interface Profile {
@@ -161,8 +239,12 @@ export interface Profile {
   banner: ProfileBanner | null;
   background: ProfileBanner | null;
   norisk_information: NoriskInformation | null;
+  kind: ProfileKind;
+  linked_client_profile_id: string | null; // Server profiles: client launched by "join this server"
 }
 
+export type ProfileKind = "client" | "server";
+
 export interface ProfileGroup {
   id: string;
   name: string;
@@ -177,6 +259,8 @@ export interface CreateProfileParams {
   loader: string;
   loader_version?: string;
   selected_norisk_pack_id?: string;
+  kind?: ProfileKind;
+  linked_client_profile_id?: string;
 }
 
 export interface UpdateProfileParams {
@@ -200,11 +284,14 @@ export interface CopyProfileParams {
   include_files?: string[];
 }
 
+export type ExportFormat = "noriskpack" | "mrpack";
+
 export interface ExportProfileParams {
   profile_id: string;
   file_name: string;
   include_files?: string[];
   open_folder: boolean;
+  format: ExportFormat;
 }
 
 // --- Payload for upload_profile_icon command ---